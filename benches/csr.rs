@@ -0,0 +1,60 @@
+#![feature(test)]
+
+extern crate petgraph;
+extern crate test;
+
+use petgraph::prelude::*;
+use petgraph::csr::Csr;
+use petgraph::graph::node_index;
+use petgraph::visit::IntoNeighbors;
+
+const NODES: usize = 10_000;
+const EDGES: usize = 100_000;
+
+/// A random directed graph, from a fixed seed linear congruential generator.
+fn random_graph() -> Graph<(), u32> {
+    let mut g = Graph::with_capacity(NODES, EDGES);
+    for _ in 0..NODES {
+        g.add_node(());
+    }
+    let mut state = 0x2545_f491_u64;
+    let mut next = || {
+        state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        (state >> 33) as usize % NODES
+    };
+    for i in 0..EDGES {
+        let a = next();
+        let b = next();
+        g.add_edge(node_index(a), node_index(b), i as u32);
+    }
+    g
+}
+
+#[bench]
+fn neighbors_graph(bench: &mut test::Bencher) {
+    let g = random_graph();
+    bench.iter(|| {
+        let mut sum = 0;
+        for a in g.node_indices() {
+            for b in g.neighbors(a) {
+                sum += b.index();
+            }
+        }
+        sum
+    });
+}
+
+#[bench]
+fn neighbors_csr(bench: &mut test::Bencher) {
+    let g = random_graph();
+    let m = Csr::from_graph(&g);
+    bench.iter(|| {
+        let mut sum = 0;
+        for a in 0..m.node_count() as u32 {
+            for b in (&m).neighbors(a) {
+                sum += b as usize;
+            }
+        }
+        sum
+    });
+}
@@ -52,3 +52,49 @@ fn bench_remove(b: &mut test::Bencher) {
         }
     })
 }
+
+fn triangle_graph() -> Graph<(), ()> {
+    let mut og = Graph::new();
+    let nodes = (0..200).map(|_| og.add_node(())).collect::<Vec<_>>();
+    for (i, &a) in nodes.iter().enumerate() {
+        for j in 1..6 {
+            let b = nodes[(i * 7 + j * 13) % nodes.len()];
+            og.add_edge(a, b, ());
+        }
+    }
+    og
+}
+
+#[bench]
+fn bench_triangles_collect(b: &mut test::Bencher) {
+    let og = triangle_graph();
+    b.iter(|| {
+        let mut count = 0;
+        for a in og.node_indices() {
+            let na = og.neighbors(a).collect::<Vec<_>>();
+            for &n in &na {
+                let nn = og.neighbors(n).collect::<Vec<_>>();
+                count += nn.iter().filter(|x| na.contains(x)).count();
+            }
+        }
+        count
+    })
+}
+
+#[bench]
+fn bench_triangles_neighbors_into(b: &mut test::Bencher) {
+    let og = triangle_graph();
+    let mut na = Vec::new();
+    let mut nn = Vec::new();
+    b.iter(|| {
+        let mut count = 0;
+        for a in og.node_indices() {
+            og.neighbors_into(a, &mut na);
+            for &n in &na {
+                og.neighbors_into(n, &mut nn);
+                count += nn.iter().filter(|x| na.contains(x)).count();
+            }
+        }
+        count
+    })
+}
@@ -0,0 +1 @@
+//! Tests for the serde support of petgraph are in `tests/`.
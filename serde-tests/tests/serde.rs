@@ -0,0 +1,106 @@
+extern crate petgraph;
+extern crate serde_json;
+extern crate bincode;
+
+use petgraph::prelude::*;
+use petgraph::graph::node_index as n;
+use petgraph::graph::edge_index as e;
+
+fn example() -> Graph<String, u32> {
+    let mut g = Graph::new();
+    let a = g.add_node("a".to_string());
+    let b = g.add_node("b".to_string());
+    let c = g.add_node("c".to_string());
+    g.add_edge(a, b, 1);
+    g.add_edge(b, c, 2);
+    g.add_edge(c, a, 3);
+    g.add_edge(a, a, 4);
+    g.add_edge(a, b, 5);
+    g
+}
+
+fn assert_same_graph<Ty: petgraph::EdgeType>(g: &Graph<String, u32, Ty>,
+                                             h: &Graph<String, u32, Ty>)
+{
+    assert_eq!(g.node_count(), h.node_count());
+    assert_eq!(g.edge_count(), h.edge_count());
+    for i in g.node_indices() {
+        assert_eq!(g[i], h[i]);
+    }
+    for i in g.edge_indices() {
+        assert_eq!(g.edge_endpoints(i), h.edge_endpoints(i));
+        assert_eq!(g[i], h[i]);
+    }
+    for i in g.node_indices() {
+        assert_eq!(g.neighbors_undirected(i).collect::<Vec<_>>(),
+                   h.neighbors_undirected(i).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn graph_json() {
+    let g = example();
+    let json = serde_json::to_string(&g).unwrap();
+    assert_eq!(json, r#"{"nodes":["a","b","c"],"edges":[[0,1,1],[1,2,2],[2,0,3],[0,0,4],[0,1,5]]}"#);
+    let h: Graph<String, u32> = serde_json::from_str(&json).unwrap();
+    assert_same_graph(&g, &h);
+}
+
+#[test]
+fn graph_bincode() {
+    let mut g = example();
+    g.remove_node(n(1));
+    let bytes = bincode::serialize(&g).unwrap();
+    let h: Graph<String, u32> = bincode::deserialize(&bytes).unwrap();
+    assert_same_graph(&g, &h);
+
+    let u = example().into_edge_type::<Undirected>();
+    let bytes = bincode::serialize(&u).unwrap();
+    let v: UnGraph<String, u32> = bincode::deserialize(&bytes).unwrap();
+    assert_same_graph(&u, &v);
+    assert_eq!(v.find_edge(n(2), n(1)), Some(e(1)));
+}
+
+#[test]
+fn graph_invalid_endpoint() {
+    let json = r#"{"nodes":[0,1],"edges":[[0,1,"x"],[1,2,"y"]]}"#;
+    let res = serde_json::from_str::<Graph<u8, String>>(json);
+    let err = res.unwrap_err().to_string();
+    assert!(err.contains("edge 1"), "{}", err);
+}
+
+#[test]
+fn graph_index_type_overflow() {
+    let mut g = Graph::<(), ()>::new();
+    for _ in 0..256 {
+        g.add_node(());
+    }
+    let json = serde_json::to_string(&g).unwrap();
+    assert!(serde_json::from_str::<Graph<(), (), Directed, u8>>(&json).is_err());
+    assert!(serde_json::from_str::<Graph<(), (), Directed, u16>>(&json).is_ok());
+}
+
+#[test]
+fn graphmap_round_trip() {
+    let mut g = DiGraphMap::new();
+    g.add_node(7);
+    g.add_edge(3, 1, 'a');
+    g.add_edge(1, 2, 'b');
+    g.add_edge(2, 2, 'c');
+    let json = serde_json::to_string(&g).unwrap();
+    assert_eq!(json, r#"{"nodes":[7,3,1,2],"edges":[[3,1,"a"],[1,2,"b"],[2,2,"c"]]}"#);
+    let h: DiGraphMap<i32, char> = serde_json::from_str(&json).unwrap();
+    assert_eq!(g.nodes().collect::<Vec<_>>(), h.nodes().collect::<Vec<_>>());
+    assert_eq!(g.all_edges().collect::<Vec<_>>(), h.all_edges().collect::<Vec<_>>());
+
+    let bytes = bincode::serialize(&g).unwrap();
+    let h: DiGraphMap<i32, char> = bincode::deserialize(&bytes).unwrap();
+    assert_eq!(g.nodes().collect::<Vec<_>>(), h.nodes().collect::<Vec<_>>());
+    assert_eq!(g.all_edges().collect::<Vec<_>>(), h.all_edges().collect::<Vec<_>>());
+}
+
+#[test]
+fn graphmap_invalid_endpoint() {
+    let json = r#"{"nodes":[1,2],"edges":[[1,3,0]]}"#;
+    assert!(serde_json::from_str::<UnGraphMap<i32, i32>>(json).is_err());
+}
@@ -0,0 +1,99 @@
+//! Approximation algorithms for vertex cover and independent set.
+
+use fixedbitset::FixedBitSet;
+
+use visit::{
+    EdgeRef,
+    IntoEdgeReferences,
+    IntoNodeIdentifiers,
+    NodeIndexable,
+};
+
+/// [Generic] Compute a vertex cover of the graph, at most twice the size of a
+/// minimum vertex cover.
+///
+/// A vertex cover is a set of nodes such that every edge has at least one of
+/// its endpoints in the set. The graph is treated as if undirected.
+///
+/// Uses the classic 2-approximation: the edges are visited in the order of
+/// `edge_references`, and both endpoints of every edge that is not yet covered
+/// are added to the cover (the endpoints of these edges form a maximal
+/// matching). A self loop adds its single endpoint.
+///
+/// The result is deterministic: it only depends on the graph and on its
+/// node and edge order. Nodes are listed in the order they were added to
+/// the cover.
+///
+/// Computes in **O(|V| + |E|)** time.
+pub fn min_vertex_cover_approx<G>(g: G) -> Vec<G::NodeId>
+    where G: IntoEdgeReferences + NodeIndexable,
+{
+    let mut covered = FixedBitSet::with_capacity(g.node_bound());
+    let mut cover = Vec::new();
+    for edge in g.edge_references() {
+        let (a, b) = (edge.source(), edge.target());
+        let (ai, bi) = (g.to_index(a), g.to_index(b));
+        if covered.contains(ai) || covered.contains(bi) {
+            continue;
+        }
+        covered.put(ai);
+        cover.push(a);
+        if ai != bi {
+            covered.put(bi);
+            cover.push(b);
+        }
+    }
+    cover
+}
+
+/// [Generic] Compute a maximal independent set of the graph, using a greedy
+/// heuristic that prefers nodes of low degree.
+///
+/// An independent set is a set of nodes such that no two of them are
+/// adjacent. The graph is treated as if undirected, and nodes with a self
+/// loop are never part of the set.
+///
+/// The nodes are considered in order of increasing degree (ties broken by
+/// node index), and each node is added unless it is adjacent to a node that
+/// was already added.
+///
+/// The result is deterministic: it only depends on the graph and on its
+/// node and edge order. Nodes are listed in the order they were added to the
+/// set.
+///
+/// Computes in **O(|V| log |V| + |E|)** time.
+pub fn max_independent_set_approx<G>(g: G) -> Vec<G::NodeId>
+    where G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+{
+    let n = g.node_bound();
+    let mut adjacent = vec![Vec::new(); n];
+    // nodes that may not be added: self loops and neighbors of chosen nodes
+    let mut blocked = FixedBitSet::with_capacity(n);
+    for edge in g.edge_references() {
+        let (a, b) = (g.to_index(edge.source()), g.to_index(edge.target()));
+        if a == b {
+            blocked.put(a);
+        } else {
+            adjacent[a].push(b);
+            adjacent[b].push(a);
+        }
+    }
+
+    let mut order = g.node_identifiers()
+                     .map(|node| g.to_index(node))
+                     .collect::<Vec<_>>();
+    order.sort_by_key(|&i| (adjacent[i].len(), i));
+
+    let mut set = Vec::new();
+    for i in order {
+        if blocked.contains(i) {
+            continue;
+        }
+        blocked.put(i);
+        for &j in &adjacent[i] {
+            blocked.put(j);
+        }
+        set.push(g.from_index(i));
+    }
+    set
+}
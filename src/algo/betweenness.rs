@@ -0,0 +1,207 @@
+//! Betweenness centrality, exact and sampled.
+
+use std::collections::BinaryHeap;
+
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+use scored::MinScored;
+use visit::{
+    EdgeRef,
+    GraphProp,
+    IntoEdges,
+    IntoNodeIdentifiers,
+    NodeIndexable,
+};
+use super::Measure;
+
+/// [Generic] Compute the betweenness centrality of every node, using
+/// Brandes' algorithm.
+///
+/// The betweenness centrality of a node `v` is the sum, over all pairs of
+/// distinct nodes `s` and `t` (both different from `v`), of the fraction of
+/// shortest paths from `s` to `t` that pass through `v`.
+///
+/// The function `edge_cost` should return the cost for a particular edge,
+/// which is used to compute path lengths. Edge costs must be positive; for an
+/// unweighted graph, use `|_| 1`. In an undirected graph each unordered pair
+/// is counted once. The values are not normalized.
+///
+/// Returns a vector indexed by node index (see `NodeIndexable`). Vacant
+/// indices get the value `0.`.
+///
+/// Computes in **O(|V| |E| + |V|² log |V|)** time for a weighted graph.
+pub fn betweenness_centrality<G, F, K>(g: G, edge_cost: F) -> Vec<f64>
+    where G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+          F: FnMut(G::EdgeRef) -> K,
+          K: Measure + Copy,
+{
+    let sources = g.node_identifiers().map(|n| g.to_index(n)).collect::<Vec<_>>();
+    betweenness_from_sources(g, &sources, edge_cost, 1.)
+}
+
+/// [Generic] Estimate the betweenness centrality of every node, using
+/// Brandes' algorithm from a random sample of source nodes.
+///
+/// This is like `betweenness_centrality`, but the single source dependency
+/// accumulation is only run from `num_samples` source nodes picked uniformly
+/// at random (without replacement), and the result is scaled by
+/// `|V| / num_samples`. If `num_samples` is at least `|V|`, every node is used
+/// as a source and the result is equal to `betweenness_centrality`.
+///
+/// The estimate is unbiased. Since the dependency of a single source on a
+/// node is at most `|V| - 2`, Hoeffding's inequality bounds the error of
+/// each node's estimate: it is more than `ε |V| (|V| - 2)` with probability at
+/// most `2 exp(-2 num_samples ε²)`. So for an error of at most `ε` relative to
+/// `|V| (|V| - 2)` on all nodes at once, with probability `1 - δ`, use
+/// `num_samples >= ln(2 |V| / δ) / (2 ε²)`.
+///
+/// The result only depends on the graph and on the random numbers drawn
+/// from `rng`, so it is reproducible with a seeded random number generator.
+///
+/// Requires crate feature `"rand"`.
+///
+/// Computes in **O(k |E| + k |V| log |V|)** time for `k` samples.
+#[cfg(feature = "rand")]
+pub fn betweenness_centrality_sampled<G, R, F, K>(g: G, rng: &mut R, num_samples: usize,
+                                                  edge_cost: F) -> Vec<f64>
+    where G: IntoEdges + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+          R: Rng,
+          F: FnMut(G::EdgeRef) -> K,
+          K: Measure + Copy,
+{
+    let nodes = g.node_identifiers().map(|n| g.to_index(n)).collect::<Vec<_>>();
+    if num_samples == 0 {
+        return vec![0.; g.node_bound()];
+    }
+    if num_samples >= nodes.len() {
+        return betweenness_from_sources(g, &nodes, edge_cost, 1.);
+    }
+    let mut sources = nodes;
+    // partial Fisher-Yates shuffle
+    for i in 0..num_samples {
+        let j = rng.gen_range(i, sources.len());
+        sources.swap(i, j);
+    }
+    let n = sources.len();
+    sources.truncate(num_samples);
+    // accumulate in the same order as the exact version does
+    sources.sort();
+    let scale = n as f64 / num_samples as f64;
+    betweenness_from_sources(g, &sources, edge_cost, scale)
+}
+
+/// Run the single source accumulation from each of `sources` and sum the
+/// dependencies, scaled by `scale`.
+fn betweenness_from_sources<G, F, K>(g: G, sources: &[usize], mut edge_cost: F,
+                                     scale: f64) -> Vec<f64>
+    where G: IntoEdges + NodeIndexable + GraphProp,
+          F: FnMut(G::EdgeRef) -> K,
+          K: Measure + Copy,
+{
+    let mut state = SingleSource::new(g.node_bound());
+    let mut centrality = vec![0.; g.node_bound()];
+    for &s in sources {
+        state.accumulate(g, s, &mut edge_cost, &mut centrality);
+    }
+    // each path was found from both of its ends
+    let scale = if g.is_directed() { scale } else { scale / 2. };
+    if scale != 1. {
+        for c in &mut centrality {
+            *c *= scale;
+        }
+    }
+    centrality
+}
+
+/// Reusable buffers for the single source shortest paths and dependency
+/// accumulation of Brandes' algorithm.
+struct SingleSource<K> {
+    /// Nodes in the order they were settled.
+    order: Vec<usize>,
+    /// Predecessors on shortest paths.
+    preds: Vec<Vec<usize>>,
+    /// Number of shortest paths.
+    sigma: Vec<f64>,
+    dist: Vec<Option<K>>,
+    settled: Vec<bool>,
+    delta: Vec<f64>,
+    heap: BinaryHeap<MinScored<K, usize>>,
+}
+
+impl<K> SingleSource<K>
+    where K: Measure + Copy,
+{
+    fn new(n: usize) -> Self {
+        SingleSource {
+            order: Vec::with_capacity(n),
+            preds: vec![Vec::new(); n],
+            sigma: vec![0.; n],
+            dist: vec![None; n],
+            settled: vec![false; n],
+            delta: vec![0.; n],
+            heap: BinaryHeap::new(),
+        }
+    }
+
+    fn reset(&mut self) {
+        for &v in &self.order {
+            self.preds[v].clear();
+            self.sigma[v] = 0.;
+            self.dist[v] = None;
+            self.settled[v] = false;
+            self.delta[v] = 0.;
+        }
+        self.order.clear();
+        self.heap.clear();
+    }
+
+    /// Add the dependencies of source `s` on every other node to
+    /// `centrality`.
+    fn accumulate<G, F>(&mut self, g: G, s: usize, edge_cost: &mut F,
+                        centrality: &mut [f64])
+        where G: IntoEdges + NodeIndexable,
+              F: FnMut(G::EdgeRef) -> K,
+    {
+        self.reset();
+        let zero = K::default();
+        self.sigma[s] = 1.;
+        self.dist[s] = Some(zero);
+        self.heap.push(MinScored(zero, s));
+        while let Some(MinScored(d, v)) = self.heap.pop() {
+            if self.settled[v] {
+                continue;
+            }
+            self.settled[v] = true;
+            self.order.push(v);
+            for edge in g.edges(g.from_index(v)) {
+                let w = g.to_index(edge.target());
+                if self.settled[w] {
+                    continue;
+                }
+                let nd = d + edge_cost(edge);
+                match self.dist[w] {
+                    Some(old) if nd > old => continue,
+                    Some(old) if nd == old => {}
+                    _ => {
+                        self.dist[w] = Some(nd);
+                        self.sigma[w] = 0.;
+                        self.preds[w].clear();
+                        self.heap.push(MinScored(nd, w));
+                    }
+                }
+                self.sigma[w] += self.sigma[v];
+                self.preds[w].push(v);
+            }
+        }
+        for &w in self.order.iter().rev() {
+            let coeff = (1. + self.delta[w]) / self.sigma[w];
+            for &v in &self.preds[w] {
+                self.delta[v] += self.sigma[v] * coeff;
+            }
+            if w != s {
+                centrality[w] += self.delta[w];
+            }
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! Transitive closure as a bitset matrix.
+
+use fixedbitset::{FixedBitSet, Ones};
+
+use visit::{
+    GraphBase,
+    IntoNeighbors,
+    IntoNodeIdentifiers,
+    NodeIndexable,
+};
+use super::tarjan_scc;
+
+/// [Generic] Compute the transitive closure of a graph.
+///
+/// Returns a `TransitiveClosure`, which answers if a node reaches another in
+/// constant time. A node reaches another if there is a path of at least one
+/// edge from the first to the second; so a node only reaches itself if it is
+/// on a cycle (a self loop counts).
+///
+/// The strongly connected components are processed in reverse topological
+/// order, and the nodes in a component share a single row of the bitset
+/// matrix, so cyclic graphs are handled too. For an undirected graph, each
+/// node reaches every node of its connected component (including itself, if
+/// it has an edge).
+///
+/// Uses **O(|C| |V|)** bits of memory, where |C| is the number of strongly
+/// connected components.
+///
+/// Computes in **O(|V| + |C| |E| / w)** time, where w is the word size.
+pub fn transitive_closure<G>(g: G) -> TransitiveClosure<G>
+    where G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let n = g.node_bound();
+    let mut component = vec![!0; n];
+    let mut rows: Vec<FixedBitSet> = Vec::new();
+    // tarjan_scc yields the components with successors first, so the rows
+    // of all successor components are complete when they are needed.
+    for scc in tarjan_scc(g) {
+        let c = rows.len();
+        for &v in &scc {
+            component[g.to_index(v)] = c;
+        }
+        let mut row = FixedBitSet::with_capacity(n);
+        for &v in &scc {
+            for w in g.neighbors(v) {
+                let wi = g.to_index(w);
+                row.insert(wi);
+                let d = component[wi];
+                if d != c {
+                    for (x, &y) in row.as_mut_slice().iter_mut().zip(rows[d].as_slice()) {
+                        *x |= y;
+                    }
+                }
+            }
+        }
+        rows.push(row);
+    }
+    TransitiveClosure {
+        graph: g,
+        component: component,
+        rows: rows,
+    }
+}
+
+/// The transitive closure of a graph, computed by `transitive_closure`.
+///
+/// Holds one row of the reachability matrix per strongly connected
+/// component.
+#[derive(Clone, Debug)]
+pub struct TransitiveClosure<G> {
+    graph: G,
+    /// Component of each node index.
+    component: Vec<usize>,
+    /// The node indices reachable from each component.
+    rows: Vec<FixedBitSet>,
+}
+
+impl<G> TransitiveClosure<G>
+    where G: NodeIndexable,
+{
+    /// Return `true` if there is a path of at least one edge from `a` to `b`.
+    ///
+    /// **Panics** if `a` or `b` is not a node of the graph.
+    pub fn reachable(&self, a: G::NodeId, b: G::NodeId) -> bool {
+        let bi = self.graph.to_index(b);
+        assert!(bi < self.component.len() && self.component[bi] != !0,
+                "TransitiveClosure::reachable: node index out of bounds");
+        self.row(a).contains(bi)
+    }
+
+    /// Return an iterator of the nodes reachable from `a`, by a path of at
+    /// least one edge, in order of increasing node index.
+    ///
+    /// **Panics** if `a` is not a node of the graph.
+    pub fn descendants(&self, a: G::NodeId) -> Descendants<G> {
+        Descendants {
+            graph: &self.graph,
+            ones: self.row(a).ones(),
+        }
+    }
+
+    fn row(&self, a: G::NodeId) -> &FixedBitSet {
+        let c = self.component[self.graph.to_index(a)];
+        &self.rows[c]
+    }
+}
+
+/// Iterator over the nodes reachable from a node.
+///
+/// Created with [`.descendants()`](struct.TransitiveClosure.html#method.descendants).
+pub struct Descendants<'a, G: 'a> {
+    graph: &'a G,
+    ones: Ones<'a>,
+}
+
+impl<'a, G> Iterator for Descendants<'a, G>
+    where G: GraphBase + NodeIndexable,
+{
+    type Item = G::NodeId;
+
+    fn next(&mut self) -> Option<G::NodeId> {
+        self.ones.next().map(|i| self.graph.from_index(i))
+    }
+}
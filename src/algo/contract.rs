@@ -0,0 +1,156 @@
+//! Contraction of chains of degree two nodes.
+
+use graph::{
+    Graph,
+    EdgeIndex,
+    NodeIndex,
+    IndexType,
+};
+use visit::EdgeRef;
+use {
+    Direction,
+    EdgeType,
+    Incoming,
+    Outgoing,
+};
+
+/// [Graph] Remove every degree two node that just passes a path through,
+/// replacing its two edges with a single edge.
+///
+/// A node `v` with edges `u - v` and `v - w` is removed, and the edge `u - w`
+/// is added with the weight `combine(weight(u - v), weight(v - w))`. Nodes
+/// for which `keep(index, &weight)` returns `true` are never removed. Return
+/// the number of nodes that were removed.
+///
+/// - `Undirected`: a node is contracted if it has exactly two edges.
+/// - `Directed`: a node is contracted if the edges form a through path: one
+///   incoming edge `u → v` and one outgoing edge `v → w`, which become
+///   `u → w`; or a two way path with edges to and from both `u` and `w`,
+///   which become `u → w` and `w → u`.
+///
+/// A node is not contracted if that would create a self loop: if it has a
+/// self loop, or if both its edges lead to the same neighbor. Contraction may
+/// create parallel edges, if `u` and `w` were already adjacent. Since a chain
+/// of degree two nodes keeps the degrees of the nodes at its ends, whole
+/// chains are contracted into one edge (except for a cycle of degree two
+/// nodes, which is contracted down to two nodes).
+///
+/// With an edge weight addition for `combine`, the length of the shortest
+/// path between any two remaining nodes is unchanged.
+///
+/// Node and edge indices are invalidated as in `remove_node`. `keep` is
+/// called with the index each node had before any node was removed.
+pub fn contract_degree_two_chains<N, E, Ty, Ix, F, P>(g: &mut Graph<N, E, Ty, Ix>,
+                                                       mut combine: F, keep: P) -> usize
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: FnMut(E, E) -> E,
+          P: Fn(NodeIndex<Ix>, &N) -> bool,
+{
+    let mut count = 0;
+    // Visit nodes from last to first: `remove_node` moves the last node into
+    // the removed node's place, so the nodes that were not yet visited keep
+    // their indices.
+    for i in (0..g.node_count()).rev() {
+        let v = NodeIndex::new(i);
+        if keep(v, &g[v]) {
+            continue;
+        }
+        let splices = match through_paths(g, v) {
+            Some(splices) => splices,
+            None => continue,
+        };
+
+        let mut edges = splices.iter()
+                               .flat_map(|&(_, a, b, _)| vec![a, b])
+                               .collect::<Vec<_>>();
+        edges.sort();
+        edges.dedup();
+        // Remove the edges from the highest index down, so the lower
+        // indices stay valid.
+        let mut weights = Vec::with_capacity(edges.len());
+        for &e in edges.iter().rev() {
+            weights.push((e, g.remove_edge(e)));
+        }
+        let mut take_weight = |e| {
+            weights.iter_mut()
+                   .find(|&&mut (f, _)| f == e)
+                   .and_then(|&mut (_, ref mut w)| w.take())
+                   .unwrap()
+        };
+
+        let last = NodeIndex::new(g.node_count() - 1);
+        g.remove_node(v);
+        let relocated = |n| if n == last { v } else { n };
+        for (u, a, b, w) in splices {
+            let weight = combine(take_weight(a), take_weight(b));
+            g.add_edge(relocated(u), relocated(w), weight);
+        }
+        count += 1;
+    }
+    count
+}
+
+/// The paths `u - v - w` through `v` that a contraction of `v` replaces, as
+/// (`u`, edge `u - v`, edge `v - w`, `w`), or `None` if `v` should not be
+/// contracted.
+fn through_paths<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, v: NodeIndex<Ix>)
+    -> Option<Vec<(NodeIndex<Ix>, EdgeIndex<Ix>, EdgeIndex<Ix>, NodeIndex<Ix>)>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    // at most `n` of the edges of `v` in direction `dir`, as (edge, other end)
+    let edges = |dir: Direction, n| {
+        let edges = g.edges_directed(v, dir)
+                     .map(|e| (e.id(), if e.source() == v { e.target() } else { e.source() }))
+                     .take(n + 1)
+                     .collect::<Vec<_>>();
+        if edges.len() == n && edges.iter().all(|&(_, u)| u != v) {
+            Some(edges)
+        } else {
+            None
+        }
+    };
+
+    if !g.is_directed() {
+        let es = match edges(Outgoing, 2) {
+            Some(es) => es,
+            None => return None,
+        };
+        let ((a, u), (b, w)) = (es[0], es[1]);
+        if u == w {
+            return None;
+        }
+        return Some(vec![(u, a, b, w)]);
+    }
+
+    let degree = g.edges_directed(v, Incoming).take(3).count();
+    if degree == 0 || degree > 2 || g.edges_directed(v, Outgoing).take(3).count() != degree {
+        return None;
+    }
+    let (ins, outs) = match (edges(Incoming, degree), edges(Outgoing, degree)) {
+        (Some(ins), Some(outs)) => (ins, outs),
+        _ => return None,
+    };
+    if degree == 1 {
+        let ((a, u), (b, w)) = (ins[0], outs[0]);
+        if u == w {
+            return None;
+        }
+        Some(vec![(u, a, b, w)])
+    } else {
+        let ((a, u), (c, w)) = (ins[0], ins[1]);
+        if u == w {
+            return None;
+        }
+        // pair up the two ways through `v`
+        let (b, d) = if outs[0].1 == w && outs[1].1 == u {
+            (outs[0].0, outs[1].0)
+        } else if outs[0].1 == u && outs[1].1 == w {
+            (outs[1].0, outs[0].0)
+        } else {
+            return None;
+        };
+        Some(vec![(u, a, b, w), (w, c, d, u)])
+    }
+}
@@ -0,0 +1,99 @@
+use std::cmp;
+
+use graph::{IndexType, NodeIndex, DefaultIx};
+use unionfind::UnionFind;
+
+/// Online connectivity for a forest that is built one edge at a time.
+///
+/// `ForestConnectivity` tracks which nodes are connected by the edges added
+/// so far, and refuses edges that would close a cycle. It is a thin wrapper
+/// over `UnionFind`, keyed by `NodeIndex<Ix>` so that it can be used next to
+/// a `Graph` or `StableGraph` with the same index type.
+///
+/// The forest grows as needed: adding an edge makes room for both of its
+/// endpoints, and nodes the forest has not seen yet are isolated.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::graph::node_index as n;
+/// use petgraph::algo::ForestConnectivity;
+///
+/// // stream edges into a graph, keeping it a forest
+/// let mut graph = Graph::<(), ()>::new();
+/// let mut forest = ForestConnectivity::<u32>::default();
+/// for _ in 0..4 {
+///     graph.add_node(());
+/// }
+/// for &(a, b) in &[(0, 1), (2, 3), (1, 0), (3, 0), (1, 2)] {
+///     if forest.try_add_edge(n(a), n(b)) {
+///         graph.add_edge(n(a), n(b), ());
+///     }
+/// }
+/// assert_eq!(graph.edge_count(), 3);
+/// assert!(forest.connected(n(1), n(2)));
+/// assert_eq!(forest.tree_count(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ForestConnectivity<Ix = DefaultIx> {
+    sets: UnionFind<Ix>,
+}
+
+impl<Ix> Default for ForestConnectivity<Ix>
+    where Ix: IndexType
+{
+    fn default() -> Self {
+        ForestConnectivity { sets: UnionFind::new_empty() }
+    }
+}
+
+impl<Ix> ForestConnectivity<Ix>
+    where Ix: IndexType
+{
+    /// Create a forest of `n` isolated nodes, with indices `0` to `n - 1`.
+    pub fn new(n: usize) -> Self {
+        ForestConnectivity { sets: UnionFind::new(n) }
+    }
+
+    /// Return the number of nodes the forest has room for.
+    pub fn node_bound(&self) -> usize {
+        self.sets.len()
+    }
+
+    /// Return the number of trees in the forest, counting isolated nodes.
+    pub fn tree_count(&self) -> usize {
+        self.sets.len_sets()
+    }
+
+    /// Add an edge between `a` and `b`, unless they are already connected.
+    ///
+    /// Return `true` if the edge was added, and `false` if it would close a
+    /// cycle (this includes a self loop), in which case the forest is
+    /// unchanged.
+    ///
+    /// **Panics** if an index is out of range for `Ix`.
+    pub fn try_add_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> bool {
+        let max = cmp::max(a.index(), b.index());
+        while self.sets.len() <= max {
+            self.sets.insert();
+        }
+        self.sets.union(Ix::new(a.index()), Ix::new(b.index()))
+    }
+
+    /// Return `true` if there is a path between `a` and `b` in the forest.
+    ///
+    /// A node is always connected to itself.
+    pub fn connected(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> bool {
+        if a == b {
+            return true;
+        }
+        let n = self.sets.len();
+        a.index() < n && b.index() < n &&
+            self.sets.find(Ix::new(a.index())) == self.sets.find(Ix::new(b.index()))
+    }
+
+    /// Remove all edges, keeping room for the same nodes.
+    pub fn reset(&mut self) {
+        let n = self.sets.len();
+        self.sets.reset(n);
+    }
+}
@@ -0,0 +1,171 @@
+//! Maximum weight bipartite matching with the Hungarian algorithm.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Sub;
+
+use visit::{
+    EdgeRef,
+    IntoEdgeReferences,
+    IntoNodeIdentifiers,
+    NodeIndexable,
+};
+use super::Measure;
+
+/// [Generic] Compute a matching of maximum total weight in a bipartite
+/// graph.
+///
+/// The nodes in `left` form one side of the graph, and all other nodes form
+/// the other side; the two sides may have different sizes. Edges are used in
+/// either direction, edges between two nodes of the same side are ignored,
+/// and of parallel edges only the heaviest is used. `edge_weight` returns the
+/// weight of an edge.
+///
+/// Pairs of nodes without an edge can't be matched. Leaving a node unmatched
+/// is always allowed, so edges of negative weight are never used, and edges
+/// of weight zero are left out of the result.
+///
+/// Return the matched pairs as (node of `left`, node of the other side), in
+/// the order of `left`, and the total weight.
+///
+/// Uses the Hungarian algorithm.
+///
+/// Computes in **O(|V|³)** time.
+pub fn max_weight_bipartite_matching<G, F, K>(g: G, left: &[G::NodeId], mut edge_weight: F)
+    -> (Vec<(G::NodeId, G::NodeId)>, K)
+    where G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+          G::NodeId: Eq + Hash,
+          F: FnMut(G::EdgeRef) -> K,
+          K: Measure + Copy + Sub<Output=K>,
+{
+    let zero = K::default();
+    let mut row_of = HashMap::new();
+    for (i, &node) in left.iter().enumerate() {
+        row_of.insert(node, i);
+    }
+    let right = g.node_identifiers()
+                 .filter(|node| !row_of.contains_key(node))
+                 .collect::<Vec<_>>();
+    let mut column_of = vec![!0; g.node_bound()];
+    for (j, &node) in right.iter().enumerate() {
+        column_of[g.to_index(node)] = j;
+    }
+
+    // The heaviest edge of each pair, if any.
+    let mut weight = vec![vec![None; right.len()]; left.len()];
+    for edge in g.edge_references() {
+        let (a, b) = (edge.source(), edge.target());
+        let (row, column) = match (row_of.get(&a), row_of.get(&b)) {
+            (Some(&i), None) => (i, column_of[g.to_index(b)]),
+            (None, Some(&i)) => (i, column_of[g.to_index(a)]),
+            _ => continue,
+        };
+        let w = edge_weight(edge);
+        let cell = &mut weight[row][column];
+        match *cell {
+            Some(old) if !(w > old) => {}
+            _ => *cell = Some(w),
+        }
+    }
+
+    // Minimize the negated weights in a square matrix, where pairs without
+    // a (positive) edge cost zero, like leaving both nodes unmatched.
+    let n = if left.len() > right.len() { left.len() } else { right.len() };
+    let cost = |i: usize, j: usize| {
+        match weight.get(i).and_then(|row| row.get(j)) {
+            Some(&Some(w)) if w > zero => zero - w,
+            _ => zero,
+        }
+    };
+    let assignment = hungarian(n, cost);
+
+    let mut pairs = Vec::new();
+    let mut total = zero;
+    for (i, &node) in left.iter().enumerate() {
+        let j = assignment[i];
+        if j < right.len() {
+            if let Some(w) = weight[i][j] {
+                if w > zero {
+                    pairs.push((node, right[j]));
+                    total = total + w;
+                }
+            }
+        }
+    }
+    (pairs, total)
+}
+
+/// Solve the assignment problem for an `n` by `n` cost matrix: return the
+/// column assigned to each row, minimizing the total cost.
+fn hungarian<K, C>(n: usize, cost: C) -> Vec<usize>
+    where K: Measure + Copy + Sub<Output=K>,
+          C: Fn(usize, usize) -> K,
+{
+    let zero = K::default();
+    // Potentials of rows (u) and columns (v), and the row assigned to each
+    // column (p); column 0 and row 0 are sentinels, so indices are 1-based.
+    let mut u = vec![zero; n + 1];
+    let mut v = vec![zero; n + 1];
+    let mut p = vec![0; n + 1];
+    let mut way = vec![0; n + 1];
+    let mut minv = vec![None; n + 1];
+    let mut used = vec![false; n + 1];
+    for i in 1..n + 1 {
+        p[0] = i;
+        let mut j0 = 0;
+        for m in &mut minv {
+            *m = None;
+        }
+        for x in &mut used {
+            *x = false;
+        }
+        // grow an alternating tree from row i until a free column is found
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = None;
+            let mut j1 = 0;
+            for j in 1..n + 1 {
+                if used[j] {
+                    continue;
+                }
+                let reduced = cost(i0 - 1, j - 1) - u[i0] - v[j];
+                if minv[j].map_or(true, |m| reduced < m) {
+                    minv[j] = Some(reduced);
+                    way[j] = j0;
+                }
+                if delta.map_or(true, |d| minv[j].unwrap() < d) {
+                    delta = minv[j];
+                    j1 = j;
+                }
+            }
+            let delta = delta.unwrap();
+            for j in 0..n + 1 {
+                if used[j] {
+                    u[p[j]] = u[p[j]] + delta;
+                    v[j] = v[j] - delta;
+                } else {
+                    minv[j] = minv[j].map(|m| m - delta);
+                }
+            }
+            j0 = j1;
+            if p[j0] == 0 {
+                break;
+            }
+        }
+        // flip the augmenting path
+        loop {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+            if j0 == 0 {
+                break;
+            }
+        }
+    }
+    let mut assignment = vec![0; n];
+    for j in 1..n + 1 {
+        assignment[p[j] - 1] = j - 1;
+    }
+    assignment
+}
@@ -0,0 +1,248 @@
+//! Approximate distances and A* search with landmarks (ALT).
+
+use std::hash::Hash;
+use std::ops::Sub;
+
+#[cfg(feature = "rand")]
+use rand::Rng;
+
+use astar::astar_impl;
+use path::Path;
+use visit::{
+    GraphBase,
+    GraphProp,
+    IntoEdges,
+    IntoEdgesDirected,
+    IntoNodeIdentifiers,
+    NodeIndexable,
+    Reversed,
+    Visitable,
+};
+use super::{dijkstra_into, DijkstraSpace, Measure};
+
+/// Shortest path distances from landmarks, for distance estimates and for
+/// A* search with landmark lower bounds (ALT).
+///
+/// For each landmark `L`, the distances from `L` to every node and from
+/// every node to `L` are precomputed. By the triangle inequality, they bound
+/// the distance from `a` to `b`:
+///
+/// - from above by `d(a, L) + d(L, b)`,
+/// - from below by `d(L, b) - d(L, a)` and by `d(a, L) - d(b, L)`.
+///
+/// Landmarks far out at the edge of the graph give the best bounds.
+///
+/// Edge costs must be non-negative. `Landmarks` only keeps the distances,
+/// in **O(k |V|)** memory for k landmarks, so `alt_astar` takes the edge
+/// costs again.
+#[derive(Clone, Debug)]
+pub struct Landmarks<G, K>
+    where G: GraphBase,
+{
+    graph: G,
+    landmarks: Vec<G::NodeId>,
+    /// Distances from each landmark, by node index.
+    from: Vec<Vec<Option<K>>>,
+    /// Distances to each landmark, by node index.
+    to: Vec<Vec<Option<K>>>,
+}
+
+/// A shortest path found by `Landmarks::alt_astar`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AltPath<N, K> {
+    /// The total cost of the path.
+    pub cost: K,
+    /// The nodes of the path, from the start to the goal.
+    pub path: Path<N>,
+    /// The number of nodes the search settled, a measure of its work.
+    pub settled: usize,
+}
+
+impl<G, K> Landmarks<G, K>
+    where G: IntoEdgesDirected + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+          K: Measure + Copy + Sub<Output=K>,
+{
+    /// [Generic] Compute the distances from and to each of `landmarks`.
+    ///
+    /// `edge_cost` returns the cost of an edge, which must be
+    /// non-negative.
+    ///
+    /// Computes in **O(k (|E| + |V|) log |V|)** time for k landmarks.
+    pub fn from_nodes<F>(g: G, landmarks: &[G::NodeId], mut edge_cost: F) -> Self
+        where F: FnMut(G::EdgeRef) -> K,
+    {
+        let mut lm = Landmarks::empty(g);
+        for &node in landmarks {
+            lm.add_landmark(node, &mut edge_cost);
+        }
+        lm
+    }
+
+    /// [Generic] Pick `k` landmarks and compute the distances from and to
+    /// each of them.
+    ///
+    /// The first landmark is picked at random, and each next one is the
+    /// node farthest from the landmarks so far (farthest point sampling);
+    /// nodes that no landmark reaches count as the farthest. If the graph
+    /// has fewer than `k` nodes, all nodes are landmarks.
+    ///
+    /// `edge_cost` returns the cost of an edge, which must be
+    /// non-negative.
+    ///
+    /// Requires crate feature `"rand"`.
+    ///
+    /// Computes in **O(k (|E| + |V|) log |V|)** time.
+    #[cfg(feature = "rand")]
+    pub fn new<R, F>(g: G, rng: &mut R, k: usize, mut edge_cost: F) -> Self
+        where R: Rng,
+              F: FnMut(G::EdgeRef) -> K,
+    {
+        let mut lm = Landmarks::empty(g);
+        let nodes = g.node_identifiers().collect::<Vec<_>>();
+        if nodes.is_empty() || k == 0 {
+            return lm;
+        }
+        lm.add_landmark(nodes[rng.gen_range(0, nodes.len())], &mut edge_cost);
+        // the distance from the nearest landmark, by node index
+        let mut nearest = lm.from[0].clone();
+        while lm.landmarks.len() < k && lm.landmarks.len() < nodes.len() {
+            let mut farthest: Option<(Option<K>, G::NodeId)> = None;
+            for &node in &nodes {
+                let d = nearest[g.to_index(node)];
+                if lm.landmarks.contains(&node) {
+                    continue;
+                }
+                let farther = match farthest {
+                    None => true,
+                    Some((None, _)) => false,
+                    Some((Some(fd), _)) => d.map_or(true, |d| d > fd),
+                };
+                if farther {
+                    farthest = Some((d, node));
+                }
+            }
+            let node = match farthest {
+                None => break,
+                Some((_, node)) => node,
+            };
+            lm.add_landmark(node, &mut edge_cost);
+            let last = lm.from.last().unwrap();
+            for (n, &d) in nearest.iter_mut().zip(last) {
+                *n = match (*n, d) {
+                    (Some(x), Some(y)) => Some(if y < x { y } else { x }),
+                    (x, None) => x,
+                    (None, y) => y,
+                };
+            }
+        }
+        lm
+    }
+
+    fn empty(g: G) -> Self {
+        Landmarks {
+            graph: g,
+            landmarks: Vec::new(),
+            from: Vec::new(),
+            to: Vec::new(),
+        }
+    }
+
+    fn add_landmark<F>(&mut self, node: G::NodeId, edge_cost: &mut F)
+        where F: FnMut(G::EdgeRef) -> K,
+    {
+        let g = self.graph;
+        let mut space = DijkstraSpace::new();
+        let mut from = vec![None; g.node_bound()];
+        dijkstra_into(g, node, &mut *edge_cost, &mut from, &mut space);
+        let to = if g.is_directed() {
+            let mut to = vec![None; g.node_bound()];
+            dijkstra_into(Reversed(g), node, |e| edge_cost(e.into_unreversed()),
+                          &mut to, &mut space);
+            to
+        } else {
+            from.clone()
+        };
+        self.landmarks.push(node);
+        self.from.push(from);
+        self.to.push(to);
+    }
+}
+
+impl<G, K> Landmarks<G, K>
+    where G: NodeIndexable,
+          K: Measure + Copy + Sub<Output=K>,
+{
+    /// Return the landmarks.
+    pub fn landmarks(&self) -> &[G::NodeId] {
+        &self.landmarks
+    }
+
+    /// Return a lower and an upper bound of the distance from `a` to `b`.
+    ///
+    /// The upper bound is `None` if no landmark is on a route from `a` to
+    /// `b`. If `b` is not reachable from `a`, the upper bound is `None` too.
+    ///
+    /// Computes in **O(k)** time.
+    pub fn estimate(&self, a: G::NodeId, b: G::NodeId) -> (K, Option<K>) {
+        let (a, b) = (self.graph.to_index(a), self.graph.to_index(b));
+        let lower = self.lower_bound(a, b).unwrap_or(K::default());
+        let mut upper = None;
+        for (from, to) in self.from.iter().zip(&self.to) {
+            if let (Some(x), Some(y)) = (to[a], from[b]) {
+                let d = x + y;
+                if upper.map_or(true, |u| d < u) {
+                    upper = Some(d);
+                }
+            }
+        }
+        if a == b {
+            upper = Some(K::default());
+        }
+        (lower, upper)
+    }
+
+    /// Return a lower bound of the distance from node index `a` to node
+    /// index `b`, or `None` if a landmark shows that `b` is not reachable.
+    fn lower_bound(&self, a: usize, b: usize) -> Option<K> {
+        let zero = K::default();
+        let mut lower = zero;
+        for (from, to) in self.from.iter().zip(&self.to) {
+            match (from[a], from[b]) {
+                (Some(x), Some(y)) => if y > x && y - x > lower { lower = y - x },
+                // L reaches a, but not b
+                (Some(_), None) => return None,
+                _ => {}
+            }
+            match (to[a], to[b]) {
+                (Some(x), Some(y)) => if x > y && x - y > lower { lower = x - y },
+                // b reaches L, but a doesn't
+                (None, Some(_)) => return None,
+                _ => {}
+            }
+        }
+        Some(lower)
+    }
+
+    /// Find a shortest path from `a` to `b`, with A* search using the
+    /// landmark lower bounds as the heuristic. Return `None` if `b` is not
+    /// reachable from `a`.
+    ///
+    /// The lower bounds are consistent, so the path is a shortest path;
+    /// with good landmarks, the search settles far fewer nodes than
+    /// Dijkstra's algorithm.
+    ///
+    /// `edge_cost` returns the cost of an edge, and must be the cost
+    /// function the landmarks were computed with.
+    pub fn alt_astar<F>(&self, a: G::NodeId, b: G::NodeId, edge_cost: F)
+        -> Option<AltPath<G::NodeId, K>>
+        where G: IntoEdges + Visitable,
+              G::NodeId: Eq + Hash,
+              F: FnMut(G::EdgeRef) -> K,
+    {
+        let g = self.graph;
+        let t = g.to_index(b);
+        // a node that can't reach `b` is not worth visiting
+        astar_impl(g, a, |x| x == b, edge_cost, |x| self.lower_bound(g.to_index(x), t))
+            .map(|(cost, path, settled)| AltPath { cost: cost, path: path, settled: settled })
+    }
+}
@@ -0,0 +1,244 @@
+//! Maximum matching in general graphs.
+
+use std::collections::VecDeque;
+
+use visit::{
+    EdgeRef,
+    GraphBase,
+    IntoEdgeReferences,
+    NodeIndexable,
+};
+
+const NONE: usize = !0;
+
+/// [Generic] Compute a maximum matching: a largest set of edges of which no
+/// two share a node.
+///
+/// The graph is treated as undirected. Self loops are ignored, and parallel
+/// edges count as one adjacency; of parallel edges, the first in the order of
+/// `edge_references` is the one put in the matching.
+///
+/// Returns a `Matching`, with the matched edges and the partner of each
+/// matched node.
+///
+/// Uses Edmonds' blossom algorithm.
+///
+/// Computes in **O(|V|³)** time.
+pub fn maximum_matching<G>(g: G) -> Matching<G>
+    where G: IntoEdgeReferences + NodeIndexable,
+{
+    let n = g.node_bound();
+    // adjacency lists without self loops or parallel edges, and the edge
+    // that joins each pair
+    let mut adjacency = vec![Vec::new(); n];
+    let mut joining = vec![Vec::new(); n];
+    for edge in g.edge_references() {
+        let (a, b) = (g.to_index(edge.source()), g.to_index(edge.target()));
+        if a == b || adjacency[a].contains(&b) {
+            continue;
+        }
+        adjacency[a].push(b);
+        adjacency[b].push(a);
+        joining[a].push(edge.id());
+        joining[b].push(edge.id());
+    }
+
+    let mut mate = vec![NONE; n];
+    // start from a greedy matching
+    for v in 0..n {
+        if mate[v] == NONE {
+            if let Some(&w) = adjacency[v].iter().find(|&&w| mate[w] == NONE) {
+                mate[v] = w;
+                mate[w] = v;
+            }
+        }
+    }
+    let mut search = Search::new(n);
+    for root in 0..n {
+        if mate[root] != NONE {
+            continue;
+        }
+        // flip the augmenting path, if any, that ends at `v`
+        let mut v = search.augmenting_path(&adjacency, &mate, root);
+        while v != NONE {
+            let pv = search.parent[v];
+            let ppv = mate[pv];
+            mate[v] = pv;
+            mate[pv] = v;
+            v = ppv;
+        }
+    }
+
+    let mut edges = Vec::new();
+    for v in 0..n {
+        let w = mate[v];
+        if w != NONE && v < w {
+            let i = adjacency[v].iter().position(|&x| x == w).unwrap();
+            edges.push(joining[v][i]);
+        }
+    }
+    Matching {
+        graph: g,
+        mate: mate,
+        edges: edges,
+    }
+}
+
+/// The state of the search for an augmenting path.
+struct Search {
+    /// The base of the blossom that contains each node.
+    base: Vec<usize>,
+    /// The parent of each odd node in the alternating tree.
+    parent: Vec<usize>,
+    /// The nodes that are even in the alternating tree.
+    used: Vec<bool>,
+    /// Scratch marks for blossoms and their lowest common ancestor.
+    blossom: Vec<bool>,
+    ancestor: Vec<bool>,
+    queue: VecDeque<usize>,
+}
+
+impl Search {
+    fn new(n: usize) -> Self {
+        Search {
+            base: vec![0; n],
+            parent: vec![NONE; n],
+            used: vec![false; n],
+            blossom: vec![false; n],
+            ancestor: vec![false; n],
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Grow an alternating tree from the free node `root`, contracting
+    /// blossoms on the way, and return the free node at the end of an
+    /// augmenting path, or `NONE`.
+    fn augmenting_path(&mut self, adjacency: &[Vec<usize>], mate: &[usize], root: usize)
+        -> usize
+    {
+        for (i, b) in self.base.iter_mut().enumerate() {
+            *b = i;
+        }
+        for p in &mut self.parent {
+            *p = NONE;
+        }
+        for u in &mut self.used {
+            *u = false;
+        }
+        self.queue.clear();
+        self.used[root] = true;
+        self.queue.push_back(root);
+        while let Some(v) = self.queue.pop_front() {
+            for &to in &adjacency[v] {
+                if self.base[v] == self.base[to] || mate[v] == to {
+                    continue;
+                }
+                if to == root || mate[to] != NONE && self.parent[mate[to]] != NONE {
+                    // an odd cycle: contract the blossom
+                    let base = self.common_ancestor(mate, v, to);
+                    for b in &mut self.blossom {
+                        *b = false;
+                    }
+                    self.mark_path(mate, v, base, to);
+                    self.mark_path(mate, to, base, v);
+                    for i in 0..self.base.len() {
+                        if self.blossom[self.base[i]] {
+                            self.base[i] = base;
+                            if !self.used[i] {
+                                self.used[i] = true;
+                                self.queue.push_back(i);
+                            }
+                        }
+                    }
+                } else if self.parent[to] == NONE {
+                    self.parent[to] = v;
+                    if mate[to] == NONE {
+                        return to;
+                    }
+                    self.used[mate[to]] = true;
+                    self.queue.push_back(mate[to]);
+                }
+            }
+        }
+        NONE
+    }
+
+    /// Return the base of the lowest common ancestor of `a` and `b` in the
+    /// alternating tree.
+    fn common_ancestor(&mut self, mate: &[usize], mut a: usize, mut b: usize) -> usize {
+        for x in &mut self.ancestor {
+            *x = false;
+        }
+        loop {
+            a = self.base[a];
+            self.ancestor[a] = true;
+            if mate[a] == NONE {
+                break;
+            }
+            a = self.parent[mate[a]];
+        }
+        loop {
+            b = self.base[b];
+            if self.ancestor[b] {
+                return b;
+            }
+            b = self.parent[mate[b]];
+        }
+    }
+
+    /// Mark the blossoms on the path from `v` up to `base`, and point the
+    /// parents along it back towards `child`.
+    fn mark_path(&mut self, mate: &[usize], mut v: usize, base: usize, mut child: usize) {
+        while self.base[v] != base {
+            self.blossom[self.base[v]] = true;
+            self.blossom[self.base[mate[v]]] = true;
+            self.parent[v] = child;
+            child = mate[v];
+            v = self.parent[mate[v]];
+        }
+    }
+}
+
+/// A matching of a graph, computed by `maximum_matching`.
+#[derive(Clone, Debug)]
+pub struct Matching<G>
+    where G: GraphBase,
+{
+    graph: G,
+    /// The partner of each node index, or `NONE`.
+    mate: Vec<usize>,
+    edges: Vec<G::EdgeId>,
+}
+
+impl<G> Matching<G>
+    where G: NodeIndexable,
+{
+    /// Return the matched edges, one for each matched pair of nodes.
+    pub fn edges(&self) -> &[G::EdgeId] {
+        &self.edges
+    }
+
+    /// Return the number of matched edges.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Return `true` if no edge is matched.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+
+    /// Return the node that `a` is matched with, or `None` if it is not
+    /// matched.
+    pub fn mate(&self, a: G::NodeId) -> Option<G::NodeId> {
+        match self.mate.get(self.graph.to_index(a)) {
+            Some(&w) if w != NONE => Some(self.graph.from_index(w)),
+            _ => None,
+        }
+    }
+
+    /// Return `true` if `a` is matched.
+    pub fn contains_node(&self, a: G::NodeId) -> bool {
+        self.mate(a).is_some()
+    }
+}
@@ -0,0 +1,187 @@
+//! Minimum cost maximum flow.
+
+use std::collections::BinaryHeap;
+use std::ops::{Mul, Sub};
+
+use scored::MinScored;
+use visit::{
+    EdgeRef,
+    IntoEdgeReferences,
+    NodeIndexable,
+};
+use super::{Measure, NegativeCycle};
+
+/// An arc of the residual graph. Arc `2 * i` is graph edge `i`, and arc
+/// `2 * i + 1` is its reverse.
+struct Arc<K> {
+    target: usize,
+    residual: K,
+    cost: K,
+}
+
+/// [Generic] Compute a maximum flow from `source` to `sink` of minimum total
+/// cost.
+///
+/// `capacity` returns the capacity of an edge and `cost` the cost of one
+/// unit of flow along it; capacities must be non-negative, but costs may be
+/// negative. The graph is treated as directed.
+///
+/// Uses successive shortest augmenting paths: the node potentials are
+/// initialized with Bellman–Ford, and then each shortest path is found with
+/// Dijkstra's algorithm on the reduced costs. If the residual graph has a
+/// cycle of negative cost reachable from `source`, a `NegativeCycle` error is
+/// returned.
+///
+/// On success, return the flow value, the total cost, and the flow along
+/// each edge, in the order of `edge_references` (for a `Graph`, that is
+/// indexed by edge index).
+///
+/// Computes in **O(|V| |E| + F (|E| log |V|))** time, where F is the number
+/// of augmenting paths, which is at most the flow value for integer
+/// capacities.
+pub fn min_cost_max_flow<G, F, C, K>(g: G, source: G::NodeId, sink: G::NodeId,
+                                     mut capacity: F, mut cost: C)
+    -> Result<(K, K, Vec<K>), NegativeCycle>
+    where G: IntoEdgeReferences + NodeIndexable,
+          F: FnMut(G::EdgeRef) -> K,
+          C: FnMut(G::EdgeRef) -> K,
+          K: Measure + Copy + Sub<Output=K> + Mul<Output=K>,
+{
+    let zero = K::default();
+    let n = g.node_bound();
+    let mut arcs = Vec::new();
+    let mut adjacency = vec![Vec::new(); n];
+    for edge in g.edge_references() {
+        let (a, b) = (g.to_index(edge.source()), g.to_index(edge.target()));
+        let edge_cost = cost(edge);
+        adjacency[a].push(arcs.len());
+        arcs.push(Arc { target: b, residual: capacity(edge), cost: edge_cost });
+        adjacency[b].push(arcs.len());
+        arcs.push(Arc { target: a, residual: zero, cost: zero - edge_cost });
+    }
+    let (s, t) = (g.to_index(source), g.to_index(sink));
+
+    let mut potential = try!(initial_potentials(&arcs, &adjacency, s));
+    let mut flow = zero;
+    let mut dist = vec![None; n];
+    let mut pred_arc = vec![!0; n];
+    let mut heap = BinaryHeap::new();
+    loop {
+        // Dijkstra on the reduced costs, which are non-negative
+        for d in &mut dist {
+            *d = None;
+        }
+        dist[s] = Some(zero);
+        heap.push(MinScored(zero, s));
+        while let Some(MinScored(d, u)) = heap.pop() {
+            match dist[u] {
+                Some(du) if d > du => continue,
+                _ => {}
+            }
+            for &i in &adjacency[u] {
+                let arc = &arcs[i];
+                if !(arc.residual > zero) {
+                    continue;
+                }
+                let v = arc.target;
+                let nd = d + arc.cost + potential[u] - potential[v];
+                let improved = match dist[v] {
+                    Some(dv) => nd < dv,
+                    None => true,
+                };
+                if improved {
+                    dist[v] = Some(nd);
+                    pred_arc[v] = i;
+                    heap.push(MinScored(nd, v));
+                }
+            }
+        }
+        // no flow from a node to itself
+        if s == t || dist[t].is_none() {
+            break;
+        }
+        for (p, d) in potential.iter_mut().zip(&dist) {
+            if let Some(d) = *d {
+                *p = *p + d;
+            }
+        }
+
+        // find the bottleneck of the path, then augment
+        let mut bottleneck = None;
+        let mut v = t;
+        while v != s {
+            let i = pred_arc[v];
+            let r = arcs[i].residual;
+            bottleneck = match bottleneck {
+                Some(b) if !(r < b) => Some(b),
+                _ => Some(r),
+            };
+            v = arcs[i ^ 1].target;
+        }
+        let bottleneck = bottleneck.unwrap();
+        let mut v = t;
+        while v != s {
+            let i = pred_arc[v];
+            arcs[i].residual = arcs[i].residual - bottleneck;
+            arcs[i ^ 1].residual = arcs[i ^ 1].residual + bottleneck;
+            v = arcs[i ^ 1].target;
+        }
+        flow = flow + bottleneck;
+    }
+
+    // the flow along an edge is the residual capacity of its reverse arc
+    let mut total_cost = zero;
+    let edge_flow = arcs.chunks(2).map(|pair| {
+        let f = pair[1].residual;
+        total_cost = total_cost + f * pair[0].cost;
+        f
+    }).collect();
+    Ok((flow, total_cost, edge_flow))
+}
+
+/// Shortest path distances from `s` with Bellman–Ford, over the arcs with
+/// residual capacity. Nodes that `s` can't reach get potential zero.
+fn initial_potentials<K>(arcs: &[Arc<K>], adjacency: &[Vec<usize>], s: usize)
+    -> Result<Vec<K>, NegativeCycle>
+    where K: Measure + Copy,
+{
+    let zero = K::default();
+    let n = adjacency.len();
+    let mut dist = vec![None; n];
+    dist[s] = Some(zero);
+    let relax = |dist: &mut [Option<K>]| {
+        let mut changed = false;
+        for u in 0..n {
+            let du = match dist[u] {
+                Some(du) => du,
+                None => continue,
+            };
+            for &i in &adjacency[u] {
+                let arc = &arcs[i];
+                if !(arc.residual > zero) {
+                    continue;
+                }
+                let nd = du + arc.cost;
+                let improved = match dist[arc.target] {
+                    Some(dv) => nd < dv,
+                    None => true,
+                };
+                if improved {
+                    dist[arc.target] = Some(nd);
+                    changed = true;
+                }
+            }
+        }
+        changed
+    };
+    for _ in 1..n {
+        if !relax(&mut dist) {
+            break;
+        }
+    }
+    // a further improvement means there is a negative cycle
+    if relax(&mut dist) {
+        return Err(NegativeCycle(()));
+    }
+    Ok(dist.into_iter().map(|d| d.unwrap_or(zero)).collect())
+}
@@ -5,10 +5,29 @@
 //! the `Graph` type.
 
 pub mod dominators;
-
-use std::collections::BinaryHeap;
+mod approx;
+mod betweenness;
+mod closure;
+mod contract;
+mod summary;
+mod min_cost_flow;
+mod hungarian;
+mod matching;
+mod landmarks;
+mod wl_hash;
+mod tsp;
+mod product;
+mod path_cover;
+mod tree;
+mod forest;
+#[cfg(feature = "rand")]
+mod random_walk;
+
+use std::collections::{BinaryHeap, VecDeque};
 use std::cmp::min;
 
+use fixedbitset::FixedBitSet;
+
 use prelude::*;
 
 use super::{
@@ -41,8 +60,49 @@ use data::{
 pub use super::isomorphism::{
     is_isomorphic,
     is_isomorphic_matching,
+    find_isomorphism,
+    find_isomorphism_matching,
+    subgraph_isomorphisms_iter,
+    SubgraphIsomorphisms,
+};
+pub use self::approx::{
+    min_vertex_cover_approx,
+    max_independent_set_approx,
+};
+pub use self::betweenness::betweenness_centrality;
+#[cfg(feature = "rand")]
+pub use self::betweenness::betweenness_centrality_sampled;
+pub use self::closure::{
+    transitive_closure,
+    TransitiveClosure,
+    Descendants,
+};
+pub use self::contract::contract_degree_two_chains;
+pub use self::summary::{summary, GraphSummary};
+pub use self::min_cost_flow::min_cost_max_flow;
+pub use self::hungarian::max_weight_bipartite_matching;
+pub use self::matching::{maximum_matching, Matching};
+pub use self::landmarks::{Landmarks, AltPath};
+pub use self::wl_hash::weisfeiler_lehman_hash;
+pub use self::tsp::{tsp_nearest_neighbor, tsp_christofides_like};
+pub use self::product::{
+    cartesian_product,
+    tensor_product,
+    CartesianEdge,
+    ProductMap,
+};
+pub use self::path_cover::minimum_path_cover;
+pub use self::tree::tree_diameter;
+pub use self::forest::ForestConnectivity;
+#[cfg(feature = "rand")]
+pub use self::random_walk::{biased_random_walks, RandomWalk};
+pub use super::astar::astar;
+pub use super::dijkstra::{
+    dijkstra,
+    dijkstra_into,
+    dijkstra_restricted,
+    DijkstraSpace,
 };
-pub use super::dijkstra::dijkstra;
 
 /// [Generic] Return the number of connected components of the graph.
 ///
@@ -50,37 +110,122 @@ pub use super::dijkstra::dijkstra;
 pub fn connected_components<G>(g: G) -> usize
     where G: NodeCompactIndexable + IntoEdgeReferences,
 {
-    let mut vertex_sets = UnionFind::new(g.node_bound());
+    let mut sets = UnionFind::new(g.node_bound());
     for edge in g.edge_references() {
-        let (a, b) = (edge.source(), edge.target());
-
-        // union the two vertices of the edge
-        vertex_sets.union(g.to_index(a), g.to_index(b));
+        sets.union(g.to_index(edge.source()), g.to_index(edge.target()));
     }
-    let mut labels = vertex_sets.into_labeling();
-    labels.sort();
-    labels.dedup();
-    labels.len()
+    sets.len_sets()
 }
 
+/// [Generic] Label the connected components of the graph, without
+/// allocating.
+///
+/// For a directed graph, this is the *weakly* connected components.
+///
+/// `labels_out` must have a length of at least `g.node_bound()`. After the
+/// call, `labels_out[i]` is the component of the node with index `i`; the
+/// components are numbered from `0` in order of their lowest node index.
+/// Return the number of components.
+///
+/// `sets` is scratch space, which is reset to `g.node_bound()` elements. It
+/// only allocates if it has room for fewer elements than that.
+///
+/// **Panics** if `labels_out` is too short.
+pub fn connected_components_into<G>(g: G, labels_out: &mut [usize],
+                                    sets: &mut UnionFind<usize>) -> usize
+    where G: NodeCompactIndexable + IntoEdgeReferences,
+{
+    let n = g.node_bound();
+    let labels = &mut labels_out[..n];
+    sets.reset(n);
+    for edge in g.edge_references() {
+        sets.union(g.to_index(edge.source()), g.to_index(edge.target()));
+    }
+    // Number each set when its lowest element is reached.
+    for label in labels.iter_mut() {
+        *label = !0;
+    }
+    let mut count = 0;
+    for i in 0..n {
+        let rep = sets.find_mut(i);
+        if labels[rep] == !0 {
+            labels[rep] = count;
+            count += 1;
+        }
+        labels[i] = labels[rep];
+    }
+    count
+}
 
 /// [Generic] Return `true` if the input graph contains a cycle.
 ///
 /// Always treats the input graph as if undirected.
 pub fn is_cyclic_undirected<G>(g: G) -> bool
     where G: NodeIndexable + IntoEdgeReferences
+{
+    forest_edge_count(g).is_none()
+}
+
+/// Union the endpoints of every edge, and return the number of edges, or
+/// `None` if an edge closed a cycle (including self loops and parallel
+/// edges).
+fn forest_edge_count<G>(g: G) -> Option<usize>
+    where G: NodeIndexable + IntoEdgeReferences
 {
     let mut edge_sets = UnionFind::new(g.node_bound());
+    let mut count = 0;
     for edge in g.edge_references() {
         let (a, b) = (edge.source(), edge.target());
 
         // union the two vertices of the edge
         //  -- if they were already the same, then we have a cycle
         if !edge_sets.union(g.to_index(a), g.to_index(b)) {
-            return true
+            return None
         }
+        count += 1;
     }
-    false
+    Some(count)
+}
+
+/// [Generic] Return `true` if the input graph is a forest: it has no cycles,
+/// self loops or parallel edges.
+///
+/// Always treats the input graph as if undirected, so two edges in
+/// opposite directions between the same nodes are parallel edges.
+///
+/// The graph without nodes is a forest.
+pub fn is_forest<G>(g: G) -> bool
+    where G: NodeIndexable + IntoEdgeReferences
+{
+    !is_cyclic_undirected(g)
+}
+
+/// [Generic] Return `true` if the input graph is a tree: it is a forest
+/// (see `is_forest`) and it is connected.
+///
+/// Always treats the input graph as if undirected. A tree with `n` nodes has
+/// exactly `n - 1` edges.
+///
+/// The graph without nodes is not a tree, since it has no connected
+/// component; a single node is a tree.
+pub fn is_tree<G>(g: G) -> bool
+    where G: NodeCount + NodeIndexable + IntoEdgeReferences
+{
+    // an acyclic graph with one edge less than nodes is connected
+    let node_count = g.node_count();
+    node_count > 0 && forest_edge_count(g) == Some(node_count - 1)
+}
+
+/// [Generic] Return `true` if the input graph is a directed acyclic graph.
+///
+/// Self loops are cycles. For an undirected graph every edge is a cycle,
+/// so only a graph without edges is a DAG.
+///
+/// The graph without nodes is a DAG.
+pub fn is_dag<G>(g: G) -> bool
+    where G: IntoNodeIdentifiers + IntoNeighbors + Visitable,
+{
+    !is_cyclic_directed(g)
 }
 
 
@@ -98,61 +243,149 @@ pub fn is_cyclic_undirected<G>(g: G) -> bool
 pub fn toposort<G>(g: G, space: Option<&mut DfsSpaceType<G>>)
     -> Result<Vec<G::NodeId>, Cycle<G::NodeId>>
     where G: IntoNeighborsDirected + IntoNodeIdentifiers + Visitable,
+{
+    let mut local_space;
+    let space = match space {
+        Some(space) => space,
+        None => {
+            local_space = DfsSpace::new(g);
+            &mut local_space
+        }
+    };
+    let mut order = Vec::new();
+    toposort_into(g, &mut order, space).map(|()| order)
+}
+
+/// [Generic] Perform a topological sort of a directed graph, without
+/// allocating.
+///
+/// This is like `toposort`, but the nodes are written to `out` (which is
+/// cleared first) and all traversal state is kept in `space`. Once `out` and
+/// `space` have grown to fit the graph — a capacity of `g.node_bound()`
+/// for `out` is enough — repeated calls don't allocate.
+///
+/// If the graph has a cycle, return a `Cycle` error; `out` is left with
+/// unspecified contents.
+pub fn toposort_into<G>(g: G, out: &mut Vec<G::NodeId>, space: &mut DfsSpaceType<G>)
+    -> Result<(), Cycle<G::NodeId>>
+    where G: IntoNeighborsDirected + IntoNodeIdentifiers + Visitable,
 {
     // based on kosaraju scc
-    with_dfs(g, space, |dfs| {
-        dfs.reset(g);
-        let mut finished = g.visit_map();
+    let dfs = &mut space.dfs;
+    let finished = &mut space.finished;
+    dfs.reset(g);
+    g.reset_map(finished);
 
-        let mut finish_stack = Vec::new();
-        for i in g.node_identifiers() {
-            if dfs.discovered.is_visited(&i) {
-                continue;
-            }
-            dfs.stack.push(i);
-            while let Some(&nx) = dfs.stack.last() {
-                if dfs.discovered.visit(nx) {
-                    // First time visiting `nx`: Push neighbors, don't pop `nx`
-                    for succ in g.neighbors(nx) {
-                        if succ == nx {
-                            // self cycle
-                            return Err(Cycle(nx));
-                        }
-                        if !dfs.discovered.is_visited(&succ) {
-                            dfs.stack.push(succ);
-                        } 
-                    }
-                } else {
-                    dfs.stack.pop();
-                    if finished.visit(nx) {
-                        // Second time: All reachable nodes must have been finished
-                        finish_stack.push(nx);
+    let finish_stack = out;
+    finish_stack.clear();
+    for i in g.node_identifiers() {
+        if dfs.discovered.is_visited(&i) {
+            continue;
+        }
+        dfs.stack.push(i);
+        while let Some(&nx) = dfs.stack.last() {
+            if dfs.discovered.visit(nx) {
+                // First time visiting `nx`: Push neighbors, don't pop `nx`
+                for succ in g.neighbors(nx) {
+                    if succ == nx {
+                        // self cycle
+                        return Err(Cycle(nx));
                     }
+                    if !dfs.discovered.is_visited(&succ) {
+                        dfs.stack.push(succ);
+                    } 
+                }
+            } else {
+                dfs.stack.pop();
+                if finished.visit(nx) {
+                    // Second time: All reachable nodes must have been finished
+                    finish_stack.push(nx);
                 }
             }
         }
-        finish_stack.reverse();
+    }
+    finish_stack.reverse();
 
-        dfs.reset(g);
-        for &i in &finish_stack {
-            dfs.move_to(i);
-            let mut cycle = false;
-            while let Some(j) = dfs.next(Reversed(g)) {
-                if cycle {
-                    return Err(Cycle(j));
-                }
-                cycle = true;
+    dfs.reset(g);
+    for &i in finish_stack.iter() {
+        dfs.move_to(i);
+        let mut cycle = false;
+        while let Some(j) = dfs.next(Reversed(g)) {
+            if cycle {
+                return Err(Cycle(j));
             }
+            cycle = true;
         }
+    }
 
-        Ok(finish_stack)
-    })
+    Ok(())
+}
+
+/// [Generic] Assign each node of a directed acyclic graph to a layer, using
+/// longest path layering.
+///
+/// Return the layers in order: every edge goes from a node in a lower layer
+/// to a node in a strictly higher layer. Nodes without incoming edges are in
+/// the first layer, and every other node is in the lowest layer allowed by
+/// its predecessors (the length of the longest path reaching it). Within a
+/// layer, nodes are in topological order.
+///
+/// If the graph has a cycle, return a `Cycle` error. Self loops are also
+/// cycles.
+pub fn topological_layers<G>(g: G) -> Result<Vec<Vec<G::NodeId>>, Cycle<G::NodeId>>
+    where G: IntoNeighborsDirected + IntoNodeIdentifiers + Visitable + NodeIndexable,
+{
+    let order = try!(toposort(g, None));
+    let layer_of = layer_numbers(g, &order);
+    let mut layers = Vec::new();
+    for node in order {
+        let layer = layer_of[g.to_index(node)];
+        if layer >= layers.len() {
+            layers.resize(layer + 1, Vec::new());
+        }
+        layers[layer].push(node);
+    }
+    Ok(layers)
+}
+
+/// [Generic] Return the layer of each node of a directed acyclic graph, as
+/// assigned by `topological_layers`.
+///
+/// The vector is indexed by node index (see `NodeIndexable`); vacant indices
+/// get layer `0`.
+///
+/// If the graph has a cycle, return a `Cycle` error. Self loops are also
+/// cycles.
+pub fn topological_layer_numbers<G>(g: G) -> Result<Vec<usize>, Cycle<G::NodeId>>
+    where G: IntoNeighborsDirected + IntoNodeIdentifiers + Visitable + NodeIndexable,
+{
+    let order = try!(toposort(g, None));
+    Ok(layer_numbers(g, &order))
+}
+
+/// Longest path layering, given a topological order of the nodes.
+fn layer_numbers<G>(g: G, order: &[G::NodeId]) -> Vec<usize>
+    where G: IntoNeighbors + NodeIndexable,
+{
+    let mut layer_of = vec![0; g.node_bound()];
+    for &node in order {
+        let layer = layer_of[g.to_index(node)] + 1;
+        for succ in g.neighbors(node) {
+            let succ_layer = &mut layer_of[g.to_index(succ)];
+            if *succ_layer < layer {
+                *succ_layer = layer;
+            }
+        }
+    }
+    layer_of
 }
 
 /// [Generic] Return `true` if the input directed graph contains a cycle.
 ///
-/// This implementation is recursive; use `toposort` if an alternative is
-/// needed.
+/// The search is built on `depth_first_search` and is not recursive, so it
+/// can handle arbitrarily deep graphs.
+///
+/// Computes in **O(|V| + |E|)** time.
 pub fn is_cyclic_directed<G>(g: G) -> bool
     where G: IntoNodeIdentifiers + IntoNeighbors + Visitable,
 {
@@ -169,20 +402,28 @@ pub fn is_cyclic_directed<G>(g: G) -> bool
 type DfsSpaceType<G> where G: Visitable = DfsSpace<G::NodeId, G::Map>;
 
 /// Workspace for a graph traversal.
+///
+/// Algorithms that take an `Option<&mut DfsSpace>` allocate a fresh
+/// visit map and stack when given `None`. Create a `DfsSpace` once and pass
+/// it instead to reuse those allocations over repeated calls on the same
+/// graph.
 #[derive(Clone, Debug)]
 pub struct DfsSpace<N, VM> {
     dfs: Dfs<N, VM>,
+    finished: VM,
 }
 
 impl<N, VM> DfsSpace<N, VM>
     where N: Copy + PartialEq,
           VM: VisitMap<N>,
 {
+    /// Create a new workspace for traversals of `g`.
     pub fn new<G>(g: G) -> Self
         where G: GraphRef + Visitable<NodeId=N, Map=VM>,
     {
         DfsSpace {
-            dfs: Dfs::empty(g)
+            dfs: Dfs::empty(g),
+            finished: g.visit_map(),
         }
     }
 }
@@ -195,7 +436,8 @@ impl<N, VM> Default for DfsSpace<N, VM>
             dfs: Dfs {
                 stack: <_>::default(),
                 discovered: <_>::default(),
-            }
+            },
+            finished: <_>::default(),
         }
     }
 }
@@ -236,6 +478,149 @@ pub fn has_path_connecting<G>(g: G, from: G::NodeId, to: G::NodeId,
     })
 }
 
+/// [Generic] Return the nodes reachable from `a`, in depth first order.
+///
+/// `a` itself is not included, even if it is on a cycle.
+///
+/// If `space` is not `None`, it is used instead of creating a new workspace for
+/// graph traversal.
+///
+/// Computes in **O(|V| + |E|)** time.
+pub fn descendants<G>(g: G, a: G::NodeId, space: Option<&mut DfsSpaceType<G>>)
+    -> Vec<G::NodeId>
+    where G: IntoNeighbors + Visitable,
+{
+    let mut out = Vec::new();
+    with_dfs(g, space, |dfs| reachable_into(g, a, &mut out, dfs));
+    out
+}
+
+/// [Generic] Write the nodes reachable from `a` into `out`, without
+/// allocating.
+///
+/// This is like `descendants`, but `out` is cleared first and the traversal
+/// state is kept in `space`, so repeated calls don't allocate once `out` and
+/// `space` have grown to fit the graph.
+pub fn descendants_into<G>(g: G, a: G::NodeId, out: &mut Vec<G::NodeId>,
+                           space: &mut DfsSpaceType<G>)
+    where G: IntoNeighbors + Visitable,
+{
+    reachable_into(g, a, out, &mut space.dfs)
+}
+
+/// [Generic] Return the nodes that `a` is reachable from, in depth first
+/// order of the reversed graph.
+///
+/// `a` itself is not included, even if it is on a cycle.
+///
+/// If `space` is not `None`, it is used instead of creating a new workspace for
+/// graph traversal.
+///
+/// Computes in **O(|V| + |E|)** time.
+pub fn ancestors<G>(g: G, a: G::NodeId, space: Option<&mut DfsSpaceType<G>>)
+    -> Vec<G::NodeId>
+    where G: IntoNeighborsDirected + Visitable,
+{
+    let mut out = Vec::new();
+    with_dfs(g, space, |dfs| reachable_into(Reversed(g), a, &mut out, dfs));
+    out
+}
+
+/// [Generic] Write the nodes that `a` is reachable from into `out`, without
+/// allocating.
+///
+/// This is like `ancestors`, but `out` is cleared first and the traversal
+/// state is kept in `space`, so repeated calls don't allocate once `out` and
+/// `space` have grown to fit the graph.
+pub fn ancestors_into<G>(g: G, a: G::NodeId, out: &mut Vec<G::NodeId>,
+                         space: &mut DfsSpaceType<G>)
+    where G: IntoNeighborsDirected + Visitable,
+{
+    reachable_into(Reversed(g), a, out, &mut space.dfs)
+}
+
+fn reachable_into<G>(g: G, a: G::NodeId, out: &mut Vec<G::NodeId>,
+                     dfs: &mut Dfs<G::NodeId, G::Map>)
+    where G: IntoNeighbors + Visitable,
+{
+    out.clear();
+    dfs.reset(g);
+    dfs.move_to(a);
+    while let Some(x) = dfs.next(g) {
+        if x != a {
+            out.push(x);
+        }
+    }
+}
+
+/// [Generic] Breadth first search restricted to a subset of the nodes.
+///
+/// Only the nodes whose index (as given by `NodeIndexable::to_index`) is
+/// contained in `allowed` are visited, so the result is the same as running
+/// a `Bfs` from `start` on the subgraph induced by `allowed`, without
+/// building that subgraph.
+///
+/// Return the visited nodes in breadth first order, starting with `start`.
+/// If `start` is not in `allowed`, the result is empty.
+pub fn bfs_restricted<G>(g: G, start: G::NodeId, allowed: &FixedBitSet) -> Vec<G::NodeId>
+    where G: IntoNeighbors + Visitable + NodeIndexable,
+{
+    let mut order = Vec::new();
+    if !allowed.contains(g.to_index(start)) {
+        return order;
+    }
+    let mut discovered = g.visit_map();
+    let mut queue = VecDeque::new();
+    discovered.visit(start);
+    queue.push_back(start);
+    while let Some(node) = queue.pop_front() {
+        for succ in g.neighbors(node) {
+            if allowed.contains(g.to_index(succ)) && discovered.visit(succ) {
+                queue.push_back(succ);
+            }
+        }
+        order.push(node);
+    }
+    order
+}
+
+/// [Generic] Compute a breadth first spanning forest with the given roots.
+///
+/// Return the parent of each node in the forest and the tree edge to it,
+/// indexed by node index (as given by `NodeIndexable::to_index`); the
+/// entry is `None` for the roots and for the nodes that no root reaches.
+/// Edges are followed in their direction in a directed graph.
+///
+/// The search starts from all roots at once, so each node is in the tree of
+/// a root with the fewest edges to it, and the path to it in the forest is a
+/// path with the fewest edges from that root; ties go to the earlier root
+/// in `roots` and the earlier edge in the order of `edges`.
+///
+/// Computes in **O(|V| + |E|)** time.
+pub fn bfs_spanning_forest<G>(g: G, roots: &[G::NodeId])
+    -> Vec<Option<(G::NodeId, G::EdgeId)>>
+    where G: IntoEdges + NodeIndexable,
+{
+    let mut parent = vec![None; g.node_bound()];
+    let mut discovered = FixedBitSet::with_capacity(g.node_bound());
+    let mut queue = VecDeque::new();
+    for &root in roots {
+        if !discovered.put(g.to_index(root)) {
+            queue.push_back(root);
+        }
+    }
+    while let Some(node) = queue.pop_front() {
+        for edge in g.edges(node) {
+            let next = edge.target();
+            if !discovered.put(g.to_index(next)) {
+                parent[g.to_index(next)] = Some((node, edge.id()));
+                queue.push_back(next);
+            }
+        }
+    }
+    parent
+}
+
 /// Renamed to `kosaraju_scc`.
 #[deprecated(note = "renamed to kosaraju_scc")]
 pub fn scc<G>(g: G) -> Vec<Vec<G::NodeId>>
@@ -538,6 +923,10 @@ impl<N> Cycle<N> {
 #[derive(Clone, Debug)]
 pub struct NegativeCycle(());
 
+/// An algorithm error: the graph is not a tree.
+#[derive(Clone, Debug)]
+pub struct NotATree(());
+
 /// [Generic] Compute shortest paths from node `source` to all other.
 ///
 /// Using the [Bellman–Ford algorithm][bf]; negative edge costs are
@@ -0,0 +1,141 @@
+//! Cartesian and tensor products of graphs.
+
+use graph::{
+    Graph,
+    IndexType,
+    NodeIndex,
+};
+use EdgeType;
+
+/// The weight of an edge of a cartesian product: a copy of an edge of the
+/// first or of the second factor.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CartesianEdge<E1, E2> {
+    /// An edge of the first factor, between nodes with the same second
+    /// component.
+    First(E1),
+    /// An edge of the second factor, between nodes with the same first
+    /// component.
+    Second(E2),
+}
+
+/// The mapping between the nodes of a product graph and the pairs of nodes
+/// of its factors, returned by `cartesian_product` and `tensor_product`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ProductMap {
+    /// The node count of the second factor.
+    n2: usize,
+}
+
+impl ProductMap {
+    /// Return the product node of node `a` of the first factor and node `b`
+    /// of the second.
+    pub fn index<Ix: IndexType>(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> NodeIndex<Ix> {
+        NodeIndex::new(a.index() * self.n2 + b.index())
+    }
+
+    /// Return the nodes of the first and the second factor that product node
+    /// `p` is the pair of.
+    pub fn factors<Ix: IndexType>(&self, p: NodeIndex<Ix>) -> (NodeIndex<Ix>, NodeIndex<Ix>) {
+        (NodeIndex::new(p.index() / self.n2), NodeIndex::new(p.index() % self.n2))
+    }
+}
+
+/// [Graph] Return the cartesian product of `g` and `h`, with the mapping
+/// between its nodes and the pairs of nodes of `g` and `h`.
+///
+/// The product has a node `(a, b)` for each node `a` of `g` and `b` of `h`,
+/// and an edge from `(a, b)` to `(c, b)` for each edge from `a` to `c` in `g`,
+/// and from `(a, b)` to `(a, d)` for each edge from `b` to `d` in `h`. The
+/// product of two paths is a grid, of two cycles a torus.
+///
+/// The node weights are pairs of the weights in `g` and `h`, and the edge
+/// weights the weight of the edge they copy.
+///
+/// Computes in **O(|V₁||V₂| + |V₁||E₂| + |E₁||V₂|)** time.
+pub fn cartesian_product<N1, E1, N2, E2, Ty, Ix>(g: &Graph<N1, E1, Ty, Ix>,
+                                                 h: &Graph<N2, E2, Ty, Ix>)
+    -> (Graph<(N1, N2), CartesianEdge<E1, E2>, Ty, Ix>, ProductMap)
+    where N1: Clone,
+          E1: Clone,
+          N2: Clone,
+          E2: Clone,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    let map = ProductMap { n2: h.node_count() };
+    let edges = g.edge_count() * h.node_count() + g.node_count() * h.edge_count();
+    let mut p = Graph::with_capacity(g.node_count() * h.node_count(), edges);
+    add_nodes(&mut p, g, h);
+    for e in g.edge_indices() {
+        let (a, c) = g.edge_endpoints(e).unwrap();
+        for b in h.node_indices() {
+            p.add_edge(map.index(a, b), map.index(c, b), CartesianEdge::First(g[e].clone()));
+        }
+    }
+    for a in g.node_indices() {
+        for e in h.edge_indices() {
+            let (b, d) = h.edge_endpoints(e).unwrap();
+            p.add_edge(map.index(a, b), map.index(a, d), CartesianEdge::Second(h[e].clone()));
+        }
+    }
+    (p, map)
+}
+
+/// [Graph] Return the tensor product of `g` and `h`, with the mapping
+/// between its nodes and the pairs of nodes of `g` and `h`.
+///
+/// The product has a node `(a, b)` for each node `a` of `g` and `b` of `h`,
+/// and an edge from `(a, b)` to `(c, d)` for each edge from `a` to `c` in `g`
+/// and edge from `b` to `d` in `h`. For undirected graphs, a pair of edges
+/// also gives the edge between `(a, d)` and `(c, b)`, unless one of them is
+/// a self loop.
+///
+/// The node weights are pairs of the weights in `g` and `h`, and the edge
+/// weights pairs of the weights of the two edges.
+///
+/// Computes in **O(|V₁||V₂| + |E₁||E₂|)** time.
+pub fn tensor_product<N1, E1, N2, E2, Ty, Ix>(g: &Graph<N1, E1, Ty, Ix>,
+                                              h: &Graph<N2, E2, Ty, Ix>)
+    -> (Graph<(N1, N2), (E1, E2), Ty, Ix>, ProductMap)
+    where N1: Clone,
+          E1: Clone,
+          N2: Clone,
+          E2: Clone,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    let map = ProductMap { n2: h.node_count() };
+    let edges = g.edge_count() * h.edge_count() * if g.is_directed() { 1 } else { 2 };
+    let mut p = Graph::with_capacity(g.node_count() * h.node_count(), edges);
+    add_nodes(&mut p, g, h);
+    for e in g.edge_indices() {
+        let (a, c) = g.edge_endpoints(e).unwrap();
+        for f in h.edge_indices() {
+            let (b, d) = h.edge_endpoints(f).unwrap();
+            let weight = (g[e].clone(), h[f].clone());
+            p.add_edge(map.index(a, b), map.index(c, d), weight.clone());
+            if !g.is_directed() && a != c && b != d {
+                p.add_edge(map.index(a, d), map.index(c, b), weight);
+            }
+        }
+    }
+    (p, map)
+}
+
+/// Add the pairs of the nodes of `g` and `h` to `p`, in the order of
+/// `ProductMap`.
+fn add_nodes<N1, E1, N2, E2, E, Ty, Ix>(p: &mut Graph<(N1, N2), E, Ty, Ix>,
+                                        g: &Graph<N1, E1, Ty, Ix>,
+                                        h: &Graph<N2, E2, Ty, Ix>)
+    where N1: Clone,
+          N2: Clone,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    for a in g.node_indices() {
+        for b in h.node_indices() {
+            p.add_node((g[a].clone(), h[b].clone()));
+        }
+    }
+}
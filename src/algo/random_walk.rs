@@ -0,0 +1,258 @@
+//! Random walks.
+
+use rand::Rng;
+
+use visit::{
+    EdgeRef,
+    IntoEdges,
+    IntoEdgeReferences,
+    IntoNodeIdentifiers,
+    NodeIndexable,
+};
+
+/// An iterator of the nodes of a random walk.
+///
+/// The walk starts at the start node, and at each step it follows a random
+/// edge of the current node: an outgoing edge for a directed graph, or any
+/// edge for an undirected graph. The iterator yields the start node first,
+/// and ends after a node without edges to follow.
+///
+/// The edges are picked uniformly with `RandomWalk::new`, or with
+/// probability proportional to a weight with `RandomWalk::with_weights`.
+/// Picking the edge is a single pass over the edges of the current node, and
+/// the walk doesn't allocate.
+///
+/// Requires crate feature `"rand"`.
+pub struct RandomWalk<G, R, F = fn(<G as IntoEdgeReferences>::EdgeRef) -> f64>
+    where G: IntoEdgeReferences,
+{
+    graph: G,
+    rng: R,
+    edge_weight: F,
+    next: Option<G::NodeId>,
+}
+
+fn unit_weight<E>(_: E) -> f64 {
+    1.
+}
+
+impl<G, R> RandomWalk<G, R>
+    where G: IntoEdges,
+          R: Rng,
+{
+    /// Create a random walk from `start`, where each step follows one of the
+    /// current node's edges picked uniformly at random.
+    pub fn new(graph: G, start: G::NodeId, rng: R) -> Self {
+        RandomWalk::with_weights(graph, start, rng, unit_weight)
+    }
+}
+
+impl<G, R, F> RandomWalk<G, R, F>
+    where G: IntoEdges,
+          R: Rng,
+          F: FnMut(G::EdgeRef) -> f64,
+{
+    /// Create a random walk from `start`, where each step follows one of the
+    /// current node's edges with probability proportional to its
+    /// `edge_weight`.
+    ///
+    /// Weights must be non-negative. Edges of weight zero are never followed,
+    /// so the walk also ends at a node whose edges all have weight zero.
+    pub fn with_weights(graph: G, start: G::NodeId, rng: R, edge_weight: F) -> Self {
+        RandomWalk {
+            graph: graph,
+            rng: rng,
+            edge_weight: edge_weight,
+            next: Some(start),
+        }
+    }
+
+    /// Pick an edge of `node`, with weighted reservoir sampling, and return
+    /// its other endpoint.
+    fn step(&mut self, node: G::NodeId) -> Option<G::NodeId> {
+        let mut total = 0.;
+        let mut chosen = None;
+        for edge in self.graph.edges(node) {
+            let weight = (self.edge_weight)(edge);
+            if !(weight > 0.) {
+                continue;
+            }
+            total += weight;
+            if self.rng.gen::<f64>() * total < weight {
+                chosen = Some(edge.target());
+            }
+        }
+        chosen
+    }
+}
+
+impl<G, R, F> Iterator for RandomWalk<G, R, F>
+    where G: IntoEdges,
+          R: Rng,
+          F: FnMut(G::EdgeRef) -> f64,
+{
+    type Item = G::NodeId;
+
+    fn next(&mut self) -> Option<G::NodeId> {
+        let node = match self.next {
+            None => return None,
+            Some(node) => node,
+        };
+        self.next = self.step(node);
+        Some(node)
+    }
+}
+
+/// [Generic] Generate second order random walks, biased like node2vec.
+///
+/// From each node, in the order of `node_identifiers`, start
+/// `walks_per_node` walks of up to `walk_length` nodes (counting the start
+/// node). A walk ends early at a node without edges to follow.
+///
+/// The first step picks an edge with probability proportional to its
+/// `edge_weight`. After a step from `t` to `v`, the next step picks an edge
+/// from `v` to `x` with probability proportional to its weight, divided by
+/// the return parameter `p` if `x` is `t`, or by the in-out parameter `q` if
+/// `x` is not a neighbor of `t`. So a large `p` makes returning less likely,
+/// and a large `q` keeps the walk close to `t`; with `p = q = 1` the walks
+/// are unbiased, like `RandomWalk`.
+///
+/// Weights must be non-negative, and edges of weight zero are never
+/// followed. The walks only depend on the graph and the state of `rng`.
+///
+/// The transition probabilities are precomputed in alias tables, one for
+/// each node and one for each edge direction, so each step takes constant
+/// time.
+///
+/// Requires crate feature `"rand"`.
+///
+/// Uses **O(Σ deg(v)²)** memory for the tables, and computes in
+/// **O(Σ deg(v)² + |V| w l)** time, where w is `walks_per_node` and l is
+/// `walk_length`.
+///
+/// **Panics** if `p` or `q` is not positive.
+pub fn biased_random_walks<G, R, F>(g: G, rng: &mut R, walks_per_node: usize,
+                                    walk_length: usize, p: f64, q: f64,
+                                    mut edge_weight: F)
+    -> Vec<Vec<G::NodeId>>
+    where G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+          R: Rng,
+          F: FnMut(G::EdgeRef) -> f64,
+{
+    assert!(p > 0. && q > 0., "biased_random_walks: p and q must be positive");
+    let n = g.node_bound();
+    // the edges of each node that can be followed, as (target, weight)
+    let mut adjacency = vec![Vec::new(); n];
+    for node in g.node_identifiers() {
+        let a = g.to_index(node);
+        for edge in g.edges(node) {
+            let w = edge_weight(edge);
+            if w > 0. {
+                adjacency[a].push((g.to_index(edge.target()), w));
+            }
+        }
+    }
+    let mut neighbors = adjacency.iter().map(|edges| {
+        edges.iter().map(|&(x, _)| x).collect::<Vec<_>>()
+    }).collect::<Vec<_>>();
+    for list in &mut neighbors {
+        list.sort();
+        list.dedup();
+    }
+
+    // one table for the first step from each node, and one for the step
+    // after each edge (t, v), at `offset[t] + i` for the i-th edge of t
+    let first = adjacency.iter().map(|edges| {
+        AliasTable::new(edges.iter().map(|&(_, w)| w))
+    }).collect::<Vec<_>>();
+    let mut offset = Vec::with_capacity(n);
+    let mut after = Vec::new();
+    for t in 0..n {
+        offset.push(after.len());
+        for &(v, _) in &adjacency[t] {
+            let biased = adjacency[v].iter().map(|&(x, w)| {
+                if x == t {
+                    w / p
+                } else if neighbors[t].binary_search(&x).is_ok() {
+                    w
+                } else {
+                    w / q
+                }
+            });
+            after.push(AliasTable::new(biased));
+        }
+    }
+
+    let mut walks = Vec::new();
+    for _ in 0..walks_per_node {
+        for start in g.node_identifiers() {
+            let mut walk = Vec::with_capacity(walk_length);
+            if walk_length > 0 {
+                walk.push(start);
+            }
+            let mut v = g.to_index(start);
+            let mut table = &first[v];
+            while walk.len() < walk_length {
+                let i = match table.sample(rng) {
+                    None => break,
+                    Some(i) => i,
+                };
+                table = &after[offset[v] + i];
+                v = adjacency[v][i].0;
+                walk.push(g.from_index(v));
+            }
+            walks.push(walk);
+        }
+    }
+    walks
+}
+
+/// An alias table, to sample an index with probability proportional to
+/// its weight in constant time (Vose's method).
+struct AliasTable {
+    /// The probability to keep each index rather than take its alias.
+    keep: Vec<f64>,
+    alias: Vec<usize>,
+}
+
+impl AliasTable {
+    fn new<I>(weights: I) -> Self
+        where I: IntoIterator<Item=f64>,
+    {
+        let weights = weights.into_iter().collect::<Vec<_>>();
+        let len = weights.len();
+        let total = weights.iter().fold(0., |sum, &w| sum + w);
+        if !(total > 0.) {
+            return AliasTable { keep: Vec::new(), alias: Vec::new() };
+        }
+        let mut keep = weights.iter().map(|&w| w * len as f64 / total).collect::<Vec<_>>();
+        let mut alias = (0..len).collect::<Vec<_>>();
+        let (mut small, mut large): (Vec<_>, Vec<_>) = (0..len).partition(|&i| keep[i] < 1.);
+        while let (Some(s), Some(&l)) = (small.pop(), large.last()) {
+            alias[s] = l;
+            keep[l] -= 1. - keep[s];
+            if keep[l] < 1. {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // what is left is 1, up to rounding
+        for i in small.into_iter().chain(large) {
+            keep[i] = 1.;
+        }
+        AliasTable { keep: keep, alias: alias }
+    }
+
+    /// Return a random index, or `None` if the table is empty.
+    fn sample<R: Rng>(&self, rng: &mut R) -> Option<usize> {
+        if self.keep.is_empty() {
+            return None;
+        }
+        let i = rng.gen_range(0, self.keep.len());
+        if rng.gen::<f64>() < self.keep[i] {
+            Some(i)
+        } else {
+            Some(self.alias[i])
+        }
+    }
+}
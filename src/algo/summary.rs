@@ -0,0 +1,142 @@
+//! Cheap global statistics of a graph.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use unionfind::UnionFind;
+use visit::{
+    EdgeRef,
+    GraphProp,
+    IntoEdgeReferences,
+    IntoNeighbors,
+    IntoNodeIdentifiers,
+    NodeCount,
+    NodeIndexable,
+    Visitable,
+};
+use super::is_cyclic_directed;
+
+/// Global statistics of a graph, computed by `summary`.
+///
+/// The `Display` implementation prints one statistic per line.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphSummary {
+    /// Number of nodes.
+    pub node_count: usize,
+    /// Number of edges, including self loops and parallel edges.
+    pub edge_count: usize,
+    /// Number of self loops.
+    pub self_loops: usize,
+    /// Number of edges that connect the same pair of nodes as an edge that
+    /// comes before them (in the same direction, for a directed graph).
+    pub parallel_edges: usize,
+    /// `|E| / (|V| (|V| - 1))` for a directed graph and twice that for an
+    /// undirected graph; `0.` if there are fewer than two nodes. Self loops
+    /// and parallel edges count too, so it can be larger than `1.`.
+    pub density: f64,
+    /// Smallest degree of a node, or `0` if there are no nodes.
+    pub min_degree: usize,
+    /// Largest degree of a node, or `0` if there are no nodes.
+    pub max_degree: usize,
+    /// Mean degree of the nodes, or `0.` if there are no nodes.
+    pub mean_degree: f64,
+    /// Number of connected components (weakly connected, for a directed
+    /// graph).
+    pub connected_components: usize,
+    /// Number of nodes in the largest connected component, or `0` if there
+    /// are no nodes.
+    pub largest_component_size: usize,
+    /// For a directed graph, whether it is acyclic; `None` for an undirected
+    /// graph.
+    pub is_dag: Option<bool>,
+}
+
+/// [Generic] Compute the cheap global statistics of a graph.
+///
+/// The degree of a node is its number of edges, incoming and outgoing; a
+/// self loop counts twice. Expensive statistics like the diameter are not
+/// included.
+///
+/// Computes in **O(|V| + |E|)** expected time.
+pub fn summary<G>(g: G) -> GraphSummary
+    where G: IntoEdgeReferences + IntoNodeIdentifiers + IntoNeighbors + NodeCount +
+             NodeIndexable + GraphProp + Visitable,
+{
+    let node_count = g.node_count();
+    let mut edge_count = 0;
+    let mut self_loops = 0;
+    let mut parallel_edges = 0;
+    let mut degree = vec![0; g.node_bound()];
+    let mut components = UnionFind::new(g.node_bound());
+    let mut pairs = HashSet::new();
+    for edge in g.edge_references() {
+        let (a, b) = (g.to_index(edge.source()), g.to_index(edge.target()));
+        edge_count += 1;
+        if a == b {
+            self_loops += 1;
+        }
+        let pair = if g.is_directed() || a <= b { (a, b) } else { (b, a) };
+        if !pairs.insert(pair) {
+            parallel_edges += 1;
+        }
+        degree[a] += 1;
+        degree[b] += 1;
+        components.union(a, b);
+    }
+
+    let mut min_degree = None;
+    let mut max_degree = 0;
+    let mut component_size = vec![0; g.node_bound()];
+    for node in g.node_identifiers() {
+        let i = g.to_index(node);
+        min_degree = Some(match min_degree {
+            Some(d) if d < degree[i] => d,
+            _ => degree[i],
+        });
+        if degree[i] > max_degree {
+            max_degree = degree[i];
+        }
+        component_size[components.find_mut(i)] += 1;
+    }
+
+    let n = node_count as f64;
+    let density = if node_count < 2 {
+        0.
+    } else if g.is_directed() {
+        edge_count as f64 / (n * (n - 1.))
+    } else {
+        2. * edge_count as f64 / (n * (n - 1.))
+    };
+    GraphSummary {
+        node_count: node_count,
+        edge_count: edge_count,
+        self_loops: self_loops,
+        parallel_edges: parallel_edges,
+        density: density,
+        min_degree: min_degree.unwrap_or(0),
+        max_degree: max_degree,
+        mean_degree: if node_count == 0 { 0. } else { 2. * edge_count as f64 / n },
+        connected_components: component_size.iter().filter(|&&size| size > 0).count(),
+        largest_component_size: component_size.iter().cloned().max().unwrap_or(0),
+        is_dag: if g.is_directed() { Some(!is_cyclic_directed(g)) } else { None },
+    }
+}
+
+impl fmt::Display for GraphSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        try!(writeln!(f, "nodes:                {}", self.node_count));
+        try!(writeln!(f, "edges:                {}", self.edge_count));
+        try!(writeln!(f, "self loops:           {}", self.self_loops));
+        try!(writeln!(f, "parallel edges:       {}", self.parallel_edges));
+        try!(writeln!(f, "density:              {:.4}", self.density));
+        try!(writeln!(f, "degree:               min {}, max {}, mean {:.2}",
+                      self.min_degree, self.max_degree, self.mean_degree));
+        try!(writeln!(f, "connected components: {} (largest: {} nodes)",
+                      self.connected_components, self.largest_component_size));
+        match self.is_dag {
+            Some(is_dag) => writeln!(f, "acyclic:              {}",
+                                     if is_dag { "yes" } else { "no" }),
+            None => Ok(()),
+        }
+    }
+}
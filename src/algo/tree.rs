@@ -0,0 +1,78 @@
+//! Algorithms for trees.
+
+use path::Path;
+use visit::{
+    EdgeRef,
+    IntoEdgeReferences,
+    IntoNodeIdentifiers,
+    NodeCount,
+    NodeIndexable,
+};
+use super::{is_tree, Measure, NotATree};
+
+const NONE: usize = !0;
+
+/// [Generic] Compute the diameter of a tree: the costliest of the paths
+/// between two of its nodes.
+///
+/// Return the cost of the diameter and the path, from one end to the other,
+/// or a `NotATree` error if the graph is not a tree (see `is_tree`). The
+/// graph is treated as undirected, so in a directed graph the path may
+/// follow edges backwards. A single node is a diameter of cost zero.
+///
+/// `edge_cost` returns the cost of an edge, which must be non-negative.
+///
+/// Finds the node farthest from the first node, and then the node farthest
+/// from that node, which are the ends of a diameter.
+///
+/// Computes in **O(|V| + |E|)** time.
+pub fn tree_diameter<G, F, K>(g: G, mut edge_cost: F)
+    -> Result<(K, Path<G::NodeId>), NotATree>
+    where G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + NodeCount,
+          F: FnMut(G::EdgeRef) -> K,
+          K: Measure + Copy,
+{
+    if !is_tree(g) {
+        return Err(NotATree(()));
+    }
+    let mut adjacency = vec![Vec::new(); g.node_bound()];
+    for edge in g.edge_references() {
+        let (a, b) = (g.to_index(edge.source()), g.to_index(edge.target()));
+        let cost = edge_cost(edge);
+        adjacency[a].push((b, cost));
+        adjacency[b].push((a, cost));
+    }
+    let first = g.to_index(g.node_identifiers().next().unwrap());
+    let (u, _, _) = farthest(&adjacency, first);
+    let (v, cost, parent) = farthest(&adjacency, u);
+    let mut path = Path::new(g.from_index(v));
+    let mut x = v;
+    while x != u {
+        x = parent[x];
+        path.push(g.from_index(x));
+    }
+    Ok((cost, path))
+}
+
+/// Return the node farthest from `start` in the tree, its distance, and the
+/// parent of each node in the tree rooted at `start`.
+fn farthest<K>(adjacency: &[Vec<(usize, K)>], start: usize) -> (usize, K, Vec<usize>)
+    where K: Measure + Copy,
+{
+    let mut parent = vec![NONE; adjacency.len()];
+    let mut best = (start, K::default());
+    let mut stack = vec![(start, K::default())];
+    parent[start] = start;
+    while let Some((x, d)) = stack.pop() {
+        if d > best.1 {
+            best = (x, d);
+        }
+        for &(y, cost) in &adjacency[x] {
+            if parent[y] == NONE {
+                parent[y] = x;
+                stack.push((y, d + cost));
+            }
+        }
+    }
+    (best.0, best.1, parent)
+}
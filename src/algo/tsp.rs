@@ -0,0 +1,241 @@
+//! Traveling salesman heuristics.
+
+use visit::{
+    EdgeRef,
+    GraphProp,
+    IntoEdgeReferences,
+    IntoNodeIdentifiers,
+    NodeIndexable,
+};
+use super::Measure;
+
+/// [Generic] Find a tour through all nodes of the graph with the nearest
+/// neighbor heuristic: start at `start` and go on to the nearest node not
+/// yet visited, until all are; then return to `start`.
+///
+/// Returns the nodes in the order of the tour, starting with `start`, and
+/// the cost of the tour including the way back to `start`. The tour has no
+/// guarantee of being short, but is quick to compute; ties are broken by
+/// the lowest node index, so the result is deterministic.
+///
+/// `edge_cost` returns the cost of an edge, which must be non-negative. If
+/// `shortest_paths` is `false`, the tour may only use edges of the graph,
+/// and `None` is returned if the heuristic runs into a missing edge. If it
+/// is `true`, the cost between two nodes is the cost of the shortest path
+/// between them; the tour then only orders the nodes, and `None` is returned
+/// only if some node is not reachable. `None` is also returned if `start`
+/// is not a node of the graph.
+///
+/// Computes in **O(|V|² + |E|)** time, or **O(|V|³ + |E|)** time with
+/// `shortest_paths`.
+pub fn tsp_nearest_neighbor<G, F, K>(g: G, start: G::NodeId, edge_cost: F,
+                                     shortest_paths: bool)
+    -> Option<(Vec<G::NodeId>, K)>
+    where G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+          F: FnMut(G::EdgeRef) -> K,
+          K: Measure + Copy,
+{
+    let costs = Costs::new(g, edge_cost, shortest_paths);
+    let s = match costs.nodes.iter().position(|&i| i == g.to_index(start)) {
+        Some(s) => s,
+        None => return None,
+    };
+    let n = costs.nodes.len();
+    let mut visited = vec![false; n];
+    let mut tour = Vec::with_capacity(n);
+    visited[s] = true;
+    tour.push(s);
+    let mut v = s;
+    for _ in 1..n {
+        let mut nearest: Option<(K, usize)> = None;
+        for w in 0..n {
+            if visited[w] {
+                continue;
+            }
+            if let Some(c) = costs.get(v, w) {
+                if nearest.map_or(true, |(best, _)| c < best) {
+                    nearest = Some((c, w));
+                }
+            }
+        }
+        v = match nearest {
+            None => return None,
+            Some((_, w)) => w,
+        };
+        visited[v] = true;
+        tour.push(v);
+    }
+    costs.tour(g, &tour)
+}
+
+/// [Generic] Find a tour through all nodes of the graph with the minimum
+/// spanning tree heuristic: visit the nodes in depth first order of a
+/// minimum spanning tree, and return to the first node.
+///
+/// Returns the nodes in the order of the tour, starting with the first node
+/// of `node_identifiers`, and the cost of the tour including the way back to
+/// the first node. If the costs obey the triangle inequality, for example
+/// with `shortest_paths`, the tour costs at most twice as much as the
+/// shortest tour. The result is deterministic.
+///
+/// The spanning tree of a directed graph uses the cheaper direction between
+/// each pair of nodes, but the tour pays for the direction it goes in; the
+/// bound holds only for symmetric costs.
+///
+/// `edge_cost` and `shortest_paths` are as for `tsp_nearest_neighbor`.
+///
+/// Computes in **O(|V|² + |E|)** time, or **O(|V|³ + |E|)** time with
+/// `shortest_paths`.
+pub fn tsp_christofides_like<G, F, K>(g: G, edge_cost: F, shortest_paths: bool)
+    -> Option<(Vec<G::NodeId>, K)>
+    where G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+          F: FnMut(G::EdgeRef) -> K,
+          K: Measure + Copy,
+{
+    let costs = Costs::new(g, edge_cost, shortest_paths);
+    let n = costs.nodes.len();
+    if n == 0 {
+        return Some((Vec::new(), K::default()));
+    }
+    let cost = |a: usize, b: usize| match (costs.get(a, b), costs.get(b, a)) {
+        (Some(x), Some(y)) => Some(if y < x { y } else { x }),
+        (x, None) => x,
+        (None, y) => y,
+    };
+    // Prim's algorithm from the first node
+    let mut in_tree = vec![false; n];
+    let mut best: Vec<Option<(K, usize)>> = vec![None; n];
+    let mut children = vec![Vec::new(); n];
+    in_tree[0] = true;
+    for w in 1..n {
+        best[w] = cost(0, w).map(|c| (c, 0));
+    }
+    for _ in 1..n {
+        let mut next: Option<(K, usize)> = None;
+        for w in 0..n {
+            if in_tree[w] {
+                continue;
+            }
+            if let Some((c, _)) = best[w] {
+                if next.map_or(true, |(nc, _)| c < nc) {
+                    next = Some((c, w));
+                }
+            }
+        }
+        let v = match next {
+            None => return None,
+            Some((_, v)) => v,
+        };
+        in_tree[v] = true;
+        children[best[v].unwrap().1].push(v);
+        for w in 0..n {
+            if in_tree[w] {
+                continue;
+            }
+            if let Some(c) = cost(v, w) {
+                if best[w].map_or(true, |(bc, _)| c < bc) {
+                    best[w] = Some((c, v));
+                }
+            }
+        }
+    }
+    // shortcut the walk around the tree to its preorder
+    let mut tour = Vec::with_capacity(n);
+    let mut stack = vec![0];
+    while let Some(v) = stack.pop() {
+        tour.push(v);
+        stack.extend(children[v].iter().rev());
+    }
+    costs.tour(g, &tour)
+}
+
+/// The cost between each pair of nodes.
+struct Costs<K> {
+    /// The node index of each node, in the order of `node_identifiers`.
+    nodes: Vec<usize>,
+    /// The cost from node `i` to node `j` at `i * nodes.len() + j`.
+    matrix: Vec<Option<K>>,
+}
+
+impl<K> Costs<K>
+    where K: Measure + Copy,
+{
+    fn new<G, F>(g: G, mut edge_cost: F, shortest_paths: bool) -> Self
+        where G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+              F: FnMut(G::EdgeRef) -> K,
+    {
+        let nodes = g.node_identifiers().map(|a| g.to_index(a)).collect::<Vec<_>>();
+        let n = nodes.len();
+        let mut position = vec![!0; g.node_bound()];
+        for (i, &a) in nodes.iter().enumerate() {
+            position[a] = i;
+        }
+        let mut matrix = vec![None; n * n];
+        for i in 0..n {
+            matrix[i * n + i] = Some(K::default());
+        }
+        for edge in g.edge_references() {
+            let a = position[g.to_index(edge.source())];
+            let b = position[g.to_index(edge.target())];
+            let c = edge_cost(edge);
+            if a == b {
+                continue;
+            }
+            for &(a, b) in &[(a, b), (b, a)] {
+                let old = &mut matrix[a * n + b];
+                if old.map_or(true, |old| c < old) {
+                    *old = Some(c);
+                }
+                if g.is_directed() {
+                    break;
+                }
+            }
+        }
+        if shortest_paths {
+            // Floyd–Warshall
+            for k in 0..n {
+                for i in 0..n {
+                    let ik = match matrix[i * n + k] {
+                        None => continue,
+                        Some(ik) => ik,
+                    };
+                    for j in 0..n {
+                        if let Some(kj) = matrix[k * n + j] {
+                            let d = ik + kj;
+                            if matrix[i * n + j].map_or(true, |ij| d < ij) {
+                                matrix[i * n + j] = Some(d);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        Costs {
+            nodes: nodes,
+            matrix: matrix,
+        }
+    }
+
+    fn get(&self, i: usize, j: usize) -> Option<K> {
+        self.matrix[i * self.nodes.len() + j]
+    }
+
+    /// Return the nodes of `tour` and the cost of the cycle through them, or
+    /// `None` if a step is missing.
+    fn tour<G>(&self, g: G, tour: &[usize]) -> Option<(Vec<G::NodeId>, K)>
+        where G: NodeIndexable,
+    {
+        let mut total = K::default();
+        if tour.len() > 1 {
+            for (k, &i) in tour.iter().enumerate() {
+                let j = tour[(k + 1) % tour.len()];
+                match self.get(i, j) {
+                    None => return None,
+                    Some(c) => total = total + c,
+                }
+            }
+        }
+        let nodes = tour.iter().map(|&i| g.from_index(self.nodes[i])).collect();
+        Some((nodes, total))
+    }
+}
@@ -0,0 +1,147 @@
+//! Weisfeiler–Lehman graph hashing.
+
+use std::hash::{Hash, Hasher};
+
+use visit::{
+    EdgeRef,
+    GraphProp,
+    IntoEdgeReferences,
+    IntoNodeReferences,
+    NodeIndexable,
+    NodeRef,
+};
+
+/// [Generic] Return a hash of the graph that is the same for isomorphic
+/// graphs with the same labels, for example to deduplicate graphs before
+/// checking the candidates with `is_isomorphic_matching`.
+///
+/// `node_label` and `edge_label` return the label of a node and of an edge;
+/// return `()` to ignore them. The edge direction is part of the hash for a
+/// directed graph.
+///
+/// Each node starts out with its label, and for `iterations` rounds is
+/// relabeled with a hash of its label and the sorted labels of its
+/// neighbors and the edges to them. The result is a hash of the sorted node
+/// labels of all rounds.
+///
+/// Graphs with different hashes are never isomorphic; graphs with the same
+/// hash rarely aren't, but some regular graphs can't be told apart, such as
+/// a cycle of six nodes and two triangles.
+///
+/// The hash doesn't depend on the node or edge order, on the platform or
+/// on the run: it uses its own hash function, which hashes integers as
+/// little endian `u64`.
+///
+/// Computes in **O(k (|E| log |E| + |V| log |V|))** time for k iterations.
+pub fn weisfeiler_lehman_hash<G, F, H, L, M>(g: G, mut node_label: F, mut edge_label: H,
+                                             iterations: usize) -> u64
+    where G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+          F: FnMut(G::NodeRef) -> L,
+          H: FnMut(G::EdgeRef) -> M,
+          L: Hash,
+          M: Hash,
+{
+    let n = g.node_bound();
+    let mut nodes = Vec::new();
+    let mut labels = vec![0; n];
+    for node in g.node_references() {
+        let i = g.to_index(node.id());
+        nodes.push(i);
+        labels[i] = hash_one(&node_label(node));
+    }
+    // the edges at each node: the edge label, the other node and whether
+    // the edge is incoming
+    let mut adjacency = vec![Vec::new(); n];
+    let mut edge_count = 0u64;
+    for edge in g.edge_references() {
+        let (a, b) = (g.to_index(edge.source()), g.to_index(edge.target()));
+        let label = hash_one(&edge_label(edge));
+        adjacency[a].push((label, b, false));
+        adjacency[b].push((label, a, g.is_directed()));
+        edge_count += 1;
+    }
+
+    let mut hasher = StableHasher::new();
+    hasher.write_u64(nodes.len() as u64);
+    hasher.write_u64(edge_count);
+    write_sorted(&mut hasher, &nodes, &labels);
+    let mut next = vec![0; n];
+    let mut signature = Vec::new();
+    for _ in 0..iterations {
+        for &i in &nodes {
+            signature.clear();
+            signature.extend(adjacency[i].iter().map(|&(label, j, incoming)| {
+                hash_one(&(incoming, label, labels[j]))
+            }));
+            signature.sort();
+            let mut h = StableHasher::new();
+            h.write_u64(labels[i]);
+            for &s in &signature {
+                h.write_u64(s);
+            }
+            next[i] = h.finish();
+        }
+        ::std::mem::swap(&mut labels, &mut next);
+        write_sorted(&mut hasher, &nodes, &labels);
+    }
+    hasher.finish()
+}
+
+/// Write the labels of `nodes` to `hasher`, in sorted order.
+fn write_sorted(hasher: &mut StableHasher, nodes: &[usize], labels: &[u64]) {
+    let mut sorted = nodes.iter().map(|&i| labels[i]).collect::<Vec<_>>();
+    sorted.sort();
+    for &label in &sorted {
+        hasher.write_u64(label);
+    }
+}
+
+fn hash_one<T: Hash>(value: &T) -> u64 {
+    let mut hasher = StableHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 64-bit FNV-1a, with integers written as little endian `u64` and a final
+/// mix (from SplitMix64) to spread the bits of the result.
+struct StableHasher(u64);
+
+impl StableHasher {
+    fn new() -> Self {
+        StableHasher(0xcbf2_9ce4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn finish(&self) -> u64 {
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= b as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn write_u8(&mut self, i: u8) { self.write_u64(i as u64) }
+    fn write_u16(&mut self, i: u16) { self.write_u64(i as u64) }
+    fn write_u32(&mut self, i: u32) { self.write_u64(i as u64) }
+    fn write_usize(&mut self, i: usize) { self.write_u64(i as u64) }
+    fn write_i8(&mut self, i: i8) { self.write_u64(i as u64) }
+    fn write_i16(&mut self, i: i16) { self.write_u64(i as u64) }
+    fn write_i32(&mut self, i: i32) { self.write_u64(i as u64) }
+    fn write_i64(&mut self, i: i64) { self.write_u64(i as u64) }
+    fn write_isize(&mut self, i: isize) { self.write_u64(i as u64) }
+
+    fn write_u64(&mut self, i: u64) {
+        let mut bytes = [0; 8];
+        for (k, b) in bytes.iter_mut().enumerate() {
+            *b = (i >> (8 * k)) as u8;
+        }
+        self.write(&bytes);
+    }
+}
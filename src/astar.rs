@@ -0,0 +1,124 @@
+use std::collections::{
+    HashMap,
+    BinaryHeap,
+};
+
+use std::hash::Hash;
+
+use scored::MinScored;
+use super::visit::{
+    Visitable,
+    VisitMap,
+    IntoEdges,
+    EdgeRef,
+};
+use algo::Measure;
+use path::Path;
+
+/// [Generic] A* shortest path algorithm.
+///
+/// Compute the length of a shortest path from `start` to a node for which
+/// `is_goal` returns `true`, and the path itself.
+///
+/// The graph should be `Visitable` and implement `IntoEdges`. The function
+/// `edge_cost` should return the cost for a particular edge, which is used
+/// to compute path costs. Edge costs must be non-negative.
+///
+/// The function `estimate_cost` should return the estimated cost from a
+/// node to the nearest goal node. For the path to be a shortest path, the
+/// estimate must be consistent: it never exceeds the cost of an edge plus
+/// the estimate at the edge's target, and it is zero at the goal nodes. An
+/// estimate of zero everywhere makes this Dijkstra's algorithm.
+///
+/// Returns the path cost and the path, from `start` to the goal node, or
+/// `None` if no goal node is reachable.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::astar;
+/// use petgraph::graph::node_index as n;
+///
+/// let gr: Graph<(), u32> = Graph::from_edges(&[(0, 1, 2), (1, 3, 2), (0, 2, 1), (2, 3, 4)]);
+/// let (cost, path) = astar(&gr, n(0), |x| x == n(3), |e| *e.weight(), |_| 0).unwrap();
+/// assert_eq!(cost, 4);
+/// assert_eq!(path.nodes(), &[n(0), n(1), n(3)]);
+/// ```
+pub fn astar<G, F, H, K, IsGoal>(graph: G, start: G::NodeId, is_goal: IsGoal,
+                                 edge_cost: F, mut estimate_cost: H)
+    -> Option<(K, Path<G::NodeId>)>
+    where G: IntoEdges + Visitable,
+          G::NodeId: Eq + Hash,
+          IsGoal: FnMut(G::NodeId) -> bool,
+          F: FnMut(G::EdgeRef) -> K,
+          H: FnMut(G::NodeId) -> K,
+          K: Measure + Copy,
+{
+    astar_impl(graph, start, is_goal, edge_cost, |n| Some(estimate_cost(n)))
+        .map(|(cost, path, _)| (cost, path))
+}
+
+/// A* search where `estimate_cost` returns `None` for nodes that can't
+/// reach a goal node, which are not visited.
+///
+/// Also return the number of nodes the search settled.
+pub fn astar_impl<G, F, H, K, IsGoal>(graph: G, start: G::NodeId, mut is_goal: IsGoal,
+                                      mut edge_cost: F, mut estimate_cost: H)
+    -> Option<(K, Path<G::NodeId>, usize)>
+    where G: IntoEdges + Visitable,
+          G::NodeId: Eq + Hash,
+          IsGoal: FnMut(G::NodeId) -> bool,
+          F: FnMut(G::EdgeRef) -> K,
+          H: FnMut(G::NodeId) -> Option<K>,
+          K: Measure + Copy,
+{
+    let mut visited = graph.visit_map();
+    let mut scores = HashMap::new();
+    let mut predecessor = HashMap::new();
+    let mut visit_next = BinaryHeap::new();
+    let mut settled = 0;
+    let zero_score = K::default();
+    if !graph.includes_node(start) {
+        return None;
+    }
+    match estimate_cost(start) {
+        None => return None,
+        Some(estimate) => visit_next.push(MinScored(estimate, start)),
+    }
+    scores.insert(start, zero_score);
+    while let Some(MinScored(_, node)) = visit_next.pop() {
+        if !visited.visit(node) {
+            continue
+        }
+        settled += 1;
+        let node_score = scores[&node];
+        if is_goal(node) {
+            let mut path = Path::new(node);
+            let mut current = node;
+            while let Some(&prev) = predecessor.get(&current) {
+                path.push(prev);
+                current = prev;
+            }
+            path.reverse();
+            return Some((node_score, path, settled));
+        }
+        for edge in graph.edges(node) {
+            let next = edge.target();
+            if visited.is_visited(&next) {
+                continue
+            }
+            let next_score = node_score + edge_cost(edge);
+            let improved = match scores.get(&next) {
+                Some(old) => next_score < *old,
+                None => true,
+            };
+            if improved {
+                if let Some(estimate) = estimate_cost(next) {
+                    scores.insert(next, next_score);
+                    predecessor.insert(next, node);
+                    visit_next.push(MinScored(next_score + estimate, next));
+                }
+            }
+        }
+    }
+    None
+}
@@ -16,6 +16,8 @@ use util::zip;
 #[doc(no_inline)]
 pub use graph::{IndexType, DefaultIx};
 
+use graph::Graph;
+
 use {
     EdgeType,
     Directed,
@@ -177,6 +179,60 @@ impl<N, E, Ty, Ix> Csr<N, E, Ty, Ix>
     where Ty: EdgeType,
           Ix: IndexType,
 {
+    /// Create a new `Csr` with the same nodes and edges as `graph`.
+    ///
+    /// Node indices are preserved. Parallel edges are merged into one,
+    /// keeping the weight of the edge with the lowest index.
+    ///
+    /// Computes in **O(|E| log |E| + |V|)** time.
+    pub fn from_graph(graph: &Graph<N, E, Ty, Ix>) -> Self
+        where N: Clone,
+              E: Clone,
+    {
+        let mut edges = Vec::with_capacity(graph.edge_count());
+        for (i, edge) in graph.raw_edges().iter().enumerate() {
+            let (a, b) = (edge.source().index(), edge.target().index());
+            edges.push((a, b, i));
+            if !Ty::is_directed() && a != b {
+                edges.push((b, a, i));
+            }
+        }
+        // sort by endpoints, then edge index, so that the first of each
+        // group of parallel edges is kept
+        edges.sort();
+        let mut previous = None;
+        edges.retain(|&(a, b, _)| {
+            let first = previous != Some((a, b));
+            previous = Some((a, b));
+            first
+        });
+
+        let mut self_ = Csr {
+            column: Vec::with_capacity(edges.len()),
+            edges: Vec::with_capacity(edges.len()),
+            row: Vec::with_capacity(graph.node_count() + 1),
+            node_weights: graph.raw_nodes().iter().map(|n| n.weight.clone()).collect(),
+            edge_count: 0,
+            ty: PhantomData,
+        };
+        let mut edges = edges.into_iter().peekable();
+        for a in 0..graph.node_count() {
+            self_.row.push(self_.column.len());
+            while let Some(&(s, b, i)) = edges.peek() {
+                if s != a {
+                    break;
+                }
+                self_.column.push(Ix::new(b));
+                self_.edges.push(graph.raw_edges()[i].weight.clone());
+                if a <= b {
+                    self_.edge_count += 1;
+                }
+                edges.next();
+            }
+        }
+        self_.row.push(self_.column.len());
+        self_
+    }
 
     pub fn node_count(&self) -> usize {
         self.row.len() - 1
@@ -271,6 +327,18 @@ impl<N, E, Ty, Ix> Csr<N, E, Ty, Ix>
         self.find_edge_pos(a, b).is_ok()
     }
 
+    /// Return a reference to the weight of the edge from `a` to `b`, if it exists.
+    ///
+    /// Computes in **O(log |V|)** time.
+    ///
+    /// **Panics** if the node `a` does not exist.
+    pub fn edge_weight(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Option<&E> {
+        match self.find_edge_pos(a, b) {
+            Ok(i) => Some(&self.edges[i]),
+            Err(_) => None,
+        }
+    }
+
     fn neighbors_range(&self, a: NodeIndex<Ix>) -> Range<usize> {
         let index = self.row[a.index()];
         let end = self.row.get(a.index() + 1).cloned().unwrap_or(self.column.len());
@@ -823,4 +891,61 @@ mod tests {
         assert_eq!(&m.column, &m2.column);
         assert_eq!(&m.edges, &m2.edges);
     }
+
+    #[test]
+    fn csr_from_graph() {
+        use graph::{Graph, UnGraph, node_index};
+        use algo::dijkstra;
+        use visit::{Bfs, IntoNeighbors};
+
+        let mut g = Graph::new();
+        let a = g.add_node("a");
+        let b = g.add_node("b");
+        let c = g.add_node("c");
+        let d = g.add_node("d");
+        g.add_edge(c, a, 4.);
+        g.add_edge(a, b, 1.);
+        g.add_edge(b, c, 2.);
+        g.add_edge(a, c, 5.);
+        g.add_edge(a, b, 9.);
+        let m = Csr::from_graph(&g);
+        assert_eq!(m.node_count(), 4);
+        assert_eq!(m.edge_count(), 4);
+        assert_eq!(&m.node_weights, &["a", "b", "c", "d"]);
+        assert_eq!(m.neighbors_slice(0), &[1, 2]);
+        assert_eq!((&m).neighbors(2).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(m.neighbors_slice(3), &[]);
+        assert_eq!(m.edge_weight(0, 1), Some(&1.));
+        assert_eq!(m.edge_weight(1, 0), None);
+        assert!(m.contains_edge(2, 0));
+        assert!(!m.contains_edge(0, 3));
+
+        let mut bfs = Bfs::new(&m, 1);
+        let mut order = Vec::new();
+        while let Some(nx) = bfs.next(&m) {
+            order.push(nx);
+        }
+        assert_eq!(order, vec![1, 2, 0]);
+
+        let dist = dijkstra(&m, 0, None, |e| *e.weight());
+        let gdist = dijkstra(&g, a, None, |e| *e.weight());
+        assert_eq!(dist.len(), gdist.len());
+        for (&n, &cost) in &dist {
+            assert_eq!(gdist[&node_index(n as usize)], cost);
+        }
+        assert!(!dist.contains_key(&(d.index() as u32)));
+
+        let mut u = UnGraph::<(), u8>::new_undirected();
+        let x = u.add_node(());
+        let y = u.add_node(());
+        u.add_edge(x, y, 1);
+        u.add_edge(y, y, 2);
+        u.add_edge(y, x, 3);
+        let m = Csr::from_graph(&u);
+        assert_eq!(m.edge_count(), 2);
+        assert_eq!(m.neighbors_slice(0), &[1]);
+        assert_eq!(m.neighbors_slice(1), &[0, 1]);
+        assert_eq!(m.edge_weight(1, 0), Some(&1));
+        assert_eq!(m.edge_weight(1, 1), Some(&2));
+    }
 }
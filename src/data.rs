@@ -10,6 +10,8 @@ use ::{
 use graph::IndexType;
 #[cfg(feature = "graphmap")]
 use graphmap::{GraphMap, NodeTrait};
+#[cfg(feature = "graphmap")]
+use std::hash::BuildHasher;
 use visit::{
     Data,
     NodeCount,
@@ -175,9 +177,10 @@ impl<N, E, Ty, Ix> Build for StableGraph<N, E, Ty, Ix>
 }
 
 #[cfg(feature = "graphmap")]
-impl<N, E, Ty> Build for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> Build for GraphMap<N, E, Ty, S>
     where Ty: EdgeType,
           N: NodeTrait,
+          S: BuildHasher,
 {
     fn add_node(&mut self, weight: Self::NodeWeight) -> Self::NodeId {
         self.add_node(weight)
@@ -226,9 +229,10 @@ impl<N, E, Ty, Ix> Create for StableGraph<N, E, Ty, Ix>
 }
 
 #[cfg(feature = "graphmap")]
-impl<N, E, Ty> Create for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> Create for GraphMap<N, E, Ty, S>
     where Ty: EdgeType,
           N: NodeTrait,
+          S: BuildHasher + Default,
 {
     fn with_capacity(nodes: usize, edges: usize) -> Self {
         Self::with_capacity(nodes, edges)
@@ -325,9 +329,10 @@ impl<N, E, Ty, Ix> FromElements for StableGraph<N, E, Ty, Ix>
 }
 
 #[cfg(feature = "graphmap")]
-impl<N, E, Ty> FromElements for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> FromElements for GraphMap<N, E, Ty, S>
     where Ty: EdgeType,
           N: NodeTrait,
+          S: BuildHasher + Default,
 {
     fn from_elements<I>(iterable: I) -> Self
         where Self: Sized,
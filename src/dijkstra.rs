@@ -9,12 +9,15 @@ use std::collections::hash_map::Entry::{
 
 use std::hash::Hash;
 
+use fixedbitset::FixedBitSet;
+
 use scored::MinScored;
 use super::visit::{
     Visitable,
     VisitMap,
     IntoEdges,
     EdgeRef,
+    NodeIndexable,
 };
 use algo::Measure;
 
@@ -30,20 +33,143 @@ use algo::Measure;
 /// If `goal` is not `None`, then the algorithm terminates once the `goal` node's
 /// cost is calculated.
 ///
-/// Returns a `HashMap` that maps `NodeId` to path cost.
+/// Returns a `HashMap` that maps `NodeId` to path cost. The map is empty if
+/// `start` is hidden by a graph view, like a node excluded by `NodeFiltered`.
 pub fn dijkstra<G, F, K>(graph: G, start: G::NodeId, goal: Option<G::NodeId>,
-                         mut edge_cost: F)
+                         edge_cost: F)
+    -> HashMap<G::NodeId, K>
+    where G: IntoEdges + Visitable,
+          G::NodeId: Eq + Hash,
+          F: FnMut(G::EdgeRef) -> K,
+          K: Measure + Copy,
+{
+    dijkstra_impl(graph, start, goal, edge_cost, |_| true)
+}
+
+/// [Generic] Dijkstra's shortest path algorithm, restricted to a subset of
+/// the nodes.
+///
+/// This is like `dijkstra`, but only the nodes whose index (as given by
+/// `NodeIndexable::to_index`) is contained in `allowed` are visited, so the
+/// result is the same as running `dijkstra` on the subgraph induced by
+/// `allowed`, without building that subgraph.
+///
+/// If `start` is not in `allowed`, the result is empty.
+///
+/// Returns a `HashMap` that maps `NodeId` to path cost.
+pub fn dijkstra_restricted<G, F, K>(graph: G, start: G::NodeId, goal: Option<G::NodeId>,
+                                    edge_cost: F, allowed: &FixedBitSet)
+    -> HashMap<G::NodeId, K>
+    where G: IntoEdges + Visitable + NodeIndexable,
+          G::NodeId: Eq + Hash,
+          F: FnMut(G::EdgeRef) -> K,
+          K: Measure + Copy,
+{
+    if !allowed.contains(graph.to_index(start)) {
+        return HashMap::new();
+    }
+    dijkstra_impl(graph, start, goal, edge_cost,
+                  |n| allowed.contains(graph.to_index(n)))
+}
+
+/// Workspace for `dijkstra_into`.
+///
+/// Create a `DijkstraSpace` once and pass it to each call to reuse the
+/// allocation of the search queue.
+#[derive(Clone, Debug)]
+pub struct DijkstraSpace<N, K> {
+    heap: BinaryHeap<MinScored<K, N>>,
+}
+
+impl<N, K> DijkstraSpace<N, K>
+    where K: PartialOrd,
+{
+    /// Create a new, empty workspace.
+    pub fn new() -> Self {
+        DijkstraSpace {
+            heap: BinaryHeap::new(),
+        }
+    }
+}
+
+impl<N, K> Default for DijkstraSpace<N, K>
+    where K: PartialOrd,
+{
+    fn default() -> Self {
+        DijkstraSpace::new()
+    }
+}
+
+/// [Generic] Dijkstra's shortest path algorithm, without allocating.
+///
+/// Compute the length of the shortest path from `start` to every reachable
+/// node, like `dijkstra`. The path costs are written to `dist_out`, indexed
+/// by node index (`NodeIndexable::to_index`): `dist_out[i]` is `None` if the
+/// node with index `i` is not reachable. Edge costs must be non-negative.
+///
+/// `dist_out` must have a length of at least `graph.node_bound()`. `space`
+/// holds the search queue; once it has grown to fit, no further allocations
+/// are made. It needs room for at most one entry per edge.
+///
+/// **Panics** if `dist_out` is too short.
+pub fn dijkstra_into<G, F, K>(graph: G, start: G::NodeId, mut edge_cost: F,
+                              dist_out: &mut [Option<K>],
+                              space: &mut DijkstraSpace<G::NodeId, K>)
+    where G: IntoEdges + NodeIndexable,
+          F: FnMut(G::EdgeRef) -> K,
+          K: Measure + Copy,
+{
+    let dist_out = &mut dist_out[..graph.node_bound()];
+    for d in dist_out.iter_mut() {
+        *d = None;
+    }
+    let heap = &mut space.heap;
+    heap.clear();
+    if !graph.includes_node(start) {
+        return;
+    }
+    let zero_score = K::default();
+    dist_out[graph.to_index(start)] = Some(zero_score);
+    heap.push(MinScored(zero_score, start));
+    while let Some(MinScored(node_score, node)) = heap.pop() {
+        // a node is pushed again each time its score improves, so skip the
+        // stale entries
+        match dist_out[graph.to_index(node)] {
+            Some(score) if node_score > score => continue,
+            _ => {}
+        }
+        for edge in graph.edges(node) {
+            let next = edge.target();
+            let next_score = node_score + edge_cost(edge);
+            let slot = &mut dist_out[graph.to_index(next)];
+            match *slot {
+                Some(score) if !(next_score < score) => {}
+                _ => {
+                    *slot = Some(next_score);
+                    heap.push(MinScored(next_score, next));
+                }
+            }
+        }
+    }
+}
+
+fn dijkstra_impl<G, F, K, P>(graph: G, start: G::NodeId, goal: Option<G::NodeId>,
+                             mut edge_cost: F, include_node: P)
     -> HashMap<G::NodeId, K>
     where G: IntoEdges + Visitable,
           G::NodeId: Eq + Hash,
           F: FnMut(G::EdgeRef) -> K,
           K: Measure + Copy,
+          P: Fn(G::NodeId) -> bool,
 {
     let mut visited = graph.visit_map();
     let mut scores = HashMap::new();
     //let mut predecessor = HashMap::new();
     let mut visit_next = BinaryHeap::new();
     let zero_score = K::default();
+    if !graph.includes_node(start) {
+        return scores;
+    }
     scores.insert(start, zero_score);
     visit_next.push(MinScored(zero_score, start));
     while let Some(MinScored(node_score, node)) = visit_next.pop() {
@@ -55,7 +181,7 @@ pub fn dijkstra<G, F, K>(graph: G, start: G::NodeId, goal: Option<G::NodeId>,
         }
         for edge in graph.edges(node) {
             let next = edge.target();
-            if visited.is_visited(&next) {
+            if visited.is_visited(&next) || !include_node(next) {
                 continue
             }
             let mut next_score = node_score + edge_cost(edge);
@@ -1,14 +1,34 @@
-//! Simple graphviz dot file format output.
+//! Simple graphviz dot file format output, and parsing.
 
 use std::fmt::{self, Display, Write};
+use std::io;
 
 use visit::{GraphRef};
 
+pub use self::parser::{parse, DotNode, DotEdge, ParseError};
+
+#[path = "dot_parser.rs"]
+mod parser;
+
 /// `Dot` implements output to graphviz .dot format for a graph.
 ///
 /// Formatting and options are rather simple, this is mostly intended
 /// for debugging. Exact output may change.
 ///
+/// `Dot` implements both `Display` and `Debug`: formatting it with `{}`
+/// uses the `Display` implementations of the weights for the labels, and
+/// `{:?}` uses their `Debug` implementations. With `{:#?}`, labels use the
+/// pretty printed `Debug` output, left justified.
+///
+/// Labels are escaped for use in a DOT string: quotes and backslashes are
+/// escaped with a backslash, and a newline becomes the left justified
+/// line break `\l`. Undirected graphs are written as `graph` with `--`
+/// edges, directed graphs as `digraph` with `->` edges.
+///
+/// Nodes are written by their index from `NodeIndexable`. For a `GraphMap`
+/// the node weight is the node itself, so `Dot::new(&graphmap)` labels each
+/// node with its value, and writes every edge once.
+///
 /// # Examples
 ///
 /// ```
@@ -45,16 +65,52 @@ use visit::{GraphRef};
 ///
 /// // If you need multiple config options, just list them all in the slice.
 /// ```
-pub struct Dot<'a, G> {
+///
+/// Per-node and per-edge attributes like colors and shapes can be added
+/// with `Dot::with_attr_getters`, and graph-wide attributes and defaults
+/// with the builder methods `.graph_attributes()`, `.node_defaults()` and
+/// `.edge_defaults()`:
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::dot::{Dot, Config};
+/// use petgraph::visit::EdgeRef;
+///
+/// let graph = Graph::<&str, u32>::from_edges(&[(0, 1, 1), (1, 2, 5)]);
+/// let dot = Dot::with_attr_getters(&graph, &[Config::NodeNoLabel],
+///     &|_, edge| if *edge.weight() > 2 { "color=red".to_string() } else { String::new() },
+///     &|_, (n, _)| if n.index() == 0 { "shape=box".to_string() } else { String::new() })
+///     .graph_attributes("rankdir=LR");
+/// assert_eq!(format!("{}", dot), "\
+/// digraph {
+///     graph [rankdir=LR]
+///     0 [shape=box]
+///     1
+///     2
+///     0 -> 1 [label=\"1\"]
+///     1 -> 2 [label=\"5\" color=red]
+/// }
+/// ");
+/// ```
+pub struct Dot<'a, G>
+    where G: IntoEdgeReferences + IntoNodeReferences,
+{
     graph: G,
     config: &'a [Config],
+    get_edge_attributes: &'a Fn(G, G::EdgeRef) -> String,
+    get_node_attributes: &'a Fn(G, G::NodeRef) -> String,
+    graph_attributes: &'a str,
+    node_defaults: &'a str,
+    edge_defaults: &'a str,
 }
 
 static TYPE: [&'static str; 2] = ["graph", "digraph"];
 static EDGE: [&'static str; 2] = ["--", "->"];
 static INDENT: &'static str = "    ";
 
-impl<'a, G> Dot<'a, G> where G: GraphRef {
+impl<'a, G> Dot<'a, G>
+    where G: GraphRef + IntoEdgeReferences + IntoNodeReferences,
+{
     /// Create a `Dot` formatting wrapper with default configuration.
     pub fn new(graph: G) -> Self {
         Self::with_config(graph, &[])
@@ -62,11 +118,104 @@ impl<'a, G> Dot<'a, G> where G: GraphRef {
 
     /// Create a `Dot` formatting wrapper with custom configuration.
     pub fn with_config(graph: G, config: &'a [Config]) -> Self {
+        Self::with_attr_getters(graph, config, &|_, _| String::new(), &|_, _| String::new())
+    }
+
+    /// Create a `Dot` formatting wrapper with custom configuration and
+    /// extra attributes for each edge and node.
+    ///
+    /// The getters return the attributes as a string in DOT syntax, like
+    /// `"color=red, shape=box"`, which is inserted after the label in the
+    /// attribute list. An empty string adds no attributes.
+    pub fn with_attr_getters(graph: G,
+                             config: &'a [Config],
+                             get_edge_attributes: &'a Fn(G, G::EdgeRef) -> String,
+                             get_node_attributes: &'a Fn(G, G::NodeRef) -> String)
+        -> Self
+    {
         Dot {
             graph: graph,
             config: config,
+            get_edge_attributes: get_edge_attributes,
+            get_node_attributes: get_node_attributes,
+            graph_attributes: "",
+            node_defaults: "",
+            edge_defaults: "",
         }
     }
+
+    /// Set graph-wide attributes, like `"rankdir=LR, splines=ortho"`.
+    ///
+    /// They are written as a `graph [...]` statement before the nodes.
+    /// An empty string (the default) writes nothing.
+    pub fn graph_attributes(mut self, attrs: &'a str) -> Self {
+        self.graph_attributes = attrs;
+        self
+    }
+
+    /// Set default attributes for all nodes, like `"shape=box"`.
+    ///
+    /// They are written as a `node [...]` statement before the nodes, so
+    /// the attributes of each node from `Dot::with_attr_getters` override
+    /// them. An empty string (the default) writes nothing.
+    pub fn node_defaults(mut self, attrs: &'a str) -> Self {
+        self.node_defaults = attrs;
+        self
+    }
+
+    /// Set default attributes for all edges, like `"color=gray"`.
+    ///
+    /// They are written as an `edge [...]` statement before the nodes, so
+    /// the attributes of each edge from `Dot::with_attr_getters` override
+    /// them. An empty string (the default) writes nothing.
+    pub fn edge_defaults(mut self, attrs: &'a str) -> Self {
+        self.edge_defaults = attrs;
+        self
+    }
+}
+
+/// Write `graph` in DOT format to `writer`, with the configuration and
+/// extra attributes of `Dot::with_attr_getters`.
+///
+/// Each node and edge statement is written to `writer` as soon as it is
+/// formatted, without building the output in memory. The writer is flushed
+/// after the node statements and at the end; wrap a file in a `BufWriter`
+/// to avoid many small writes.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::dot::{self, Config};
+///
+/// let graph = Graph::<&str, u32>::from_edges(&[(0, 1, 7)]);
+/// let mut output = Vec::new();
+/// dot::write_dot(&mut output, &graph, &[Config::NodeNoLabel],
+///                &|_, _| String::new(), &|_, _| String::new()).unwrap();
+/// assert_eq!(output, b"digraph {\n    0\n    1\n    0 -> 1 [label=\"7\"]\n}\n");
+/// ```
+pub fn write_dot<W, G>(writer: &mut W,
+                       graph: G,
+                       config: &[Config],
+                       get_edge_attributes: &Fn(G, G::EdgeRef) -> String,
+                       get_node_attributes: &Fn(G, G::NodeRef) -> String)
+    -> io::Result<()>
+    where W: io::Write,
+          G: GraphRef + IntoEdgeReferences + IntoNodeReferences + NodeIndexable + GraphProp,
+          G::EdgeWeight: fmt::Display,
+          G::NodeWeight: fmt::Display,
+{
+    let dot = Dot::with_attr_getters(graph, config, get_edge_attributes, get_node_attributes);
+    try!(write!(writer, "{}", FnFmt(|f: &mut fmt::Formatter| dot.header_fmt(graph, f))));
+    for node in graph.node_references() {
+        let stmt = FnFmt(|f: &mut fmt::Formatter| dot.node_fmt(graph, node, f, |n, cb| cb(n)));
+        try!(write!(writer, "{}", stmt));
+    }
+    try!(writer.flush());
+    for (i, edge) in graph.edge_references().enumerate() {
+        let stmt = FnFmt(|f: &mut fmt::Formatter| dot.edge_fmt(graph, i, edge, f, |e, cb| cb(e)));
+        try!(write!(writer, "{}", stmt));
+    }
+    try!(writeln!(writer, "}}"));
+    writer.flush()
 }
 
 /// `Dot` configuration.
@@ -80,6 +229,8 @@ pub enum Config {
     EdgeIndexLabel,
     /// Use no edge labels.
     EdgeNoLabel,
+    /// Use no node labels.
+    NodeNoLabel,
     #[doc(hidden)]
     _Incomplete(()),
 }
@@ -88,6 +239,7 @@ use visit::{ IntoNodeReferences, NodeIndexable, IntoEdgeReferences, EdgeRef};
 use visit::{ Data, NodeRef, GraphProp, };
 
 impl<'a, G> Dot<'a, G>
+    where G: IntoEdgeReferences + IntoNodeReferences,
 {
     fn graph_fmt<NF, EF, NW, EW>(&self, g: G, f: &mut fmt::Formatter,
                     mut node_fmt: NF, mut edge_fmt: EF) -> fmt::Result
@@ -97,41 +249,101 @@ impl<'a, G> Dot<'a, G>
               NF: FnMut(&NW, &mut FnMut(&Display) -> fmt::Result) -> fmt::Result,
               EF: FnMut(&EW, &mut FnMut(&Display) -> fmt::Result) -> fmt::Result,
     {
-        try!(writeln!(f, "{} {{", TYPE[g.is_directed() as usize]));
-
+        try!(self.header_fmt(g, f));
         // output all labels
         for node in g.node_references() {
-            try!(write!(f, "{}{}", INDENT, g.to_index(node.id())));
-            if self.config.contains(&Config::NodeIndexLabel) {
-                try!(writeln!(f, ""));
-            } else {
-                try!(write!(f, " [label=\""));
-                try!(node_fmt(node.weight(), &mut |d| Escaped(d).fmt(f)));
-                try!(writeln!(f, "\"]"));
-            }
-
+            try!(self.node_fmt(g, node, f, &mut node_fmt));
         }
         // output all edges
         for (i, edge) in g.edge_references().enumerate() {
-            try!(write!(f, "{}{} {} {}",
-                        INDENT,
-                        g.to_index(edge.source()),
-                        EDGE[g.is_directed() as usize],
-                        g.to_index(edge.target())));
-            if self.config.contains(&Config::EdgeNoLabel) {
-                try!(writeln!(f, ""));
-            } else if self.config.contains(&Config::EdgeIndexLabel) {
-                try!(writeln!(f, " [label=\"{}\"]", i));
-            } else {
-                try!(write!(f, " [label=\""));
-                try!(edge_fmt(edge.weight(), &mut |d| Escaped(d).fmt(f)));
-                try!(writeln!(f, "\"]"));
-            }
+            try!(self.edge_fmt(g, i, edge, f, &mut edge_fmt));
         }
+        writeln!(f, "}}")
+    }
 
-        try!(writeln!(f, "}}"));
+    /// Write the opening line and the graph-wide attribute statements.
+    fn header_fmt(&self, g: G, f: &mut fmt::Formatter) -> fmt::Result
+        where G: GraphProp,
+    {
+        try!(writeln!(f, "{} {{", TYPE[g.is_directed() as usize]));
+
+        // output graph-wide attribute statements
+        for &(kind, attrs) in &[("graph", self.graph_attributes),
+                                ("node", self.node_defaults),
+                                ("edge", self.edge_defaults)] {
+            if !attrs.is_empty() {
+                try!(writeln!(f, "{}{} [{}]", INDENT, kind, attrs));
+            }
+        }
         Ok(())
     }
+
+    /// Write the statement of `node`.
+    fn node_fmt<NF, NW>(&self, g: G, node: G::NodeRef, f: &mut fmt::Formatter,
+                        mut node_fmt: NF) -> fmt::Result
+        where G: NodeIndexable + Data<NodeWeight=NW>,
+              NF: FnMut(&NW, &mut FnMut(&Display) -> fmt::Result) -> fmt::Result,
+    {
+        try!(write!(f, "{}{}", INDENT, g.to_index(node.id())));
+        let attrs = (self.get_node_attributes)(g, node);
+        if self.config.contains(&Config::NodeIndexLabel) ||
+            self.config.contains(&Config::NodeNoLabel)
+        {
+            write_attrs(f, false, &attrs)
+        } else {
+            try!(write!(f, " [label=\""));
+            try!(node_fmt(node.weight(), &mut |d| Escaped(d).fmt(f)));
+            try!(write!(f, "\""));
+            write_attrs(f, true, &attrs)
+        }
+    }
+
+    /// Write the statement of `edge`, the `i`th edge.
+    fn edge_fmt<EF, EW>(&self, g: G, i: usize, edge: G::EdgeRef, f: &mut fmt::Formatter,
+                        mut edge_fmt: EF) -> fmt::Result
+        where G: NodeIndexable + GraphProp + Data<EdgeWeight=EW>,
+              EF: FnMut(&EW, &mut FnMut(&Display) -> fmt::Result) -> fmt::Result,
+    {
+        try!(write!(f, "{}{} {} {}",
+                    INDENT,
+                    g.to_index(edge.source()),
+                    EDGE[g.is_directed() as usize],
+                    g.to_index(edge.target())));
+        let attrs = (self.get_edge_attributes)(g, edge);
+        if self.config.contains(&Config::EdgeNoLabel) {
+            write_attrs(f, false, &attrs)
+        } else if self.config.contains(&Config::EdgeIndexLabel) {
+            try!(write!(f, " [label=\"{}\"", i));
+            write_attrs(f, true, &attrs)
+        } else {
+            try!(write!(f, " [label=\""));
+            try!(edge_fmt(edge.weight(), &mut |d| Escaped(d).fmt(f)));
+            try!(write!(f, "\""));
+            write_attrs(f, true, &attrs)
+        }
+    }
+}
+
+/// Display by calling a closure.
+struct FnFmt<F>(F);
+
+impl<F> fmt::Display for FnFmt<F>
+    where F: Fn(&mut fmt::Formatter) -> fmt::Result
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (self.0)(f)
+    }
+}
+
+/// Finish a node or edge statement: write the extra attributes `attrs`,
+/// after a label if `open` is true, and close the attribute list.
+fn write_attrs(f: &mut fmt::Formatter, open: bool, attrs: &str) -> fmt::Result {
+    match (open, attrs.is_empty()) {
+        (false, true) => writeln!(f, ""),
+        (false, false) => writeln!(f, " [{}]", attrs),
+        (true, true) => writeln!(f, "]"),
+        (true, false) => writeln!(f, " {}]", attrs),
+    }
 }
 
 impl<'a, G> fmt::Display for Dot<'a, G>
@@ -171,7 +383,7 @@ impl<W> fmt::Write for Escaper<W>
 
     fn write_char(&mut self, c: char) -> fmt::Result {
         match c {
-            '"' => try!(self.0.write_char('\\')),
+            '"' | '\\' => try!(self.0.write_char('\\')),
             // \l is for left justified linebreak
             '\n' => return self.0.write_str(r#"\l"#),
             _   => { }
@@ -188,7 +400,8 @@ impl<T> fmt::Display for Escaped<T>
 {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         if f.alternate() {
-            write!(&mut Escaper(f), "{:#}\\l", &self.0)
+            try!(write!(&mut Escaper(&mut *f), "{:#}", &self.0));
+            f.write_str("\\l")
         } else {
             write!(&mut Escaper(f), "{}", &self.0)
         }
@@ -0,0 +1,591 @@
+//! Parse graphviz dot files into a `Graph`.
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+use graph::{Graph, NodeIndex};
+use EdgeType;
+
+/// Node weight of a parsed graph: the node's DOT identifier and attributes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DotNode {
+    /// The node identifier, for example `a` in `a [color=red]`.
+    pub id: String,
+    /// The node's attributes, including attributes set with a default
+    /// `node [...]` statement before the node was created.
+    pub attributes: HashMap<String, String>,
+}
+
+/// Edge weight of a parsed graph: the edge's attributes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DotEdge {
+    /// The edge's attributes, including attributes set with a default
+    /// `edge [...]` statement before the edge.
+    pub attributes: HashMap<String, String>,
+}
+
+/// An error from `parse`, with the position where it occurred.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl ParseError {
+    /// The line of the error, starting at 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column of the error, in characters, starting at 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// A description of the error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+/// Parse a graph in the graphviz DOT language.
+///
+/// Each node becomes a node of the graph, in order of first appearance,
+/// with its identifier and attributes as the weight. Each edge becomes an
+/// edge, with its attributes as the weight; an edge chain `a -> b -> c`
+/// adds one edge per step. Default `node [...]` and `edge [...]`
+/// attributes are applied to the nodes and edges that follow them.
+///
+/// Subgraphs are flattened into the graph: their nodes and edges are added
+/// as usual, and a subgraph used as an edge endpoint stands for all of the
+/// nodes in it. Graph attributes and ports are parsed, but ignored.
+///
+/// The graph must be a `digraph` for a directed `Graph` and a `graph` for
+/// an undirected one. Quoted strings are unescaped per the DOT language:
+/// only `\"` is an escape sequence, other backslashes are kept.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::dot::{self, DotNode, DotEdge};
+///
+/// let g: Graph<DotNode, DotEdge> = dot::parse(r#"
+///     digraph {
+///         a -> b -> c [weight=2]
+///         c [shape=box]
+///     }
+/// "#).unwrap();
+/// assert_eq!(g.node_count(), 3);
+/// assert_eq!(g.edge_count(), 2);
+/// assert_eq!(g.raw_nodes()[2].weight.attributes["shape"], "box");
+/// ```
+pub fn parse<Ty>(input: &str) -> Result<Graph<DotNode, DotEdge, Ty>, ParseError>
+    where Ty: EdgeType,
+{
+    let mut parser = Parser {
+        lexer: Lexer::new(input),
+        peeked: None,
+        graph: Graph::with_capacity(0, 0),
+        ids: HashMap::new(),
+        node_defaults: HashMap::new(),
+        edge_defaults: HashMap::new(),
+    };
+    try!(parser.parse_graph());
+    Ok(parser.graph)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Tok {
+    /// An identifier; `bare` if it was not quoted, so that it may be a keyword.
+    Id { text: String, bare: bool },
+    LBrace,
+    RBrace,
+    LBracket,
+    RBracket,
+    Equals,
+    Semi,
+    Comma,
+    Colon,
+    Plus,
+    /// An edge operator, `->` if directed, `--` otherwise.
+    EdgeOp { directed: bool },
+    Eof,
+}
+
+#[derive(Clone, Debug)]
+struct Token {
+    tok: Tok,
+    line: usize,
+    column: usize,
+}
+
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Lexer {
+    fn new(input: &str) -> Self {
+        Lexer {
+            chars: input.chars().collect(),
+            pos: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn peek_char(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).cloned()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek_char(0);
+        if let Some(c) = c {
+            self.pos += 1;
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        c
+    }
+
+    fn error<T>(&self, line: usize, column: usize, message: String) -> Result<T, ParseError> {
+        Err(ParseError { line: line, column: column, message: message })
+    }
+
+    /// Skip whitespace, comments and `#` preprocessor lines.
+    fn skip_trivia(&mut self) -> Result<(), ParseError> {
+        loop {
+            match (self.peek_char(0), self.peek_char(1)) {
+                (Some(c), _) if c.is_whitespace() => { self.bump(); }
+                (Some('#'), _) if self.column == 1 => self.skip_line(),
+                (Some('/'), Some('/')) => self.skip_line(),
+                (Some('/'), Some('*')) => {
+                    let (line, column) = (self.line, self.column);
+                    self.bump();
+                    self.bump();
+                    loop {
+                        match (self.peek_char(0), self.peek_char(1)) {
+                            (Some('*'), Some('/')) => {
+                                self.bump();
+                                self.bump();
+                                break;
+                            }
+                            (Some(_), _) => { self.bump(); }
+                            (None, _) => {
+                                return self.error(line, column,
+                                                  "unterminated comment".to_string());
+                            }
+                        }
+                    }
+                }
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn skip_line(&mut self) {
+        while let Some(c) = self.bump() {
+            if c == '\n' {
+                break;
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Token, ParseError> {
+        try!(self.skip_trivia());
+        let (line, column) = (self.line, self.column);
+        let c = match self.peek_char(0) {
+            None => return Ok(Token { tok: Tok::Eof, line: line, column: column }),
+            Some(c) => c,
+        };
+        let tok = match c {
+            '{' => { self.bump(); Tok::LBrace }
+            '}' => { self.bump(); Tok::RBrace }
+            '[' => { self.bump(); Tok::LBracket }
+            ']' => { self.bump(); Tok::RBracket }
+            '=' => { self.bump(); Tok::Equals }
+            ';' => { self.bump(); Tok::Semi }
+            ',' => { self.bump(); Tok::Comma }
+            ':' => { self.bump(); Tok::Colon }
+            '+' => { self.bump(); Tok::Plus }
+            '-' if self.peek_char(1) == Some('>') || self.peek_char(1) == Some('-') => {
+                self.bump();
+                let directed = self.bump() == Some('>');
+                Tok::EdgeOp { directed: directed }
+            }
+            '"' => {
+                self.bump();
+                let mut text = String::new();
+                loop {
+                    match self.bump() {
+                        None => {
+                            return self.error(line, column, "unterminated string".to_string());
+                        }
+                        Some('"') => break,
+                        Some('\\') if self.peek_char(0) == Some('"') => {
+                            self.bump();
+                            text.push('"');
+                        }
+                        Some('\\') if self.peek_char(0) == Some('\n') => {
+                            // line continuation
+                            self.bump();
+                        }
+                        Some(c) => text.push(c),
+                    }
+                }
+                Tok::Id { text: text, bare: false }
+            }
+            '<' => {
+                self.bump();
+                let mut text = String::new();
+                let mut depth = 1;
+                loop {
+                    match self.bump() {
+                        None => {
+                            return self.error(line, column,
+                                              "unterminated HTML string".to_string());
+                        }
+                        Some('<') => depth += 1,
+                        Some('>') => {
+                            depth -= 1;
+                            if depth == 0 {
+                                break;
+                            }
+                        }
+                        Some(_) => {}
+                    }
+                    text.push(self.chars[self.pos - 1]);
+                }
+                Tok::Id { text: text, bare: false }
+            }
+            c if c == '-' || c == '.' || c.is_digit(10) => {
+                let mut text = String::new();
+                text.push(self.bump().unwrap());
+                while let Some(c) = self.peek_char(0) {
+                    if c == '.' || c.is_digit(10) {
+                        text.push(c);
+                        self.bump();
+                    } else {
+                        break;
+                    }
+                }
+                if !text.chars().any(|c| c.is_digit(10)) {
+                    return self.error(line, column, format!("invalid numeral {:?}", text));
+                }
+                Tok::Id { text: text, bare: true }
+            }
+            c if c == '_' || c.is_alphabetic() => {
+                let mut text = String::new();
+                while let Some(c) = self.peek_char(0) {
+                    if c == '_' || c.is_alphanumeric() {
+                        text.push(c);
+                        self.bump();
+                    } else {
+                        break;
+                    }
+                }
+                Tok::Id { text: text, bare: true }
+            }
+            c => return self.error(line, column, format!("unexpected character {:?}", c)),
+        };
+        Ok(Token { tok: tok, line: line, column: column })
+    }
+}
+
+struct Parser<Ty> {
+    lexer: Lexer,
+    peeked: Option<Token>,
+    graph: Graph<DotNode, DotEdge, Ty>,
+    ids: HashMap<String, NodeIndex>,
+    node_defaults: HashMap<String, String>,
+    edge_defaults: HashMap<String, String>,
+}
+
+fn describe(tok: &Tok) -> String {
+    match *tok {
+        Tok::Id { ref text, .. } => format!("{:?}", text),
+        Tok::LBrace => "`{`".to_string(),
+        Tok::RBrace => "`}`".to_string(),
+        Tok::LBracket => "`[`".to_string(),
+        Tok::RBracket => "`]`".to_string(),
+        Tok::Equals => "`=`".to_string(),
+        Tok::Semi => "`;`".to_string(),
+        Tok::Comma => "`,`".to_string(),
+        Tok::Colon => "`:`".to_string(),
+        Tok::Plus => "`+`".to_string(),
+        Tok::EdgeOp { directed: true } => "`->`".to_string(),
+        Tok::EdgeOp { directed: false } => "`--`".to_string(),
+        Tok::Eof => "end of input".to_string(),
+    }
+}
+
+fn is_keyword(tok: &Tok, keyword: &str) -> bool {
+    match *tok {
+        // the keywords are lower case
+        Tok::Id { ref text, bare: true } => text.to_lowercase() == keyword,
+        _ => false,
+    }
+}
+
+impl<Ty> Parser<Ty>
+    where Ty: EdgeType,
+{
+    fn peek(&mut self) -> Result<&Token, ParseError> {
+        if self.peeked.is_none() {
+            self.peeked = Some(try!(self.lexer.next_token()));
+        }
+        Ok(self.peeked.as_ref().unwrap())
+    }
+
+    fn next(&mut self) -> Result<Token, ParseError> {
+        match self.peeked.take() {
+            Some(token) => Ok(token),
+            None => self.lexer.next_token(),
+        }
+    }
+
+    fn error_at<T>(&self, token: &Token, message: String) -> Result<T, ParseError> {
+        Err(ParseError { line: token.line, column: token.column, message: message })
+    }
+
+    fn expect(&mut self, tok: Tok) -> Result<Token, ParseError> {
+        let token = try!(self.next());
+        if token.tok != tok {
+            return self.error_at(&token, format!("expected {}, found {}",
+                                                 describe(&tok), describe(&token.tok)));
+        }
+        Ok(token)
+    }
+
+    /// Consume the next token if it is `tok`.
+    fn eat(&mut self, tok: Tok) -> Result<bool, ParseError> {
+        if try!(self.peek()).tok == tok {
+            try!(self.next());
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Parse an identifier, including `"a" + "b"` concatenation.
+    fn parse_id(&mut self) -> Result<String, ParseError> {
+        let token = try!(self.next());
+        match token.tok {
+            Tok::Id { text, bare } => {
+                let mut text = text;
+                if !bare {
+                    while try!(self.eat(Tok::Plus)) {
+                        let token = try!(self.next());
+                        match token.tok {
+                            Tok::Id { text: ref more, bare: false } => text.push_str(more),
+                            ref tok => {
+                                return self.error_at(&token, format!(
+                                    "expected a quoted string after `+`, found {}",
+                                    describe(tok)));
+                            }
+                        }
+                    }
+                }
+                Ok(text)
+            }
+            ref tok => self.error_at(&token, format!("expected an identifier, found {}",
+                                                     describe(tok))),
+        }
+    }
+
+    fn parse_graph(&mut self) -> Result<(), ParseError> {
+        if is_keyword(&try!(self.peek()).tok, "strict") {
+            try!(self.next());
+        }
+        let token = try!(self.next());
+        let directed = if is_keyword(&token.tok, "digraph") {
+            true
+        } else if is_keyword(&token.tok, "graph") {
+            false
+        } else {
+            return self.error_at(&token, format!("expected `graph` or `digraph`, found {}",
+                                                 describe(&token.tok)));
+        };
+        if directed != Ty::is_directed() {
+            return self.error_at(&token, format!(
+                "expected a {}, for a graph with {} edges",
+                if Ty::is_directed() { "digraph" } else { "graph" },
+                if Ty::is_directed() { "directed" } else { "undirected" }));
+        }
+        if let Tok::Id { .. } = try!(self.peek()).tok {
+            try!(self.parse_id());
+        }
+        try!(self.expect(Tok::LBrace));
+        try!(self.parse_stmt_list());
+        try!(self.expect(Tok::RBrace));
+        try!(self.expect(Tok::Eof));
+        Ok(())
+    }
+
+    /// Parse statements up to a closing brace; return all nodes used in them.
+    fn parse_stmt_list(&mut self) -> Result<Vec<NodeIndex>, ParseError> {
+        let mut nodes = Vec::new();
+        loop {
+            match try!(self.peek()).tok {
+                Tok::RBrace | Tok::Eof => return Ok(nodes),
+                _ => {}
+            }
+            try!(self.parse_stmt(&mut nodes));
+            try!(self.eat(Tok::Semi));
+        }
+    }
+
+    fn parse_stmt(&mut self, nodes: &mut Vec<NodeIndex>) -> Result<(), ParseError> {
+        let token = try!(self.peek()).clone();
+        if is_keyword(&token.tok, "graph") {
+            try!(self.next());
+            try!(self.parse_attr_list());
+            return Ok(());
+        }
+        if is_keyword(&token.tok, "node") || is_keyword(&token.tok, "edge") {
+            try!(self.next());
+            let attrs = try!(self.parse_attr_list());
+            let defaults = if is_keyword(&token.tok, "node") {
+                &mut self.node_defaults
+            } else {
+                &mut self.edge_defaults
+            };
+            defaults.extend(attrs);
+            return Ok(());
+        }
+        let first = if is_keyword(&token.tok, "subgraph") || token.tok == Tok::LBrace {
+            try!(self.parse_subgraph())
+        } else {
+            let id = try!(self.parse_id());
+            if try!(self.eat(Tok::Equals)) {
+                // graph attribute `ID = ID`
+                try!(self.parse_id());
+                return Ok(());
+            }
+            try!(self.parse_port());
+            if let Tok::EdgeOp { .. } = try!(self.peek()).tok {
+                vec![self.node(id)]
+            } else {
+                // node statement
+                let attrs = try!(self.parse_attr_list());
+                let a = self.node(id);
+                self.graph[a].attributes.extend(attrs);
+                nodes.push(a);
+                return Ok(());
+            }
+        };
+        nodes.extend(first.iter().cloned());
+        let mut groups = vec![first];
+        while let Tok::EdgeOp { directed } = try!(self.peek()).tok {
+            let token = try!(self.next());
+            if directed != Ty::is_directed() {
+                return self.error_at(&token, format!("edge operator {} in a {}",
+                    describe(&token.tok),
+                    if Ty::is_directed() { "digraph" } else { "graph" }));
+            }
+            let token = try!(self.peek()).clone();
+            let group = if is_keyword(&token.tok, "subgraph") || token.tok == Tok::LBrace {
+                try!(self.parse_subgraph())
+            } else {
+                let id = try!(self.parse_id());
+                try!(self.parse_port());
+                vec![self.node(id)]
+            };
+            nodes.extend(group.iter().cloned());
+            groups.push(group);
+        }
+        if groups.len() == 1 {
+            // a subgraph by itself
+            return Ok(());
+        }
+        let mut attributes = self.edge_defaults.clone();
+        attributes.extend(try!(self.parse_attr_list()));
+        for pair in groups.windows(2) {
+            for &a in &pair[0] {
+                for &b in &pair[1] {
+                    self.graph.add_edge(a, b, DotEdge { attributes: attributes.clone() });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse `[subgraph [ID]] { stmt_list }`; default attributes set inside
+    /// are local to the subgraph.
+    fn parse_subgraph(&mut self) -> Result<Vec<NodeIndex>, ParseError> {
+        if is_keyword(&try!(self.peek()).tok, "subgraph") {
+            try!(self.next());
+            if let Tok::Id { .. } = try!(self.peek()).tok {
+                try!(self.parse_id());
+            }
+        }
+        try!(self.expect(Tok::LBrace));
+        let node_defaults = self.node_defaults.clone();
+        let edge_defaults = self.edge_defaults.clone();
+        let nodes = try!(self.parse_stmt_list());
+        try!(self.expect(Tok::RBrace));
+        self.node_defaults = node_defaults;
+        self.edge_defaults = edge_defaults;
+        Ok(nodes)
+    }
+
+    /// Parse and ignore an optional port `:ID[:ID]` after a node id.
+    fn parse_port(&mut self) -> Result<(), ParseError> {
+        if try!(self.eat(Tok::Colon)) {
+            try!(self.parse_id());
+            if try!(self.eat(Tok::Colon)) {
+                try!(self.parse_id());
+            }
+        }
+        Ok(())
+    }
+
+    /// Parse zero or more `[ID = ID, ...]` lists.
+    fn parse_attr_list(&mut self) -> Result<Vec<(String, String)>, ParseError> {
+        let mut attrs = Vec::new();
+        while try!(self.eat(Tok::LBracket)) {
+            while !try!(self.eat(Tok::RBracket)) {
+                let key = try!(self.parse_id());
+                try!(self.expect(Tok::Equals));
+                let value = try!(self.parse_id());
+                attrs.push((key, value));
+                if !try!(self.eat(Tok::Comma)) {
+                    try!(self.eat(Tok::Semi));
+                }
+            }
+        }
+        Ok(attrs)
+    }
+
+    /// Return the node with identifier `id`, adding it if needed.
+    fn node(&mut self, id: String) -> NodeIndex {
+        if let Some(&a) = self.ids.get(&id) {
+            return a;
+        }
+        let a = self.graph.add_node(DotNode {
+            id: id.clone(),
+            attributes: self.node_defaults.clone(),
+        });
+        self.ids.insert(id, a);
+        a
+    }
+}
@@ -0,0 +1,172 @@
+//! `FrozenGraph<N, E, Ty, Ix>` is a read only `Graph`, for sharing between
+//! threads.
+
+use std::fmt;
+use std::ops::Deref;
+use std::sync::Arc;
+
+use fixedbitset::FixedBitSet;
+
+use algo::{dijkstra_into, DijkstraSpace, Measure};
+use graph::{
+    DefaultIx,
+    EdgeReference,
+    Graph,
+    IndexType,
+    NodeIndex,
+};
+use visit::{Bfs, VisitMap, Visitable};
+use {
+    Directed,
+    EdgeType,
+};
+
+/// A read only `Graph`.
+///
+/// A `FrozenGraph` is created by consuming a `Graph`, and it only gives
+/// shared access to it (through `Deref`), so it can't be changed anymore. It
+/// is `Send` and `Sync` if the weights are, and is meant to be put in an
+/// `Arc` and shared between threads, each with its own `Searcher` for the
+/// traversal state.
+///
+/// ```
+/// use std::sync::Arc;
+/// use std::thread;
+/// use petgraph::Graph;
+/// use petgraph::frozen_graph::{FrozenGraph, Searcher};
+///
+/// let gr: Graph<(), u32> = Graph::from_edges(&[(0, 1, 2), (1, 2, 2), (0, 2, 5)]);
+/// let gr = Arc::new(FrozenGraph::new(gr));
+///
+/// let threads = (0..3).map(|i| {
+///     let mut searcher = Searcher::new(gr.clone());
+///     thread::spawn(move || {
+///         let start = searcher.graph().node_indices().nth(i).unwrap();
+///         searcher.dijkstra(start, |e| *e.weight()).to_vec()
+///     })
+/// }).collect::<Vec<_>>();
+/// let distances = threads.into_iter().map(|t| t.join().unwrap()).collect::<Vec<_>>();
+/// assert_eq!(distances[0], vec![Some(0), Some(2), Some(4)]);
+/// ```
+pub struct FrozenGraph<N, E, Ty = Directed, Ix = DefaultIx> {
+    graph: Graph<N, E, Ty, Ix>,
+}
+
+impl<N, E, Ty, Ix> FrozenGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Create a `FrozenGraph` from `graph`.
+    pub fn new(graph: Graph<N, E, Ty, Ix>) -> Self {
+        FrozenGraph { graph: graph }
+    }
+
+    /// Return the underlying `Graph`, which can be changed again.
+    pub fn into_inner(self) -> Graph<N, E, Ty, Ix> {
+        self.graph
+    }
+}
+
+impl<N, E, Ty, Ix> From<Graph<N, E, Ty, Ix>> for FrozenGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn from(graph: Graph<N, E, Ty, Ix>) -> Self {
+        FrozenGraph::new(graph)
+    }
+}
+
+impl<N, E, Ty, Ix> Clone for FrozenGraph<N, E, Ty, Ix>
+    where N: Clone,
+          E: Clone,
+          Ix: IndexType,
+{
+    fn clone(&self) -> Self {
+        FrozenGraph { graph: self.graph.clone() }
+    }
+}
+
+impl<N, E, Ty, Ix> fmt::Debug for FrozenGraph<N, E, Ty, Ix>
+    where N: fmt::Debug,
+          E: fmt::Debug,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.graph.fmt(f)
+    }
+}
+
+impl<N, E, Ty, Ix> Deref for FrozenGraph<N, E, Ty, Ix> {
+    type Target = Graph<N, E, Ty, Ix>;
+    fn deref(&self) -> &Graph<N, E, Ty, Ix> {
+        &self.graph
+    }
+}
+
+/// Traversal state for searching a shared `FrozenGraph`.
+///
+/// Each thread should have its own `Searcher`. The buffers are reused
+/// between searches, so after the first search a `Searcher` does not
+/// allocate. `K` is the type of path costs for `dijkstra`.
+pub struct Searcher<N, E, K, Ty = Directed, Ix = DefaultIx>
+    where Ix: IndexType,
+{
+    graph: Arc<FrozenGraph<N, E, Ty, Ix>>,
+    dist: Vec<Option<K>>,
+    dijkstra: DijkstraSpace<NodeIndex<Ix>, K>,
+    bfs: Bfs<NodeIndex<Ix>, FixedBitSet>,
+    order: Vec<NodeIndex<Ix>>,
+}
+
+impl<N, E, K, Ty, Ix> Searcher<N, E, K, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          K: Measure + Copy,
+{
+    /// Create a `Searcher` for `graph`.
+    pub fn new(graph: Arc<FrozenGraph<N, E, Ty, Ix>>) -> Self {
+        let bfs = Bfs {
+            stack: Default::default(),
+            discovered: graph.visit_map(),
+        };
+        Searcher {
+            dist: vec![None; graph.node_count()],
+            dijkstra: DijkstraSpace::new(),
+            bfs: bfs,
+            order: Vec::new(),
+            graph: graph,
+        }
+    }
+
+    /// Return the graph that is searched.
+    pub fn graph(&self) -> &Arc<FrozenGraph<N, E, Ty, Ix>> {
+        &self.graph
+    }
+
+    /// Compute the length of the shortest path from `start` to every node,
+    /// like `algo::dijkstra_into`.
+    ///
+    /// Return the path costs, indexed by node index; `None` for the nodes
+    /// that are not reachable.
+    pub fn dijkstra<F>(&mut self, start: NodeIndex<Ix>, edge_cost: F) -> &[Option<K>]
+        where F: FnMut(EdgeReference<E, Ix>) -> K,
+    {
+        dijkstra_into(&**self.graph, start, edge_cost, &mut self.dist, &mut self.dijkstra);
+        &self.dist
+    }
+
+    /// Return the nodes reachable from `start`, in breadth first order.
+    pub fn bfs(&mut self, start: NodeIndex<Ix>) -> &[NodeIndex<Ix>] {
+        let graph = &**self.graph;
+        graph.reset_map(&mut self.bfs.discovered);
+        self.bfs.stack.clear();
+        self.bfs.discovered.visit(start);
+        self.bfs.stack.push_back(start);
+        self.order.clear();
+        while let Some(node) = self.bfs.next(graph) {
+            self.order.push(node);
+        }
+        &self.order
+    }
+}
@@ -1,9 +1,11 @@
 use std::cmp;
 use std::fmt;
+use std::collections::{HashMap, VecDeque};
+use std::collections::hash_map::Entry;
 use std::hash::Hash;
 use std::iter;
 use std::marker::PhantomData;
-use std::mem::size_of;
+use std::mem::{self, size_of};
 use std::ops::{Deref, Index, IndexMut, Range};
 use std::slice;
 
@@ -24,8 +26,9 @@ use iter_format::{
 use visit::EdgeRef;
 use visit::{Data, IntoNodeIdentifiers, GraphProp, NodeIndexable, IntoNeighborsDirected};
 use visit::{IntoNeighbors, IntoNodeReferences, IntoEdgeReferences, Visitable};
-use visit::{NodeCompactIndexable, GetAdjacencyMatrix, NodeCount, IntoEdges};
+use visit::{NodeCompactIndexable, GetAdjacencyMatrix, NodeCount, IntoEdges, IntoEdgesDirected};
 use data::{DataMap, DataMapMut};
+use stats::{Histogram, WeightStats};
 
 
 /// The default integer type for graph indices.
@@ -200,6 +203,12 @@ impl<E, Ix> Clone for Node<E, Ix> where E: Clone, Ix: Copy {
 
 impl<N, Ix: IndexType> Node<N, Ix>
 {
+    /// Return the node weight.
+    pub fn weight(&self) -> &N
+    {
+        &self.weight
+    }
+
     /// Accessor for data structure internals: the first edge in the given direction.
     pub fn next_edge(&self, dir: Direction) -> EdgeIndex<Ix>
     {
@@ -229,6 +238,12 @@ impl<E, Ix> Clone for Edge<E, Ix> where E: Clone, Ix: Copy {
 
 impl<E, Ix: IndexType> Edge<E, Ix>
 {
+    /// Return the edge weight.
+    pub fn weight(&self) -> &E
+    {
+        &self.weight
+    }
+
     /// Accessor for data structure internals: the next edge for the given direction.
     pub fn next_edge(&self, dir: Direction) -> EdgeIndex<Ix>
     {
@@ -248,6 +263,43 @@ impl<E, Ix: IndexType> Edge<E, Ix>
     }
 }
 
+/// `Graph` creation error: an edge endpoint is not a node of the graph.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InvalidEdge {
+    edge: usize,
+}
+
+impl InvalidEdge {
+    /// Return the position of the first edge with an endpoint out of bounds.
+    pub fn edge(&self) -> usize {
+        self.edge
+    }
+}
+
+/// `Graph` error: a node index is not a node of the graph.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct InvalidNode<Ix = DefaultIx> {
+    node: NodeIndex<Ix>,
+}
+
+impl<Ix: IndexType> InvalidNode<Ix> {
+    /// Return the node index that is out of bounds.
+    pub fn node_id(&self) -> NodeIndex<Ix> {
+        self.node
+    }
+}
+
+/// `Graph` error: an edge could not be added.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EdgeAddError {
+    /// The source node of the edge doesn't exist.
+    SourceMissing,
+    /// The target node of the edge doesn't exist.
+    TargetMissing,
+    /// The graph is at the maximum number of edges for its index type.
+    IndexExhausted,
+}
+
 /// `Graph<N, E, Ty, Ix>` is a graph datastructure using an adjacency list representation.
 ///
 /// `Graph` is parameterized over:
@@ -507,6 +559,26 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         self.nodes.get_mut(a.index()).map(|n| &mut n.weight)
     }
 
+    /// Replace the weight of node `a` with `weight`, and return the old
+    /// weight, or `None` if the node doesn't exist (`weight` is then
+    /// dropped).
+    pub fn replace_node_weight(&mut self, a: NodeIndex<Ix>, weight: N) -> Option<N>
+    {
+        self.nodes.get_mut(a.index()).map(|n| mem::replace(&mut n.weight, weight))
+    }
+
+    /// Swap the weights of nodes `a` and `b`. The edges stay as they are.
+    ///
+    /// **Panics** if any of the nodes don't exist.
+    pub fn swap_node_weights(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>)
+    {
+        match index_twice(&mut self.nodes, a.index(), b.index()) {
+            Pair::None => panic!("Graph::swap_node_weights: node indices out of bounds"),
+            Pair::One(_) => {}
+            Pair::Both(an, bn) => mem::swap(&mut an.weight, &mut bn.weight),
+        }
+    }
+
     /// Add an edge from `a` to `b` to the graph, with its associated
     /// data `weight`.
     ///
@@ -522,15 +594,44 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
     /// to avoid this, use [`.update_edge(a, b, weight)`](#method.update_edge) instead.
     pub fn add_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E) -> EdgeIndex<Ix>
     {
+        match self.try_add_edge(a, b, weight) {
+            Ok(edge_idx) => edge_idx,
+            Err(EdgeAddError::IndexExhausted) => {
+                panic!("Graph::add_edge: the maximum number of edges is reached")
+            }
+            Err(_) => panic!("Graph::add_edge: node indices out of bounds"),
+        }
+    }
+
+    /// Add an edge from `a` to `b` to the graph, with its associated
+    /// data `weight`, or return an error if it can't be added.
+    ///
+    /// Return the index of the new edge, or an `EdgeAddError` telling
+    /// whether `a` or `b` doesn't exist (`a` is checked first), or if the
+    /// Graph is at the maximum number of edges for its index type. The graph
+    /// is unchanged on error.
+    ///
+    /// Computes in **O(1)** time.
+    pub fn try_add_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E)
+        -> Result<EdgeIndex<Ix>, EdgeAddError>
+    {
+        if a.index() >= self.nodes.len() {
+            return Err(EdgeAddError::SourceMissing);
+        }
+        if b.index() >= self.nodes.len() {
+            return Err(EdgeAddError::TargetMissing);
+        }
         let edge_idx = EdgeIndex::new(self.edges.len());
-        assert!(Ix::max().index() == !0 || EdgeIndex::end() != edge_idx);
+        if Ix::max().index() != !0 && EdgeIndex::end() == edge_idx {
+            return Err(EdgeAddError::IndexExhausted);
+        }
         let mut edge = Edge {
             weight: weight,
             node: [a, b],
             next: [EdgeIndex::end(); 2],
         };
         match index_twice(&mut self.nodes, a.index(), b.index()) {
-            Pair::None => panic!("Graph::add_edge: node indices out of bounds"),
+            Pair::None => unreachable!(),
             Pair::One(an) => {
                 edge.next = an.next;
                 an.next[0] = edge_idx;
@@ -544,7 +645,7 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
             }
         }
         self.edges.push(edge);
-        edge_idx
+        Ok(edge_idx)
     }
 
     /// Add or update an edge from `a` to `b`.
@@ -557,14 +658,92 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
     ///
     /// **Panics** if any of the nodes don't exist.
     pub fn update_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E) -> EdgeIndex<Ix>
+    {
+        match self.try_update_edge(a, b, weight) {
+            Ok(edge_idx) => edge_idx,
+            Err(EdgeAddError::IndexExhausted) => {
+                panic!("Graph::update_edge: the maximum number of edges is reached")
+            }
+            Err(_) => panic!("Graph::update_edge: node indices out of bounds"),
+        }
+    }
+
+    /// Add an edge from `a` to `b` with weight `default`, or if there already
+    /// is one, apply `f` to its weight instead.
+    ///
+    /// Return the index of the affected edge.
+    ///
+    /// ```
+    /// use petgraph::Graph;
+    ///
+    /// // count how often each pair occurs
+    /// let mut gr = Graph::<(), u32>::new();
+    /// let a = gr.add_node(());
+    /// let b = gr.add_node(());
+    /// for &(x, y) in &[(a, b), (b, a), (a, b)] {
+    ///     gr.update_edge_with(x, y, 1, |count| *count += 1);
+    /// }
+    /// assert_eq!(gr[gr.find_edge(a, b).unwrap()], 2);
+    /// assert_eq!(gr[gr.find_edge(b, a).unwrap()], 1);
+    /// ```
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of edges
+    /// connected to `a` (and `b`, if the graph edges are undirected).
+    ///
+    /// **Panics** if any of the nodes don't exist.
+    pub fn update_edge_with<F>(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, default: E,
+                               f: F) -> EdgeIndex<Ix>
+        where F: FnOnce(&mut E),
+    {
+        match self.find_edge(a, b) {
+            Some(ix) => {
+                f(&mut self.edges[ix.index()].weight);
+                ix
+            }
+            None => self.add_edge(a, b, default),
+        }
+    }
+
+    /// Add or update an edge from `a` to `b`, like `.update_edge()`, and
+    /// also return the weight it replaced.
+    ///
+    /// Return the index of the affected edge, and its old weight if the edge
+    /// already existed.
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of edges
+    /// connected to `a` (and `b`, if the graph edges are undirected).
+    ///
+    /// **Panics** if any of the nodes don't exist.
+    pub fn update_edge_replacing(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E)
+        -> (EdgeIndex<Ix>, Option<E>)
+    {
+        match self.find_edge(a, b) {
+            Some(ix) => {
+                let old = mem::replace(&mut self.edges[ix.index()].weight, weight);
+                (ix, Some(old))
+            }
+            None => (self.add_edge(a, b, weight), None),
+        }
+    }
+
+    /// Add or update an edge from `a` to `b`, or return an error if it can't
+    /// be added.
+    ///
+    /// Like `.update_edge()`, but return an `EdgeAddError` where that panics,
+    /// as for `.try_add_edge()`.
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of edges
+    /// connected to `a` (and `b`, if the graph edges are undirected).
+    pub fn try_update_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E)
+        -> Result<EdgeIndex<Ix>, EdgeAddError>
     {
         if let Some(ix) = self.find_edge(a, b) {
             if let Some(ed) = self.edge_weight_mut(ix) {
                 *ed = weight;
-                return ix;
+                return Ok(ix);
             }
         }
-        self.add_edge(a, b, weight)
+        self.try_add_edge(a, b, weight)
     }
 
     /// Access the weight for edge `e`.
@@ -583,7 +762,11 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         self.edges.get_mut(e.index()).map(|ed| &mut ed.weight)
     }
 
-    /// Access the source and target nodes for `e`.
+    /// Access the source and target nodes for `e`, or `None` if the edge
+    /// doesn't exist.
+    ///
+    /// For an undirected graph, the source and target are the endpoints in
+    /// the order they were passed to `.add_edge()`.
     pub fn edge_endpoints(&self, e: EdgeIndex<Ix>)
         -> Option<(NodeIndex<Ix>, NodeIndex<Ix>)>
     {
@@ -703,6 +886,226 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         self.remove_edge_adjust_indices(e)
     }
 
+    /// Remove an edge from `a` to `b` and return its edge weight, or `None`
+    /// if there is no such edge.
+    ///
+    /// - `Directed`: Only an edge from `a` to `b` is removed.
+    /// - `Undirected`: An edge between `a` and `b` in either orientation is
+    ///   removed.
+    ///
+    /// If there are several parallel edges, only one of them is removed: the
+    /// first one, with the lowest edge index.
+    ///
+    /// Like `.remove_edge()`, this invalidates the last edge index in the graph.
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of edges
+    /// connected to `a`, plus the cost of `.remove_edge()`.
+    pub fn remove_edge_between(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Option<E>
+    {
+        let mut first: Option<EdgeIndex<Ix>> = None;
+        if let Some(node) = self.nodes.get(a.index()) {
+            // a directed edge from `a` to `b` is only in the outgoing list
+            let n_dirs = if self.is_directed() { 1 } else { 2 };
+            for &d in &DIRECTIONS[..n_dirs] {
+                let k = d.index();
+                let mut edix = node.next[k];
+                while let Some(edge) = self.edges.get(edix.index()) {
+                    if edge.node[1 - k] == b &&
+                        first.map_or(true, |f| edix.index() < f.index())
+                    {
+                        first = Some(edix);
+                    }
+                    edix = edge.next[k];
+                }
+            }
+        }
+        match first {
+            None => None,
+            Some(e) => self.remove_edge(e),
+        }
+    }
+
+    /// Remove all edges from `a` to `b` and return their edge weights, in the
+    /// order they were removed.
+    ///
+    /// - `Directed`: Only edges from `a` to `b` are removed.
+    /// - `Undirected`: All edges between `a` and `b` are removed.
+    ///
+    /// Each edge is removed as by `.remove_edge_between(a, b)`, so this
+    /// invalidates edge indices like that many calls of `.remove_edge()`.
+    ///
+    /// Computes in **O(k e')** time, where **k** is the number of edges
+    /// removed and **e'** the number of edges connected to `a` (and `b`, if
+    /// the graph edges are undirected), plus the cost of `.remove_edge()`.
+    pub fn remove_edges_between(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Vec<E>
+    {
+        let mut removed = Vec::new();
+        while let Some(weight) = self.remove_edge_between(a, b) {
+            removed.push(weight);
+        }
+        removed
+    }
+
+    /// Merge parallel edges, so that at most one edge is left from each node
+    /// to each other node, and return the number of edges removed.
+    ///
+    /// - `Directed`: Edges from `a` to `b` are merged; an edge from `b` to
+    ///   `a` is not parallel to them.
+    /// - `Undirected`: All edges between `a` and `b` are merged.
+    ///
+    /// Parallel self loops are merged too. The weights of parallel edges are
+    /// combined with `merge(weight, next_weight)`, in the order of their edge
+    /// indices, and the merged edge takes the place of the first of them.
+    ///
+    /// The remaining edges keep their relative order, but their edge indices
+    /// are invalidated if any edge is removed.
+    ///
+    /// If `merge` panics, the two weights it was given are lost along with
+    /// their edges. The graph keeps all its other edges, with the weights
+    /// merged so far, and stays usable.
+    ///
+    /// Computes in **O(|V| + |E|)** time.
+    pub fn merge_parallel_edges<F>(&mut self, mut merge: F) -> usize
+        where F: FnMut(E, E) -> E,
+    {
+        /// Add the edges of the weights not merged yet when dropped, so that
+        /// they are kept if `merge` panics.
+        struct Rebuild<'a, N: 'a, E: 'a, Ty: 'a, Ix: 'a>
+            where Ty: EdgeType,
+                  Ix: IndexType,
+        {
+            graph: &'a mut Graph<N, E, Ty, Ix>,
+            groups: Vec<(NodeIndex<Ix>, NodeIndex<Ix>, ::std::vec::IntoIter<E>)>,
+        }
+
+        impl<'a, N, E, Ty, Ix> Drop for Rebuild<'a, N, E, Ty, Ix>
+            where Ty: EdgeType,
+                  Ix: IndexType,
+        {
+            fn drop(&mut self) {
+                for &mut (a, b, ref mut weights) in &mut self.groups {
+                    for weight in weights {
+                        self.graph.add_edge(a, b, weight);
+                    }
+                }
+            }
+        }
+
+        let edge_count = self.edge_count();
+        let edges = mem::replace(&mut self.edges, Vec::with_capacity(edge_count));
+        self.clear_edges();
+        // group the weights of parallel edges, in order of their first edge
+        let mut groups: Vec<(NodeIndex<Ix>, NodeIndex<Ix>, Vec<E>)> = Vec::new();
+        let mut position = HashMap::<_, usize>::new();
+        for edge in edges {
+            let (a, b) = (edge.source(), edge.target());
+            let key = if !self.is_directed() && b < a { (b, a) } else { (a, b) };
+            match position.entry(key) {
+                Entry::Occupied(ent) => groups[*ent.get()].2.push(edge.weight),
+                Entry::Vacant(ent) => {
+                    ent.insert(groups.len());
+                    groups.push((a, b, vec![edge.weight]));
+                }
+            }
+        }
+        let removed = edge_count - groups.len();
+        let mut rebuild = Rebuild {
+            graph: self,
+            groups: groups.into_iter().map(|(a, b, w)| (a, b, w.into_iter())).collect(),
+        };
+        for i in 0..rebuild.groups.len() {
+            let (a, b) = (rebuild.groups[i].0, rebuild.groups[i].1);
+            let mut weight = rebuild.groups[i].2.next().unwrap();
+            while let Some(next_weight) = rebuild.groups[i].2.next() {
+                weight = merge(weight, next_weight);
+            }
+            rebuild.graph.add_edge(a, b, weight);
+        }
+        removed
+    }
+
+    /// Merge node `b` into node `a`: the edges of `b` are moved to `a`, `b`
+    /// is removed, and the weight of `a` becomes `merge(weight_a, weight_b)`.
+    /// Return the index of the merged node.
+    ///
+    /// Edges between `a` and `b` become self loops of the merged node if
+    /// `self_loops` is `true`, and are removed otherwise; self loops that
+    /// `a` or `b` already had are kept. Parallel edges are kept as they are,
+    /// including the parallel edges that the merge creates, when `a` and `b`
+    /// both have an edge to the same node.
+    ///
+    /// Like `.remove_node(b)`, this invalidates the last node index in the
+    /// graph, which is the index of the merged node if `a` was the last node.
+    /// The edges of `b` are removed and added again, so edge indices are
+    /// invalidated as by `.remove_edge()` and `.add_edge()` of each of them.
+    ///
+    /// **Panics** if `a` or `b` doesn't exist.
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of edges of
+    /// `b`, times the cost of `.remove_edge()`.
+    pub fn merge_nodes<F>(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, merge: F,
+                          self_loops: bool) -> NodeIndex<Ix>
+        where F: FnOnce(N, N) -> N,
+    {
+        assert!(a.index() < self.node_count() && b.index() < self.node_count(),
+                "Graph::merge_nodes: node index out of bounds");
+        if a == b {
+            return a;
+        }
+        for d in &DIRECTIONS {
+            loop {
+                let next = self.nodes[b.index()].next[d.index()];
+                if next == EdgeIndex::end() {
+                    break;
+                }
+                let node = self.edges[next.index()].node;
+                let (source, target) = (node[0], node[1]);
+                let joins = source == a || target == a;
+                let weight = self.remove_edge(next).unwrap();
+                if joins && !self_loops {
+                    continue;
+                }
+                let source = if source == b { a } else { source };
+                let target = if target == b { a } else { target };
+                self.add_edge(source, target, weight);
+            }
+        }
+        let weight_b = self.remove_node(b).unwrap();
+        // the last node, which `a` may be, takes the place of `b`
+        let a = if a.index() == self.node_count() { b } else { a };
+        // move the node to the end to take its weight and put it back
+        let last = self.node_count() - 1;
+        self.nodes.swap(a.index(), last);
+        let node = self.nodes.pop().unwrap();
+        self.nodes.push(Node {
+            weight: merge(node.weight, weight_b),
+            next: node.next,
+        });
+        self.nodes.swap(a.index(), last);
+        a
+    }
+
+    /// Contract the edge `e`: remove it and merge its target into its source
+    /// with `.merge_nodes(source, target, merge, self_loops)`. Return the index
+    /// of the merged node, or `None` if the edge doesn't exist.
+    ///
+    /// A self loop is only removed. The other edges between the endpoints
+    /// become self loops or are removed as `self_loops` says, and parallel
+    /// edges are kept, as for `.merge_nodes()`.
+    ///
+    /// Invalidates node and edge indices like `.merge_nodes()`.
+    pub fn contract_edge<F>(&mut self, e: EdgeIndex<Ix>, merge: F, self_loops: bool)
+        -> Option<NodeIndex<Ix>>
+        where F: FnOnce(N, N) -> N,
+    {
+        let (a, b) = match self.edge_endpoints(e) {
+            None => return None,
+            Some(x) => x,
+        };
+        self.remove_edge(e);
+        Some(self.merge_nodes(a, b, merge, self_loops))
+    }
+
     fn remove_edge_adjust_indices(&mut self, e: EdgeIndex<Ix>) -> Option<E>
     {
         // swap_remove the edge -- only the removed edge
@@ -775,6 +1178,11 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
     ///
     /// - `Directed` and `Undirected`: All edges from or to `a`.
     ///
+    /// The neighbors of the outgoing edges come first, then those of the
+    /// incoming edges, each the most recently added edge first. A neighbor
+    /// is produced once for each edge, so parallel edges produce repeated
+    /// neighbors; a self loop produces `a` only once.
+    ///
     /// Produces an empty iterator if the node doesn't exist.<br>
     /// Iterator element type is `NodeIndex<Ix>`.
     ///
@@ -795,6 +1203,95 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         }
     }
 
+    /// Return the number of edges from `a`.
+    ///
+    /// - `Directed`: The number of outgoing edges; a self loop counts once.
+    /// - `Undirected`: The same as `.degree(a)`.
+    ///
+    /// Parallel edges are each counted. Returns `0` if the node doesn't exist.
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of edges
+    /// connected to `a`.
+    pub fn out_degree(&self, a: NodeIndex<Ix>) -> usize {
+        if self.is_directed() {
+            self.edge_list_len(a, Outgoing)
+        } else {
+            self.degree(a)
+        }
+    }
+
+    /// Return the number of edges to `a`.
+    ///
+    /// - `Directed`: The number of incoming edges; a self loop counts once.
+    /// - `Undirected`: The same as `.degree(a)`.
+    ///
+    /// Parallel edges are each counted. Returns `0` if the node doesn't exist.
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of edges
+    /// connected to `a`.
+    pub fn in_degree(&self, a: NodeIndex<Ix>) -> usize {
+        if self.is_directed() {
+            self.edge_list_len(a, Incoming)
+        } else {
+            self.degree(a)
+        }
+    }
+
+    /// Return the number of edges connected to `a`, with each self loop
+    /// counted twice.
+    ///
+    /// - `Directed`: The sum of `.in_degree(a)` and `.out_degree(a)`.
+    /// - `Undirected`: The number of edges between `a` and another node,
+    ///   plus twice the number of self loops of `a`.
+    ///
+    /// Parallel edges are each counted, so the degrees sum to twice the edge
+    /// count. Returns `0` if the node doesn't exist.
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of edges
+    /// connected to `a`.
+    pub fn degree(&self, a: NodeIndex<Ix>) -> usize {
+        // a self loop is in both the outgoing and the incoming list of `a`
+        self.edge_list_len(a, Outgoing) + self.edge_list_len(a, Incoming)
+    }
+
+    /// Return the length of the outgoing or incoming edge list of `a`.
+    fn edge_list_len(&self, a: NodeIndex<Ix>, dir: Direction) -> usize {
+        let k = dir.index();
+        let mut len = 0;
+        let mut edix = match self.nodes.get(a.index()) {
+            None => return 0,
+            Some(node) => node.next[k],
+        };
+        while let Some(edge) = self.edges.get(edix.index()) {
+            len += 1;
+            edix = edge.next[k];
+        }
+        len
+    }
+
+    /// Clear `buf` and fill it with the neighbors of `a`, in the same order as
+    /// [`.neighbors(a)`](#method.neighbors).
+    ///
+    /// Parallel edges produce repeated neighbors, just like the iterator.
+    /// Reusing the same buffer for many nodes avoids allocating a new vector
+    /// for each of them.
+    pub fn neighbors_into(&self, a: NodeIndex<Ix>, buf: &mut Vec<NodeIndex<Ix>>)
+    {
+        buf.clear();
+        buf.extend(self.neighbors(a));
+    }
+
+    /// Clear `buf` and fill it with the edges of `a`, in the same order as
+    /// [`.edges(a)`](#method.edges).
+    ///
+    /// Reusing the same buffer for many nodes avoids allocating a new vector
+    /// for each of them.
+    pub fn edges_into<'a>(&'a self, a: NodeIndex<Ix>, buf: &mut Vec<EdgeReference<'a, E, Ix>>)
+    {
+        buf.clear();
+        buf.extend(self.edges(a));
+    }
+
     /// Return an iterator of all edges of `a`.
     ///
     /// - `Directed`: Outgoing edges from `a`.
@@ -905,6 +1402,29 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         }
     }
 
+    /// Return an iterator over all edges from `a` to `b`.
+    ///
+    /// - `Directed`: Outgoing edges from `a` to `b`.
+    /// - `Undirected`: All edges between `a` and `b`.
+    ///
+    /// Unlike `.find_edge()`, this produces every one of the parallel edges
+    /// between the nodes. The edges are produced in the order of
+    /// `.edges(a)`.
+    ///
+    /// Produces an empty iterator if the node `a` doesn't exist.<br>
+    /// Iterator element type is `EdgeReference<E, Ix>`.
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of edges
+    /// connected to `a`.
+    pub fn edges_connecting(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>)
+        -> EdgesConnecting<E, Ty, Ix>
+    {
+        EdgesConnecting {
+            target_node: b,
+            edges: self.edges(a),
+        }
+    }
+
     /// Return an iterator over either the nodes without edges to them
     /// (`Incoming`) or from them (`Outgoing`).
     ///
@@ -926,6 +1446,40 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         NodeIndices { r: 0..self.node_count(), ty: PhantomData }
     }
 
+    /// Return the index of the first node whose weight matches `predicate`,
+    /// or `None` if there is none.
+    ///
+    /// Computes in **O(|V|)** time.
+    pub fn node_index_by_weight<F>(&self, mut predicate: F) -> Option<NodeIndex<Ix>>
+        where F: FnMut(&N) -> bool,
+    {
+        self.nodes.iter().position(|node| predicate(&node.weight)).map(node_index)
+    }
+
+    /// Return an iterator over the indices of all nodes whose weight matches
+    /// `predicate`, in indexed order.
+    ///
+    /// Use `NodeIndexMap` to look up nodes by weight repeatedly.
+    ///
+    /// The whole iteration computes in **O(|V|)** time.
+    pub fn node_indices_by_weight<F>(&self, predicate: F) -> NodeIndicesByWeight<N, F, Ix>
+        where F: FnMut(&N) -> bool,
+    {
+        NodeIndicesByWeight {
+            iter: self.nodes.iter().enumerate(),
+            predicate: predicate,
+        }
+    }
+
+    /// Create an iterator over all nodes, in indexed order.
+    ///
+    /// Iterator element type is `(NodeIndex<Ix>, &N)`.
+    pub fn node_references(&self) -> NodeReferences<N, Ix> {
+        NodeReferences {
+            iter: self.nodes.iter().enumerate()
+        }
+    }
+
     /// Return an iterator yielding mutable access to all node weights.
     ///
     /// The order in which weights are yielded matches the order of their
@@ -958,6 +1512,52 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         EdgeWeightsMut { edges: self.edges.iter_mut() }
     }
 
+    /// Return summary statistics of the node weights, as mapped to `f64` by
+    /// `f`, or `None` if there are no nodes (or all values are NaN).
+    ///
+    /// See [`WeightStats`](../stats/struct.WeightStats.html).
+    pub fn node_weight_stats<F>(&self, f: F) -> Option<WeightStats>
+        where F: Fn(&N) -> f64,
+    {
+        WeightStats::from_weights(self.nodes.iter().map(|node| f(&node.weight)))
+    }
+
+    /// Return summary statistics of the edge weights, as mapped to `f64` by
+    /// `f`, or `None` if there are no edges (or all values are NaN).
+    ///
+    /// See [`WeightStats`](../stats/struct.WeightStats.html).
+    pub fn edge_weight_stats<F>(&self, f: F) -> Option<WeightStats>
+        where F: Fn(&E) -> f64,
+    {
+        WeightStats::from_weights(self.edges.iter().map(|edge| f(&edge.weight)))
+    }
+
+    /// Return a histogram with `bins` bins of the node weights, as mapped to
+    /// `f64` by `f`, or `None` if there are no nodes (or all values are NaN).
+    ///
+    /// See [`Histogram`](../stats/struct.Histogram.html).
+    ///
+    /// **Panics** if `bins` is zero.
+    pub fn node_weight_histogram<F>(&self, bins: usize, f: F) -> Option<Histogram>
+        where F: Fn(&N) -> f64,
+    {
+        let f = &f;
+        Histogram::from_weights(self.nodes.iter().map(|node| f(&node.weight)), bins)
+    }
+
+    /// Return a histogram with `bins` bins of the edge weights, as mapped to
+    /// `f64` by `f`, or `None` if there are no edges (or all values are NaN).
+    ///
+    /// See [`Histogram`](../stats/struct.Histogram.html).
+    ///
+    /// **Panics** if `bins` is zero.
+    pub fn edge_weight_histogram<F>(&self, bins: usize, f: F) -> Option<Histogram>
+        where F: Fn(&E) -> f64,
+    {
+        let f = &f;
+        Histogram::from_weights(self.edges.iter().map(|edge| f(&edge.weight)), bins)
+    }
+
     // Remaining methods are of the more internal flavour, read-only access to
     // the data structure's internals.
 
@@ -978,6 +1578,41 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         (self.nodes, self.edges)
     }
 
+    /// Create a new `Graph` from its node weights, its edge weights and the
+    /// endpoints of each edge.
+    ///
+    /// Node `i` gets `node_weights[i]`, and edge `i` goes from
+    /// `endpoints[i].0` to `endpoints[i].1` with weight `edge_weights[i]`, as
+    /// if the edges were added in order. This is the inverse of
+    /// `.into_nodes_edges()`, for storage that keeps the weights and the
+    /// endpoints apart.
+    ///
+    /// Return an `InvalidEdge` error if an endpoint is not one of the nodes.
+    ///
+    /// **Panics** if the number of edge weights and endpoints differ, or if
+    /// the graph is at the maximum number of nodes or edges for its index
+    /// type.
+    ///
+    /// Computes in **O(|V| + |E|)** time.
+    pub fn from_raw_parts(node_weights: Vec<N>, edge_weights: Vec<E>,
+                          endpoints: &[(NodeIndex<Ix>, NodeIndex<Ix>)])
+        -> Result<Self, InvalidEdge>
+    {
+        assert_eq!(edge_weights.len(), endpoints.len());
+        let n = node_weights.len();
+        if let Some(i) = endpoints.iter().position(|&(a, b)| a.index() >= n || b.index() >= n) {
+            return Err(InvalidEdge { edge: i });
+        }
+        let mut g = Self::with_capacity(n, endpoints.len());
+        for weight in node_weights {
+            g.add_node(weight);
+        }
+        for (weight, &(a, b)) in edge_weights.into_iter().zip(endpoints) {
+            g.add_edge(a, b, weight);
+        }
+        Ok(g)
+    }
+
     /// Accessor for data structure internals: the first edge in the given direction.
     pub fn first_edge(&self, a: NodeIndex<Ix>, dir: Direction) -> Option<EdgeIndex<Ix>>
     {
@@ -1071,6 +1706,19 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         }
     }
 
+    /// Return a copy of the graph with the direction of all edges reversed.
+    ///
+    /// Like `.reverse()` on a clone: the copy has the same node and edge
+    /// indices.
+    pub fn reversed(&self) -> Self
+        where N: Clone,
+              E: Clone,
+    {
+        let mut g = self.clone();
+        g.reverse();
+        g
+    }
+
     /// Remove all nodes and edges
     pub fn clear(&mut self) {
         self.nodes.clear();
@@ -1078,6 +1726,9 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
     }
 
     /// Remove all edges
+    ///
+    /// The nodes and their indices are kept, and the edge storage keeps its
+    /// capacity, so new edges can be added without reallocating.
     pub fn clear_edges(&mut self) {
         self.edges.clear();
         for node in &mut self.nodes {
@@ -1144,6 +1795,15 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         self.edges.shrink_to_fit();
     }
 
+    /// Borrow the graph as `Frozen`: its structure is read-only, but its
+    /// node and edge weights can still be modified.
+    ///
+    /// Node and edge indices stay valid for as long as the `Frozen` borrow
+    /// lasts, since nodes and edges can't be added or removed through it.
+    pub fn frozen(&mut self) -> Frozen<Self> {
+        Frozen(self)
+    }
+
     /// Keep all nodes that return `true` from the `visit` closure,
     /// remove the others.
     ///
@@ -1239,6 +1899,73 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         }
     }
 
+    /// Add copies of all nodes and edges of `other` to the graph, as a
+    /// disjoint union.
+    ///
+    /// Return the new node index of each node of `other`: element `i` is the
+    /// index in `self` of node `i` of `other`. The edges keep their order,
+    /// and the edge index of edge `i` of `other` is `i` more than the edge
+    /// count before the call.
+    ///
+    /// Computes in **O(|V| + |E|)** time for the nodes and edges of `other`.
+    pub fn extend_from_graph(&mut self, other: &Graph<N, E, Ty, Ix>) -> Vec<NodeIndex<Ix>>
+        where N: Clone,
+              E: Clone,
+    {
+        self.reserve_nodes(other.node_count());
+        self.reserve_edges(other.edge_count());
+        let map = other.nodes.iter().map(|node| self.add_node(node.weight.clone()))
+                             .collect::<Vec<_>>();
+        for edge in &other.edges {
+            self.add_edge(map[edge.source().index()], map[edge.target().index()],
+                          edge.weight.clone());
+        }
+        map
+    }
+
+    /// Add the nodes and edges of `other` to the graph, merging the nodes that
+    /// have the same key.
+    ///
+    /// `key` returns the key of a node weight, in both graphs. A node of
+    /// `other` whose key is already used by a node of the graph, or by an
+    /// earlier node of `other`, is merged with that node, which keeps its
+    /// weight; each other node is added as a copy. All edges of `other` are
+    /// added as copies between the nodes they are mapped to, so two graphs
+    /// with the same edge result in parallel edges.
+    ///
+    /// Return the node index of each node of `other`: element `i` is the
+    /// index in `self` of node `i` of `other`.
+    ///
+    /// Computes in **O(|V| + |E|)** time for the nodes and edges of both
+    /// graphs.
+    pub fn union<F, K>(&mut self, other: &Graph<N, E, Ty, Ix>, mut key: F)
+        -> Vec<NodeIndex<Ix>>
+        where N: Clone,
+              E: Clone,
+              F: FnMut(&N) -> K,
+              K: Hash + Eq,
+    {
+        let mut keys = HashMap::new();
+        for (i, node) in enumerate(&self.nodes) {
+            keys.entry(key(&node.weight)).or_insert(NodeIndex::new(i));
+        }
+        self.reserve_nodes(other.node_count());
+        self.reserve_edges(other.edge_count());
+        let mut map = Vec::with_capacity(other.node_count());
+        for node in &other.nodes {
+            let a = match keys.entry(key(&node.weight)) {
+                Entry::Occupied(ent) => *ent.get(),
+                Entry::Vacant(ent) => *ent.insert(self.add_node(node.weight.clone())),
+            };
+            map.push(a);
+        }
+        for edge in &other.edges {
+            self.add_edge(map[edge.source().index()], map[edge.target().index()],
+                          edge.weight.clone());
+        }
+        map
+    }
+
 
     /// Create a new `Graph` by mapping node and
     /// edge weights to new values.
@@ -1267,6 +1994,33 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         g
     }
 
+    /// Create a new `Graph` by mapping node and edge weights to new values,
+    /// consuming `self` so that the weights are moved, not borrowed.
+    ///
+    /// The resulting graph has the same structure and the same
+    /// graph indices as `self`.
+    pub fn into_map<F, G, N2, E2>(self, mut node_map: F, mut edge_map: G)
+        -> Graph<N2, E2, Ty, Ix>
+        where F: FnMut(NodeIndex<Ix>, N) -> N2,
+              G: FnMut(EdgeIndex<Ix>, E) -> E2,
+    {
+        let mut g = Graph::with_capacity(self.node_count(), self.edge_count());
+        for (i, node) in enumerate(self.nodes) {
+            g.nodes.push(Node {
+                weight: node_map(NodeIndex::new(i), node.weight),
+                next: node.next,
+            });
+        }
+        for (i, edge) in enumerate(self.edges) {
+            g.edges.push(Edge {
+                weight: edge_map(EdgeIndex::new(i), edge.weight),
+                next: edge.next,
+                node: edge.node,
+            });
+        }
+        g
+    }
+
     /// Create a new `Graph` by mapping nodes and edges.
     /// A node or edge may be mapped to `None` to exclude it from
     /// the resulting graph.
@@ -1279,35 +2033,209 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
     /// If no nodes are removed, the resulting graph has compatible node
     /// indices; if neither nodes nor edges are removed, the result has
     /// the same graph indices as `self`.
-    pub fn filter_map<'a, F, G, N2, E2>(&'a self, mut node_map: F, mut edge_map: G)
+    pub fn filter_map<'a, F, G, N2, E2>(&'a self, node_map: F, edge_map: G)
         -> Graph<N2, E2, Ty, Ix>
         where F: FnMut(NodeIndex<Ix>, &'a N) -> Option<N2>,
               G: FnMut(EdgeIndex<Ix>, &'a E) -> Option<E2>,
+    {
+        self.filter_map_with_mapping(node_map, edge_map).0
+    }
+
+    /// Create a new `Graph` by mapping nodes and edges, like `.filter_map()`,
+    /// and return it with the new index of each node.
+    ///
+    /// Element `i` of the mapping is the index in the new graph of node `i`
+    /// of `self`, or `None` if the node was excluded.
+    pub fn filter_map_with_mapping<'a, F, G, N2, E2>(&'a self, mut node_map: F,
+                                                     mut edge_map: G)
+        -> (Graph<N2, E2, Ty, Ix>, Vec<Option<NodeIndex<Ix>>>)
+        where F: FnMut(NodeIndex<Ix>, &'a N) -> Option<N2>,
+              G: FnMut(EdgeIndex<Ix>, &'a E) -> Option<E2>,
     {
         let mut g = Graph::with_capacity(0, 0);
-        // mapping from old node index to new node index, end represents removed.
-        let mut node_index_map = vec![NodeIndex::end(); self.node_count()];
+        // mapping from old node index to new node index, None represents removed.
+        let mut node_index_map = vec![None; self.node_count()];
         for (i, node) in enumerate(&self.nodes) {
             if let Some(nw) = node_map(NodeIndex::new(i), &node.weight) {
-                node_index_map[i] = g.add_node(nw);
+                node_index_map[i] = Some(g.add_node(nw));
             }
         }
         for (i, edge) in enumerate(&self.edges) {
             // skip edge if any endpoint was removed
             let source = node_index_map[edge.source().index()];
             let target = node_index_map[edge.target().index()];
-            if source != NodeIndex::end() && target != NodeIndex::end() {
+            if let (Some(source), Some(target)) = (source, target) {
                 if let Some(ew) = edge_map(EdgeIndex::new(i), &edge.weight) {
                     g.add_edge(source, target, ew);
                 }
             }
         }
+        (g, node_index_map)
+    }
+
+    /// Create the subgraph induced by `nodes`: a graph with those nodes and
+    /// every edge between them, and return it with the original index of
+    /// each of its nodes.
+    ///
+    /// The nodes are in the order they first appear in `nodes`; repeated
+    /// nodes are only added once. Element `i` of the mapping is the index in
+    /// `self` of node `i` of the subgraph. The edges keep their relative
+    /// order, and the weights are cloned.
+    ///
+    /// Return an `InvalidNode` error for the first node that doesn't exist.
+    ///
+    /// Computes in **O(|V| + |E|)** time.
+    pub fn subgraph<I>(&self, nodes: I)
+        -> Result<(Self, Vec<NodeIndex<Ix>>), InvalidNode<Ix>>
+        where I: IntoIterator<Item=NodeIndex<Ix>>,
+              N: Clone,
+              E: Clone,
+    {
+        let mut g = Graph::with_capacity(0, 0);
+        let mut original = Vec::new();
+        let mut node_index_map = vec![None; self.node_count()];
+        for a in nodes {
+            match node_index_map.get_mut(a.index()) {
+                None => return Err(InvalidNode { node: a }),
+                Some(&mut Some(_)) => {}
+                Some(new) => {
+                    *new = Some(g.add_node(self[a].clone()));
+                    original.push(a);
+                }
+            }
+        }
+        for edge in &self.edges {
+            let source = node_index_map[edge.source().index()];
+            let target = node_index_map[edge.target().index()];
+            if let (Some(source), Some(target)) = (source, target) {
+                g.add_edge(source, target, edge.weight.clone());
+            }
+        }
+        Ok((g, original))
+    }
+
+    /// Create the complement of the graph: a graph with the same nodes, and
+    /// an edge exactly between the nodes that `self` has no edge between.
+    ///
+    /// The node weights are cloned and the edges have unit weights. If
+    /// `self_loops` is `true`, each node without a self loop gets one,
+    /// otherwise the complement has no self loops.
+    ///
+    /// The resulting graph has the same node indices as `self`.
+    ///
+    /// Computes in **O(|V|² + |E|)** time.
+    pub fn complement(&self, self_loops: bool) -> Graph<N, (), Ty, Ix>
+        where N: Clone,
+    {
+        let n = self.node_count();
+        let matrix = self.adjacency_matrix();
+        let mut g = Graph::with_capacity(n, 0);
+        for node in &self.nodes {
+            g.add_node(node.weight.clone());
+        }
+        for i in 0..n {
+            // each pair once for an undirected graph
+            let start = if self.is_directed() { 0 } else { i };
+            for j in start..n {
+                let (a, b) = (NodeIndex::new(i), NodeIndex::new(j));
+                if (i != j || self_loops) && !self.is_adjacent(&matrix, a, b) {
+                    g.add_edge(a, b, ());
+                }
+            }
+        }
+        g
+    }
+
+    /// Create a dense, row-major *n* × *n* adjacency matrix of the graph,
+    /// with the weights mapped by `edge_map`.
+    ///
+    /// The entry for the edge from node *i* to node *j* is at index
+    /// *i · n + j*, where *n* is the node count, and it is `None` if there is
+    /// no such edge. For an undirected graph the matrix is symmetric: each
+    /// edge fills both *(i, j)* and *(j, i)*. A self loop fills the
+    /// diagonal entry *(i, i)*. For parallel edges, the edge with the lowest
+    /// index wins.
+    ///
+    /// The unweighted version is `.adjacency_matrix()` from the
+    /// [`GetAdjacencyMatrix`](../visit/trait.GetAdjacencyMatrix.html) trait,
+    /// with the same layout.
+    ///
+    /// Computes in **O(|V|² + |E|)** time.
+    pub fn adjacency_matrix_weighted<F, W>(&self, mut edge_map: F) -> Vec<Option<W>>
+        where F: FnMut(EdgeIndex<Ix>, &E) -> W,
+              W: Clone,
+    {
+        let n = self.node_count();
+        let mut matrix = vec![None; n * n];
+        for (i, edge) in self.edges.iter().enumerate() {
+            let (a, b) = (edge.source().index(), edge.target().index());
+            if matrix[a * n + b].is_some() {
+                continue;
+            }
+            let weight = edge_map(EdgeIndex::new(i), &edge.weight);
+            if !self.is_directed() && a != b {
+                matrix[b * n + a] = Some(weight.clone());
+            }
+            matrix[a * n + b] = Some(weight);
+        }
+        matrix
+    }
+
+    /// Create a new `Graph` with `n` nodes from a dense adjacency matrix,
+    /// given as the function `edge_weight`.
+    ///
+    /// `edge_weight(i, j)` returns the weight of the edge from node *i* to
+    /// node *j*, or `None` for no edge. Nodes are numbered `0..n`, and the
+    /// edges are added in row-major order.
+    ///
+    /// - `Directed`: `edge_weight` is called for every pair *(i, j)*.
+    /// - `Undirected`: the matrix is assumed to be symmetric, so
+    ///   `edge_weight` is only called for *i ≤ j*, the upper triangle.
+    ///
+    /// Diagonal entries *(i, i)* create self loops.
+    ///
+    /// Node weights `N` are set to default values.
+    ///
+    /// Computes in **O(|V|²)** time.
+    ///
+    /// ```
+    /// use petgraph::Graph;
+    ///
+    /// let matrix = [[0, 2, 0],
+    ///               [0, 0, 1],
+    ///               [4, 0, 0]];
+    /// let g = Graph::<(), i32>::from_adjacency_matrix(3, |i, j| {
+    ///     if matrix[i][j] != 0 { Some(matrix[i][j]) } else { None }
+    /// });
+    /// assert_eq!(g.edge_count(), 3);
+    /// ```
+    pub fn from_adjacency_matrix<F>(n: usize, mut edge_weight: F) -> Self
+        where F: FnMut(usize, usize) -> Option<E>,
+              N: Default,
+    {
+        let mut g = Self::with_capacity(n, 0);
+        for _ in 0..n {
+            g.add_node(N::default());
+        }
+        for i in 0..n {
+            let start = if g.is_directed() { 0 } else { i };
+            for j in start..n {
+                if let Some(weight) = edge_weight(i, j) {
+                    g.add_edge(NodeIndex::new(i), NodeIndex::new(j), weight);
+                }
+            }
+        }
         g
     }
 
     /// Convert the graph into either undirected or directed. No edge adjustments
     /// are done, so you may want to go over the result to remove or add edges.
     ///
+    /// The node and edge weights are not copied: only the edge type changes.
+    /// Parallel edges are kept, so a directed graph with the edges `a → b`
+    /// and `b → a` becomes an undirected graph with two edges between `a`
+    /// and `b`; use `.to_undirected()` to merge them.
+    ///
     /// Computes in **O(1)** time.
     pub fn into_edge_type<NewTy>(self) -> Graph<N, E, NewTy, Ix> where
         NewTy: EdgeType
@@ -1315,6 +2243,88 @@ impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
         Graph{nodes: self.nodes, edges: self.edges,
               ty: PhantomData}
     }
+
+    /// Return an undirected copy of the graph, where `merge` may combine
+    /// each pair of antiparallel edges into one edge.
+    ///
+    /// Each edge `b → a` is paired with the first edge `a → b` before it
+    /// that is not paired yet, and `merge` is called with their weights, in
+    /// edge order. If it returns `Some`, the two edges are replaced by one
+    /// edge with that weight, in the place of the first; if it returns
+    /// `None`, both are kept. Self loops and edges without a partner are
+    /// copied as they are.
+    ///
+    /// The nodes keep their indices; the edges keep their order, but their
+    /// indices change if edges are merged.
+    ///
+    /// Computes in **O(|V| + |E|)** time.
+    pub fn to_undirected<F>(&self, mut merge: F) -> Graph<N, E, Undirected, Ix>
+        where N: Clone,
+              E: Clone,
+              F: FnMut(&E, &E) -> Option<E>,
+    {
+        // the weight and endpoints of each edge of the copy, if not merged
+        let mut edges: Vec<Option<(E, [NodeIndex<Ix>; 2])>> = Vec::with_capacity(self.edge_count());
+        // the unpaired edges from a to b, by position in `edges`
+        let mut unpaired = HashMap::<_, VecDeque<usize>>::new();
+        for edge in &self.edges {
+            let (a, b) = (edge.source(), edge.target());
+            if a != b {
+                if let Some(i) = unpaired.get_mut(&(b, a)).and_then(|q| q.pop_front()) {
+                    let first = edges[i].take().unwrap();
+                    match merge(&first.0, &edge.weight) {
+                        Some(w) => {
+                            edges[i] = Some((w, first.1));
+                            edges.push(None);
+                        }
+                        None => {
+                            edges[i] = Some(first);
+                            edges.push(Some((edge.weight.clone(), edge.node)));
+                        }
+                    }
+                    continue;
+                }
+                unpaired.entry((a, b)).or_insert_with(VecDeque::new).push_back(edges.len());
+            }
+            edges.push(Some((edge.weight.clone(), edge.node)));
+        }
+        let mut g = Graph::with_capacity(self.node_count(), edges.len());
+        for node in &self.nodes {
+            g.add_node(node.weight.clone());
+        }
+        for (weight, node) in edges.into_iter().filter_map(|e| e) {
+            g.add_edge(node[0], node[1], weight);
+        }
+        g
+    }
+}
+
+/// Iterator over the indices of the nodes whose weight matches a predicate.
+///
+/// Created with `.node_indices_by_weight(predicate)`.
+pub struct NodeIndicesByWeight<'a, N: 'a, F, Ix: IndexType = DefaultIx> {
+    iter: iter::Enumerate<slice::Iter<'a, Node<N, Ix>>>,
+    predicate: F,
+}
+
+impl<'a, N, F, Ix> Iterator for NodeIndicesByWeight<'a, N, F, Ix>
+    where F: FnMut(&N) -> bool,
+          Ix: IndexType,
+{
+    type Item = NodeIndex<Ix>;
+
+    fn next(&mut self) -> Option<NodeIndex<Ix>> {
+        while let Some((i, node)) = self.iter.next() {
+            if (self.predicate)(&node.weight) {
+                return Some(node_index(i));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
 }
 
 /// An iterator over either the nodes without edges to them or from them.
@@ -1470,6 +2480,16 @@ impl<'a, N, E, Ty, Ix> IntoEdges for &'a Graph<N, E, Ty, Ix>
     }
 }
 
+impl<'a, N, E, Ty, Ix> IntoEdgesDirected for &'a Graph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type EdgesDirected = Edges<'a, E, Ty, Ix>;
+    fn edges_directed(self, a: Self::NodeId, dir: Direction) -> Self::EdgesDirected {
+        self.edges_directed(a, dir)
+    }
+}
+
 
 /// Iterator over the edges of from or to a node
 pub struct Edges<'a, E: 'a, Ty, Ix: 'a = DefaultIx>
@@ -1558,6 +2578,45 @@ impl<'a, E, Ty, Ix> Clone for Edges<'a, E, Ty, Ix>
     }
 }
 
+/// Iterator over the edges between a pair of nodes.
+///
+/// Created with [`.edges_connecting()`](struct.Graph.html#method.edges_connecting).
+pub struct EdgesConnecting<'a, E: 'a, Ty, Ix: 'a = DefaultIx>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    target_node: NodeIndex<Ix>,
+    edges: Edges<'a, E, Ty, Ix>,
+}
+
+impl<'a, E, Ty, Ix> Iterator for EdgesConnecting<'a, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Item = EdgeReference<'a, E, Ix>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(edge) = self.edges.next() {
+            if edge.node[1] == self.target_node {
+                return Some(edge);
+            }
+        }
+        None
+    }
+}
+
+impl<'a, E, Ty, Ix> Clone for EdgesConnecting<'a, E, Ty, Ix>
+    where Ix: IndexType,
+          Ty: EdgeType,
+{
+    fn clone(&self) -> Self {
+        EdgesConnecting {
+            target_node: self.target_node,
+            edges: self.edges.clone(),
+        }
+    }
+}
+
 /// Iterator yielding mutable access to all node weights.
 pub struct NodeWeightsMut<'a, N: 'a, Ix: IndexType = DefaultIx> {
     nodes: ::std::slice::IterMut<'a, Node<N, Ix>>,
@@ -1712,6 +2771,35 @@ impl<Ix: IndexType> GraphIndex for EdgeIndex<Ix> {
 /// assert_eq!(gr[b], 4.);
 /// assert_eq!(gr[c], 2.);
 /// ```
+///
+/// The walker only keeps the index of the next edge in each edge list it
+/// follows. Changing weights does not affect it, and edges added during the
+/// walk are not visited, since they go in front of the edge lists.
+/// Removing an edge moves the last edge into its index, so the walker may
+/// skip or repeat edges afterwards; to remove edges along the walk, start a
+/// new walker after each removal:
+///
+/// ```
+/// use petgraph::Graph;
+///
+/// let mut gr = Graph::<(), i32>::new();
+/// let a = gr.add_node(());
+/// let b = gr.add_node(());
+/// for i in 0..6 {
+///     gr.add_edge(a, b, i);
+/// }
+///
+/// // remove the edges of `a` with odd weights
+/// let mut edges = gr.neighbors(a).detach();
+/// while let Some(edge) = edges.next_edge(&gr) {
+///     if gr[edge] % 2 == 1 {
+///         gr.remove_edge(edge);
+///         edges = gr.neighbors(a).detach();
+///     }
+/// }
+/// assert_eq!(gr.edge_count(), 3);
+/// assert!(gr.raw_edges().iter().all(|e| e.weight % 2 == 0));
+/// ```
 pub struct WalkNeighbors<Ix> {
     skip_start: NodeIndex<Ix>,
     next: [EdgeIndex<Ix>; 2],
@@ -1860,13 +2948,12 @@ impl<'a, N, E, Ty, Ix> IntoNodeReferences for &'a Graph<N, E, Ty, Ix>
     type NodeRef = (NodeIndex<Ix>, &'a N);
     type NodeReferences = NodeReferences<'a, N, Ix>;
     fn node_references(self) -> Self::NodeReferences {
-        NodeReferences {
-            iter: self.nodes.iter().enumerate()
-        }
+        Graph::node_references(self)
     }
 }
 
 /// Iterator over all nodes of a graph.
+#[derive(Clone)]
 pub struct NodeReferences<'a, N: 'a, Ix: IndexType = DefaultIx> {
     iter: iter::Enumerate<slice::Iter<'a, Node<N, Ix>>>,
 }
@@ -1887,6 +2974,20 @@ impl<'a, N, Ix> Iterator for NodeReferences<'a, N, Ix>
     }
 }
 
+impl<'a, N, Ix> DoubleEndedIterator for NodeReferences<'a, N, Ix>
+    where Ix: IndexType
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(i, node)|
+            (node_index(i), &node.weight)
+        )
+    }
+}
+
+impl<'a, N, Ix> ExactSizeIterator for NodeReferences<'a, N, Ix>
+    where Ix: IndexType
+{}
+
 impl<'a, Ix, E> EdgeReference<'a, E, Ix>
     where Ix: IndexType,
 {
@@ -1912,6 +3013,7 @@ impl<'a, Ix, E> EdgeRef for EdgeReference<'a, E, Ix>
 
 
 /// Iterator over all edges of a graph.
+#[derive(Clone)]
 pub struct EdgeReferences<'a, E: 'a, Ix: IndexType = DefaultIx> {
     iter: iter::Enumerate<slice::Iter<'a, Edge<E, Ix>>>,
 }
@@ -1936,19 +3038,70 @@ impl<'a, E, Ix> Iterator for EdgeReferences<'a, E, Ix>
     }
 }
 
+impl<'a, E, Ix> DoubleEndedIterator for EdgeReferences<'a, E, Ix>
+    where Ix: IndexType
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|(i, edge)|
+            EdgeReference {
+                index: edge_index(i),
+                node: edge.node,
+                weight: &edge.weight,
+            }
+        )
+    }
+}
+
+impl<'a, E, Ix> ExactSizeIterator for EdgeReferences<'a, E, Ix>
+    where Ix: IndexType
+{}
+
 #[cfg(feature = "stable_graph")]
 #[path = "stable_graph.rs"]
 pub mod stable_graph;
 
+#[path = "graph_tx.rs"]
+mod graph_tx;
+pub use self::graph_tx::GraphTx;
+
+#[path = "node_index_map.rs"]
+mod node_index_map;
+pub use self::node_index_map::NodeIndexMap;
+
+#[cfg(feature = "serde")]
+#[path = "graph_serde.rs"]
+mod graph_serde;
+
 /// `Frozen` only allows shared access (read-only) to the
 /// underlying graph `G`, but it allows mutable access to its
 /// node and edge weights.
 ///
 /// This is used to ensure immutability of the graph's structure
 /// while permitting weights to change.
+///
+/// All of the graph's read-only methods are available through `Deref`,
+/// and it implements the same graph traits as `&G`, so it can be passed
+/// to the visitors and algorithms. Weights can be modified with indexing
+/// syntax, and for `Graph` with `.node_weight_mut()`, `.edge_weight_mut()`,
+/// `.node_weights_mut()` and `.edge_weights_mut()`.
+///
+/// ```
+/// use petgraph::Graph;
+///
+/// let mut graph = Graph::<i32, i32>::from_edges(&[(0, 1, 1), (1, 2, 2)]);
+/// let mut frozen = graph.frozen();
+/// let a = frozen.node_indices().next().unwrap();
+/// let neighbors = frozen.neighbors(a).collect::<Vec<_>>();
+/// for b in neighbors {
+///     frozen[b] += 10;
+/// }
+/// // frozen.add_node(0); // error: add_node needs `&mut Graph`
+/// assert_eq!(frozen.node_weights_mut().map(|w| *w).collect::<Vec<_>>(), vec![0, 10, 0]);
+/// ```
 pub struct Frozen<'a, G: 'a>(&'a mut G);
 
 impl<'a, G> Frozen<'a, G> {
+    /// Create a new `Frozen` from a mutable reference to a graph.
     pub fn new(gr: &'a mut G) -> Self {
         Frozen(gr)
     }
@@ -1989,6 +3142,32 @@ impl<'a, N, E, Ty, Ix> Frozen<'a, Graph<N, E, Ty, Ix>>
     {
         self.0.index_twice_mut(i, j)
     }
+
+    /// Access the weight for node `a`, mutably.
+    ///
+    /// Also available with indexing syntax: `&mut frozen[a]`.
+    pub fn node_weight_mut(&mut self, a: NodeIndex<Ix>) -> Option<&mut N> {
+        self.0.node_weight_mut(a)
+    }
+
+    /// Access the weight for edge `e`, mutably.
+    ///
+    /// Also available with indexing syntax: `&mut frozen[e]`.
+    pub fn edge_weight_mut(&mut self, e: EdgeIndex<Ix>) -> Option<&mut E> {
+        self.0.edge_weight_mut(e)
+    }
+
+    /// Return an iterator yielding mutable access to all node weights,
+    /// in node index order.
+    pub fn node_weights_mut(&mut self) -> NodeWeightsMut<N, Ix> {
+        self.0.node_weights_mut()
+    }
+
+    /// Return an iterator yielding mutable access to all edge weights,
+    /// in edge index order.
+    pub fn edge_weights_mut(&mut self) -> EdgeWeightsMut<E, Ix> {
+        self.0.edge_weights_mut()
+    }
 }
 
 macro_rules! access0 {
@@ -2001,6 +3180,7 @@ DataMapMut!{delegate_impl [['a, G], G, Frozen<'a, G>, access0]}
 GetAdjacencyMatrix!{delegate_impl [['a, G], G, Frozen<'a, G>, deref_twice]}
 IntoEdgeReferences!{delegate_impl [['a, 'b, G], G, &'b Frozen<'a, G>, deref_twice]}
 IntoEdges!{delegate_impl [['a, 'b, G], G, &'b Frozen<'a, G>, deref_twice]}
+IntoEdgesDirected!{delegate_impl [['a, 'b, G], G, &'b Frozen<'a, G>, deref_twice]}
 IntoNeighbors!{delegate_impl [['a, 'b, G], G, &'b Frozen<'a, G>, deref_twice]}
 IntoNeighborsDirected!{delegate_impl [['a, 'b, G], G, &'b Frozen<'a, G>, deref_twice]}
 IntoNodeIdentifiers!{delegate_impl [['a, 'b, G], G, &'b Frozen<'a, G>, deref_twice]}
@@ -2010,3 +3190,78 @@ NodeCount!{delegate_impl [['a, G], G, Frozen<'a, G>, deref_twice]}
 NodeIndexable!{delegate_impl [['a, G], G, Frozen<'a, G>, deref_twice]}
 GraphProp!{delegate_impl [['a, G], G, Frozen<'a, G>, deref_twice]}
 Visitable!{delegate_impl [['a, G], G, Frozen<'a, G>, deref_twice]}
+
+// The delegations above need `G` itself to implement the `Into*` traits;
+// for `Graph` they are implemented by `&Graph`, so delegate to that.
+
+impl<'a, 'b, N, E: 'b, Ty, Ix> IntoNeighbors for &'b Frozen<'a, Graph<N, E, Ty, Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Neighbors = Neighbors<'b, E, Ix>;
+    fn neighbors(self, a: NodeIndex<Ix>) -> Self::Neighbors {
+        self.0.neighbors(a)
+    }
+}
+
+impl<'a, 'b, N, E: 'b, Ty, Ix> IntoNeighborsDirected for &'b Frozen<'a, Graph<N, E, Ty, Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type NeighborsDirected = Neighbors<'b, E, Ix>;
+    fn neighbors_directed(self, a: NodeIndex<Ix>, dir: Direction) -> Self::NeighborsDirected {
+        self.0.neighbors_directed(a, dir)
+    }
+}
+
+impl<'a, 'b, N, E: 'b, Ty, Ix> IntoEdgeReferences for &'b Frozen<'a, Graph<N, E, Ty, Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type EdgeRef = EdgeReference<'b, E, Ix>;
+    type EdgeReferences = EdgeReferences<'b, E, Ix>;
+    fn edge_references(self) -> Self::EdgeReferences {
+        self.0.edge_references()
+    }
+}
+
+impl<'a, 'b, N, E: 'b, Ty, Ix> IntoEdges for &'b Frozen<'a, Graph<N, E, Ty, Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Edges = Edges<'b, E, Ty, Ix>;
+    fn edges(self, a: NodeIndex<Ix>) -> Self::Edges {
+        self.0.edges(a)
+    }
+}
+
+impl<'a, 'b, N, E: 'b, Ty, Ix> IntoEdgesDirected for &'b Frozen<'a, Graph<N, E, Ty, Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type EdgesDirected = Edges<'b, E, Ty, Ix>;
+    fn edges_directed(self, a: NodeIndex<Ix>, dir: Direction) -> Self::EdgesDirected {
+        self.0.edges_directed(a, dir)
+    }
+}
+
+impl<'a, 'b, N, E: 'b, Ty, Ix> IntoNodeIdentifiers for &'b Frozen<'a, Graph<N, E, Ty, Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type NodeIdentifiers = NodeIndices<Ix>;
+    fn node_identifiers(self) -> Self::NodeIdentifiers {
+        self.0.node_indices()
+    }
+}
+
+impl<'a, 'b, N: 'b, E: 'b, Ty, Ix> IntoNodeReferences for &'b Frozen<'a, Graph<N, E, Ty, Ix>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type NodeRef = (NodeIndex<Ix>, &'b N);
+    type NodeReferences = NodeReferences<'b, N, Ix>;
+    fn node_references(self) -> Self::NodeReferences {
+        self.0.node_references()
+    }
+}
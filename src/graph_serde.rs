@@ -0,0 +1,112 @@
+//! `Serialize` and `Deserialize` for `Graph`, behind the `serde` feature.
+//!
+//! A graph is represented as a struct with a `nodes` list of node weights
+//! and an `edges` list of `(source, target, weight)` triples, where source
+//! and target are node indices. Both lists are in index order, so a round
+//! trip preserves all node and edge indices.
+
+use serde::{Serialize, Serializer, Deserialize, Deserializer};
+use serde::de::Error;
+use serde::ser::SerializeStruct;
+
+use EdgeType;
+
+use super::{
+    Graph,
+    IndexType,
+    NodeIndex,
+};
+
+struct NodeWeights<'a, N: 'a, E: 'a, Ty: 'a, Ix: 'a>(&'a Graph<N, E, Ty, Ix>);
+
+impl<'a, N, E, Ty, Ix> Serialize for NodeWeights<'a, N, E, Ty, Ix>
+    where N: Serialize,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.collect_seq(self.0.raw_nodes().iter().map(|node| &node.weight))
+    }
+}
+
+struct EdgeTriples<'a, N: 'a, E: 'a, Ty: 'a, Ix: 'a>(&'a Graph<N, E, Ty, Ix>);
+
+impl<'a, N, E, Ty, Ix> Serialize for EdgeTriples<'a, N, E, Ty, Ix>
+    where E: Serialize,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        serializer.collect_seq(self.0.raw_edges().iter().map(|edge| {
+            (edge.source().index(), edge.target().index(), &edge.weight)
+        }))
+    }
+}
+
+/// The owned form of the serialized graph.
+#[derive(Deserialize)]
+#[serde(rename = "Graph")]
+struct GraphRepr<N, E> {
+    nodes: Vec<N>,
+    edges: Vec<(usize, usize, E)>,
+}
+
+impl<N, E, Ty, Ix> Serialize for Graph<N, E, Ty, Ix>
+    where N: Serialize,
+          E: Serialize,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where S: Serializer
+    {
+        let mut state = try!(serializer.serialize_struct("Graph", 2));
+        try!(state.serialize_field("nodes", &NodeWeights(self)));
+        try!(state.serialize_field("edges", &EdgeTriples(self)));
+        state.end()
+    }
+}
+
+impl<'de, N, E, Ty, Ix> Deserialize<'de> for Graph<N, E, Ty, Ix>
+    where N: Deserialize<'de>,
+          E: Deserialize<'de>,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Fails with a deserialization error if there are more nodes or edges
+    /// than `Ix` can index, or if an edge endpoint is not a node index.
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where D: Deserializer<'de>
+    {
+        let repr: GraphRepr<N, E> = try!(Deserialize::deserialize(deserializer));
+        let max = <Ix as IndexType>::max().index();
+        if repr.nodes.len() > max {
+            return Err(D::Error::custom(format_args!(
+                "graph has {} nodes, more than the index type can represent",
+                repr.nodes.len())));
+        }
+        if repr.edges.len() > max {
+            return Err(D::Error::custom(format_args!(
+                "graph has {} edges, more than the index type can represent",
+                repr.edges.len())));
+        }
+        let node_count = repr.nodes.len();
+        let mut graph = Graph::with_capacity(node_count, repr.edges.len());
+        for weight in repr.nodes {
+            graph.add_node(weight);
+        }
+        for (i, (a, b, weight)) in repr.edges.into_iter().enumerate() {
+            if a >= node_count || b >= node_count {
+                return Err(D::Error::custom(format_args!(
+                    "edge {} has endpoint ({}, {}) out of bounds for {} nodes",
+                    i, a, b, node_count)));
+            }
+            graph.add_edge(NodeIndex::new(a), NodeIndex::new(b), weight);
+        }
+        Ok(graph)
+    }
+}
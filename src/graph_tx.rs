@@ -0,0 +1,355 @@
+//! `GraphTx` stages changes to a `Graph`, for `Graph::transaction`.
+
+use std::collections::HashMap;
+
+use fixedbitset::FixedBitSet;
+
+use {
+    Direction,
+    EdgeType,
+    Incoming,
+    Outgoing,
+};
+
+use super::{
+    EdgeIndex,
+    Graph,
+    IndexType,
+    NodeIndex,
+};
+
+impl<N, E, Ty, Ix> Graph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Change the graph as one transaction.
+    ///
+    /// `f` is passed a `GraphTx`, which records the nodes and edges to add
+    /// and remove without changing the graph yet. If `f` returns `Ok`, all
+    /// the recorded changes are applied to the graph, and if it returns
+    /// `Err`, none are. Reads through the `GraphTx` see the changes recorded
+    /// so far.
+    ///
+    /// Inside the transaction, indices are stable: added nodes and edges
+    /// get the next indices after the graph's, and removing doesn't move
+    /// other nodes or edges. When the changes are applied, removals
+    /// invalidate indices like `remove_node` and `remove_edge` do.
+    ///
+    /// ```
+    /// use petgraph::Graph;
+    /// use petgraph::graph::node_index as n;
+    ///
+    /// let mut gr: Graph<(), ()> = Graph::from_edges(&[(0, 1), (1, 2)]);
+    ///
+    /// // replace 0 → 1 → 2 with 0 → 2
+    /// let result: Result<(), &str> = gr.transaction(|tx| {
+    ///     tx.remove_node(n(1));
+    ///     try!(tx.add_edge(n(0), n(2), ()).ok_or("missing node"));
+    ///     Ok(())
+    /// });
+    /// assert!(result.is_ok());
+    /// assert_eq!(gr.node_count(), 2);
+    /// assert!(gr.contains_edge(n(0), n(1)));
+    ///
+    /// // a failing transaction changes nothing
+    /// let result: Result<(), &str> = gr.transaction(|tx| {
+    ///     tx.remove_node(n(0));
+    ///     try!(tx.add_edge(n(0), n(1), ()).ok_or("missing node"));
+    ///     Ok(())
+    /// });
+    /// assert_eq!(result, Err("missing node"));
+    /// assert_eq!(gr.node_count(), 2);
+    /// ```
+    pub fn transaction<F, T, Err>(&mut self, f: F) -> Result<T, Err>
+        where F: FnOnce(&mut GraphTx<N, E, Ty, Ix>) -> Result<T, Err>,
+    {
+        let (result, log) = {
+            let mut tx = GraphTx::new(self);
+            let result = f(&mut tx);
+            (result, tx.into_log())
+        };
+        if result.is_ok() {
+            log.apply(self);
+        }
+        result
+    }
+}
+
+/// The changes recorded by a `GraphTx`.
+struct Log<N, E, Ix> {
+    nodes: Vec<N>,
+    edges: Vec<(NodeIndex<Ix>, NodeIndex<Ix>, E)>,
+    removed_nodes: FixedBitSet,
+    removed_edges: FixedBitSet,
+}
+
+impl<N, E, Ix> Log<N, E, Ix>
+    where Ix: IndexType,
+{
+    fn apply<Ty>(self, g: &mut Graph<N, E, Ty, Ix>)
+        where Ty: EdgeType,
+    {
+        for weight in self.nodes {
+            g.add_node(weight);
+        }
+        for (a, b, weight) in self.edges {
+            g.add_edge(a, b, weight);
+        }
+        // Removing an index only moves the last node or edge, so removing
+        // in descending order doesn't move the ones still to remove. The
+        // edges of removed nodes are all in `removed_edges`.
+        let removed_edges = self.removed_edges.ones().collect::<Vec<_>>();
+        for &e in removed_edges.iter().rev() {
+            g.remove_edge(EdgeIndex::new(e));
+        }
+        let removed_nodes = self.removed_nodes.ones().collect::<Vec<_>>();
+        for &a in removed_nodes.iter().rev() {
+            g.remove_node(NodeIndex::new(a));
+        }
+    }
+}
+
+/// A transaction on a `Graph`, created by `Graph::transaction`.
+///
+/// `GraphTx` keeps the added nodes and edges, and which nodes and edges are
+/// removed, next to the unchanged graph; the staged edges are kept in
+/// adjacency lists of their own, so that reads combine them with the
+/// graph's.
+pub struct GraphTx<'a, N: 'a, E: 'a, Ty: 'a, Ix: 'a>
+    where Ix: IndexType,
+{
+    graph: &'a Graph<N, E, Ty, Ix>,
+    log: Log<N, E, Ix>,
+    /// The staged edges of each node, outgoing and incoming.
+    staged: HashMap<NodeIndex<Ix>, [Vec<EdgeIndex<Ix>>; 2]>,
+    node_count: usize,
+    edge_count: usize,
+}
+
+impl<'a, N, E, Ty, Ix> GraphTx<'a, N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn new(graph: &'a Graph<N, E, Ty, Ix>) -> Self {
+        GraphTx {
+            graph: graph,
+            log: Log {
+                nodes: Vec::new(),
+                edges: Vec::new(),
+                removed_nodes: FixedBitSet::with_capacity(graph.node_count()),
+                removed_edges: FixedBitSet::with_capacity(graph.edge_count()),
+            },
+            staged: HashMap::new(),
+            node_count: graph.node_count(),
+            edge_count: graph.edge_count(),
+        }
+    }
+
+    fn into_log(self) -> Log<N, E, Ix> {
+        self.log
+    }
+
+    /// Return the number of nodes, with the staged changes.
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Return the number of edges, with the staged changes.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Return `true` if the node `a` exists, with the staged changes.
+    pub fn contains_node(&self, a: NodeIndex<Ix>) -> bool {
+        a.index() < self.graph.node_count() + self.log.nodes.len()
+            && !self.log.removed_nodes.contains(a.index())
+    }
+
+    fn contains_edge_index(&self, e: EdgeIndex<Ix>) -> bool {
+        e.index() < self.graph.edge_count() + self.log.edges.len()
+            && !self.log.removed_edges.contains(e.index())
+    }
+
+    /// Access the weight of node `a`, or `None` if it doesn't exist.
+    pub fn node_weight(&self, a: NodeIndex<Ix>) -> Option<&N> {
+        if !self.contains_node(a) {
+            return None;
+        }
+        match a.index().checked_sub(self.graph.node_count()) {
+            None => self.graph.node_weight(a),
+            Some(i) => self.log.nodes.get(i),
+        }
+    }
+
+    /// Access the weight of edge `e`, or `None` if it doesn't exist.
+    pub fn edge_weight(&self, e: EdgeIndex<Ix>) -> Option<&E> {
+        if !self.contains_edge_index(e) {
+            return None;
+        }
+        match e.index().checked_sub(self.graph.edge_count()) {
+            None => self.graph.edge_weight(e),
+            Some(i) => self.log.edges.get(i).map(|edge| &edge.2),
+        }
+    }
+
+    /// Return the source and target of edge `e`, or `None` if it doesn't
+    /// exist.
+    pub fn edge_endpoints(&self, e: EdgeIndex<Ix>) -> Option<(NodeIndex<Ix>, NodeIndex<Ix>)> {
+        if !self.contains_edge_index(e) {
+            return None;
+        }
+        match e.index().checked_sub(self.graph.edge_count()) {
+            None => self.graph.edge_endpoints(e),
+            Some(i) => self.log.edges.get(i).map(|edge| (edge.0, edge.1)),
+        }
+    }
+
+    /// Return the edges of `a` in direction `dir`, with the staged changes.
+    ///
+    /// For an undirected graph, return all edges of `a`.
+    pub fn edges_directed(&self, a: NodeIndex<Ix>, dir: Direction) -> Vec<EdgeIndex<Ix>> {
+        let mut edges = Vec::new();
+        if !self.contains_node(a) {
+            return edges;
+        }
+        let dirs = if Ty::is_directed() { &[dir][..] } else { &[Outgoing, Incoming][..] };
+        for &d in dirs {
+            let mut next = self.graph.first_edge(a, d);
+            while let Some(e) = next {
+                if !self.log.removed_edges.contains(e.index()) {
+                    edges.push(e);
+                }
+                next = self.graph.next_edge(e, d);
+            }
+            if let Some(staged) = self.staged.get(&a) {
+                for &e in &staged[d.index()] {
+                    if !self.log.removed_edges.contains(e.index()) {
+                        edges.push(e);
+                    }
+                }
+            }
+        }
+        // an undirected self loop is in both lists
+        if !Ty::is_directed() {
+            edges.sort();
+            edges.dedup();
+        }
+        edges
+    }
+
+    /// Return the neighbors of `a` in direction `dir`, with the staged
+    /// changes: the other endpoint of each of `edges_directed(a, dir)`.
+    pub fn neighbors_directed(&self, a: NodeIndex<Ix>, dir: Direction) -> Vec<NodeIndex<Ix>> {
+        self.edges_directed(a, dir).into_iter().map(|e| {
+            let (source, target) = self.edge_endpoints(e).unwrap();
+            if source == a { target } else { source }
+        }).collect()
+    }
+
+    /// Return the neighbors of `a` along outgoing edges (or all edges, for
+    /// an undirected graph), with the staged changes.
+    pub fn neighbors(&self, a: NodeIndex<Ix>) -> Vec<NodeIndex<Ix>> {
+        self.neighbors_directed(a, Outgoing)
+    }
+
+    /// Return an edge from `a` to `b`, with the staged changes, or `None`
+    /// if there is none.
+    pub fn find_edge(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Option<EdgeIndex<Ix>> {
+        self.edges_directed(a, Outgoing).into_iter().find(|&e| {
+            let (source, target) = self.edge_endpoints(e).unwrap();
+            (source == a && target == b) || (!Ty::is_directed() && source == b && target == a)
+        })
+    }
+
+    /// Return `true` if there is an edge from `a` to `b`, with the staged
+    /// changes.
+    pub fn contains_edge(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> bool {
+        self.find_edge(a, b).is_some()
+    }
+
+    /// Return `true` if there is a path from `a` to `b` (of zero or more
+    /// edges), with the staged changes.
+    ///
+    /// For example, adding an edge from `b` to `a` makes a cycle exactly if
+    /// there is a path from `a` to `b`.
+    pub fn has_path(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> bool {
+        if !self.contains_node(a) || !self.contains_node(b) {
+            return false;
+        }
+        let mut discovered = FixedBitSet::with_capacity(
+            self.graph.node_count() + self.log.nodes.len());
+        let mut stack = vec![a];
+        discovered.insert(a.index());
+        while let Some(node) = stack.pop() {
+            if node == b {
+                return true;
+            }
+            for next in self.neighbors(node) {
+                if !discovered.put(next.index()) {
+                    stack.push(next);
+                }
+            }
+        }
+        false
+    }
+
+    /// Stage adding a node with weight `weight`, and return its index.
+    ///
+    /// **Panics** if the graph would be at the maximum number of nodes for
+    /// its index type.
+    pub fn add_node(&mut self, weight: N) -> NodeIndex<Ix> {
+        let a = NodeIndex::new(self.graph.node_count() + self.log.nodes.len());
+        assert!(<Ix as IndexType>::max().index() == !0 || NodeIndex::end() != a);
+        self.log.nodes.push(weight);
+        self.log.removed_nodes.grow(a.index() + 1);
+        self.node_count += 1;
+        a
+    }
+
+    /// Stage adding an edge from `a` to `b`, and return its index, or
+    /// `None` if `a` or `b` doesn't exist.
+    ///
+    /// **Panics** if the graph would be at the maximum number of edges for
+    /// its index type.
+    pub fn add_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E)
+        -> Option<EdgeIndex<Ix>>
+    {
+        if !self.contains_node(a) || !self.contains_node(b) {
+            return None;
+        }
+        let e = EdgeIndex::new(self.graph.edge_count() + self.log.edges.len());
+        assert!(<Ix as IndexType>::max().index() == !0 || EdgeIndex::end() != e);
+        self.log.edges.push((a, b, weight));
+        self.log.removed_edges.grow(e.index() + 1);
+        self.staged.entry(a).or_insert_with(Default::default)[0].push(e);
+        self.staged.entry(b).or_insert_with(Default::default)[1].push(e);
+        self.edge_count += 1;
+        Some(e)
+    }
+
+    /// Stage removing edge `e`, and return `true` if it existed.
+    pub fn remove_edge(&mut self, e: EdgeIndex<Ix>) -> bool {
+        if !self.contains_edge_index(e) {
+            return false;
+        }
+        self.log.removed_edges.insert(e.index());
+        self.edge_count -= 1;
+        true
+    }
+
+    /// Stage removing node `a` and all its edges, and return `true` if it
+    /// existed.
+    pub fn remove_node(&mut self, a: NodeIndex<Ix>) -> bool {
+        if !self.contains_node(a) {
+            return false;
+        }
+        for &dir in &[Outgoing, Incoming] {
+            for e in self.edges_directed(a, dir) {
+                self.remove_edge(e);
+            }
+        }
+        self.log.removed_nodes.insert(a.index());
+        self.node_count -= 1;
+        true
+    }
+}
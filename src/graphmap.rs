@@ -2,7 +2,9 @@
 //! keys.
 
 use std::cmp::Ordering;
-use std::hash::{self, Hash};
+use std::collections::HashMap;
+use std::collections::hash_map::RandomState;
+use std::hash::{self, BuildHasher, Hash};
 use std::iter::Cloned;
 use std::slice::{
     Iter,
@@ -13,6 +15,7 @@ use std::iter::FromIterator;
 use std::marker::PhantomData;
 use ordermap::OrderMap;
 use ordermap::Iter as OrderMapIter;
+use ordermap::IterMut as OrderMapIterMut;
 use ordermap::Keys;
 
 use {
@@ -25,6 +28,8 @@ use {
 };
 
 use IntoWeightedEdge;
+use algo::{astar, dijkstra, Measure};
+use path::Path;
 use visit::{IntoNodeIdentifiers, NodeCount, IntoNodeReferences, NodeIndexable};
 use visit::{NodeCompactIndexable, IntoEdgeReferences, IntoEdges};
 use graph::Graph;
@@ -59,20 +64,29 @@ pub type DiGraphMap<N, E> = GraphMap<N, E, Directed>;
 /// - `E` can be of arbitrary type.
 /// - Edge type `Ty` that determines whether the graph edges are directed or
 /// undirected.
+/// - Hasher `S` used for the maps of nodes and edges, the standard library's
+/// `RandomState` by default.
 ///
 /// You can use the type aliases `UnGraphMap` and `DiGraphMap` for convenience.
 ///
 /// `GraphMap` does not allow parallel edges, but self loops are allowed.
 ///
+/// The iteration order of the nodes, of the edges and of the neighbors of
+/// each node is the order they were added in, and does not depend on the
+/// hasher: building the same graph twice gives the same order. Removing a
+/// node or an edge moves the last one into its place in the order.
+///
 /// Depends on crate feature `graphmap` (default).
 #[derive(Clone)]
-pub struct GraphMap<N, E, Ty> {
-    nodes: OrderMap<N, Vec<(N, CompactDirection)>>,
-    edges: OrderMap<(N, N), E>,
+pub struct GraphMap<N, E, Ty, S = RandomState> {
+    nodes: OrderMap<N, Vec<(N, CompactDirection)>, S>,
+    edges: OrderMap<(N, N), E, S>,
     ty: PhantomData<Ty>,
 }
 
-impl<N: Eq + Hash + fmt::Debug, E: fmt::Debug, Ty: EdgeType> fmt::Debug for GraphMap<N, E, Ty> {
+impl<N: Eq + Hash + fmt::Debug, E: fmt::Debug, Ty: EdgeType, S: BuildHasher> fmt::Debug
+    for GraphMap<N, E, Ty, S>
+{
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         self.nodes.fmt(f)
     }
@@ -104,9 +118,10 @@ impl PartialEq<Direction> for CompactDirection {
     }
 }
 
-impl<N, E, Ty> GraphMap<N, E, Ty>
+impl<N, E, Ty, S> GraphMap<N, E, Ty, S>
     where N: NodeTrait,
           Ty: EdgeType,
+          S: BuildHasher + Default,
 {
     /// Create a new `GraphMap`
     pub fn new() -> Self {
@@ -116,32 +131,12 @@ impl<N, E, Ty> GraphMap<N, E, Ty>
     /// Create a new `GraphMap` with estimated capacity.
     pub fn with_capacity(nodes: usize, edges: usize) -> Self {
         GraphMap {
-            nodes: OrderMap::with_capacity(nodes),
-            edges: OrderMap::with_capacity(edges),
+            nodes: OrderMap::with_capacity_and_hasher(nodes, S::default()),
+            edges: OrderMap::with_capacity_and_hasher(edges, S::default()),
             ty: PhantomData,
         }
     }
 
-    /// Return the current node and edge capacity of the graph.
-    pub fn capacity(&self) -> (usize, usize) {
-        (self.nodes.capacity(), self.edges.capacity())
-    }
-
-    /// Use their natual order to map the node pair (a, b) to a canonical edge id.
-    #[inline]
-    fn edge_key(a: N, b: N) -> (N, N) {
-        if Ty::is_directed() {
-            (a, b)
-        } else {
-            if a <= b { (a, b) } else { (b, a) }
-        }
-    }
-
-    /// Whether the graph has directed edges.
-    pub fn is_directed(&self) -> bool {
-        Ty::is_directed()
-    }
-
     /// Create a new `GraphMap` from an iterable of edges.
     ///
     /// Node values are taken directly from the list.
@@ -167,6 +162,53 @@ impl<N, E, Ty> GraphMap<N, E, Ty>
     {
         Self::from_iter(iterable)
     }
+}
+
+impl<N, E, Ty, S> GraphMap<N, E, Ty, S>
+    where N: NodeTrait,
+          Ty: EdgeType,
+          S: BuildHasher + Clone,
+{
+    /// Create a new `GraphMap` that uses `hasher` to hash its nodes and edges.
+    pub fn with_hasher(hasher: S) -> Self {
+        Self::with_capacity_and_hasher(0, 0, hasher)
+    }
+
+    /// Create a new `GraphMap` with estimated capacity, that uses `hasher` to
+    /// hash its nodes and edges.
+    pub fn with_capacity_and_hasher(nodes: usize, edges: usize, hasher: S) -> Self {
+        GraphMap {
+            nodes: OrderMap::with_capacity_and_hasher(nodes, hasher.clone()),
+            edges: OrderMap::with_capacity_and_hasher(edges, hasher),
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<N, E, Ty, S> GraphMap<N, E, Ty, S>
+    where N: NodeTrait,
+          Ty: EdgeType,
+          S: BuildHasher,
+{
+    /// Return the current node and edge capacity of the graph.
+    pub fn capacity(&self) -> (usize, usize) {
+        (self.nodes.capacity(), self.edges.capacity())
+    }
+
+    /// Use their natual order to map the node pair (a, b) to a canonical edge id.
+    #[inline]
+    fn edge_key(a: N, b: N) -> (N, N) {
+        if Ty::is_directed() {
+            (a, b)
+        } else {
+            if a <= b { (a, b) } else { (b, a) }
+        }
+    }
+
+    /// Whether the graph has directed edges.
+    pub fn is_directed(&self) -> bool {
+        Ty::is_directed()
+    }
 
     /// Return the number of nodes in the graph.
     pub fn node_count(&self) -> usize {
@@ -190,17 +232,22 @@ impl<N, E, Ty> GraphMap<N, E, Ty>
         n
     }
 
-    /// Return `true` if node `n` was removed.
+    /// Remove node `n` and all its edges from the graph, and return `true`
+    /// if it was in the graph.
+    ///
+    /// The removed edges are removed from the adjacency lists of the other
+    /// nodes too.
     pub fn remove_node(&mut self, n: N) -> bool {
         let links = match self.nodes.swap_remove(&n) {
             None => return false,
             Some(sus) => sus,
         };
-        for (succ, _) in links {
-            // remove all successor links
-            self.remove_single_edge(&succ, &n, Incoming);
+        for (succ, dir) in links {
+            let edge = if dir == Outgoing { (n, succ) } else { (succ, n) };
+            // remove all successor and predecessor links
+            self.remove_single_edge(&succ, &n, if dir == Outgoing { Incoming } else { Outgoing });
             // Remove all edge values
-            self.edges.swap_remove(&Self::edge_key(n, succ));
+            self.edges.swap_remove(&Self::edge_key(edge.0, edge.1));
         }
         true
     }
@@ -295,12 +342,33 @@ impl<N, E, Ty> GraphMap<N, E, Ty>
         weight
     }
 
+    /// Keep the edges for which `keep(a, b, &mut weight)` returns `true`,
+    /// and remove the others. The endpoints are produced as by
+    /// [`.all_edges()`](#method.all_edges).
+    ///
+    /// Computes in **O(|E| + k e')** time, where **k** is the number of edges
+    /// removed and **e'** the number of edges connected to their endpoints.
+    pub fn retain_edges<F>(&mut self, mut keep: F)
+        where F: FnMut(N, N, &mut E) -> bool,
+    {
+        let mut removed = Vec::new();
+        for (&(a, b), weight) in self.edges.iter_mut() {
+            if !keep(a, b, weight) {
+                removed.push((a, b));
+            }
+        }
+        for (a, b) in removed {
+            self.remove_edge(a, b);
+        }
+    }
+
     /// Return `true` if the edge connecting `a` with `b` is contained in the graph.
     pub fn contains_edge(&self, a: N, b: N) -> bool {
         self.edges.contains_key(&Self::edge_key(a, b))
     }
 
-    /// Return an iterator over the nodes of the graph.
+    /// Return an iterator over the nodes of the graph, in the order they
+    /// were added.
     ///
     /// Iterator element type is `N`.
     pub fn nodes(&self) -> Nodes<N> {
@@ -324,6 +392,24 @@ impl<N, E, Ty> GraphMap<N, E, Ty>
         }
     }
 
+    /// Return the number of edges connected to `a`, with a self loop counted
+    /// twice.
+    ///
+    /// - `Directed`: The number of outgoing and incoming edges.
+    /// - `Undirected`: The number of edges from or to `a`.
+    ///
+    /// Returns `0` if the node doesn't exist.
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of edges
+    /// connected to `a`.
+    pub fn degree(&self, a: N) -> usize {
+        match self.nodes.get(&a) {
+            None => 0,
+            // a self loop has only one entry in the adjacency list
+            Some(neigh) => neigh.len() + neigh.iter().filter(|&&(n, _)| n == a).count(),
+        }
+    }
+
     /// Return an iterator of all neighbors that have an edge between them and
     /// `a`, in the specified direction.
     /// If the graph's edges are undirected, this is equivalent to *.neighbors(a)*.
@@ -342,6 +428,7 @@ impl<N, E, Ty> GraphMap<N, E, Ty>
                 Some(neigh) => neigh.iter(),
                 None => [].iter(),
             },
+            start_node: a,
             dir: dir,
             ty: self.ty,
         }
@@ -355,7 +442,7 @@ impl<N, E, Ty> GraphMap<N, E, Ty>
     ///
     /// Produces an empty iterator if the node doesn't exist.<br>
     /// Iterator element type is `(N, &E)`.
-    pub fn edges(&self, from: N) -> Edges<N, E, Ty> {
+    pub fn edges(&self, from: N) -> Edges<N, E, Ty, S> {
         Edges {
             from: from,
             iter: self.neighbors(from),
@@ -363,6 +450,26 @@ impl<N, E, Ty> GraphMap<N, E, Ty>
         }
     }
 
+    /// Clear `buf` and fill it with the neighbors of `a`, in the same order as
+    /// [`.neighbors(a)`](#method.neighbors).
+    ///
+    /// Reusing the same buffer for many nodes avoids allocating a new vector
+    /// for each of them.
+    pub fn neighbors_into(&self, a: N, buf: &mut Vec<N>) {
+        buf.clear();
+        buf.extend(self.neighbors(a));
+    }
+
+    /// Clear `buf` and fill it with the edges of `a`, in the same order as
+    /// [`.edges(a)`](#method.edges).
+    ///
+    /// Reusing the same buffer for many nodes avoids allocating a new vector
+    /// for each of them.
+    pub fn edges_into<'a>(&'a self, a: N, buf: &mut Vec<(N, N, &'a E)>) {
+        buf.clear();
+        buf.extend(self.edges(a));
+    }
+
     /// Return a reference to the edge weight connecting `a` with `b`, or
     /// `None` if the edge does not exist in the graph.
     pub fn edge_weight(&self, a: N, b: N) -> Option<&E> {
@@ -375,7 +482,14 @@ impl<N, E, Ty> GraphMap<N, E, Ty>
         self.edges.get_mut(&Self::edge_key(a, b))
     }
 
-    /// Return an iterator over all edges of the graph with their weight in arbitrary order.
+    /// Return an iterator over all edges of the graph with their weight, in
+    /// the order they were added.
+    ///
+    /// Each edge is produced once, as `(a, b, weight)`:
+    ///
+    /// - `Directed`: `a` is the source and `b` the target of the edge.
+    /// - `Undirected`: `a` and `b` are the endpoints in their natural order,
+    ///   `a <= b`, whichever order they were given to `add_edge` in.
     ///
     /// Iterator element type is `(N, N, &E)`
     pub fn all_edges(&self) -> AllEdges<N, E, Ty> {
@@ -385,15 +499,33 @@ impl<N, E, Ty> GraphMap<N, E, Ty>
         }
     }
 
+    /// Return an iterator over all edges of the graph, in the order they were
+    /// added, with a mutable reference to their weight.
+    ///
+    /// The endpoints are produced in the same order as by
+    /// [`.all_edges()`](#method.all_edges).
+    ///
+    /// Iterator element type is `(N, N, &mut E)`
+    pub fn all_edges_mut(&mut self) -> AllEdgesMut<N, E, Ty> {
+        AllEdgesMut {
+            inner: self.edges.iter_mut(),
+            ty: self.ty,
+        }
+    }
+
     /// Return a `Graph` that corresponds to this `GraphMap`.
     ///
     /// Note: node and edge indices in the `Graph` have nothing in common
     /// with the `GraphMap`s node weights `N`. The node weights `N` are
     /// used as node weights in the resulting `Graph`, too.
+    ///
+    /// The nodes get their indices in the order of
+    /// [`.nodes()`](#method.nodes), which is the order they were added in
+    /// unless nodes were removed, and the edges in the order of
+    /// [`.all_edges()`](#method.all_edges).
     pub fn into_graph<Ix>(self) -> Graph<N, E, Ty, Ix>
         where Ix: ::graph::IndexType,
     {
-        // assuming two successive iterations of the same hashmap produce the same order
         let mut gr = Graph::with_capacity(self.node_count(), self.edge_count());
         for (&node, _) in &self.nodes {
             gr.add_node(node);
@@ -407,11 +539,78 @@ impl<N, E, Ty> GraphMap<N, E, Ty>
     }
 }
 
+impl<N, E, Ty, S> GraphMap<N, E, Ty, S>
+    where N: NodeTrait,
+          E: Clone,
+          Ty: EdgeType,
+          S: BuildHasher + Default,
+{
+    /// Create a new `GraphMap` from a `Graph`, with `key(weight)` as the
+    /// node of each node of the `Graph`.
+    ///
+    /// Nodes of the `Graph` with the same key become one node, and edges
+    /// between the same nodes become one edge; the weight of the edge is
+    /// the weight of the last of them, in the order of the edge indices, as
+    /// with repeated `.add_edge()`.
+    ///
+    /// Computes in **O(|V| + |E|)** time.
+    pub fn from_graph<N2, F, Ix>(g: &Graph<N2, E, Ty, Ix>, mut key: F) -> Self
+        where F: FnMut(&N2) -> N,
+              Ix: ::graph::IndexType,
+    {
+        let mut map = Self::with_capacity(g.node_count(), g.edge_count());
+        let nodes = g.raw_nodes().iter()
+                     .map(|node| map.add_node(key(&node.weight)))
+                     .collect::<Vec<_>>();
+        for edge in g.raw_edges() {
+            map.add_edge(nodes[edge.source().index()], nodes[edge.target().index()],
+                         edge.weight.clone());
+        }
+        map
+    }
+}
+
+/// Shortest paths, for a `GraphMap` whose edge weights are the edge costs.
+impl<N, E, Ty, S> GraphMap<N, E, Ty, S>
+    where N: NodeTrait,
+          E: Measure + Copy,
+          Ty: EdgeType,
+          S: BuildHasher,
+{
+    /// Compute the length of the shortest path from `start` to every
+    /// reachable node, using the edge weights as edge costs.
+    ///
+    /// This is [`algo::dijkstra`](../algo/fn.dijkstra.html) with the edge
+    /// weight as `edge_cost`. Edge weights must be non-negative.
+    ///
+    /// If `goal` is not `None`, then the algorithm terminates once the `goal`
+    /// node's cost is calculated.
+    ///
+    /// Returns a `HashMap` that maps each reached node to its path cost.
+    pub fn dijkstra(&self, start: N, goal: Option<N>) -> HashMap<N, E> {
+        dijkstra(self, start, goal, |(_, _, &weight)| weight)
+    }
+
+    /// Return the length of a shortest path from `a` to `b` and the path
+    /// itself, using the edge weights as edge costs, or `None` if there is no
+    /// such path.
+    ///
+    /// The path starts with `a` and ends with `b`. Edge weights must be
+    /// non-negative.
+    pub fn shortest_path(&self, a: N, b: N) -> Option<(E, Path<N>)> {
+        if !self.contains_node(a) || !self.contains_node(b) {
+            return None;
+        }
+        astar(self, a, |n| n == b, |(_, _, &weight)| weight, |_| E::default())
+    }
+}
+
 /// Create a new `GraphMap` from an iterable of edges.
-impl<N, E, Ty, Item> FromIterator<Item> for GraphMap<N, E, Ty>
+impl<N, E, Ty, Item, S> FromIterator<Item> for GraphMap<N, E, Ty, S>
     where Item: IntoWeightedEdge<E, NodeId=N>,
           N: NodeTrait,
           Ty: EdgeType,
+          S: BuildHasher + Default,
 {
     fn from_iter<I>(iterable: I) -> Self
         where I: IntoIterator<Item=Item>,
@@ -427,10 +626,11 @@ impl<N, E, Ty, Item> FromIterator<Item> for GraphMap<N, E, Ty>
 /// Extend the graph from an iterable of edges.
 ///
 /// Nodes are inserted automatically to match the edges.
-impl<N, E, Ty, Item> Extend<Item> for GraphMap<N, E, Ty>
+impl<N, E, Ty, Item, S> Extend<Item> for GraphMap<N, E, Ty, S>
     where Item: IntoWeightedEdge<E, NodeId=N>,
           N: NodeTrait,
           Ty: EdgeType,
+          S: BuildHasher,
 {
     fn extend<I>(&mut self, iterable: I)
         where I: IntoIterator<Item=Item>,
@@ -508,6 +708,7 @@ pub struct NeighborsDirected<'a, N, Ty>
           Ty: EdgeType,
 {
     iter: Iter<'a, (N, CompactDirection)>,
+    start_node: N,
     dir: Direction,
     ty: PhantomData<Ty>,
 }
@@ -520,8 +721,10 @@ impl<'a, N, Ty> Iterator for NeighborsDirected<'a, N, Ty>
     fn next(&mut self) -> Option<N> {
         if Ty::is_directed() {
             let self_dir = self.dir;
+            let start_node = self.start_node;
+            // a self loop only has the Outgoing entry
             (&mut self.iter)
-                .filter_map(move |&(n, dir)| if dir == self_dir {
+                .filter_map(move |&(n, dir)| if dir == self_dir || n == start_node {
                     Some(n)
                 } else { None })
                 .next()
@@ -531,18 +734,19 @@ impl<'a, N, Ty> Iterator for NeighborsDirected<'a, N, Ty>
     }
 }
 
-pub struct Edges<'a, N, E: 'a, Ty>
+pub struct Edges<'a, N, E: 'a, Ty, S: 'a = RandomState>
     where N: 'a + NodeTrait,
           Ty: EdgeType
 {
     from: N,
-    edges: &'a OrderMap<(N, N), E>,
+    edges: &'a OrderMap<(N, N), E, S>,
     iter: Neighbors<'a, N, Ty>,
 }
 
-impl<'a, N, E, Ty> Iterator for Edges<'a, N, E, Ty>
+impl<'a, N, E, Ty, S> Iterator for Edges<'a, N, E, Ty, S>
     where N: 'a + NodeTrait, E: 'a,
           Ty: EdgeType,
+          S: BuildHasher,
 {
     type Item = (N, N, &'a E);
     fn next(&mut self) -> Option<Self::Item> {
@@ -550,7 +754,7 @@ impl<'a, N, E, Ty> Iterator for Edges<'a, N, E, Ty>
             None => None,
             Some(b) => {
                 let a = self.from;
-                match self.edges.get(&GraphMap::<N, E, Ty>::edge_key(a, b)) {
+                match self.edges.get(&GraphMap::<N, E, Ty, S>::edge_key(a, b)) {
                     None => unreachable!(),
                     Some(edge) => {
                         Some((a, b, edge))
@@ -561,9 +765,10 @@ impl<'a, N, E, Ty> Iterator for Edges<'a, N, E, Ty>
     }
 }
 
-impl<'a, N: 'a, E: 'a, Ty> IntoEdgeReferences for &'a GraphMap<N, E, Ty>
+impl<'a, N: 'a, E: 'a, Ty, S> IntoEdgeReferences for &'a GraphMap<N, E, Ty, S>
     where N: NodeTrait,
           Ty: EdgeType,
+          S: BuildHasher,
 {
     type EdgeRef = (N, N, &'a E);
     type EdgeReferences = AllEdges<'a, N, E, Ty>;
@@ -591,11 +796,31 @@ impl<'a, N, E, Ty> Iterator for AllEdges<'a, N, E, Ty>
     }
 }
 
-impl<'a, N: 'a, E: 'a, Ty> IntoEdges for &'a GraphMap<N, E, Ty>
+pub struct AllEdgesMut<'a, N, E: 'a, Ty> where N: 'a + NodeTrait {
+    inner: OrderMapIterMut<'a, (N, N), E>,
+    ty: PhantomData<Ty>,
+}
+
+impl<'a, N, E, Ty> Iterator for AllEdgesMut<'a, N, E, Ty>
+    where N: 'a + NodeTrait, E: 'a,
+          Ty: EdgeType,
+{
+    type Item = (N, N, &'a mut E);
+    fn next(&mut self) -> Option<Self::Item>
+    {
+        match self.inner.next() {
+            None => None,
+            Some((&(a, b), v)) => Some((a, b, v))
+        }
+    }
+}
+
+impl<'a, N: 'a, E: 'a, Ty, S> IntoEdges for &'a GraphMap<N, E, Ty, S>
     where N: NodeTrait,
           Ty: EdgeType,
+          S: BuildHasher,
 {
-    type Edges = Edges<'a, N, E, Ty>;
+    type Edges = Edges<'a, N, E, Ty, S>;
     fn edges(self, a: Self::NodeId) -> Self::Edges {
         self.edges(a)
     }
@@ -603,9 +828,10 @@ impl<'a, N: 'a, E: 'a, Ty> IntoEdges for &'a GraphMap<N, E, Ty>
 
 
 /// Index `GraphMap` by node pairs to access edge weights.
-impl<N, E, Ty> Index<(N, N)> for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> Index<(N, N)> for GraphMap<N, E, Ty, S>
     where N: NodeTrait,
           Ty: EdgeType,
+          S: BuildHasher,
 {
     type Output = E;
     fn index(&self, index: (N, N)) -> &E
@@ -616,9 +842,10 @@ impl<N, E, Ty> Index<(N, N)> for GraphMap<N, E, Ty>
 }
 
 /// Index `GraphMap` by node pairs to access edge weights.
-impl<N, E, Ty> IndexMut<(N, N)> for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> IndexMut<(N, N)> for GraphMap<N, E, Ty, S>
     where N: NodeTrait,
           Ty: EdgeType,
+          S: BuildHasher,
 {
     fn index_mut(&mut self, index: (N, N)) -> &mut E {
         let index = Self::edge_key(index.0, index.1);
@@ -627,9 +854,10 @@ impl<N, E, Ty> IndexMut<(N, N)> for GraphMap<N, E, Ty>
 }
 
 /// Create a new empty `GraphMap`.
-impl<N, E, Ty> Default for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> Default for GraphMap<N, E, Ty, S>
     where N: NodeTrait,
           Ty: EdgeType,
+          S: BuildHasher + Default,
 {
     fn default() -> Self { GraphMap::with_capacity(0, 0) }
 }
@@ -702,9 +930,10 @@ impl<'b, T: fmt::Debug> fmt::Debug for Ptr<'b, T> {
     }
 }
 
-impl<'a, N, E: 'a, Ty> IntoNodeIdentifiers for &'a GraphMap<N, E, Ty>
+impl<'a, N, E: 'a, Ty, S> IntoNodeIdentifiers for &'a GraphMap<N, E, Ty, S>
     where N: NodeTrait,
           Ty: EdgeType,
+          S: BuildHasher,
 {
     type NodeIdentifiers = NodeIdentifiers<'a, N, E, Ty>;
 
@@ -717,9 +946,10 @@ impl<'a, N, E: 'a, Ty> IntoNodeIdentifiers for &'a GraphMap<N, E, Ty>
     }
 }
 
-impl<N, E, Ty> NodeCount for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> NodeCount for GraphMap<N, E, Ty, S>
     where N: NodeTrait,
           Ty: EdgeType,
+          S: BuildHasher,
 {
     fn node_count(&self) -> usize {
         (*self).node_count()
@@ -743,9 +973,10 @@ impl<'a, N, E, Ty> Iterator for NodeIdentifiers<'a, N, E, Ty>
     }
 }
 
-impl<'a, N, E, Ty> IntoNodeReferences for &'a GraphMap<N, E, Ty>
+impl<'a, N, E, Ty, S> IntoNodeReferences for &'a GraphMap<N, E, Ty, S>
     where N: NodeTrait,
           Ty: EdgeType,
+          S: BuildHasher,
 {
     type NodeRef = (N, &'a N);
     type NodeReferences = NodeReferences<'a, N, E, Ty>;
@@ -775,9 +1006,10 @@ impl<'a, N, E, Ty> Iterator for NodeReferences<'a, N, E, Ty>
     }
 }
 
-impl<N, E, Ty> NodeIndexable for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> NodeIndexable for GraphMap<N, E, Ty, S>
     where N: NodeTrait,
           Ty: EdgeType,
+          S: BuildHasher,
 {
     fn node_bound(&self) -> usize { self.node_count() }
     fn to_index(&self, ix: Self::NodeId) -> usize {
@@ -790,9 +1022,103 @@ impl<N, E, Ty> NodeIndexable for GraphMap<N, E, Ty>
     }
 }
 
-impl<N, E, Ty> NodeCompactIndexable for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> NodeCompactIndexable for GraphMap<N, E, Ty, S>
     where N: NodeTrait,
           Ty: EdgeType,
+          S: BuildHasher,
 {
 }
 
+
+/// `GraphMap` serializes as a `nodes` list of node identifiers and an
+/// `edges` list of `(a, b, weight)` triples, both in insertion order.
+#[cfg(feature = "serde")]
+mod serialization {
+    use std::hash::BuildHasher;
+
+    use serde::{Serialize, Serializer, Deserialize, Deserializer};
+    use serde::de::Error;
+    use serde::ser::SerializeStruct;
+
+    use EdgeType;
+    use super::{GraphMap, NodeTrait};
+
+    struct Nodes<'a, N: 'a, E: 'a, Ty: 'a, S: 'a>(&'a GraphMap<N, E, Ty, S>);
+
+    impl<'a, N, E, Ty, S> Serialize for Nodes<'a, N, E, Ty, S>
+        where N: NodeTrait + Serialize,
+              Ty: EdgeType,
+              S: BuildHasher,
+    {
+        fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+            where Ser: Serializer
+        {
+            serializer.collect_seq(self.0.nodes.keys())
+        }
+    }
+
+    struct Edges<'a, N: 'a, E: 'a, Ty: 'a, S: 'a>(&'a GraphMap<N, E, Ty, S>);
+
+    impl<'a, N, E, Ty, S> Serialize for Edges<'a, N, E, Ty, S>
+        where N: NodeTrait + Serialize,
+              E: Serialize,
+              Ty: EdgeType,
+              S: BuildHasher,
+    {
+        fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+            where Ser: Serializer
+        {
+            serializer.collect_seq(self.0.edges.iter().map(|(&(a, b), weight)| (a, b, weight)))
+        }
+    }
+
+    #[derive(Deserialize)]
+    #[serde(rename = "GraphMap")]
+    struct GraphMapRepr<N, E> {
+        nodes: Vec<N>,
+        edges: Vec<(N, N, E)>,
+    }
+
+    impl<N, E, Ty, S> Serialize for GraphMap<N, E, Ty, S>
+        where N: NodeTrait + Serialize,
+              E: Serialize,
+              Ty: EdgeType,
+              S: BuildHasher,
+    {
+        fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+            where Ser: Serializer
+        {
+            let mut state = try!(serializer.serialize_struct("GraphMap", 2));
+            try!(state.serialize_field("nodes", &Nodes(self)));
+            try!(state.serialize_field("edges", &Edges(self)));
+            state.end()
+        }
+    }
+
+    impl<'de, N, E, Ty, S> Deserialize<'de> for GraphMap<N, E, Ty, S>
+        where N: NodeTrait + Deserialize<'de>,
+              E: Deserialize<'de>,
+              Ty: EdgeType,
+              S: BuildHasher + Default,
+    {
+        /// Fails with a deserialization error if an edge endpoint is not
+        /// in the node list.
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where D: Deserializer<'de>
+        {
+            let repr: GraphMapRepr<N, E> = try!(Deserialize::deserialize(deserializer));
+            let mut graph = GraphMap::with_capacity(repr.nodes.len(), repr.edges.len());
+            for n in repr.nodes {
+                graph.add_node(n);
+            }
+            for (i, (a, b, weight)) in repr.edges.into_iter().enumerate() {
+                if !graph.contains_node(a) || !graph.contains_node(b) {
+                    return Err(D::Error::custom(format_args!(
+                        "edge {} has an endpoint that is not in the node list", i)));
+                }
+                graph.add_edge(a, b, weight);
+            }
+            Ok(graph)
+        }
+    }
+}
@@ -0,0 +1,632 @@
+//! GraphML output and parsing.
+//!
+//! [GraphML](http://graphml.graphdrawing.org/) is an XML format for graphs
+//! used by tools like Gephi and yEd. `to_graphml` writes a graph with
+//! string attributes on its nodes and edges, and `parse` reads a GraphML
+//! document into a `Graph` whose weights are the attribute maps.
+//!
+//! ```
+//! use std::collections::HashMap;
+//! use petgraph::Graph;
+//! use petgraph::graphml;
+//!
+//! let mut g = Graph::new();
+//! let a = g.add_node("a");
+//! let b = g.add_node("b");
+//! g.add_edge(a, b, 1.5);
+//!
+//! let mut output = Vec::new();
+//! graphml::to_graphml(&mut output, &g,
+//!                     |(_, w)| vec![("name".to_string(), w.to_string())],
+//!                     |e| vec![("weight".to_string(), e.weight().to_string())]).unwrap();
+//!
+//! let parsed: Graph<HashMap<String, String>, HashMap<String, String>> =
+//!     graphml::parse(&String::from_utf8(output).unwrap()).unwrap();
+//! assert_eq!(parsed.node_count(), 2);
+//! assert_eq!(parsed.raw_nodes()[1].weight["name"], "b");
+//! assert_eq!(parsed.raw_edges()[0].weight["weight"], "1.5");
+//! ```
+
+use std::collections::{BTreeSet, HashMap};
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+
+use graph::{Graph, NodeIndex};
+use util::XmlEscaped;
+use visit::{
+    EdgeRef,
+    GraphProp,
+    IntoEdgeReferences,
+    IntoNodeReferences,
+    NodeIndexable,
+    NodeRef,
+};
+use EdgeType;
+
+static HEADER: &'static str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns"
+         xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+         xsi:schemaLocation="http://graphml.graphdrawing.org/xmlns http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd">
+"#;
+
+/// Write `graph` as a GraphML document to `writer`.
+///
+/// `node_attrs` and `edge_attrs` return the attributes of each node and
+/// edge as (name, value) pairs. A `<key>` of type `string` is declared for
+/// each attribute name that occurs; nodes are written with ids `n0`, `n1`,
+/// ... by node index, and edges with ids `e0`, `e1`, ... in edge order.
+/// The graph's `edgedefault` is `directed` or `undirected` according to
+/// the graph's edge type.
+pub fn to_graphml<G, W, NF, NA, EF, EA>(mut writer: W, graph: G,
+                                        mut node_attrs: NF, mut edge_attrs: EF)
+    -> io::Result<()>
+    where G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+          W: Write,
+          NF: FnMut(G::NodeRef) -> NA,
+          NA: IntoIterator<Item=(String, String)>,
+          EF: FnMut(G::EdgeRef) -> EA,
+          EA: IntoIterator<Item=(String, String)>,
+{
+    // The keys are declared before the graph, so collect the attributes first.
+    let nodes = graph.node_references()
+        .map(|node| (graph.to_index(node.id()), node_attrs(node).into_iter().collect()))
+        .collect::<Vec<(usize, Vec<_>)>>();
+    let edges = graph.edge_references()
+        .map(|edge| (graph.to_index(edge.source()), graph.to_index(edge.target()),
+                     edge_attrs(edge).into_iter().collect()))
+        .collect::<Vec<(usize, usize, Vec<_>)>>();
+    let node_keys = nodes.iter()
+        .flat_map(|&(_, ref attrs)| attrs.iter().map(|&(ref name, _)| &name[..]))
+        .collect::<BTreeSet<_>>()
+        .into_iter().enumerate()
+        .map(|(i, name)| (name, format!("n_d{}", i)))
+        .collect::<HashMap<_, _>>();
+    let edge_keys = edges.iter()
+        .flat_map(|&(_, _, ref attrs)| attrs.iter().map(|&(ref name, _)| &name[..]))
+        .collect::<BTreeSet<_>>()
+        .into_iter().enumerate()
+        .map(|(i, name)| (name, format!("e_d{}", i)))
+        .collect::<HashMap<_, _>>();
+
+    try!(writer.write_all(HEADER.as_bytes()));
+    for &(keys, domain) in &[(&node_keys, "node"), (&edge_keys, "edge")] {
+        let mut keys = keys.iter().collect::<Vec<_>>();
+        keys.sort();
+        for (name, id) in keys {
+            try!(writeln!(writer,
+                          r#"  <key id="{}" for="{}" attr.name="{}" attr.type="string"/>"#,
+                          id, domain, XmlEscaped(name)));
+        }
+    }
+    try!(writeln!(writer, r#"  <graph id="G" edgedefault="{}">"#,
+                  if graph.is_directed() { "directed" } else { "undirected" }));
+    for &(i, ref attrs) in &nodes {
+        try!(write!(writer, r#"    <node id="n{}""#, i));
+        try!(write_data(&mut writer, &node_keys, attrs));
+        try!(writeln!(writer, "</node>"));
+    }
+    for (i, &(source, target, ref attrs)) in edges.iter().enumerate() {
+        try!(write!(writer, r#"    <edge id="e{}" source="n{}" target="n{}""#,
+                    i, source, target));
+        try!(write_data(&mut writer, &edge_keys, attrs));
+        try!(writeln!(writer, "</edge>"));
+    }
+    try!(writeln!(writer, "  </graph>"));
+    try!(writeln!(writer, "</graphml>"));
+    Ok(())
+}
+
+/// Finish a node or edge start tag and write its `<data>` elements.
+fn write_data<W: Write>(writer: &mut W, keys: &HashMap<&str, String>,
+                        attrs: &[(String, String)]) -> io::Result<()>
+{
+    if attrs.is_empty() {
+        return write!(writer, ">");
+    }
+    try!(writeln!(writer, ">"));
+    for &(ref name, ref value) in attrs {
+        try!(writeln!(writer, r#"      <data key="{}">{}</data>"#,
+                      keys[&name[..]], XmlEscaped(value)));
+    }
+    write!(writer, "    ")
+}
+
+/// An error from `parse`, with the position where it occurred.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    line: usize,
+    column: usize,
+    message: String,
+}
+
+impl ParseError {
+    /// The line of the error, starting at 1.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column of the error, in characters, starting at 1.
+    pub fn column(&self) -> usize {
+        self.column
+    }
+
+    /// A description of the error.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl Error for ParseError {
+    fn description(&self) -> &str {
+        &self.message
+    }
+}
+
+/// Attribute maps, the node and edge weights of a parsed graph.
+pub type Attributes = HashMap<String, String>;
+
+/// Parse a GraphML document.
+///
+/// Each `<node>` becomes a node of the graph, in document order, and each
+/// `<edge>` an edge. Their weights map attribute names to values: the
+/// `attr.name` of the `<key>` of each `<data>` element (or the key id if
+/// it is not declared), with the key's `<default>` filled in for
+/// attributes a node or edge doesn't have. Node and edge ids are not kept.
+///
+/// The graph's `edgedefault` must be `directed` for a directed `Graph`
+/// and `undirected` for an undirected one. Elements and attributes that
+/// aren't used, like ports, hyperedges and `<data>` with XML content (for
+/// example yEd's graphics), are skipped. Nested graphs are flattened.
+pub fn parse<Ty>(input: &str) -> Result<Graph<Attributes, Attributes, Ty>, ParseError>
+    where Ty: EdgeType,
+{
+    let mut lexer = Lexer::new(input);
+    let mut graph = Graph::with_capacity(0, 0);
+    // key id => (attribute name, domain, default value)
+    let mut keys = HashMap::<String, (String, String, Option<String>)>::new();
+    let mut ids = HashMap::<String, NodeIndex>::new();
+    let mut edges = Vec::new();
+    // the innermost open node or edge, by index into the graph or `edges`
+    let mut owners = Vec::new();
+    let mut stack = Vec::<Element>::new();
+    let mut seen_root = false;
+    let mut seen_graph = false;
+
+    loop {
+        let (line, column) = (lexer.line, lexer.column);
+        let error = |message: String| ParseError { line: line, column: column, message: message };
+        let event = match try!(lexer.next_event()) {
+            None => break,
+            Some(event) => event,
+        };
+        match event {
+            Event::Start { name, attrs, empty } => {
+                if stack.is_empty() {
+                    if seen_root || name != "graphml" {
+                        return Err(error(format!("expected `<graphml>`, found `<{}>`", name)));
+                    }
+                    seen_root = true;
+                }
+                if let Some(parent) = stack.last_mut() {
+                    parent.has_children = true;
+                }
+                let attr = |key: &str| {
+                    attrs.iter().find(|&&(ref k, _)| k == key).map(|&(_, ref v)| v.clone())
+                };
+                let required = |key: &str| {
+                    attr(key).ok_or_else(|| error(format!(
+                        "`<{}>` is missing the `{}` attribute", name, key)))
+                };
+                let parent = stack.last().map(|e| &e.name[..]);
+                match (&name[..], parent) {
+                    ("key", Some("graphml")) => {
+                        let id = try!(required("id"));
+                        let attr_name = attr("attr.name").unwrap_or_else(|| id.clone());
+                        let domain = attr("for").unwrap_or_else(|| "all".to_string());
+                        keys.insert(id, (attr_name, domain, None));
+                    }
+                    ("graph", _) if !seen_graph => {
+                        seen_graph = true;
+                        let directed = match attr("edgedefault") {
+                            Some(ref d) if d == "directed" => true,
+                            Some(ref d) if d == "undirected" => false,
+                            Some(d) => {
+                                return Err(error(format!("invalid edgedefault `{}`", d)));
+                            }
+                            None => return Err(error(
+                                "`<graph>` is missing the `edgedefault` attribute".to_string())),
+                        };
+                        if directed != Ty::is_directed() {
+                            return Err(error(format!(
+                                "expected a graph with {} edges",
+                                if Ty::is_directed() { "directed" } else { "undirected" })));
+                        }
+                    }
+                    ("node", Some("graph")) => {
+                        let id = try!(required("id"));
+                        if ids.contains_key(&id) {
+                            return Err(error(format!("duplicate node id `{}`", id)));
+                        }
+                        let a = graph.add_node(HashMap::new());
+                        ids.insert(id, a);
+                        owners.push(Owner::Node(a));
+                    }
+                    ("edge", Some("graph")) => {
+                        let source = try!(required("source"));
+                        let target = try!(required("target"));
+                        edges.push((source, target, HashMap::new(), line, column));
+                        owners.push(Owner::Edge(edges.len() - 1));
+                    }
+                    _ => {}
+                }
+                let element = Element {
+                    name: name,
+                    id: attr("id"),
+                    key: attr("key"),
+                    text: String::new(),
+                    has_children: false,
+                };
+                if empty {
+                    try!(end_element(element, &mut stack, &mut keys, &mut graph,
+                                     &mut edges, &mut owners));
+                } else {
+                    stack.push(element);
+                }
+            }
+            Event::End { name } => {
+                let element = match stack.pop() {
+                    Some(ref element) if element.name != name => {
+                        return Err(error(format!("expected `</{}>`, found `</{}>`",
+                                                 element.name, name)));
+                    }
+                    Some(element) => element,
+                    None => return Err(error(format!("unexpected `</{}>`", name))),
+                };
+                try!(end_element(element, &mut stack, &mut keys, &mut graph,
+                                 &mut edges, &mut owners));
+            }
+            Event::Text(text) => {
+                match stack.last_mut() {
+                    Some(element) => element.text.push_str(&text),
+                    None if text.trim().is_empty() => {}
+                    None => return Err(error("text outside of `<graphml>`".to_string())),
+                }
+            }
+        }
+    }
+    if let Some(element) = stack.last() {
+        return Err(ParseError {
+            line: lexer.line,
+            column: lexer.column,
+            message: format!("unclosed `<{}>`", element.name),
+        });
+    }
+    if !seen_root {
+        return Err(ParseError {
+            line: lexer.line,
+            column: lexer.column,
+            message: "expected `<graphml>`".to_string(),
+        });
+    }
+
+    let defaults = |domain: &str| {
+        keys.values()
+            .filter(|&&(_, ref d, ref default)| (d == domain || d == "all") && default.is_some())
+            .map(|&(ref name, _, ref default)| (name.clone(), default.clone().unwrap()))
+            .collect::<Vec<_>>()
+    };
+    let node_defaults = defaults("node");
+    let edge_defaults = defaults("edge");
+    for weight in graph.node_weights_mut() {
+        for &(ref name, ref value) in &node_defaults {
+            weight.entry(name.clone()).or_insert_with(|| value.clone());
+        }
+    }
+    for (source, target, mut weight, line, column) in edges {
+        let node = |id: String| {
+            ids.get(&id).cloned().ok_or_else(|| ParseError {
+                line: line,
+                column: column,
+                message: format!("edge endpoint `{}` is not a node", id),
+            })
+        };
+        let a = try!(node(source));
+        let b = try!(node(target));
+        for &(ref name, ref value) in &edge_defaults {
+            weight.entry(name.clone()).or_insert_with(|| value.clone());
+        }
+        graph.add_edge(a, b, weight);
+    }
+    Ok(graph)
+}
+
+struct Element {
+    name: String,
+    id: Option<String>,
+    key: Option<String>,
+    text: String,
+    has_children: bool,
+}
+
+enum Owner {
+    Node(NodeIndex),
+    Edge(usize),
+}
+
+/// Handle the end of an element: store `<data>` and `<default>` values.
+fn end_element<Ty>(element: Element,
+                   stack: &mut Vec<Element>,
+                   keys: &mut HashMap<String, (String, String, Option<String>)>,
+                   graph: &mut Graph<Attributes, Attributes, Ty>,
+                   edges: &mut Vec<(String, String, Attributes, usize, usize)>,
+                   owners: &mut Vec<Owner>)
+    -> Result<(), ParseError>
+    where Ty: EdgeType,
+{
+    let parent = stack.last().map(|e| &e.name[..]);
+    match (&element.name[..], parent) {
+        ("node", Some("graph")) | ("edge", Some("graph")) => { owners.pop(); }
+        ("default", Some("key")) => {
+            let id = stack.last().and_then(|e| e.id.clone());
+            if let Some(key) = id.and_then(|id| keys.get_mut(&id)) {
+                key.2 = Some(element.text);
+            }
+        }
+        ("data", Some("node")) | ("data", Some("edge")) if !element.has_children => {
+            if let Some(id) = element.key {
+                let name = keys.get(&id).map(|k| k.0.clone()).unwrap_or(id);
+                let weight = match owners.last() {
+                    Some(&Owner::Node(a)) => &mut graph[a],
+                    Some(&Owner::Edge(i)) => &mut edges[i].2,
+                    None => return Ok(()),
+                };
+                weight.insert(name, element.text);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+enum Event {
+    Start { name: String, attrs: Vec<(String, String)>, empty: bool },
+    End { name: String },
+    Text(String),
+}
+
+/// A small XML tokenizer; it skips declarations, comments, processing
+/// instructions and doctypes, and resolves entities and CDATA sections.
+struct Lexer {
+    chars: Vec<char>,
+    pos: usize,
+    line: usize,
+    column: usize,
+}
+
+impl Lexer {
+    fn new(input: &str) -> Self {
+        Lexer {
+            chars: input.chars().collect(),
+            pos: 0,
+            line: 1,
+            column: 1,
+        }
+    }
+
+    fn error<T>(&self, message: String) -> Result<T, ParseError> {
+        Err(ParseError { line: self.line, column: self.column, message: message })
+    }
+
+    fn peek_char(&self) -> Option<char> {
+        self.chars.get(self.pos).cloned()
+    }
+
+    fn starts_with(&self, s: &str) -> bool {
+        s.chars().enumerate().all(|(i, c)| self.chars.get(self.pos + i) == Some(&c))
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek_char();
+        if let Some(c) = c {
+            self.pos += 1;
+            if c == '\n' {
+                self.line += 1;
+                self.column = 1;
+            } else {
+                self.column += 1;
+            }
+        }
+        c
+    }
+
+    fn skip(&mut self, n: usize) {
+        for _ in 0..n {
+            self.bump();
+        }
+    }
+
+    /// Skip past `end`.
+    fn skip_past(&mut self, end: &str, what: &str) -> Result<(), ParseError> {
+        let (line, column) = (self.line, self.column);
+        while !self.starts_with(end) {
+            if self.bump().is_none() {
+                return Err(ParseError {
+                    line: line,
+                    column: column,
+                    message: format!("unterminated {}", what),
+                });
+            }
+        }
+        self.skip(end.len());
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek_char().map_or(false, |c| c.is_whitespace()) {
+            self.bump();
+        }
+    }
+
+    fn next_event(&mut self) -> Result<Option<Event>, ParseError> {
+        loop {
+            if self.peek_char().is_none() {
+                return Ok(None);
+            }
+            if self.starts_with("<!--") {
+                try!(self.skip_past("-->", "comment"));
+            } else if self.starts_with("<?") {
+                try!(self.skip_past("?>", "processing instruction"));
+            } else if self.starts_with("<![CDATA[") {
+                self.skip(9);
+                let start = self.pos;
+                try!(self.skip_past("]]>", "CDATA section"));
+                let text = self.chars[start..self.pos - 3].iter().cloned().collect();
+                return Ok(Some(Event::Text(text)));
+            } else if self.starts_with("<!") {
+                try!(self.skip_doctype());
+            } else if self.starts_with("</") {
+                self.skip(2);
+                let name = try!(self.parse_name());
+                self.skip_whitespace();
+                if self.peek_char() != Some('>') {
+                    return self.error(format!("expected `>` to close `</{}`", name));
+                }
+                self.bump();
+                return Ok(Some(Event::End { name: name }));
+            } else if self.starts_with("<") {
+                self.bump();
+                return self.parse_start_tag().map(Some);
+            } else {
+                let mut text = String::new();
+                while let Some(c) = self.peek_char() {
+                    if c == '<' {
+                        break;
+                    }
+                    text.push(try!(self.parse_char()));
+                }
+                return Ok(Some(Event::Text(text)));
+            }
+        }
+    }
+
+    /// Skip `<!DOCTYPE ...>`, including an internal subset in brackets.
+    fn skip_doctype(&mut self) -> Result<(), ParseError> {
+        let mut depth = 0;
+        loop {
+            match self.bump() {
+                Some('[') => depth += 1,
+                Some(']') => depth -= 1,
+                Some('>') if depth == 0 => return Ok(()),
+                Some(_) => {}
+                None => return self.error("unterminated doctype".to_string()),
+            }
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<String, ParseError> {
+        let mut name = String::new();
+        while let Some(c) = self.peek_char() {
+            if c.is_alphanumeric() || c == '_' || c == ':' || c == '-' || c == '.' {
+                name.push(c);
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        if name.is_empty() {
+            return self.error("expected a name".to_string());
+        }
+        Ok(name)
+    }
+
+    fn parse_start_tag(&mut self) -> Result<Event, ParseError> {
+        let name = try!(self.parse_name());
+        let mut attrs = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if self.starts_with("/>") {
+                self.skip(2);
+                return Ok(Event::Start { name: name, attrs: attrs, empty: true });
+            }
+            if self.starts_with(">") {
+                self.skip(1);
+                return Ok(Event::Start { name: name, attrs: attrs, empty: false });
+            }
+            let key = try!(self.parse_name());
+            self.skip_whitespace();
+            if self.peek_char() != Some('=') {
+                return self.error(format!("expected `=` after attribute `{}`", key));
+            }
+            self.bump();
+            self.skip_whitespace();
+            let quote = match self.peek_char() {
+                Some(q) if q == '"' || q == '\'' => q,
+                _ => return self.error(format!("expected a quoted value for `{}`", key)),
+            };
+            self.bump();
+            let mut value = String::new();
+            loop {
+                match self.peek_char() {
+                    Some(c) if c == quote => {
+                        self.bump();
+                        break;
+                    }
+                    Some('<') | None => {
+                        return self.error(format!("unterminated value for `{}`", key));
+                    }
+                    Some(_) => value.push(try!(self.parse_char())),
+                }
+            }
+            attrs.push((key, value));
+        }
+    }
+
+    /// Parse one character of text, resolving an entity reference.
+    fn parse_char(&mut self) -> Result<char, ParseError> {
+        if self.peek_char() != Some('&') {
+            return Ok(self.bump().unwrap());
+        }
+        let (line, column) = (self.line, self.column);
+        let mut entity = String::new();
+        self.bump();
+        loop {
+            match self.bump() {
+                Some(';') => break,
+                Some(c) if entity.len() < 10 => entity.push(c),
+                _ => {
+                    return Err(ParseError {
+                        line: line,
+                        column: column,
+                        message: "unterminated entity reference".to_string(),
+                    });
+                }
+            }
+        }
+        let c = match &entity[..] {
+            "lt" => Some('<'),
+            "gt" => Some('>'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "apos" => Some('\''),
+            e if e.starts_with("#x") => {
+                u32::from_str_radix(&e[2..], 16).ok().and_then(::std::char::from_u32)
+            }
+            e if e.starts_with('#') => {
+                e[1..].parse().ok().and_then(::std::char::from_u32)
+            }
+            _ => None,
+        };
+        c.ok_or_else(|| ParseError {
+            line: line,
+            column: column,
+            message: format!("unknown entity `&{};`", entity),
+        })
+    }
+}
@@ -0,0 +1,435 @@
+//! Reading and writing graphs as edge lists, and writing them in the
+//! Pajek and GEXF formats.
+//!
+//! An edge list is a text format with one edge per line: the source and
+//! target node ids and an optional weight, separated by whitespace or a
+//! delimiter like `,`.
+//!
+//! ```text
+//! # source target weight
+//! 0 1 2.5
+//! 1 2 0.5
+//! 2 0
+//! ```
+//!
+//! ```
+//! use petgraph::Graph;
+//! use petgraph::io::{read_edge_list, write_edge_list, EdgeListOptions};
+//!
+//! let input = "# source,target,weight\n1,2,2.5\n2,3,0.5\n3,1\n";
+//! let options = EdgeListOptions::new().delimiter(',').one_based(true);
+//! let g: Graph<(), f64> = read_edge_list(input.as_bytes(), &options).unwrap();
+//! assert_eq!(g.node_count(), 3);
+//! assert_eq!(g.edge_count(), 3);
+//! assert_eq!(g.raw_edges()[2].weight, 0.);
+//!
+//! let mut output = Vec::new();
+//! write_edge_list(&mut output, &g, &options).unwrap();
+//! assert_eq!(output, b"1,2,2.5\n2,3,0.5\n3,1,0\n");
+//! ```
+
+use std::cmp;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::error::Error;
+use std::fmt;
+#[cfg(feature = "graphmap")]
+use std::hash::BuildHasher;
+use std::io::{self, BufRead, Write};
+use std::str::FromStr;
+
+use graph::{Graph, IndexType, NodeIndex};
+#[cfg(feature = "graphmap")]
+use graphmap::{GraphMap, NodeTrait};
+use util::XmlEscaped;
+use visit::{
+    EdgeRef,
+    GraphProp,
+    IntoEdgeReferences,
+    IntoNodeReferences,
+    NodeIndexable,
+    NodeRef,
+};
+use EdgeType;
+
+/// How to handle an edge that occurs more than once in an edge list.
+///
+/// In an undirected graph, `a b` and `b a` are the same edge.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Duplicates {
+    /// Add every edge, so that duplicates become parallel edges.
+    Keep,
+    /// Keep one edge, with the weight of its last occurrence.
+    Merge,
+    /// Return an error for the line with the duplicate edge.
+    Error,
+}
+
+/// Options for reading and writing edge lists.
+///
+/// The defaults are: fields separated by whitespace, 0-based node ids,
+/// comment lines starting with `#`, and duplicate edges kept.
+#[derive(Clone, Debug)]
+pub struct EdgeListOptions {
+    delimiter: Option<char>,
+    one_based: bool,
+    comment: String,
+    duplicates: Duplicates,
+}
+
+impl EdgeListOptions {
+    /// Create the default options.
+    pub fn new() -> Self {
+        EdgeListOptions {
+            delimiter: None,
+            one_based: false,
+            comment: "#".to_string(),
+            duplicates: Duplicates::Keep,
+        }
+    }
+
+    /// Separate fields with `delimiter` instead of whitespace.
+    ///
+    /// Whitespace around each field is ignored.
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = Some(delimiter);
+        self
+    }
+
+    /// Use node ids starting from 1 instead of 0 in the text: node id `1`
+    /// is node index `0`.
+    pub fn one_based(mut self, one_based: bool) -> Self {
+        self.one_based = one_based;
+        self
+    }
+
+    /// Skip lines starting with `prefix` (after leading whitespace).
+    /// An empty prefix disables comments. Blank lines are always skipped.
+    pub fn comment_prefix(mut self, prefix: &str) -> Self {
+        self.comment = prefix.to_string();
+        self
+    }
+
+    /// Set how to handle duplicate edges when reading.
+    pub fn duplicates(mut self, duplicates: Duplicates) -> Self {
+        self.duplicates = duplicates;
+        self
+    }
+}
+
+impl Default for EdgeListOptions {
+    fn default() -> Self {
+        EdgeListOptions::new()
+    }
+}
+
+/// An error from reading an edge list.
+#[derive(Debug)]
+pub enum ReadError {
+    /// Reading from the input failed.
+    Io(io::Error),
+    /// A line could not be parsed.
+    Parse {
+        /// The line number, starting at 1.
+        line: usize,
+        /// A description of the problem.
+        message: String,
+    },
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ReadError::Io(ref e) => write!(f, "{}", e),
+            ReadError::Parse { line, ref message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl Error for ReadError {
+    fn description(&self) -> &str {
+        match *self {
+            ReadError::Io(_) => "I/O error",
+            ReadError::Parse { ref message, .. } => message,
+        }
+    }
+
+    fn cause(&self) -> Option<&Error> {
+        match *self {
+            ReadError::Io(ref e) => Some(e),
+            ReadError::Parse { .. } => None,
+        }
+    }
+}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> Self {
+        ReadError::Io(e)
+    }
+}
+
+/// Parse the edges of an edge list and pass each one to `add_edge`,
+/// with its line number.
+fn read_edges<R, E, F>(reader: R, options: &EdgeListOptions, mut add_edge: F)
+    -> Result<(), ReadError>
+    where R: BufRead,
+          E: FromStr + Default,
+          E::Err: fmt::Display,
+          F: FnMut(usize, usize, E, usize) -> Result<(), ReadError>,
+{
+    for (i, line) in reader.lines().enumerate() {
+        let line = try!(line);
+        let line_number = i + 1;
+        let error = |message: String| ReadError::Parse { line: line_number, message: message };
+        let trimmed = line.trim();
+        if trimmed.is_empty() ||
+            (!options.comment.is_empty() && trimmed.starts_with(&options.comment[..]))
+        {
+            continue;
+        }
+        let fields = match options.delimiter {
+            None => trimmed.split_whitespace().collect::<Vec<_>>(),
+            Some(d) => trimmed.split(d).map(|f| f.trim()).collect(),
+        };
+        if fields.len() != 2 && fields.len() != 3 {
+            return Err(error(format!("expected 2 or 3 fields, found {}", fields.len())));
+        }
+        let mut ids = [0; 2];
+        for (id, field) in ids.iter_mut().zip(&fields) {
+            *id = match field.parse::<usize>() {
+                Ok(0) if options.one_based => {
+                    return Err(error("node id 0 in a 1-based edge list".to_string()));
+                }
+                Ok(x) => x - options.one_based as usize,
+                Err(_) => return Err(error(format!("invalid node id {:?}", field))),
+            };
+        }
+        let weight = match fields.get(2) {
+            None => E::default(),
+            Some(field) => match field.parse() {
+                Ok(w) => w,
+                Err(e) => return Err(error(format!("invalid weight {:?}: {}", field, e))),
+            },
+        };
+        try!(add_edge(ids[0], ids[1], weight, line_number));
+    }
+    Ok(())
+}
+
+/// Read a `Graph` from an edge list.
+///
+/// Node id `i` becomes node index `i` (`i - 1` if the options are
+/// 1-based), and nodes are added up to the largest id, with default
+/// weights. Edge weights are parsed with `FromStr`; lines without a
+/// weight use the default weight.
+///
+/// Return an error with the line number if a line is malformed: it has
+/// the wrong number of fields, a node id is not a valid index, the weight
+/// can't be parsed, or it is a duplicate edge and duplicates are errors.
+pub fn read_edge_list<R, N, E, Ty, Ix>(reader: R, options: &EdgeListOptions)
+    -> Result<Graph<N, E, Ty, Ix>, ReadError>
+    where R: BufRead,
+          N: Default,
+          E: FromStr + Default,
+          E::Err: fmt::Display,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    let mut graph = Graph::with_capacity(0, 0);
+    let mut seen = HashMap::new();
+    try!(read_edges(reader, options, |a, b, weight, line| {
+        let hi = cmp::max(a, b);
+        // an index equal to the maximum is the invalid `end` index
+        if hi >= <Ix as IndexType>::max().index() {
+            return Err(ReadError::Parse {
+                line: line,
+                message: format!("node id {} is too large for the index type",
+                                 hi + options.one_based as usize),
+            });
+        }
+        while graph.node_count() <= hi {
+            graph.add_node(N::default());
+        }
+        let (a, b) = (NodeIndex::new(a), NodeIndex::new(b));
+        if options.duplicates == Duplicates::Keep {
+            graph.add_edge(a, b, weight);
+            return Ok(());
+        }
+        let key = if Ty::is_directed() || a <= b { (a, b) } else { (b, a) };
+        match seen.entry(key) {
+            Entry::Vacant(v) => { v.insert(graph.add_edge(a, b, weight)); }
+            Entry::Occupied(o) => {
+                if options.duplicates == Duplicates::Error {
+                    return Err(ReadError::Parse {
+                        line: line,
+                        message: "duplicate edge".to_string(),
+                    });
+                }
+                graph[*o.get()] = weight;
+            }
+        }
+        Ok(())
+    }));
+    Ok(graph)
+}
+
+/// Read a `GraphMap` from an edge list.
+///
+/// The nodes are the node ids (minus one if the options are 1-based) that
+/// occur in the list. Edge weights are parsed with `FromStr`; lines
+/// without a weight use the default weight.
+///
+/// A `GraphMap` has no parallel edges, so `Duplicates::Keep` behaves like
+/// `Duplicates::Merge`.
+///
+/// Return an error with the line number if a line is malformed: it has
+/// the wrong number of fields, a node id is not a number, the weight
+/// can't be parsed, or it is a duplicate edge and duplicates are errors.
+#[cfg(feature = "graphmap")]
+pub fn read_edge_list_graphmap<R, E, Ty>(reader: R, options: &EdgeListOptions)
+    -> Result<GraphMap<usize, E, Ty>, ReadError>
+    where R: BufRead,
+          E: FromStr + Default,
+          E::Err: fmt::Display,
+          Ty: EdgeType,
+{
+    let mut graph = GraphMap::new();
+    try!(read_edges(reader, options, |a, b, weight, line| {
+        if graph.add_edge(a, b, weight).is_some() && options.duplicates == Duplicates::Error {
+            return Err(ReadError::Parse {
+                line: line,
+                message: "duplicate edge".to_string(),
+            });
+        }
+        Ok(())
+    }));
+    Ok(graph)
+}
+
+/// Write the edges of `graph` as an edge list, one `source target weight`
+/// line per edge, in edge order.
+///
+/// The fields are separated by the delimiter of the options, or a space,
+/// and node ids are 1-based if the options are. The node ids are node
+/// indices, also for a `GraphMap`; use `write_edge_list_graphmap` to write
+/// its node values instead. Nodes without edges are not written.
+pub fn write_edge_list<W, G>(mut writer: W, graph: G, options: &EdgeListOptions)
+    -> io::Result<()>
+    where W: Write,
+          G: IntoEdgeReferences + NodeIndexable,
+          G::EdgeWeight: fmt::Display,
+{
+    let delimiter = options.delimiter.unwrap_or(' ');
+    let offset = options.one_based as usize;
+    for edge in graph.edge_references() {
+        try!(writeln!(writer, "{}{}{}{}{}",
+                      graph.to_index(edge.source()) + offset, delimiter,
+                      graph.to_index(edge.target()) + offset, delimiter,
+                      edge.weight()));
+    }
+    Ok(())
+}
+
+/// Write the edges of a `GraphMap` as an edge list, one `source target
+/// weight` line per edge, in edge order.
+///
+/// The node ids are the node values, so that `read_edge_list_graphmap`
+/// reads the same graph back. The fields are separated by the delimiter of
+/// the options, or a space; the node values are written as they are, even
+/// if the options are 1-based. Nodes without edges are not written.
+#[cfg(feature = "graphmap")]
+pub fn write_edge_list_graphmap<W, N, E, Ty, S>(mut writer: W, graph: &GraphMap<N, E, Ty, S>,
+                                                options: &EdgeListOptions)
+    -> io::Result<()>
+    where W: Write,
+          N: NodeTrait + fmt::Display,
+          E: fmt::Display,
+          Ty: EdgeType,
+          S: BuildHasher,
+{
+    let delimiter = options.delimiter.unwrap_or(' ');
+    for (a, b, weight) in graph.all_edges() {
+        try!(writeln!(writer, "{}{}{}{}{}", a, delimiter, b, delimiter, weight));
+    }
+    Ok(())
+}
+
+/// Return the position of each node in `graph.node_references()`, by node
+/// index, so that nodes are numbered consecutively even with holes in the
+/// indices.
+fn node_positions<G>(graph: G) -> Vec<usize>
+    where G: IntoNodeReferences + NodeIndexable,
+{
+    let mut positions = vec![0; graph.node_bound()];
+    for (i, node) in graph.node_references().enumerate() {
+        positions[graph.to_index(node.id())] = i;
+    }
+    positions
+}
+
+/// Write `graph` in the Pajek `.net` format.
+///
+/// The nodes are numbered from 1 in the `*Vertices` section, with the
+/// label from `node_label` in double quotes; Pajek has no escapes, so
+/// double quotes in a label are written as single quotes and line breaks
+/// as spaces. The edges follow in an `*Arcs` section for a directed graph
+/// or an `*Edges` section for an undirected one, with their weights.
+pub fn write_pajek<W, G, F>(mut writer: W, graph: G, mut node_label: F) -> io::Result<()>
+    where W: Write,
+          G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+          G::EdgeWeight: fmt::Display,
+          F: FnMut(G::NodeRef) -> String,
+{
+    let positions = node_positions(graph);
+    try!(writeln!(writer, "*Vertices {}", graph.node_references().count()));
+    for (i, node) in graph.node_references().enumerate() {
+        let label = node_label(node).replace('"', "'").replace(|c| c == '\n' || c == '\r', " ");
+        try!(writeln!(writer, "{} \"{}\"", i + 1, label));
+    }
+    try!(writeln!(writer, "{}", if graph.is_directed() { "*Arcs" } else { "*Edges" }));
+    for edge in graph.edge_references() {
+        try!(writeln!(writer, "{} {} {}",
+                      positions[graph.to_index(edge.source())] + 1,
+                      positions[graph.to_index(edge.target())] + 1,
+                      edge.weight()));
+    }
+    Ok(())
+}
+
+/// Write `graph` in the GEXF 1.2 format.
+///
+/// The nodes have ids `0`, `1`, ... in node order, with the label from
+/// `node_label`, and the edges have weights. The graph's
+/// `defaultedgetype` is `directed` or `undirected` according to the
+/// graph's edge type. GEXF weights are numbers, so the edge weights should
+/// display as numbers.
+pub fn write_gexf<W, G, F>(mut writer: W, graph: G, mut node_label: F) -> io::Result<()>
+    where W: Write,
+          G: IntoNodeReferences + IntoEdgeReferences + NodeIndexable + GraphProp,
+          G::EdgeWeight: fmt::Display,
+          F: FnMut(G::NodeRef) -> String,
+{
+    let positions = node_positions(graph);
+    try!(writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#));
+    try!(writeln!(writer, r#"<gexf xmlns="http://www.gexf.net/1.2draft" version="1.2">"#));
+    try!(writeln!(writer, r#"  <graph mode="static" defaultedgetype="{}">"#,
+                  if graph.is_directed() { "directed" } else { "undirected" }));
+    try!(writeln!(writer, "    <nodes>"));
+    for (i, node) in graph.node_references().enumerate() {
+        try!(writeln!(writer, r#"      <node id="{}" label="{}"/>"#,
+                      i, XmlEscaped(&node_label(node))));
+    }
+    try!(writeln!(writer, "    </nodes>"));
+    try!(writeln!(writer, "    <edges>"));
+    for (i, edge) in graph.edge_references().enumerate() {
+        try!(writeln!(writer, r#"      <edge id="{}" source="{}" target="{}" weight="{}"/>"#,
+                      i,
+                      positions[graph.to_index(edge.source())],
+                      positions[graph.to_index(edge.target())],
+                      XmlEscaped(&edge.weight().to_string())));
+    }
+    try!(writeln!(writer, "    </edges>"));
+    try!(writeln!(writer, "  </graph>"));
+    try!(writeln!(writer, "</gexf>"));
+    Ok(())
+}
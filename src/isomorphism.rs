@@ -1,11 +1,16 @@
 use std::marker;
+use std::mem;
 use fixedbitset::FixedBitSet;
 
 use super::{
+    Direction,
     EdgeType,
     Incoming,
+    Outgoing,
 };
 use super::graph::{
+    node_index,
+    EdgeIndex,
     Graph,
     IndexType,
     NodeIndex,
@@ -168,12 +173,24 @@ pub fn is_isomorphic<N, E, Ty, Ix>(g0: &Graph<N, E, Ty, Ix>,
     where Ty: EdgeType,
           Ix: IndexType,
 {
-    if g0.node_count() != g1.node_count() || g0.edge_count() != g1.edge_count() {
-        return false
-    }
+    find_isomorphism(g0, g1).is_some()
+}
 
-    let mut st = [Vf2State::new(g0), Vf2State::new(g1)];
-    try_match(&mut st, g0, g1, &mut NoSemanticMatch, &mut NoSemanticMatch).unwrap_or(false)
+/// [Graph] Return an isomorphism from `g0` to `g1`, or `None` if the graphs
+/// are not isomorphic.
+///
+/// The mapping is indexed by the node indices of `g0`: element `i` is the
+/// node of `g1` that node `i` of `g0` corresponds to.
+///
+/// Like `is_isomorphic`, only matching graph structure. The graphs should
+/// not be multigraphs.
+pub fn find_isomorphism<N, E, Ty, Ix>(g0: &Graph<N, E, Ty, Ix>,
+                                      g1: &Graph<N, E, Ty, Ix>)
+    -> Option<Vec<NodeIndex<Ix>>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    find_mapping(g0, g1, &mut NoSemanticMatch, &mut NoSemanticMatch)
 }
 
 /// [Graph] Return `true` if the graphs `g0` and `g1` are isomorphic.
@@ -181,22 +198,64 @@ pub fn is_isomorphic<N, E, Ty, Ix>(g0: &Graph<N, E, Ty, Ix>,
 /// Using the VF2 algorithm, examining both syntactic and semantic
 /// graph isomorphism (graph structure and matching node and edge weights).
 ///
+/// `node_match` and `edge_match` are checked for each candidate pair of
+/// nodes as the search extends the mapping, not only for a complete
+/// mapping, so weights prune the search early.
+///
 /// The graphs should not be multigraphs.
 pub fn is_isomorphic_matching<N, E, Ty, Ix, F, G>(g0: &Graph<N, E, Ty, Ix>,
                                                   g1: &Graph<N, E, Ty, Ix>,
-                                                  mut node_match: F,
-                                                  mut edge_match: G) -> bool
+                                                  node_match: F,
+                                                  edge_match: G) -> bool
     where Ty: EdgeType,
           Ix: IndexType,
           F: FnMut(&N, &N) -> bool,
           G: FnMut(&E, &E) -> bool,
+{
+    find_isomorphism_matching(g0, g1, node_match, edge_match).is_some()
+}
+
+/// [Graph] Return an isomorphism from `g0` to `g1` that matches the node and
+/// edge weights, or `None` if there is none.
+///
+/// The mapping is indexed by the node indices of `g0`: element `i` is the
+/// node of `g1` that node `i` of `g0` corresponds to.
+///
+/// Like `is_isomorphic_matching`, which this is the mapping for. The graphs
+/// should not be multigraphs.
+pub fn find_isomorphism_matching<N, E, Ty, Ix, F, G>(g0: &Graph<N, E, Ty, Ix>,
+                                                     g1: &Graph<N, E, Ty, Ix>,
+                                                     mut node_match: F,
+                                                     mut edge_match: G)
+    -> Option<Vec<NodeIndex<Ix>>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: FnMut(&N, &N) -> bool,
+          G: FnMut(&E, &E) -> bool,
+{
+    find_mapping(g0, g1, &mut node_match, &mut edge_match)
+}
+
+fn find_mapping<N, E, Ty, Ix, F, G>(g0: &Graph<N, E, Ty, Ix>,
+                                    g1: &Graph<N, E, Ty, Ix>,
+                                    node_match: &mut F,
+                                    edge_match: &mut G)
+    -> Option<Vec<NodeIndex<Ix>>>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: SemanticMatcher<N>,
+          G: SemanticMatcher<E>,
 {
     if g0.node_count() != g1.node_count() || g0.edge_count() != g1.edge_count() {
-        return false
+        return None
     }
 
     let mut st = [Vf2State::new(g0), Vf2State::new(g1)];
-    try_match(&mut st, g0, g1, &mut node_match, &mut edge_match).unwrap_or(false)
+    match try_match(&mut st, g0, g1, node_match, edge_match) {
+        // a complete mapping is left in the state
+        Some(true) => Some(mem::replace(&mut st[0].mapping, Vec::new())),
+        _ => None,
+    }
 }
 
 trait SemanticMatcher<T> {
@@ -454,3 +513,283 @@ fn try_match<N, E, Ty, Ix, F, G>(st: &mut [Vf2State<Ty, Ix>; 2],
     None
 }
 
+
+/// [Graph] Return an iterator of the occurrences of the graph `pattern` in
+/// the graph `target`: mappings of the nodes of `pattern` to distinct nodes of
+/// `target` such that each edge of `pattern` has a matching edge in `target`
+/// (subgraph monomorphisms).
+///
+/// Each mapping is indexed by the node indices of `pattern`: element `i` is
+/// the node of `target` that node `i` of `pattern` is mapped to. An
+/// occurrence is found once for each symmetry of the pattern; a triangle in
+/// `target` matches a triangle `pattern` six times.
+///
+/// If `induced` is `true`, only induced subgraphs match: two target nodes
+/// that the pattern maps to may be adjacent only if their pattern nodes are.
+///
+/// `node_match` and `edge_match` compare a pattern weight with a target
+/// weight, and are checked as the search extends a mapping.
+///
+/// The mappings are searched for lazily, as the iterator is advanced, with a
+/// VF2 style depth first search: pattern nodes are visited in an order in
+/// which each node is, if possible, adjacent to an earlier one, and the
+/// candidates for it are the target neighbors of that earlier node's image.
+/// A target node is pruned unless it has at least the pattern node's degree.
+///
+/// The graphs should not be multigraphs.
+pub fn subgraph_isomorphisms_iter<'a, N, E, Ty, Ix, F, G>(pattern: &'a Graph<N, E, Ty, Ix>,
+                                                        target: &'a Graph<N, E, Ty, Ix>,
+                                                        node_match: F,
+                                                        edge_match: G,
+                                                        induced: bool)
+    -> SubgraphIsomorphisms<'a, N, E, Ty, Ix, F, G>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: FnMut(&N, &N) -> bool,
+          G: FnMut(&E, &E) -> bool,
+{
+    let n = pattern.node_count();
+    // order the pattern nodes: next is the node with the most edges to the
+    // nodes before it, then the one with the highest degree
+    let mut order = Vec::with_capacity(n);
+    let mut depth = vec![NONE; n];
+    let mut links = vec![0; n];
+    for _ in 0..n {
+        let mut next = NONE;
+        for a in pattern.node_indices() {
+            if depth[a.index()] != NONE {
+                continue;
+            }
+            if next == NONE || (links[a.index()], degree(pattern, a)) >
+                               (links[next], degree(pattern, node_index(next)))
+            {
+                next = a.index();
+            }
+        }
+        depth[next] = order.len();
+        order.push(node_index(next));
+        for b in pattern.neighbors_undirected(node_index(next)) {
+            links[b.index()] += 1;
+        }
+    }
+
+    // the edges from each pattern node to the nodes before it, and the
+    // earlier neighbor whose image's neighbors are the candidates
+    let mut steps = Vec::with_capacity(n);
+    for (d, &a) in order.iter().enumerate() {
+        let mut step = Step {
+            node: a,
+            parent: None,
+            edges: Vec::new(),
+            out_degree: pattern.neighbors_directed(a, Outgoing).count(),
+            in_degree: if pattern.is_directed() {
+                pattern.neighbors_directed(a, Incoming).count()
+            } else {
+                0
+            },
+        };
+        let dirs = if pattern.is_directed() { &DIRECTIONS[..] } else { &DIRECTIONS[..1] };
+        for &dir in dirs {
+            let mut edges = pattern.neighbors_directed(a, dir).detach();
+            while let Some((e, b)) = edges.next(pattern) {
+                let db = depth[b.index()];
+                if db > d || (db == d && dir == Incoming) {
+                    continue;
+                }
+                if step.parent.is_none() && db < d {
+                    step.parent = Some((db, dir));
+                }
+                step.edges.push((db, dir, e));
+            }
+        }
+        steps.push(step);
+    }
+
+    SubgraphIsomorphisms {
+        pattern: pattern,
+        target: target,
+        node_match: node_match,
+        edge_match: edge_match,
+        induced: induced,
+        steps: steps,
+        image: Vec::with_capacity(n),
+        depth_of: vec![NONE; target.node_count()],
+        stack: Vec::with_capacity(n),
+        started: false,
+    }
+}
+
+const NONE: usize = !0;
+const DIRECTIONS: [Direction; 2] = [Outgoing, Incoming];
+
+fn degree<N, E, Ty, Ix>(g: &Graph<N, E, Ty, Ix>, a: NodeIndex<Ix>) -> usize
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    g.neighbors_undirected(a).count()
+}
+
+/// What is known about the pattern node at one depth of the search.
+#[derive(Debug)]
+struct Step<Ix> {
+    node: NodeIndex<Ix>,
+    /// The depth of an earlier neighbor and the direction of the edge to it.
+    parent: Option<(usize, Direction)>,
+    /// The edges to the nodes at the same or earlier depths: the depth of the
+    /// other node, the direction and the edge.
+    edges: Vec<(usize, Direction, EdgeIndex<Ix>)>,
+    out_degree: usize,
+    in_degree: usize,
+}
+
+/// An iterator of the occurrences of a pattern graph in a target graph.
+///
+/// Created with `subgraph_isomorphisms_iter`.
+///
+/// Iterator element type is `Vec<NodeIndex<Ix>>`, indexed by the node
+/// indices of the pattern.
+pub struct SubgraphIsomorphisms<'a, N: 'a, E: 'a, Ty: 'a, Ix: 'a, F, G> {
+    pattern: &'a Graph<N, E, Ty, Ix>,
+    target: &'a Graph<N, E, Ty, Ix>,
+    node_match: F,
+    edge_match: G,
+    induced: bool,
+    steps: Vec<Step<Ix>>,
+    /// The target node of each depth mapped so far.
+    image: Vec<NodeIndex<Ix>>,
+    /// The depth that each target node is mapped at, or `NONE`.
+    depth_of: Vec<usize>,
+    /// The candidates of each depth and the position of the next one.
+    stack: Vec<(Vec<NodeIndex<Ix>>, usize)>,
+    started: bool,
+}
+
+impl<'a, N, E, Ty, Ix, F, G> SubgraphIsomorphisms<'a, N, E, Ty, Ix, F, G>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: FnMut(&N, &N) -> bool,
+          G: FnMut(&E, &E) -> bool,
+{
+    fn candidates(&self, d: usize) -> Vec<NodeIndex<Ix>> {
+        match self.steps[d].parent {
+            None => self.target.node_indices().collect(),
+            Some((db, dir)) => {
+                // the pattern edge goes `dir` from this node, so the target
+                // edge goes the opposite way from the earlier node's image
+                let mut nodes = self.target.neighbors_directed(self.image[db], dir.opposite())
+                                           .collect::<Vec<_>>();
+                nodes.sort();
+                nodes.dedup();
+                nodes
+            }
+        }
+    }
+
+    /// Return `true` if the pattern node at depth `d` can be mapped to `t`.
+    fn feasible(&mut self, d: usize, t: NodeIndex<Ix>) -> bool {
+        let (pattern, target) = (self.pattern, self.target);
+        let step = &self.steps[d];
+        if self.depth_of[t.index()] != NONE {
+            return false;
+        }
+        if !(self.node_match)(&pattern[step.node], &target[t]) {
+            return false;
+        }
+        if target.neighbors_directed(t, Outgoing).count() < step.out_degree ||
+            target.is_directed() &&
+            target.neighbors_directed(t, Incoming).count() < step.in_degree
+        {
+            return false;
+        }
+        for &(db, dir, e) in &step.edges {
+            let u = if db == d { t } else { self.image[db] };
+            let te = match dir {
+                Outgoing => target.find_edge(t, u),
+                Incoming => target.find_edge(u, t),
+            };
+            match te {
+                Some(te) if (self.edge_match)(&pattern[e], &target[te]) => {}
+                _ => return false,
+            }
+        }
+        if self.induced {
+            let dirs = if target.is_directed() { &DIRECTIONS[..] } else { &DIRECTIONS[..1] };
+            for &dir in dirs {
+                for u in target.neighbors_directed(t, dir) {
+                    let du = if u == t { d } else { self.depth_of[u.index()] };
+                    if du == NONE {
+                        continue;
+                    }
+                    let b = self.steps[du].node;
+                    let edge = match dir {
+                        Outgoing => pattern.find_edge(step.node, b),
+                        Incoming => pattern.find_edge(b, step.node),
+                    };
+                    if edge.is_none() {
+                        return false;
+                    }
+                }
+            }
+        }
+        true
+    }
+}
+
+impl<'a, N, E, Ty, Ix, F, G> Iterator for SubgraphIsomorphisms<'a, N, E, Ty, Ix, F, G>
+    where Ty: EdgeType,
+          Ix: IndexType,
+          F: FnMut(&N, &N) -> bool,
+          G: FnMut(&E, &E) -> bool,
+{
+    type Item = Vec<NodeIndex<Ix>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            if self.steps.is_empty() {
+                return Some(Vec::new());
+            }
+            if self.steps.len() > self.target.node_count() {
+                return None;
+            }
+            let candidates = self.candidates(0);
+            self.stack.push((candidates, 0));
+        }
+        while !self.stack.is_empty() {
+            let d = self.stack.len() - 1;
+            // backtrack from the current choice at this depth, if any
+            if self.image.len() > d {
+                let t = self.image.pop().unwrap();
+                self.depth_of[t.index()] = NONE;
+            }
+            let mut found = None;
+            while self.stack[d].1 < self.stack[d].0.len() {
+                let t = self.stack[d].0[self.stack[d].1];
+                self.stack[d].1 += 1;
+                if self.feasible(d, t) {
+                    found = Some(t);
+                    break;
+                }
+            }
+            let t = match found {
+                None => {
+                    self.stack.pop();
+                    continue;
+                }
+                Some(t) => t,
+            };
+            self.depth_of[t.index()] = d;
+            self.image.push(t);
+            if d + 1 == self.steps.len() {
+                let mut mapping = vec![NodeIndex::end(); self.steps.len()];
+                for (step, &t) in self.steps.iter().zip(&self.image) {
+                    mapping[step.node.index()] = t;
+                }
+                return Some(mapping);
+            }
+            let candidates = self.candidates(d + 1);
+            self.stack.push((candidates, 0));
+        }
+        None
+    }
+}
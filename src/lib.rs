@@ -11,11 +11,18 @@
 //! which is backed by a hash table and the node identifiers are the keys
 //! into the table.
 //!
+//! - [`MatrixGraph`](./matrix_graph/struct.MatrixGraph.html) is an adjacency
+//! matrix graph, with constant time edge lookup, for small, dense graphs.
+//!
 #![doc(html_root_url = "https://docs.rs/petgraph/0.4/")]
 
 extern crate fixedbitset;
 #[cfg(feature = "graphmap")]
 extern crate ordermap;
+#[cfg(feature = "rand")]
+extern crate rand;
+#[cfg(feature = "serde")]
+extern crate serde;
 
 #[doc(no_inline)]
 pub use graph::Graph;
@@ -40,9 +47,16 @@ pub mod graphmap;
 #[path = "graph.rs"]
 mod graph_impl;
 pub mod dot;
+pub mod graphml;
+pub mod io;
 pub mod unionfind;
 mod dijkstra;
+mod astar;
 pub mod csr;
+pub mod matrix_graph;
+pub mod frozen_graph;
+pub mod path;
+pub mod stats;
 mod iter_format;
 mod isomorphism;
 mod traits_graph;
@@ -56,19 +70,27 @@ pub mod prelude;
 pub mod graph {
     pub use graph_impl::{
         Edge,
+        EdgeAddError,
         EdgeIndex,
         EdgeIndices,
         EdgeReference,
         EdgeReferences,
         EdgeWeightsMut,
         Edges,
+        EdgesConnecting,
         Externals,
         Frozen,
         Graph,
+        GraphTx,
+        InvalidEdge,
+        InvalidNode,
         Neighbors,
         Node,
         NodeIndex,
+        NodeIndexMap,
         NodeIndices,
+        NodeIndicesByWeight,
+        NodeReferences,
         NodeWeightsMut,
         WalkNeighbors,
         GraphIndex,
@@ -107,6 +129,12 @@ pub enum Direction {
 copyclone!(Direction);
 
 impl Direction {
+    /// Return both directions, `Outgoing` first.
+    #[inline]
+    pub fn all() -> [Direction; 2] {
+        [Outgoing, Incoming]
+    }
+
     /// Return the opposite `Direction`.
     #[inline]
     pub fn opposite(&self) -> Direction {
@@ -0,0 +1,713 @@
+//! `MatrixGraph<N, E, Ty, Ix>` is a graph datastructure backed by an adjacency matrix.
+
+use std::cmp;
+use std::iter::Enumerate;
+use std::marker::PhantomData;
+use std::mem;
+use std::slice;
+use std::ops::{Index, IndexMut};
+
+use fixedbitset::FixedBitSet;
+
+use visit::{GraphBase, Data, GraphProp, Visitable, NodeIndexable, NodeCount};
+use visit::{IntoNeighbors, IntoNeighborsDirected, IntoEdgeReferences, IntoEdges};
+use visit::{IntoNodeIdentifiers, GetAdjacencyMatrix};
+
+#[doc(no_inline)]
+pub use graph::{NodeIndex, IndexType, DefaultIx, node_index};
+
+use {
+    Direction,
+    Incoming,
+    EdgeType,
+    Directed,
+    Undirected,
+};
+
+/// A `MatrixGraph` with directed edges.
+pub type DiMatrix<N, E, Ix = DefaultIx> = MatrixGraph<N, E, Directed, Ix>;
+
+/// A `MatrixGraph` with undirected edges.
+pub type UnMatrix<N, E, Ix = DefaultIx> = MatrixGraph<N, E, Undirected, Ix>;
+
+/// `MatrixGraph<N, E, Ty, Ix>` is a graph datastructure using an adjacency
+/// matrix representation.
+///
+/// It stores an *n* × *n* matrix of optional edge weights, where *n* is the
+/// node capacity. This makes it a good fit for small, dense graphs: adding,
+/// updating, removing and looking up an edge are all **O(1)**, while
+/// iterating the neighbors of a node is **O(|V|)**.
+///
+/// The graph is directed or undirected depending on `Ty`, and allows self
+/// loops but no parallel edges. An undirected edge is stored once.
+///
+/// Uses **O(|V|²)** space.
+///
+/// ### Node indices
+///
+/// `NodeIndex` is the same type as for `Graph`. Removing a node does not
+/// invalidate other node indices, but the index of a removed node may be
+/// reused by a later `add_node`.
+#[derive(Clone, Debug)]
+pub struct MatrixGraph<N, E, Ty = Directed, Ix = DefaultIx> {
+    /// Node weights; `None` for a vacant slot.
+    nodes: Vec<Option<N>>,
+    /// Vacant node slots, available for reuse.
+    free: Vec<usize>,
+    /// Row-major `capacity` × `capacity` matrix of edge weights.
+    matrix: Vec<Option<E>>,
+    capacity: usize,
+    node_count: usize,
+    edge_count: usize,
+    ty: PhantomData<Ty>,
+    ix: PhantomData<Ix>,
+}
+
+impl<N, E> MatrixGraph<N, E, Directed> {
+    /// Create a new `MatrixGraph` with directed edges.
+    pub fn new() -> Self {
+        MatrixGraph::with_capacity(0)
+    }
+}
+
+impl<N, E> MatrixGraph<N, E, Undirected> {
+    /// Create a new `MatrixGraph` with undirected edges.
+    pub fn new_undirected() -> Self {
+        MatrixGraph::with_capacity(0)
+    }
+}
+
+impl<N, E, Ty, Ix> Default for MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn default() -> Self {
+        MatrixGraph::with_capacity(0)
+    }
+}
+
+impl<N, E, Ty, Ix> MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Create a new `MatrixGraph` with room for `nodes` nodes.
+    pub fn with_capacity(nodes: usize) -> Self {
+        let mut matrix = Vec::with_capacity(nodes * nodes);
+        for _ in 0..nodes * nodes {
+            matrix.push(None);
+        }
+        MatrixGraph {
+            nodes: Vec::with_capacity(nodes),
+            free: Vec::new(),
+            matrix: matrix,
+            capacity: nodes,
+            node_count: 0,
+            edge_count: 0,
+            ty: PhantomData,
+            ix: PhantomData,
+        }
+    }
+
+    /// Return the number of nodes in the graph.
+    pub fn node_count(&self) -> usize {
+        self.node_count
+    }
+
+    /// Return the number of edges in the graph.
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    /// Whether the graph has directed edges or not.
+    #[inline]
+    pub fn is_directed(&self) -> bool {
+        Ty::is_directed()
+    }
+
+    /// Remove all nodes and edges.
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+        for elt in &mut self.matrix {
+            *elt = None;
+        }
+        self.node_count = 0;
+        self.edge_count = 0;
+    }
+
+    /// Return the position of the edge `(a, b)` in the matrix.
+    #[inline]
+    fn matrix_index(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> usize {
+        let (a, b) = (a.index(), b.index());
+        let (row, column) = if !self.is_directed() && a > b { (b, a) } else { (a, b) };
+        row * self.capacity + column
+    }
+
+    fn assert_node(&self, a: NodeIndex<Ix>) {
+        assert!(self.contains_node(a), "MatrixGraph: node index {:?} does not exist", a);
+    }
+
+    /// Grow the matrix so that it has room for at least `nodes` nodes.
+    fn grow(&mut self, nodes: usize) {
+        if nodes <= self.capacity {
+            return;
+        }
+        let new_capacity = cmp::max(nodes, 2 * self.capacity);
+        let mut matrix = Vec::with_capacity(new_capacity * new_capacity);
+        for _ in 0..new_capacity * new_capacity {
+            matrix.push(None);
+        }
+        for row in 0..self.capacity {
+            for column in 0..self.capacity {
+                matrix[row * new_capacity + column] =
+                    self.matrix[row * self.capacity + column].take();
+            }
+        }
+        self.matrix = matrix;
+        self.capacity = new_capacity;
+    }
+
+    /// Add a node (also called vertex) with associated data `weight` to the
+    /// graph.
+    ///
+    /// Computes in **O(1)** time, or **O(|V|²)** when the matrix has to
+    /// grow, which happens when the node capacity doubles.
+    ///
+    /// Return the index of the new node.
+    ///
+    /// **Panics** if the graph is at the maximum number of nodes for its
+    /// index type.
+    pub fn add_node(&mut self, weight: N) -> NodeIndex<Ix> {
+        self.node_count += 1;
+        if let Some(i) = self.free.pop() {
+            self.nodes[i] = Some(weight);
+            return node_index(i);
+        }
+        let i = self.nodes.len();
+        assert!(<Ix as IndexType>::max().index() == !0 || i < <Ix as IndexType>::max().index(),
+                "MatrixGraph: node index type is exhausted");
+        self.grow(i + 1);
+        self.nodes.push(Some(weight));
+        node_index(i)
+    }
+
+    /// Remove `a` from the graph, along with all edges connected to it,
+    /// and return its weight.
+    ///
+    /// The index `a` becomes vacant and may be reused by a later `add_node`.
+    ///
+    /// Computes in **O(|V|)** time.
+    ///
+    /// **Panics** if the node `a` does not exist.
+    pub fn remove_node(&mut self, a: NodeIndex<Ix>) -> N {
+        self.assert_node(a);
+        for b in 0..self.nodes.len() {
+            let b = node_index(b);
+            self.remove_edge(a, b);
+            self.remove_edge(b, a);
+        }
+        self.node_count -= 1;
+        self.free.push(a.index());
+        self.nodes[a.index()].take().unwrap()
+    }
+
+    /// Return `true` if the node `a` exists in the graph.
+    pub fn contains_node(&self, a: NodeIndex<Ix>) -> bool {
+        match self.nodes.get(a.index()) {
+            Some(&Some(_)) => true,
+            _ => false,
+        }
+    }
+
+    /// Access the weight for node `a`.
+    ///
+    /// Also available with indexing syntax: `&graph[a]`.
+    pub fn node_weight(&self, a: NodeIndex<Ix>) -> Option<&N> {
+        match self.nodes.get(a.index()) {
+            Some(&Some(ref w)) => Some(w),
+            _ => None,
+        }
+    }
+
+    /// Access the weight for node `a`, mutably.
+    ///
+    /// Also available with indexing syntax: `&mut graph[a]`.
+    pub fn node_weight_mut(&mut self, a: NodeIndex<Ix>) -> Option<&mut N> {
+        match self.nodes.get_mut(a.index()) {
+            Some(&mut Some(ref mut w)) => Some(w),
+            _ => None,
+        }
+    }
+
+    /// Add an edge from `a` to `b` to the graph, with its associated
+    /// data `weight`.
+    ///
+    /// Computes in **O(1)** time.
+    ///
+    /// **Panics** if any of the nodes don't exist, or if the edge
+    /// already exists.<br>
+    /// Use `update_edge` to replace the weight of an existing edge instead.
+    pub fn add_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E) {
+        let old = self.update_edge(a, b, weight);
+        assert!(old.is_none(), "MatrixGraph: edge ({:?}, {:?}) already exists", a, b);
+    }
+
+    /// Add or update the edge from `a` to `b`.
+    ///
+    /// If the edge already exists, its weight is replaced and the old weight
+    /// is returned.
+    ///
+    /// Computes in **O(1)** time.
+    ///
+    /// **Panics** if any of the nodes don't exist.
+    pub fn update_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E) -> Option<E> {
+        self.assert_node(a);
+        self.assert_node(b);
+        let i = self.matrix_index(a, b);
+        let old = mem::replace(&mut self.matrix[i], Some(weight));
+        if old.is_none() {
+            self.edge_count += 1;
+        }
+        old
+    }
+
+    /// Remove the edge from `a` to `b` and return its weight, or `None`
+    /// if it didn't exist.
+    ///
+    /// Computes in **O(1)** time.
+    pub fn remove_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Option<E> {
+        if !self.contains_node(a) || !self.contains_node(b) {
+            return None;
+        }
+        let i = self.matrix_index(a, b);
+        let old = self.matrix[i].take();
+        if old.is_some() {
+            self.edge_count -= 1;
+        }
+        old
+    }
+
+    /// Return `true` if there is an edge from `a` to `b`.
+    ///
+    /// Computes in **O(1)** time.
+    pub fn has_edge(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> bool {
+        self.edge_weight(a, b).is_some()
+    }
+
+    /// Access the weight of the edge from `a` to `b`.
+    ///
+    /// Also available with indexing syntax: `&graph[(a, b)]`.
+    ///
+    /// Computes in **O(1)** time.
+    pub fn edge_weight(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Option<&E> {
+        if !self.contains_node(a) || !self.contains_node(b) {
+            return None;
+        }
+        self.matrix[self.matrix_index(a, b)].as_ref()
+    }
+
+    /// Access the weight of the edge from `a` to `b`, mutably.
+    ///
+    /// Also available with indexing syntax: `&mut graph[(a, b)]`.
+    ///
+    /// Computes in **O(1)** time.
+    pub fn edge_weight_mut(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Option<&mut E> {
+        if !self.contains_node(a) || !self.contains_node(b) {
+            return None;
+        }
+        let i = self.matrix_index(a, b);
+        self.matrix[i].as_mut()
+    }
+
+    /// Return an iterator of all neighbors of `a`.
+    ///
+    /// - `Directed`: Targets of outgoing edges from `a`.
+    /// - `Undirected`: All other endpoints of edges connected to `a`.
+    ///
+    /// Neighbors are produced in increasing index order, and a self loop
+    /// produces `a` once.
+    ///
+    /// Computes in **O(|V|)** time for the whole iteration.
+    ///
+    /// **Panics** if the node `a` does not exist.<br>
+    /// Iterator element type is `NodeIndex<Ix>`.
+    pub fn neighbors(&self, a: NodeIndex<Ix>) -> Neighbors<E, Ty, Ix> {
+        self.neighbors_directed(a, Direction::Outgoing)
+    }
+
+    /// Return an iterator of all neighbors of `a`, in the specified direction.
+    ///
+    /// - `Directed`, `Outgoing`: Targets of edges from `a`.
+    /// - `Directed`, `Incoming`: Sources of edges to `a`.
+    /// - `Undirected`: All other endpoints of edges connected to `a`.
+    ///
+    /// Computes in **O(|V|)** time for the whole iteration.
+    ///
+    /// **Panics** if the node `a` does not exist.<br>
+    /// Iterator element type is `NodeIndex<Ix>`.
+    pub fn neighbors_directed(&self, a: NodeIndex<Ix>, dir: Direction)
+        -> Neighbors<E, Ty, Ix>
+    {
+        Neighbors {
+            iter: self.edges_directed(a, dir),
+        }
+    }
+
+    /// Return an iterator of all edges of `a`, as `(a, b, &weight)` triples
+    /// where `b` is the other endpoint.
+    ///
+    /// - `Directed`: Outgoing edges from `a`.
+    /// - `Undirected`: All edges connected to `a`.
+    ///
+    /// Computes in **O(|V|)** time for the whole iteration.
+    ///
+    /// **Panics** if the node `a` does not exist.
+    pub fn edges(&self, a: NodeIndex<Ix>) -> Edges<E, Ty, Ix> {
+        self.edges_directed(a, Direction::Outgoing)
+    }
+
+    fn edges_directed(&self, a: NodeIndex<Ix>, dir: Direction) -> Edges<E, Ty, Ix> {
+        self.assert_node(a);
+        Edges {
+            graph_matrix: &self.matrix,
+            capacity: self.capacity,
+            node: a,
+            next: 0,
+            end: self.nodes.len(),
+            incoming: self.is_directed() && dir == Incoming,
+            ty: PhantomData,
+        }
+    }
+
+    /// Return an iterator over the node indices of the graph, in
+    /// increasing order.
+    pub fn node_indices(&self) -> NodeIndices<N, Ix> {
+        NodeIndices {
+            iter: self.nodes.iter().enumerate(),
+            ix: PhantomData,
+        }
+    }
+}
+
+/// Iterator over the node indices of a `MatrixGraph`.
+#[derive(Clone, Debug)]
+pub struct NodeIndices<'a, N: 'a, Ix = DefaultIx> {
+    iter: Enumerate<slice::Iter<'a, Option<N>>>,
+    ix: PhantomData<Ix>,
+}
+
+impl<'a, N, Ix: IndexType> Iterator for NodeIndices<'a, N, Ix> {
+    type Item = NodeIndex<Ix>;
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((i, node)) = self.iter.next() {
+            if node.is_some() {
+                return Some(node_index(i));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+/// Iterator over the edges of a node in a `MatrixGraph`.
+///
+/// Created with `.edges(a)`; the element type is `(NodeIndex<Ix>, NodeIndex<Ix>, &E)`.
+#[derive(Debug)]
+pub struct Edges<'a, E: 'a, Ty = Directed, Ix: 'a = DefaultIx> {
+    graph_matrix: &'a [Option<E>],
+    capacity: usize,
+    node: NodeIndex<Ix>,
+    next: usize,
+    end: usize,
+    incoming: bool,
+    ty: PhantomData<Ty>,
+}
+
+impl<'a, E, Ty, Ix: Copy> Clone for Edges<'a, E, Ty, Ix> {
+    fn clone(&self) -> Self {
+        Edges {
+            graph_matrix: self.graph_matrix,
+            capacity: self.capacity,
+            node: self.node,
+            next: self.next,
+            end: self.end,
+            incoming: self.incoming,
+            ty: self.ty,
+        }
+    }
+}
+
+impl<'a, E, Ty, Ix> Iterator for Edges<'a, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Item = (NodeIndex<Ix>, NodeIndex<Ix>, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let a = self.node.index();
+        while self.next < self.end {
+            let b = self.next;
+            self.next += 1;
+            let (row, column) = if self.incoming || (!Ty::is_directed() && a > b) {
+                (b, a)
+            } else {
+                (a, b)
+            };
+            if let Some(ref w) = self.graph_matrix[row * self.capacity + column] {
+                return Some((self.node, node_index(b), w));
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.end - self.next))
+    }
+}
+
+/// Iterator over the neighbors of a node in a `MatrixGraph`.
+///
+/// Created with `.neighbors(a)` or `.neighbors_directed(a, dir)`.
+#[derive(Debug)]
+pub struct Neighbors<'a, E: 'a, Ty = Directed, Ix: 'a = DefaultIx> {
+    iter: Edges<'a, E, Ty, Ix>,
+}
+
+impl<'a, E, Ty, Ix: Copy> Clone for Neighbors<'a, E, Ty, Ix> {
+    fn clone(&self) -> Self {
+        Neighbors { iter: self.iter.clone() }
+    }
+}
+
+impl<'a, E, Ty, Ix> Iterator for Neighbors<'a, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Item = NodeIndex<Ix>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(_, b, _)| b)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+/// Iterator over all edges of a `MatrixGraph`, in row-major order.
+///
+/// An undirected edge is produced once, with the lower index as source.
+#[derive(Debug)]
+pub struct EdgeReferences<'a, E: 'a, Ty = Directed, Ix: 'a = DefaultIx> {
+    graph_matrix: &'a [Option<E>],
+    capacity: usize,
+    row: usize,
+    column: usize,
+    end: usize,
+    ty: PhantomData<Ty>,
+    ix: PhantomData<Ix>,
+}
+
+impl<'a, E, Ty, Ix> Iterator for EdgeReferences<'a, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Item = (NodeIndex<Ix>, NodeIndex<Ix>, &'a E);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.row < self.end {
+            if self.column >= self.end {
+                self.row += 1;
+                self.column = if Ty::is_directed() { 0 } else { self.row };
+                continue;
+            }
+            let (row, column) = (self.row, self.column);
+            self.column += 1;
+            if let Some(ref w) = self.graph_matrix[row * self.capacity + column] {
+                return Some((node_index(row), node_index(column), w));
+            }
+        }
+        None
+    }
+}
+
+/// Index the `MatrixGraph` by `NodeIndex` to access node weights.
+///
+/// **Panics** if the node doesn't exist.
+impl<N, E, Ty, Ix> Index<NodeIndex<Ix>> for MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Output = N;
+    fn index(&self, a: NodeIndex<Ix>) -> &N {
+        self.node_weight(a).expect("MatrixGraph::index: node does not exist")
+    }
+}
+
+/// Index the `MatrixGraph` by `NodeIndex` to access node weights.
+///
+/// **Panics** if the node doesn't exist.
+impl<N, E, Ty, Ix> IndexMut<NodeIndex<Ix>> for MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn index_mut(&mut self, a: NodeIndex<Ix>) -> &mut N {
+        self.node_weight_mut(a).expect("MatrixGraph::index_mut: node does not exist")
+    }
+}
+
+/// Index the `MatrixGraph` by a pair of `NodeIndex` to access edge weights.
+///
+/// **Panics** if the edge doesn't exist.
+impl<N, E, Ty, Ix> Index<(NodeIndex<Ix>, NodeIndex<Ix>)> for MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Output = E;
+    fn index(&self, (a, b): (NodeIndex<Ix>, NodeIndex<Ix>)) -> &E {
+        self.edge_weight(a, b).expect("MatrixGraph::index: edge does not exist")
+    }
+}
+
+/// Index the `MatrixGraph` by a pair of `NodeIndex` to access edge weights.
+///
+/// **Panics** if the edge doesn't exist.
+impl<N, E, Ty, Ix> IndexMut<(NodeIndex<Ix>, NodeIndex<Ix>)> for MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn index_mut(&mut self, (a, b): (NodeIndex<Ix>, NodeIndex<Ix>)) -> &mut E {
+        self.edge_weight_mut(a, b).expect("MatrixGraph::index_mut: edge does not exist")
+    }
+}
+
+impl<N, E, Ty, Ix> GraphBase for MatrixGraph<N, E, Ty, Ix>
+    where Ix: IndexType,
+{
+    type NodeId = NodeIndex<Ix>;
+    type EdgeId = (NodeIndex<Ix>, NodeIndex<Ix>);
+}
+
+impl<N, E, Ty, Ix> Data for MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type NodeWeight = N;
+    type EdgeWeight = E;
+}
+
+impl<N, E, Ty, Ix> GraphProp for MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type EdgeType = Ty;
+}
+
+impl<N, E, Ty, Ix> Visitable for MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Map = FixedBitSet;
+    fn visit_map(&self) -> FixedBitSet {
+        FixedBitSet::with_capacity(self.node_bound())
+    }
+
+    fn reset_map(&self, map: &mut Self::Map) {
+        map.clear();
+        map.grow(self.node_bound());
+    }
+}
+
+impl<N, E, Ty, Ix> NodeIndexable for MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn node_bound(&self) -> usize { self.nodes.len() }
+    fn to_index(&self, a: NodeIndex<Ix>) -> usize { a.index() }
+    fn from_index(&self, i: usize) -> NodeIndex<Ix> { node_index(i) }
+}
+
+impl<N, E, Ty, Ix> NodeCount for MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn node_count(&self) -> usize {
+        self.node_count
+    }
+}
+
+/// The `MatrixGraph` is its own adjacency matrix.
+impl<N, E, Ty, Ix> GetAdjacencyMatrix for MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type AdjMatrix = ();
+    #[inline]
+    fn adjacency_matrix(&self) { }
+    #[inline]
+    fn is_adjacent(&self, _: &(), a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> bool {
+        self.has_edge(a, b)
+    }
+}
+
+impl<'a, N, E: 'a, Ty, Ix> IntoNeighbors for &'a MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Neighbors = Neighbors<'a, E, Ty, Ix>;
+    fn neighbors(self, a: NodeIndex<Ix>) -> Self::Neighbors {
+        MatrixGraph::neighbors(self, a)
+    }
+}
+
+impl<'a, N, E: 'a, Ty, Ix> IntoNeighborsDirected for &'a MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type NeighborsDirected = Neighbors<'a, E, Ty, Ix>;
+    fn neighbors_directed(self, a: NodeIndex<Ix>, dir: Direction) -> Self::NeighborsDirected {
+        MatrixGraph::neighbors_directed(self, a, dir)
+    }
+}
+
+impl<'a, N: 'a, E: 'a, Ty, Ix> IntoNodeIdentifiers for &'a MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type NodeIdentifiers = NodeIndices<'a, N, Ix>;
+    fn node_identifiers(self) -> Self::NodeIdentifiers {
+        self.node_indices()
+    }
+}
+
+impl<'a, N, E: 'a, Ty, Ix> IntoEdgeReferences for &'a MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type EdgeRef = (NodeIndex<Ix>, NodeIndex<Ix>, &'a E);
+    type EdgeReferences = EdgeReferences<'a, E, Ty, Ix>;
+    fn edge_references(self) -> Self::EdgeReferences {
+        EdgeReferences {
+            graph_matrix: &self.matrix,
+            capacity: self.capacity,
+            row: 0,
+            column: 0,
+            end: self.nodes.len(),
+            ty: PhantomData,
+            ix: PhantomData,
+        }
+    }
+}
+
+impl<'a, N, E: 'a, Ty, Ix> IntoEdges for &'a MatrixGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type Edges = Edges<'a, E, Ty, Ix>;
+    fn edges(self, a: NodeIndex<Ix>) -> Self::Edges {
+        MatrixGraph::edges(self, a)
+    }
+}
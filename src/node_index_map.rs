@@ -0,0 +1,193 @@
+//! `NodeIndexMap` builds a `Graph` whose nodes are looked up by weight.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::fmt;
+use std::hash::Hash;
+use std::ops::Deref;
+
+use {
+    Directed,
+    EdgeType,
+};
+
+use super::{
+    DefaultIx,
+    EdgeIndex,
+    Graph,
+    IndexType,
+    InvalidNode,
+    NodeIndex,
+};
+
+/// `NodeIndexMap` is a `Graph` together with a map from node weight to
+/// node index, so that each node weight occurs at most once.
+///
+/// It is useful for building a graph from input that names its nodes,
+/// like a parser would: `.get_or_insert(weight)` returns the index of the
+/// node with that weight, adding it first if needed. Unlike `GraphMap`,
+/// the result is a regular `Graph`, available with `.into_graph()`.
+///
+/// `NodeIndexMap` dereferences to the `Graph`, so all of its read-only
+/// methods are available. Nodes can't be removed and node weights can't be
+/// changed, since that would make the map stale; edges can be added and
+/// their weights changed.
+///
+/// ```
+/// use petgraph::graph::NodeIndexMap;
+///
+/// let mut map = NodeIndexMap::<String, ()>::new();
+/// for line in "a -> b\nb -> c\na -> c".lines() {
+///     let mut names = line.split(" -> ");
+///     let a = map.get_or_insert(names.next().unwrap().to_string());
+///     let b = map.get_or_insert(names.next().unwrap().to_string());
+///     map.add_edge(a, b, ());
+/// }
+/// assert_eq!(map.node_count(), 3);
+/// assert!(map.contains_edge(map.get("a").unwrap(), map.get("c").unwrap()));
+/// let graph = map.into_graph();
+/// assert_eq!(graph.edge_count(), 3);
+/// ```
+pub struct NodeIndexMap<N, E, Ty = Directed, Ix = DefaultIx> {
+    graph: Graph<N, E, Ty, Ix>,
+    index: HashMap<N, NodeIndex<Ix>>,
+}
+
+impl<N, E, Ty, Ix> NodeIndexMap<N, E, Ty, Ix>
+    where N: Clone + Eq + Hash,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    /// Create a new, empty `NodeIndexMap`.
+    pub fn new() -> Self {
+        NodeIndexMap {
+            graph: Graph::with_capacity(0, 0),
+            index: HashMap::new(),
+        }
+    }
+
+    /// Create a `NodeIndexMap` from an existing graph.
+    ///
+    /// Return an error with the index of the first node whose weight
+    /// duplicates an earlier node's weight.
+    ///
+    /// Computes in **O(|V|)** time.
+    pub fn from_graph(graph: Graph<N, E, Ty, Ix>) -> Result<Self, InvalidNode<Ix>> {
+        let mut index = HashMap::with_capacity(graph.node_count());
+        for (i, weight) in graph.node_references() {
+            match index.entry(weight.clone()) {
+                Entry::Occupied(_) => return Err(InvalidNode { node: i }),
+                Entry::Vacant(v) => { v.insert(i); }
+            }
+        }
+        Ok(NodeIndexMap {
+            graph: graph,
+            index: index,
+        })
+    }
+
+    /// Return the index of the node with weight `weight`, adding the node
+    /// if there is none.
+    ///
+    /// Computes in **O(1)** time (average).
+    ///
+    /// **Panics** if the graph is at the maximum number of nodes for its
+    /// index type.
+    pub fn get_or_insert(&mut self, weight: N) -> NodeIndex<Ix> {
+        match self.index.entry(weight) {
+            Entry::Occupied(o) => *o.get(),
+            Entry::Vacant(v) => {
+                let a = self.graph.add_node(v.key().clone());
+                v.insert(a);
+                a
+            }
+        }
+    }
+
+    /// Return the index of the node with weight `weight`, if it exists.
+    ///
+    /// Computes in **O(1)** time (average).
+    pub fn get<Q: ?Sized>(&self, weight: &Q) -> Option<NodeIndex<Ix>>
+        where N: Borrow<Q>,
+              Q: Eq + Hash,
+    {
+        self.index.get(weight).cloned()
+    }
+
+    /// Add an edge from `a` to `b` to the graph, with its associated
+    /// data `weight`. See `Graph::add_edge`.
+    ///
+    /// **Panics** if any of the nodes don't exist.
+    pub fn add_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E) -> EdgeIndex<Ix> {
+        self.graph.add_edge(a, b, weight)
+    }
+
+    /// Add or update an edge from `a` to `b`. See `Graph::update_edge`.
+    ///
+    /// **Panics** if any of the nodes don't exist.
+    pub fn update_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E) -> EdgeIndex<Ix> {
+        self.graph.update_edge(a, b, weight)
+    }
+
+    /// Access the weight for edge `e`, mutably.
+    pub fn edge_weight_mut(&mut self, e: EdgeIndex<Ix>) -> Option<&mut E> {
+        self.graph.edge_weight_mut(e)
+    }
+
+    /// Remove an edge and return its edge weight. See `Graph::remove_edge`.
+    pub fn remove_edge(&mut self, e: EdgeIndex<Ix>) -> Option<E> {
+        self.graph.remove_edge(e)
+    }
+
+    /// Return the underlying graph.
+    pub fn graph(&self) -> &Graph<N, E, Ty, Ix> {
+        &self.graph
+    }
+
+    /// Consume the `NodeIndexMap` and return the underlying graph.
+    pub fn into_graph(self) -> Graph<N, E, Ty, Ix> {
+        self.graph
+    }
+}
+
+impl<N, E, Ty, Ix> Clone for NodeIndexMap<N, E, Ty, Ix>
+    where N: Clone + Eq + Hash,
+          E: Clone,
+          Ix: IndexType,
+{
+    fn clone(&self) -> Self {
+        NodeIndexMap {
+            graph: self.graph.clone(),
+            index: self.index.clone(),
+        }
+    }
+}
+
+impl<N, E, Ty, Ix> fmt::Debug for NodeIndexMap<N, E, Ty, Ix>
+    where N: fmt::Debug + Eq + Hash,
+          E: fmt::Debug,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.graph.fmt(f)
+    }
+}
+
+impl<N, E, Ty, Ix> Default for NodeIndexMap<N, E, Ty, Ix>
+    where N: Clone + Eq + Hash,
+          Ty: EdgeType,
+          Ix: IndexType,
+{
+    fn default() -> Self {
+        NodeIndexMap::new()
+    }
+}
+
+impl<N, E, Ty, Ix> Deref for NodeIndexMap<N, E, Ty, Ix> {
+    type Target = Graph<N, E, Ty, Ix>;
+    fn deref(&self) -> &Graph<N, E, Ty, Ix> {
+        &self.graph
+    }
+}
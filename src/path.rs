@@ -0,0 +1,190 @@
+//! `Path<N>` is a walk through a graph, as a sequence of nodes.
+
+use std::slice;
+
+use algo::Measure;
+use visit::{
+    EdgeRef,
+    IntoEdges,
+    IntoNodeIdentifiers,
+};
+
+/// A walk through a graph, as the sequence of its nodes.
+///
+/// A path only stores node identifiers, so it is not tied to one graph; use
+/// `is_valid` to check that it is a walk in a particular graph, and `edges`
+/// to find the edges it follows.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::graph::node_index as n;
+/// use petgraph::path::Path;
+///
+/// let gr: Graph<(), u32> = Graph::from_edges(&[(0, 1, 2), (1, 2, 3), (1, 2, 1)]);
+/// let path = Path::from(vec![n(0), n(1)]);
+/// let path = path.concat(&Path::from(vec![n(1), n(2)])).unwrap();
+/// assert!(path.is_valid(&gr));
+/// assert_eq!(path.cost(&gr, |e| *e.weight()), Some(3));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Path<N> {
+    nodes: Vec<N>,
+}
+
+impl<N> Path<N>
+    where N: Copy + PartialEq,
+{
+    /// Create a path of just the node `start`, without edges.
+    pub fn new(start: N) -> Self {
+        Path { nodes: vec![start] }
+    }
+
+    /// Return the nodes of the path, in order.
+    pub fn nodes(&self) -> &[N] {
+        &self.nodes
+    }
+
+    /// Return the first node of the path, or `None` if it is empty.
+    pub fn start(&self) -> Option<N> {
+        self.nodes.first().cloned()
+    }
+
+    /// Return the last node of the path, or `None` if it is empty.
+    pub fn end(&self) -> Option<N> {
+        self.nodes.last().cloned()
+    }
+
+    /// Return the number of edges of the path.
+    pub fn len(&self) -> usize {
+        self.nodes.len().saturating_sub(1)
+    }
+
+    /// Return `true` if the path has no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Append the node `node` to the end of the path.
+    pub fn push(&mut self, node: N) {
+        self.nodes.push(node);
+    }
+
+    /// Reverse the path, so that it runs from its end to its start.
+    ///
+    /// For a directed graph, the reversed path is a walk in the reversed
+    /// graph.
+    pub fn reverse(&mut self) {
+        self.nodes.reverse();
+    }
+
+    /// Return the path of `self` followed by `other`, or `None` if `other`
+    /// doesn't start where `self` ends.
+    ///
+    /// The shared node appears only once in the result.
+    pub fn concat(&self, other: &Path<N>) -> Option<Path<N>> {
+        match (self.end(), other.start()) {
+            (Some(a), Some(b)) if a == b => {}
+            _ => return None,
+        }
+        let mut nodes = Vec::with_capacity(self.nodes.len() + other.nodes.len() - 1);
+        nodes.extend_from_slice(&self.nodes);
+        nodes.extend_from_slice(&other.nodes[1..]);
+        Some(Path { nodes: nodes })
+    }
+
+    /// Return `true` if the path is a walk in `g`: it has at least one node,
+    /// all its nodes are in the graph, and each node is followed by a
+    /// neighbor (along an outgoing edge, for a directed graph).
+    pub fn is_valid<G>(&self, g: G) -> bool
+        where G: IntoEdges<NodeId=N> + IntoNodeIdentifiers,
+    {
+        match self.nodes.len() {
+            0 => false,
+            1 => g.node_identifiers().any(|node| node == self.nodes[0]),
+            _ => self.nodes.windows(2).all(|w| g.edges(w[0]).any(|e| e.target() == w[1])),
+        }
+    }
+
+    /// Return the edges that the path follows in `g`, or `None` if it is not
+    /// a walk in `g`.
+    ///
+    /// Where there are parallel edges, the one with the lowest `edge_cost`
+    /// is used, and of equally cheap edges the one with the lowest edge id.
+    pub fn edges<G, F, K>(&self, g: G, mut edge_cost: F) -> Option<Vec<G::EdgeId>>
+        where G: IntoEdges<NodeId=N> + IntoNodeIdentifiers,
+              G::EdgeId: Ord,
+              F: FnMut(G::EdgeRef) -> K,
+              K: Measure + Copy,
+    {
+        if !self.is_valid(g) {
+            return None;
+        }
+        let edges = self.nodes.windows(2).map(|w| {
+            cheapest_edge(g, w[0], w[1], &mut edge_cost).unwrap().1
+        }).collect();
+        Some(edges)
+    }
+
+    /// Return the total `edge_cost` of the path in `g`, using the cheapest
+    /// of parallel edges, or `None` if it is not a walk in `g`.
+    pub fn cost<G, F, K>(&self, g: G, mut edge_cost: F) -> Option<K>
+        where G: IntoEdges<NodeId=N> + IntoNodeIdentifiers,
+              G::EdgeId: Ord,
+              F: FnMut(G::EdgeRef) -> K,
+              K: Measure + Copy,
+    {
+        if !self.is_valid(g) {
+            return None;
+        }
+        let mut total = K::default();
+        for w in self.nodes.windows(2) {
+            total = total + cheapest_edge(g, w[0], w[1], &mut edge_cost).unwrap().0;
+        }
+        Some(total)
+    }
+
+    /// Return the nodes of the path.
+    pub fn into_nodes(self) -> Vec<N> {
+        self.nodes
+    }
+}
+
+/// Return the cost and id of the cheapest edge from `a` to `b`, the lowest
+/// id in case of a tie.
+fn cheapest_edge<G, F, K>(g: G, a: G::NodeId, b: G::NodeId, edge_cost: &mut F)
+    -> Option<(K, G::EdgeId)>
+    where G: IntoEdges,
+          G::EdgeId: Ord,
+          F: FnMut(G::EdgeRef) -> K,
+          K: Measure + Copy,
+{
+    let mut cheapest: Option<(K, G::EdgeId)> = None;
+    for edge in g.edges(a).filter(|e| e.target() == b) {
+        let cost = edge_cost(edge);
+        match cheapest {
+            Some((c, id)) if c < cost || (!(cost < c) && id < edge.id()) => {}
+            _ => cheapest = Some((cost, edge.id())),
+        }
+    }
+    cheapest
+}
+
+impl<N> From<Vec<N>> for Path<N> {
+    fn from(nodes: Vec<N>) -> Self {
+        Path { nodes: nodes }
+    }
+}
+
+impl<N> From<Path<N>> for Vec<N> {
+    fn from(path: Path<N>) -> Self {
+        path.nodes
+    }
+}
+
+impl<'a, N> IntoIterator for &'a Path<N> {
+    type Item = &'a N;
+    type IntoIter = slice::Iter<'a, N>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.nodes.iter()
+    }
+}
@@ -22,7 +22,8 @@ use graphmap::{
 /// and a probability for each possible edge to exist.
 ///
 /// The result will be simple graph or digraph, self loops
-/// possible, no parallel edges.
+/// possible, no parallel edges. The edge probability is biased towards
+/// sparse graphs, so isolated nodes are common.
 impl<N, E, Ty, Ix> Arbitrary for Graph<N, E, Ty, Ix>
     where N: Arbitrary,
           E: Arbitrary,
@@ -123,11 +124,13 @@ impl<N, E, Ty, Ix> Arbitrary for StableGraph<N, E, Ty, Ix>
 /// `Arbitrary` for `GraphMap` creates a graph by selecting a node count
 /// and a probability for each possible edge to exist.
 ///
-/// The result will be simple graph or digraph, self loops
-/// possible, no parallel edges.
+/// The nodes are distinct arbitrary values of `N`, so the node count is
+/// bounded by the generator's size. The result will be simple graph or
+/// digraph, self loops possible, no parallel edges. The edge probability is
+/// biased towards sparse graphs, so isolated nodes are common.
 impl<N, E, Ty> Arbitrary for GraphMap<N, E, Ty>
     where N: NodeTrait + Arbitrary,
-          E: Arbitrary,
+          E: Arbitrary + Clone,
           Ty: EdgeType + Clone + Send + 'static,
 {
     fn arbitrary<G: Gen>(g: &mut G) -> Self {
@@ -157,4 +160,29 @@ impl<N, E, Ty> Arbitrary for GraphMap<N, E, Ty>
         }
         gr
     }
+    // shrink the graph by splitting its nodes in two, every other node
+    // in node order
+    fn shrink(&self) -> Box<Iterator<Item=Self>> {
+        let self_ = self.clone();
+        Box::new((0..2).filter_map(move |x| {
+            let keep = self_.nodes().enumerate()
+                                    .filter(|&(i, _)| i % 2 == x)
+                                    .map(|(_, n)| n);
+            let mut gr = GraphMap::with_capacity(self_.node_count() / 2 + 1, 0);
+            for n in keep {
+                gr.add_node(n);
+            }
+            for (a, b, w) in self_.all_edges() {
+                if gr.contains_node(a) && gr.contains_node(b) {
+                    gr.add_edge(a, b, w.clone());
+                }
+            }
+            // make sure we shrink
+            if gr.node_count() < self_.node_count() {
+                Some(gr)
+            } else {
+                None
+            }
+        }))
+    }
 }
@@ -39,6 +39,7 @@ use IntoWeightedEdge;
 use visit::{
     EdgeRef,
     IntoEdges,
+    IntoEdgesDirected,
     IntoEdgeReferences,
     NodeIndexable,
 };
@@ -761,6 +762,16 @@ impl<'a, N, E, Ty, Ix> IntoEdges for &'a StableGraph<N, E, Ty, Ix>
     }
 }
 
+impl<'a, N, E, Ty, Ix> IntoEdgesDirected for &'a StableGraph<N, E, Ty, Ix>
+    where Ty: EdgeType,
+          Ix: IndexType,
+{
+    type EdgesDirected = Edges<'a, E, Ty, Ix>;
+    fn edges_directed(self, a: Self::NodeId, dir: Direction) -> Self::EdgesDirected {
+        self.edges_directed(a, dir)
+    }
+}
+
 
 
 /// Iterator over the edges of from or to a node
@@ -0,0 +1,194 @@
+//! Summary statistics and histograms of weights, for example of the edge
+//! weights of a graph (see `Graph::edge_weight_stats`).
+//!
+//! Weights are `f64` values; NaN values are counted but otherwise ignored.
+
+use std::cmp::Ordering;
+
+/// Summary statistics of a sequence of weights.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct WeightStats {
+    /// The number of weights, not counting NaN.
+    pub count: usize,
+    /// The number of NaN weights, which are left out of the other fields.
+    pub nan_count: usize,
+    /// The smallest weight.
+    pub min: f64,
+    /// The largest weight.
+    pub max: f64,
+    /// The arithmetic mean.
+    pub mean: f64,
+    /// The median (the 50th percentile).
+    pub median: f64,
+    /// The 95th percentile.
+    pub p95: f64,
+}
+
+impl WeightStats {
+    /// Compute the statistics of `weights`.
+    ///
+    /// Percentiles are interpolated linearly between the two nearest
+    /// weights; the median of an even number of weights is the mean of the
+    /// middle two.
+    ///
+    /// Return `None` if there are no weights other than NaN.
+    ///
+    /// Uses one pass over `weights` and a sorted buffer of the weights.
+    ///
+    /// Computes in **O(n log n)** time and **O(n)** space.
+    pub fn from_weights<I>(weights: I) -> Option<Self>
+        where I: IntoIterator<Item=f64>,
+    {
+        let weights = weights.into_iter();
+        let mut sorted = Vec::with_capacity(weights.size_hint().0);
+        let mut nan_count = 0;
+        let mut sum = 0.;
+        for w in weights {
+            if w.is_nan() {
+                nan_count += 1;
+            } else {
+                sum += w;
+                sorted.push(w);
+            }
+        }
+        if sorted.is_empty() {
+            return None;
+        }
+        sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        Some(WeightStats {
+            count: sorted.len(),
+            nan_count: nan_count,
+            min: sorted[0],
+            max: sorted[sorted.len() - 1],
+            mean: sum / sorted.len() as f64,
+            median: percentile(&sorted, 50.),
+            p95: percentile(&sorted, 95.),
+        })
+    }
+}
+
+/// Return the `p`th percentile of the non-empty, sorted `sorted`.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100. * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let fraction = rank - lower as f64;
+    if fraction == 0. {
+        sorted[lower]
+    } else {
+        sorted[lower] + fraction * (sorted[lower + 1] - sorted[lower])
+    }
+}
+
+/// A histogram of a sequence of weights, with bins of equal width from the
+/// smallest to the largest weight.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Histogram {
+    /// The smallest weight, the start of the first bin.
+    pub min: f64,
+    /// The largest weight, the end of the last bin.
+    pub max: f64,
+    /// The number of weights in each bin.
+    pub counts: Vec<usize>,
+}
+
+impl Histogram {
+    /// Compute a histogram of `weights` with `bins` bins.
+    ///
+    /// Each bin includes its start, and the last bin also its end. If all
+    /// weights are equal, they are all in the first bin. NaN weights are
+    /// left out.
+    ///
+    /// Return `None` if there are no weights other than NaN.
+    ///
+    /// Uses two passes over `weights` and no buffer besides the counts.
+    ///
+    /// **Panics** if `bins` is zero.
+    pub fn from_weights<I>(weights: I, bins: usize) -> Option<Self>
+        where I: IntoIterator<Item=f64>,
+              I::IntoIter: Clone,
+    {
+        assert!(bins > 0, "Histogram::from_weights: bins must be positive");
+        let weights = weights.into_iter().filter(|w| !w.is_nan());
+        let mut range = None;
+        for w in weights.clone() {
+            range = match range {
+                None => Some((w, w)),
+                Some((min, max)) => Some((if w < min { w } else { min },
+                                          if w > max { w } else { max })),
+            };
+        }
+        let (min, max) = match range {
+            None => return None,
+            Some(range) => range,
+        };
+        let mut histogram = Histogram {
+            min: min,
+            max: max,
+            counts: vec![0; bins],
+        };
+        for w in weights {
+            let bin = histogram.bin(w).unwrap();
+            histogram.counts[bin] += 1;
+        }
+        Some(histogram)
+    }
+
+    /// Return the width of each bin.
+    pub fn bin_width(&self) -> f64 {
+        (self.max - self.min) / self.counts.len() as f64
+    }
+
+    /// Return the index of the bin that `weight` belongs in, or `None` if it
+    /// is outside the range of the histogram or NaN.
+    pub fn bin(&self, weight: f64) -> Option<usize> {
+        if !(weight >= self.min && weight <= self.max) {
+            return None;
+        }
+        let width = self.bin_width();
+        if width == 0. {
+            return Some(0);
+        }
+        let bin = ((weight - self.min) / width) as usize;
+        if bin >= self.counts.len() {
+            Some(self.counts.len() - 1)
+        } else {
+            Some(bin)
+        }
+    }
+
+    /// Return an iterator of the start of each bin with its count.
+    pub fn iter(&self) -> HistogramIter {
+        HistogramIter { histogram: self, index: 0 }
+    }
+}
+
+/// An iterator of the bins of a `Histogram`, created by `Histogram::iter`.
+///
+/// Iterator element type is `(f64, usize)`: the start of the bin and its
+/// count.
+#[derive(Clone, Debug)]
+pub struct HistogramIter<'a> {
+    histogram: &'a Histogram,
+    index: usize,
+}
+
+impl<'a> Iterator for HistogramIter<'a> {
+    type Item = (f64, usize);
+
+    fn next(&mut self) -> Option<(f64, usize)> {
+        let h = self.histogram;
+        match h.counts.get(self.index) {
+            None => None,
+            Some(&count) => {
+                let start = h.min + self.index as f64 * h.bin_width();
+                self.index += 1;
+                Some((start, count))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.histogram.counts.len() - self.index;
+        (len, Some(len))
+    }
+}
@@ -18,6 +18,11 @@ use super::visit::GetAdjacencyMatrix;
 
 /// The adjacency matrix for **Graph** is a bitmap that's computed by
 /// `.adjacency_matrix()`.
+///
+/// The bitmap is a row-major *n* × *n* matrix, where *n* is the node count:
+/// bit *i · n + j* is set if there is an edge from node *i* to node *j*.
+/// It is symmetric for an undirected graph, and a self loop sets the
+/// diagonal bit *(i, i)*.
 impl<N, E, Ty, Ix> GetAdjacencyMatrix for Graph<N, E, Ty, Ix> where
     Ty: EdgeType,
     Ix: IndexType,
@@ -5,6 +5,10 @@ use super::graph::IndexType;
 /// `UnionFind<K>` is a disjoint-set data structure. It tracks set membership of *n* elements
 /// indexed from *0* to *n - 1*. The scalar type is `K` which must be an unsigned integer type.
 ///
+/// The elements are given up front with `new(n)`, or added one at a time with `insert()`.
+/// The structure doesn't grow by itself: all methods that take elements panic on elements
+/// that were not created yet.
+///
 /// http://en.wikipedia.org/wiki/Disjoint-set_data_structure
 ///
 /// Too awesome not to quote:
@@ -18,12 +22,12 @@ pub struct UnionFind<K>
     // stores its own index. This forms equivalence classes which are the disjoint sets, each
     // with a unique representative.
     parent: Vec<K>,
-    // It is a balancing tree structure,
-    // so the ranks are logarithmic in the size of the container -- a byte is more than enough.
-    //
-    // Rank is separated out both to save space and to save cache in when searching in the parent
-    // vector.
-    rank: Vec<u8>,
+    // For a representative at index *i*, store the size of its set minus one, so that the
+    // size of a set of all *n* elements fits in `K`. Unions put the smaller set below the
+    // larger, which keeps the trees shallow.
+    size: Vec<K>,
+    // The number of disjoint sets.
+    sets: usize,
 }
 
 #[inline]
@@ -33,16 +37,87 @@ unsafe fn get_unchecked<K>(xs: &[K], index: usize) -> &K
     xs.get_unchecked(index)
 }
 
+impl<K> Default for UnionFind<K>
+    where K: IndexType
+{
+    fn default() -> Self
+    {
+        UnionFind::new_empty()
+    }
+}
+
 impl<K> UnionFind<K>
     where K: IndexType
 {
     /// Create a new `UnionFind` of `n` disjoint sets.
     pub fn new(n: usize) -> Self
     {
-        let rank = vec![0; n];
+        let size = vec![K::new(0); n];
         let parent = (0..n).map(K::new).collect::<Vec<K>>();
 
-        UnionFind{parent: parent, rank: rank}
+        UnionFind{parent: parent, size: size, sets: n}
+    }
+
+    /// Create a new, empty `UnionFind`.
+    ///
+    /// Use `.insert()` to add elements.
+    pub fn new_empty() -> Self
+    {
+        UnionFind{parent: Vec::new(), size: Vec::new(), sets: 0}
+    }
+
+    /// Return the number of elements.
+    pub fn len(&self) -> usize
+    {
+        self.parent.len()
+    }
+
+    /// Return `true` if there are no elements.
+    pub fn is_empty(&self) -> bool
+    {
+        self.parent.is_empty()
+    }
+
+    /// Reset to `n` disjoint sets, reusing the current allocation.
+    pub fn reset(&mut self, n: usize)
+    {
+        self.parent.clear();
+        self.parent.extend((0..n).map(K::new));
+        self.size.clear();
+        self.size.resize(n, K::new(0));
+        self.sets = n;
+    }
+
+    /// Reserve capacity for at least `additional` more elements.
+    pub fn reserve(&mut self, additional: usize)
+    {
+        self.parent.reserve(additional);
+        self.size.reserve(additional);
+    }
+
+    /// Add a new element in a set of its own, and return it.
+    ///
+    /// The new element is `K::new(self.len())`; elements are numbered in
+    /// order of insertion, after those created by `new`.
+    ///
+    /// **Panics** if the new element is out of range for `K`.
+    pub fn insert(&mut self) -> K
+    {
+        let x = self.parent.len();
+        assert!(x <= <K as IndexType>::max().index(),
+                "UnionFind::insert: element {} is out of range for the index type", x);
+        let k = K::new(x);
+        self.parent.push(k);
+        self.size.push(K::new(0));
+        self.sets += 1;
+        k
+    }
+
+    fn assert_in_bounds(&self, x: K)
+    {
+        assert!(x.index() < self.parent.len(),
+                "UnionFind: element {} is out of bounds for {} elements",
+                x.index(), self.parent.len());
     }
 
     /// Return the representative for `x`.
@@ -50,7 +125,7 @@ impl<K> UnionFind<K>
     /// **Panics** if `x` is out of bounds.
     pub fn find(&self, x: K) -> K
     {
-        assert!(x.index() < self.parent.len());
+        self.assert_in_bounds(x);
         unsafe {
             let mut x = x;
             loop {
@@ -73,7 +148,7 @@ impl<K> UnionFind<K>
     /// **Panics** if `x` is out of bounds.
     pub fn find_mut(&mut self, x: K) -> K
     {
-        assert!(x.index() < self.parent.len());
+        self.assert_in_bounds(x);
         unsafe {
             self.find_mut_recursive(x)
         }
@@ -99,37 +174,84 @@ impl<K> UnionFind<K>
     /// 
     /// **Panics** if `x` or `y` is out of bounds.
     pub fn union(&mut self, x: K, y: K) -> bool
+    {
+        self.union_rep(x, y).is_some()
+    }
+
+    /// Unify the two sets containing `x` and `y`, and return the
+    /// representative of the unified set.
+    ///
+    /// Return `None` if the sets were already the same. The representative
+    /// is always one of the two former representatives, which is useful for
+    /// merging data kept per set.
+    ///
+    /// **Panics** if `x` or `y` is out of bounds.
+    ///
+    /// ```
+    /// use petgraph::unionfind::UnionFind;
+    ///
+    /// let mut sets = UnionFind::<u32>::new(3);
+    /// let mut sums = vec![1, 2, 4];
+    /// for &(a, b) in &[(0, 1), (1, 0), (2, 1)] {
+    ///     let (ra, rb) = (sets.find_mut(a), sets.find_mut(b));
+    ///     if let Some(rep) = sets.union_rep(a, b) {
+    ///         let other = if rep == ra { rb } else { ra };
+    ///         sums[rep as usize] += sums[other as usize];
+    ///     }
+    /// }
+    /// assert_eq!(sums[sets.find(0) as usize], 7);
+    /// ```
+    pub fn union_rep(&mut self, x: K, y: K) -> Option<K>
     {
         if x == y {
-            return false
+            return None
         }
         let xrep = self.find_mut(x);
         let yrep = self.find_mut(y);
 
         if xrep == yrep {
-            return false
+            return None
         }
 
         let xrepu = xrep.index();
         let yrepu = yrep.index();
-        let xrank = self.rank[xrepu];
-        let yrank = self.rank[yrepu];
+        let xsize = self.size[xrepu];
+        let ysize = self.size[yrepu];
+        // the sizes minus one add up to the new size minus two
+        let size = K::new(xsize.index() + ysize.index() + 1);
+        self.sets -= 1;
 
-        // The rank corresponds roughly to the depth of the treeset, so put the 
-        // smaller set below the larger
-        if xrank < yrank {
+        // Put the smaller set below the larger
+        if xsize < ysize {
             self.parent[xrepu] = yrep;
-        } else if xrank > yrank {
-            self.parent[yrepu] = xrep;
+            self.size[yrepu] = size;
+            Some(yrep)
         } else {
             // put y below x when equal.
             self.parent[yrepu] = xrep;
-            self.rank[xrepu] += 1;
+            self.size[xrepu] = size;
+            Some(xrep)
         }
-        true
+    }
+
+    /// Return the number of disjoint sets.
+    pub fn len_sets(&self) -> usize
+    {
+        self.sets
+    }
+
+    /// Return the number of elements in the set containing `x`.
+    ///
+    /// **Panics** if `x` is out of bounds.
+    pub fn set_size(&self, x: K) -> usize
+    {
+        self.size[self.find(x).index()].index() + 1
     }
 
     /// Return a vector mapping each element to its representative.
+    ///
+    /// Elements of the same set have the same representative, so the vector
+    /// has `.len_sets()` distinct values.
     pub fn into_labeling(mut self) -> Vec<K>
     {
         // write in the labeling of each element
@@ -1,4 +1,5 @@
 
+use std::fmt;
 use std::iter::Zip;
 
 /// intoiterator .zip()
@@ -9,3 +10,21 @@ pub fn zip<I, J>(i: I, j: J) -> Zip<I::IntoIter, J::IntoIter>
     i.into_iter().zip(j)
 }
 
+/// Escape text for use in XML content and attribute values.
+pub struct XmlEscaped<'a>(pub &'a str);
+
+impl<'a> fmt::Display for XmlEscaped<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => try!(f.write_str("&amp;")),
+                '<' => try!(f.write_str("&lt;")),
+                '>' => try!(f.write_str("&gt;")),
+                '"' => try!(f.write_str("&quot;")),
+                '\'' => try!(f.write_str("&apos;")),
+                c => try!(write!(f, "{}", c)),
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,172 @@
+use std::collections::VecDeque;
+
+use visit::{IntoNeighbors, NodeIndexable};
+use visit::{VisitMap, Visitable};
+use super::{ControlFlow, Time};
+
+/// A breadth first search (BFS) visitor event.
+#[derive(Copy, Clone, Debug)]
+pub enum BfsEvent<N> {
+    /// A node is discovered, and put in the queue.
+    Discover(N, Time),
+    /// An edge of the tree formed by the traversal.
+    TreeEdge(N, N),
+    /// An edge to an already discovered node.
+    NonTreeEdge(N, N),
+    /// All edges of a node have been examined.
+    Finish(N, Time),
+}
+
+/// A breadth first search, with events for each node and edge.
+///
+/// Starting points are the nodes in the iterator `starts`; all of them are
+/// discovered first, and then searched together, in order of their
+/// distance from the closest start.
+///
+/// `visitor` is called for each event, see [`BfsEvent`][be] for possible
+/// values. Like for [`depth_first_search`][dfs], the visitor may return `()`
+/// to run the search until it is finished, or a `Control<B>`:
+///
+/// - `Control::Break(b)` stops the search and is returned by the function.
+/// - `Control::Prune` in response to `BfsEvent::Discover(u, _)` skips the
+///   edges of *u*, and in response to `BfsEvent::TreeEdge(u, v)` doesn't
+///   follow the edge to *v*, which may still be discovered through another
+///   edge. For other events it is the same as `Continue`.
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// [be]: enum.BfsEvent.html
+/// [dfs]: fn.depth_first_search.html
+///
+/// # Example
+///
+/// Find the nodes that need rebuilding after a change to node 0, without
+/// searching past nodes that are marked as already built.
+///
+/// ```
+/// use petgraph::prelude::*;
+/// use petgraph::graph::node_index as n;
+/// use petgraph::visit::{breadth_first_search, BfsEvent, Control};
+///
+/// // node weight: already built
+/// let mut gr = Graph::<bool, ()>::new();
+/// let a = gr.add_node(false);
+/// let b = gr.add_node(true);
+/// let c = gr.add_node(false);
+/// let d = gr.add_node(false);
+/// gr.extend_with_edges(&[(a, b), (a, c), (b, d)]);
+///
+/// let mut rebuild = Vec::new();
+/// breadth_first_search(&gr, Some(a), |event| {
+///     if let BfsEvent::Discover(u, _) = event {
+///         if gr[u] {
+///             return Control::<()>::Prune;
+///         }
+///         rebuild.push(u);
+///     }
+///     Control::Continue
+/// });
+/// rebuild.sort();
+/// assert_eq!(rebuild, vec![n(0), n(2)]);
+/// ```
+pub fn breadth_first_search<G, I, F, C>(graph: G, starts: I, mut visitor: F) -> C
+    where G: IntoNeighbors + Visitable,
+          I: IntoIterator<Item=G::NodeId>,
+          F: FnMut(BfsEvent<G::NodeId>) -> C,
+          C: ControlFlow,
+{
+    let mut time = Time(0);
+    let mut discovered = graph.visit_map();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !graph.includes_node(start) {
+            continue;
+        }
+        try_control!(discover(start, &mut visitor, &mut discovered, &mut time, &mut queue));
+    }
+    while let Some(u) = queue.pop_front() {
+        for v in graph.neighbors(u) {
+            if !discovered.is_visited(&v) {
+                try_control!(visitor(BfsEvent::TreeEdge(u, v)), continue);
+                try_control!(discover(v, &mut visitor, &mut discovered, &mut time, &mut queue));
+            } else {
+                try_control!(visitor(BfsEvent::NonTreeEdge(u, v)));
+            }
+        }
+        try_control!(visitor(BfsEvent::Finish(u, time_post_inc(&mut time))));
+    }
+    C::continuing()
+}
+
+/// Discover `u` if it is new, and put it in the queue, or finish it right
+/// away if the visitor prunes it.
+fn discover<N, M, F, C>(u: N, visitor: &mut F, discovered: &mut M, time: &mut Time,
+                        queue: &mut VecDeque<N>) -> C
+    where N: Copy,
+          M: VisitMap<N>,
+          F: FnMut(BfsEvent<N>) -> C,
+          C: ControlFlow,
+{
+    if !discovered.visit(u) {
+        return C::continuing();
+    }
+    try_control!(visitor(BfsEvent::Discover(u, time_post_inc(time))), {
+        try_control!(visitor(BfsEvent::Finish(u, time_post_inc(time))));
+        return C::continuing();
+    });
+    queue.push_back(u);
+    C::continuing()
+}
+
+fn time_post_inc(x: &mut Time) -> Time {
+    let v = *x;
+    x.0 += 1;
+    v
+}
+
+/// Run one breadth first search from all of `sources` and label each node
+/// with the source that reaches it first.
+///
+/// The result is indexed by node index (`NodeIndexable::to_index`) and holds
+/// `Some((i, d))` for a node whose closest source is `sources[i]`, at a
+/// distance of `d` edges, and `None` for a node no source reaches. When
+/// several sources are equally close, the one that comes first in `sources`
+/// wins. The labels split the reachable part of the graph into regions, one
+/// per source.
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::graph::node_index as n;
+/// use petgraph::visit::multi_source_bfs;
+///
+/// // a path 0 - 1 - 2 - 3 - 4 and a lone node 5
+/// let mut gr = Graph::<(), (), petgraph::Undirected>::new_undirected();
+/// gr.extend_with_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+/// gr.add_node(());
+///
+/// let labels = multi_source_bfs(&gr, vec![n(0), n(4)]);
+/// assert_eq!(labels, vec![Some((0, 0)), Some((0, 1)), Some((0, 2)),
+///                         Some((1, 1)), Some((1, 0)), None]);
+/// ```
+pub fn multi_source_bfs<G, I>(graph: G, sources: I) -> Vec<Option<(usize, u32)>>
+    where G: IntoNeighbors + NodeIndexable + Visitable,
+          I: IntoIterator<Item=G::NodeId>,
+{
+    let sources = sources.into_iter().collect::<Vec<_>>();
+    let mut labels = vec![None; graph.node_bound()];
+    for (i, &s) in sources.iter().enumerate() {
+        let label = &mut labels[graph.to_index(s)];
+        if label.is_none() {
+            *label = Some((i, 0));
+        }
+    }
+    breadth_first_search(graph, sources, |event| {
+        if let BfsEvent::TreeEdge(u, v) = event {
+            labels[graph.to_index(v)] = labels[graph.to_index(u)].map(|(i, d)| (i, d + 1));
+        }
+    });
+    labels
+}
@@ -1,6 +1,6 @@
 
 
-use visit::IntoNeighbors;
+use visit::{IntoNeighbors, IntoNodeIdentifiers, NodeIndexable};
 use visit::{VisitMap, Visitable};
 
 /// Strictly monotonically increasing event time for a depth first search.
@@ -23,7 +23,8 @@ pub enum DfsEvent<N> {
     Finish(N, Time),
 }
 
-/// Return if the expression is a break value.
+/// Return if the expression is a break value, execute the provided statement
+/// if it is a prune value.
 macro_rules! try_control {
     ($e:expr) => {
         match $e {
@@ -31,7 +32,16 @@ macro_rules! try_control {
                 return x;
             }
         }
-    }
+    };
+    ($e:expr, $p:expr) => {
+        match $e {
+            x => if x.should_break() {
+                return x;
+            } else if x.should_prune() {
+                $p;
+            }
+        }
+    };
 }
 
 /// Control flow for callbacks.
@@ -39,8 +49,15 @@ macro_rules! try_control {
 /// `Break` can carry a value.
 #[derive(Copy, Clone, Debug)]
 pub enum Control<B> {
+    /// Continue the search.
     Continue,
+    /// Stop the search, with a value.
     Break(B),
+    /// Don't search past the current node: in response to
+    /// `DfsEvent::Discover(u, _)`, skip the edges of *u*, and in response to
+    /// `DfsEvent::TreeEdge(u, v)`, don't follow the edge to *v*. For other
+    /// events it is the same as `Continue`.
+    Prune,
 }
 
 impl<B> Control<B> {
@@ -48,7 +65,7 @@ impl<B> Control<B> {
     /// Get the value in `Control::Break(_)`, if present.
     pub fn break_value(self) -> Option<B> {
         match self {
-            Control::Continue => None,
+            Control::Continue | Control::Prune => None,
             Control::Break(b) => Some(b),
         }
     }
@@ -60,6 +77,9 @@ impl<B> Control<B> {
 pub trait ControlFlow {
     fn continuing() -> Self;
     fn should_break(&self) -> bool;
+    /// Return `true` if the search should skip past the current node; the
+    /// default is `false`.
+    fn should_prune(&self) -> bool { false }
 }
 
 impl ControlFlow for () {
@@ -73,6 +93,9 @@ impl<B> ControlFlow for Control<B> {
     fn should_break(&self) -> bool {
         if let Control::Break(_) = *self { true } else { false }
     }
+    fn should_prune(&self) -> bool {
+        if let Control::Prune = *self { true } else { false }
+    }
 }
 
 impl<E> ControlFlow for Result<(), E> {
@@ -87,7 +110,7 @@ impl<B> Default for Control<B> {
     fn default() -> Self { Control::Continue }
 }
 
-/// A recursive depth first search.
+/// A depth first search, with events for each node and edge.
 ///
 /// Starting points are the nodes in the iterator `starts` (specify just one
 /// start vertex *x* by using `Some(x)`).
@@ -99,7 +122,10 @@ impl<B> Default for Control<B> {
 /// If the return value of the visitor is simply `()`, the visit runs until it
 /// is finished. If the return value is a `Control<B>`, it can be used to
 /// break the visit early, and the last control value is returned by the
-/// function.
+/// function, or to prune the search at the current node with
+/// `Control::Prune`.
+///
+/// The search is not recursive, so it can handle arbitrarily deep graphs.
 ///
 /// [de]: enum.DfsEvent.html
 ///
@@ -154,16 +180,98 @@ pub fn depth_first_search<G, I, F, C>(graph: G, starts: I, mut visitor: F) -> C
     let time = &mut Time(0);
     let discovered = &mut graph.visit_map();
     let finished = &mut graph.visit_map();
+    let stack = &mut Vec::new();
 
     for start in starts {
-        try_control!(dfs_visitor(graph, start, &mut visitor, discovered, finished, time));
+        if !graph.includes_node(start) {
+            continue;
+        }
+        try_control!(dfs_visitor(graph, start, &mut visitor, discovered, finished, time, stack));
     }
     C::continuing()
 }
 
-fn dfs_visitor<G, F, C>(graph: G, u: G::NodeId, visitor: &mut F,
+/// Return the discovery and finish times of each node in a depth first
+/// search.
+///
+/// The search starts from each node in `starts` in turn, and then from the
+/// remaining nodes that are not yet visited, so every node gets a time.
+/// Discovery and finish events share one clock, as in the events of
+/// [`depth_first_search`](fn.depth_first_search.html), so the times are
+/// distinct and go from `0` to `2 |V| - 1`. Node *v* is a descendant of *u*
+/// in the search forest if and only if its interval of discovery and finish
+/// time is nested in the interval of *u*.
+///
+/// The vectors are indexed by node index (`NodeIndexable::to_index`).
+/// Indices that don't correspond to a node have the time `usize::MAX`.
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::graph::node_index as n;
+/// use petgraph::visit::dfs_timestamps;
+///
+/// let gr = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (3, 1)]);
+/// let (discover, finish) = dfs_timestamps(&gr, Some(n(0)));
+/// assert_eq!(discover, vec![0, 1, 2, 6]);
+/// assert_eq!(finish, vec![5, 4, 3, 7]);
+/// ```
+pub fn dfs_timestamps<G, I>(graph: G, starts: I) -> (Vec<usize>, Vec<usize>)
+    where G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + Visitable,
+          I: IntoIterator<Item=G::NodeId>,
+{
+    let mut discover = vec![!0; graph.node_bound()];
+    let mut finish = vec![!0; graph.node_bound()];
+    depth_first_search(graph, starts.into_iter().chain(graph.node_identifiers()), |event| {
+        match event {
+            DfsEvent::Discover(u, Time(t)) => discover[graph.to_index(u)] = t,
+            DfsEvent::Finish(u, Time(t)) => finish[graph.to_index(u)] = t,
+            _ => {}
+        }
+    });
+    (discover, finish)
+}
+
+/// Search from `start`, with a stack of the nodes being visited and their
+/// remaining neighbors.
+fn dfs_visitor<G, F, C>(graph: G, start: G::NodeId, visitor: &mut F,
+                        discovered: &mut G::Map, finished: &mut G::Map,
+                        time: &mut Time, stack: &mut Vec<(G::NodeId, G::Neighbors)>) -> C
+    where G: IntoNeighbors + Visitable,
+          F: FnMut(DfsEvent<G::NodeId>) -> C,
+          C: ControlFlow,
+{
+    try_control!(discover(graph, start, visitor, discovered, finished, time, stack));
+    loop {
+        let (u, next) = match stack.last_mut() {
+            None => return C::continuing(),
+            Some(&mut (u, ref mut neighbors)) => (u, neighbors.next()),
+        };
+        match next {
+            Some(v) => if !discovered.is_visited(&v) {
+                try_control!(visitor(DfsEvent::TreeEdge(u, v)), continue);
+                try_control!(discover(graph, v, visitor, discovered, finished, time, stack));
+            } else if !finished.is_visited(&v) {
+                try_control!(visitor(DfsEvent::BackEdge(u, v)));
+            } else {
+                try_control!(visitor(DfsEvent::CrossForwardEdge(u, v)));
+            },
+            None => {
+                stack.pop();
+                let first_finish = finished.visit(u);
+                debug_assert!(first_finish);
+                try_control!(visitor(DfsEvent::Finish(u, time_post_inc(time))));
+            }
+        }
+    }
+}
+
+/// Discover `u` if it is new, and push it on the stack, or finish it right
+/// away if the visitor prunes it.
+fn discover<G, F, C>(graph: G, u: G::NodeId, visitor: &mut F,
                      discovered: &mut G::Map, finished: &mut G::Map,
-                     time: &mut Time) -> C
+                     time: &mut Time, stack: &mut Vec<(G::NodeId, G::Neighbors)>) -> C
     where G: IntoNeighbors + Visitable,
           F: FnMut(DfsEvent<G::NodeId>) -> C,
           C: ControlFlow,
@@ -171,20 +279,12 @@ fn dfs_visitor<G, F, C>(graph: G, u: G::NodeId, visitor: &mut F,
     if !discovered.visit(u) {
         return C::continuing();
     }
-    try_control!(visitor(DfsEvent::Discover(u, time_post_inc(time))));
-    for v in graph.neighbors(u) {
-        if !discovered.is_visited(&v) {
-            try_control!(visitor(DfsEvent::TreeEdge(u, v)));
-            try_control!(dfs_visitor(graph, v, visitor, discovered, finished, time));
-        } else if !finished.is_visited(&v) {
-            try_control!(visitor(DfsEvent::BackEdge(u, v)));
-        } else {
-            try_control!(visitor(DfsEvent::CrossForwardEdge(u, v)));
-        }
-    }
-    let first_finish = finished.visit(u);
-    debug_assert!(first_finish);
-    try_control!(visitor(DfsEvent::Finish(u, time_post_inc(time))));
+    try_control!(visitor(DfsEvent::Discover(u, time_post_inc(time))), {
+        finished.visit(u);
+        try_control!(visitor(DfsEvent::Finish(u, time_post_inc(time))));
+        return C::continuing();
+    });
+    stack.push((u, graph.neighbors(u)));
     C::continuing()
 }
 
@@ -56,6 +56,34 @@ impl<N, S> FilterNode<N> for HashSet<N, S>
 }
 
 /// A node-filtering graph adaptor.
+///
+/// The adaptor hides the nodes that the filter excludes, together with all
+/// edges to or from them. The filter implements the trait `FilterNode`.
+/// Closures of type `Fn(G::NodeId) -> bool`, `FixedBitSet` and `HashSet`
+/// already implement this trait.
+///
+/// An excluded node has no neighbors or edges, and the traversals skip it
+/// when they reach it, so a traversal started at an excluded node emits
+/// nothing.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::visit::{Dfs, NodeFiltered, Walker};
+///
+/// let mut graph = Graph::<(), ()>::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// graph.extend_with_edges(&[(a, b), (b, c), (a, c)]);
+///
+/// // a search on the graph without b
+/// let without_b = NodeFiltered::from_fn(&graph, |n| n != b);
+/// let reached = Dfs::new(&without_b, a).iter(&without_b).collect::<Vec<_>>();
+/// assert_eq!(reached, vec![a, c]);
+///
+/// // b itself is not a valid start node
+/// assert_eq!(Dfs::new(&without_b, b).iter(&without_b).count(), 0);
+/// ```
 #[derive(Copy, Clone, Debug)]
 pub struct NodeFiltered<G, F>(pub G, pub F);
 
@@ -86,6 +114,9 @@ impl<'a, G, F> IntoNeighbors for &'a NodeFiltered<G, F>
             f: &self.1,
         }
     }
+    fn includes_node(self, n: G::NodeId) -> bool {
+        self.1.include_node(n) && self.0.includes_node(n)
+    }
 }
 
 /// A filtered neighbors iterator.
@@ -335,6 +366,9 @@ impl<'a, G, F> IntoNeighbors for &'a EdgeFiltered<G, F>
             f: &self.1,
         }
     }
+    fn includes_node(self, n: G::NodeId) -> bool {
+        self.0.includes_node(n)
+    }
 }
 
 /// A filtered neighbors iterator.
@@ -3,9 +3,10 @@
 /// implementations of it.
 ///
 /// Well almost: There *must* be markers of
-/// `@section type`, `@section self` and `@section self_ref`, `@section self_mut`,
-/// `@section ignore`, before the associated types, `self` methods
-/// and `&self` methods, `&mut self` methods and methods to skip in delegation
+/// `@section type`, `@section self`, `@section self_default`,
+/// `@section self_ref`, `@section self_mut`, `@section ignore`, before the
+/// associated types, `self` methods, `self` methods with a default body,
+/// `&self` methods, `&mut self` methods and methods to skip in delegation
 /// respectively.
 macro_rules! trait_template {
     ($(#[$doc:meta])* pub trait $name:ident $($methods:tt)*) => {
@@ -89,6 +90,14 @@ macro_rules! delegate_impl {
         )+
         )*
         $(
+        @section self_default
+        $(
+            $(#[$_attr5:meta])*
+            fn $fname_default:ident(self $(,$arg5:ident : $argty5:ty)*) -> $ret5:ty
+                { $($_body5:tt)* }
+        )+
+        )*
+        $(
         @section self_ref
         $(
             $(#[$_attr3:meta])*
@@ -124,6 +133,13 @@ macro_rules! delegate_impl {
             )*
             )*
             $(
+            $(
+                fn $fname_default(self $(,$arg5: $argty5)*) -> $ret5 {
+                    $self_map!(self).$fname_default($($arg5),*)
+                }
+            )*
+            )*
+            $(
             $(
                 fn $fname(&self $(,$arg: $argty)*) -> $ret {
                     $self_map!(self).$fname($($arg),*)
@@ -44,9 +44,11 @@ pub use self::reversed::*;
 
 #[macro_use] mod macros;
 
-mod dfsvisit;
+#[macro_use] mod dfsvisit;
+mod bfsvisit;
 mod traversal;
 pub use self::dfsvisit::*;
+pub use self::bfsvisit::*;
 pub use self::traversal::*;
 
 use fixedbitset::FixedBitSet;
@@ -119,9 +121,10 @@ impl<'a, N, E: 'a, Ty, Ix> IntoNeighbors for &'a StableGraph<N, E, Ty, Ix>
 
 
 #[cfg(feature = "graphmap")]
-impl<'a, N: 'a, E, Ty> IntoNeighbors for &'a GraphMap<N, E, Ty>
+impl<'a, N: 'a, E, Ty, S> IntoNeighbors for &'a GraphMap<N, E, Ty, S>
     where N: Copy + Ord + Hash,
           Ty: EdgeType,
+          S: BuildHasher,
 {
     type Neighbors = graphmap::Neighbors<'a, N, Ty>;
     fn neighbors(self, n: Self::NodeId) -> Self::Neighbors {
@@ -143,6 +146,15 @@ pub trait IntoNeighbors : GraphRef {
     @section self
     /// Return an iterator of the neighbors of node `a`.
     fn neighbors(self, a: Self::NodeId) -> Self::Neighbors;
+    @section self_default
+    /// Return `false` if node `a` is hidden by a graph view, like a node
+    /// excluded by `NodeFiltered`, so that traversals skip it.
+    ///
+    /// The default implementation returns `true`.
+    fn includes_node(self, a: Self::NodeId) -> bool {
+        let _ = a;
+        true
+    }
 }
 }
 
@@ -204,9 +216,10 @@ impl<'a, N, E: 'a, Ty, Ix> IntoNeighborsDirected for &'a StableGraph<N, E, Ty, I
 }
 
 #[cfg(feature = "graphmap")]
-impl<'a, N: 'a, E, Ty> IntoNeighborsDirected for &'a GraphMap<N, E, Ty>
+impl<'a, N: 'a, E, Ty, S> IntoNeighborsDirected for &'a GraphMap<N, E, Ty, S>
     where N: Copy + Ord + Hash,
           Ty: EdgeType,
+          S: BuildHasher,
 {
     type NeighborsDirected = graphmap::NeighborsDirected<'a, N, Ty>;
     fn neighbors_directed(self, n: N, dir: Direction)
@@ -239,6 +252,27 @@ pub trait IntoEdges : IntoEdgeReferences + IntoNeighbors {
 
 IntoEdges!{delegate_impl []}
 
+trait_template! {
+/// Access to all edges of each node, in the specified direction.
+///
+/// The edges are, depending on the direction and the graph’s edge type:
+///
+/// - `Directed`, `Outgoing`: All edges from `a`.
+/// - `Directed`, `Incoming`: All edges to `a`.
+/// - `Undirected`: All edges connected to `a`.
+///
+/// Edge references keep the orientation of the edge, so the edges to `a`
+/// have `a` as their target.
+pub trait IntoEdgesDirected : IntoEdges + IntoNeighborsDirected {
+    @section type
+    type EdgesDirected: Iterator<Item=Self::EdgeRef>;
+    @section self
+    fn edges_directed(self, a: Self::NodeId, dir: Direction) -> Self::EdgesDirected;
+}
+}
+
+IntoEdgesDirected!{delegate_impl []}
+
 trait_template! {
 /// Access to the sequence of the graph’s `NodeId`s.
 pub trait IntoNodeIdentifiers : GraphRef {
@@ -393,7 +427,7 @@ pub trait IntoEdgeReferences : Data + GraphRef {
 IntoEdgeReferences!{delegate_impl [] }
 
 #[cfg(feature = "graphmap")]
-impl<N, E, Ty> Data for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> Data for GraphMap<N, E, Ty, S>
     where N: Copy + PartialEq,
           Ty: EdgeType,
 {
@@ -433,9 +467,10 @@ impl<N, E, Ty, Ix> GraphProp for StableGraph<N, E, Ty, Ix>
 }
 
 #[cfg(feature = "graphmap")]
-impl<N, E, Ty> GraphProp for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> GraphProp for GraphMap<N, E, Ty, S>
     where N: NodeTrait,
           Ty: EdgeType,
+          S: BuildHasher,
 {
     type EdgeType = Ty;
 }
@@ -628,7 +663,7 @@ impl<N, E, Ty, Ix> Data for StableGraph<N, E, Ty, Ix>
 
 
 #[cfg(feature = "graphmap")]
-impl<N, E, Ty> GraphBase for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> GraphBase for GraphMap<N, E, Ty, S>
     where N: Copy + PartialEq,
 {
     type NodeId = N;
@@ -636,9 +671,10 @@ impl<N, E, Ty> GraphBase for GraphMap<N, E, Ty>
 }
 
 #[cfg(feature = "graphmap")]
-impl<N, E, Ty> Visitable for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> Visitable for GraphMap<N, E, Ty, S>
     where N: Copy + Ord + Hash,
           Ty: EdgeType,
+          S: BuildHasher,
 {
     type Map = HashSet<N>;
     fn visit_map(&self) -> HashSet<N> { HashSet::with_capacity(self.node_count()) }
@@ -671,9 +707,10 @@ GetAdjacencyMatrix!{delegate_impl []}
 
 #[cfg(feature = "graphmap")]
 /// The `GraphMap` keeps an adjacency matrix internally.
-impl<N, E, Ty> GetAdjacencyMatrix for GraphMap<N, E, Ty>
+impl<N, E, Ty, S> GetAdjacencyMatrix for GraphMap<N, E, Ty, S>
     where N: Copy + Ord + Hash,
           Ty: EdgeType,
+          S: BuildHasher,
 {
     type AdjMatrix = ();
     #[inline]
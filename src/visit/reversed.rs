@@ -12,6 +12,8 @@ use visit::{
     IntoNeighbors,
     IntoNeighborsDirected,
     IntoEdgeReferences,
+    IntoEdges,
+    IntoEdgesDirected,
     NodeCompactIndexable,
     NodeCount,
     NodeIndexable,
@@ -23,7 +25,31 @@ use visit::{
 
 /// An edge-reversing graph adaptor.
 ///
-/// All edges have the opposite direction with `Reversed`.
+/// All edges have the opposite direction with `Reversed`. The adaptor is
+/// meant for directed graphs; nothing is copied, outgoing edges are simply
+/// read as incoming and the other way around.
+///
+/// A search over `Reversed` follows edges backwards, for example a `Dfs`
+/// visits the ancestors of its start node, and `dijkstra` computes the
+/// distances *to* its start node.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::algo::dijkstra;
+/// use petgraph::visit::{Dfs, EdgeRef, Reversed, Walker};
+///
+/// let mut graph = Graph::<(), u32>::new();
+/// graph.extend_with_edges(&[(0, 1, 2), (1, 2, 3), (0, 2, 7), (2, 3, 1)]);
+/// let c = 2.into();
+///
+/// let ancestors = Dfs::new(Reversed(&graph), c).iter(Reversed(&graph)).count();
+/// assert_eq!(ancestors, 3);
+///
+/// // the distances to c
+/// let to_c = dijkstra(Reversed(&graph), c, None, |e| *e.weight());
+/// assert_eq!(to_c[&0.into()], 5);
+/// assert_eq!(to_c.get(&3.into()), None);
+/// ```
 #[derive(Copy, Clone, Debug)]
 pub struct Reversed<G>(pub G);
 
@@ -44,6 +70,9 @@ impl<G> IntoNeighbors for Reversed<G>
     {
         self.0.neighbors_directed(n, Incoming)
     }
+    fn includes_node(self, n: G::NodeId) -> bool {
+        self.0.includes_node(n)
+    }
 }
 
 impl<G> IntoNeighborsDirected for Reversed<G>
@@ -57,6 +86,43 @@ impl<G> IntoNeighborsDirected for Reversed<G>
     }
 }
 
+impl<G> IntoEdges for Reversed<G>
+    where G: IntoEdgesDirected
+{
+    type Edges = ReversedEdges<G::EdgesDirected>;
+    fn edges(self, a: Self::NodeId) -> Self::Edges {
+        ReversedEdges {
+            iter: self.0.edges_directed(a, Incoming),
+        }
+    }
+}
+
+impl<G> IntoEdgesDirected for Reversed<G>
+    where G: IntoEdgesDirected
+{
+    type EdgesDirected = ReversedEdges<G::EdgesDirected>;
+    fn edges_directed(self, a: Self::NodeId, dir: Direction) -> Self::EdgesDirected {
+        ReversedEdges {
+            iter: self.0.edges_directed(a, dir.opposite()),
+        }
+    }
+}
+
+/// A reversed edges iterator.
+pub struct ReversedEdges<I> {
+    iter: I,
+}
+
+impl<I> Iterator for ReversedEdges<I>
+    where I: Iterator,
+          I::Item: EdgeRef,
+{
+    type Item = ReversedEdgeReference<I::Item>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(ReversedEdgeReference)
+    }
+}
+
 impl<G: Visitable> Visitable for Reversed<G>
 {
     type Map = G::Map;
@@ -73,6 +139,13 @@ impl<G: Visitable> Visitable for Reversed<G>
 #[derive(Copy, Clone, Debug)]
 pub struct ReversedEdgeReference<R>(R);
 
+impl<R> ReversedEdgeReference<R> {
+    /// Return the original, unreversed edge reference.
+    pub fn into_unreversed(self) -> R {
+        self.0
+    }
+}
+
 /// An edge reference
 impl<R> EdgeRef for ReversedEdgeReference<R>
     where R: EdgeRef,
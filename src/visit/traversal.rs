@@ -1,8 +1,10 @@
 
 use {Incoming};
 use super::{IntoNeighbors, IntoNeighborsDirected, Visitable, VisitMap};
-use super::{GraphRef, Reversed, IntoNodeIdentifiers};
-use std::collections::VecDeque;
+use super::{GraphRef, Reversed, IntoNodeIdentifiers, NodeCount};
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::mem::replace;
 
 /// Visit nodes of a graph in a depth-first-search (DFS) emitting nodes in
 /// preorder (when they are first discovered).
@@ -32,13 +34,33 @@ use std::collections::VecDeque;
 /// assert_eq!(graph[a], 1);
 /// ```
 ///
+/// Marking nodes in `discovered` before the traversal makes it avoid them,
+/// as if they were removed from the graph:
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::visit::{Dfs, VisitMap};
+///
+/// let mut graph = Graph::<(), ()>::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// graph.extend_with_edges(&[(a, b), (b, c)]);
+///
+/// let mut dfs = Dfs::new(&graph, a);
+/// dfs.discovered.visit(b);
+/// assert_eq!(dfs.next(&graph), Some(a));
+/// assert_eq!(dfs.next(&graph), None);
+/// ```
+///
 /// **Note:** The algorithm may not behave correctly if nodes are removed
 /// during iteration. It may not necessarily visit added nodes or edges.
 #[derive(Clone, Debug)]
 pub struct Dfs<N, VM> {
     /// The stack of nodes to visit
     pub stack: Vec<N>,
-    /// The map of discovered nodes
+    /// The map of discovered nodes; nodes marked as visited before the
+    /// traversal reaches them are skipped
     pub discovered: VM,
 }
 
@@ -96,6 +118,9 @@ impl<N, VM> Dfs<N, VM>
         where G: IntoNeighbors<NodeId=N>,
     {
         while let Some(node) = self.stack.pop() {
+            if !graph.includes_node(node) {
+                continue;
+            }
             for succ in graph.neighbors(node) {
                 if self.discovered.visit(succ) {
                     self.stack.push(succ);
@@ -114,7 +139,38 @@ impl<N, VM> Dfs<N, VM>
 /// `DfsPostOrder` is not recursive.
 ///
 /// The traversal starts at a given node and only traverses nodes reachable
-/// from it.
+/// from it. Each node is emitted once, also if the graph has cycles. In a
+/// directed acyclic graph, the reversed postorder is a topological order.
+///
+/// Like `Dfs`, `DfsPostOrder` does not borrow the graph, so it can be used
+/// to compute values bottom-up while modifying the graph:
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::visit::DfsPostOrder;
+///
+/// // an expression graph for (1 + 2) * (1 + 2), each node with its value
+/// let mut graph = Graph::<_, ()>::new();
+/// let mul = graph.add_node(('*', 0));
+/// let add = graph.add_node(('+', 0));
+/// let one = graph.add_node(('1', 1));
+/// let two = graph.add_node(('2', 2));
+/// graph.extend_with_edges(&[(mul, add), (mul, add), (add, one), (add, two)]);
+///
+/// let mut dfs = DfsPostOrder::new(&graph, mul);
+/// while let Some(nx) = dfs.next(&graph) {
+///     let operands = graph.neighbors(nx).map(|a| graph[a].1).collect::<Vec<_>>();
+///     match graph[nx].0 {
+///         '+' => graph[nx].1 = operands.iter().sum(),
+///         '*' => graph[nx].1 = operands.iter().product(),
+///         _ => {}
+///     }
+/// }
+/// assert_eq!(graph[mul].1, 9);
+/// ```
+///
+/// Use `.move_to()` to continue the traversal from another node, reusing
+/// the visit maps: nodes that are already finished are not emitted again.
 #[derive(Clone, Debug)]
 pub struct DfsPostOrder<N, VM> {
     /// The stack of nodes to visit
@@ -172,6 +228,10 @@ impl<N, VM> DfsPostOrder<N, VM>
         where G: IntoNeighbors<NodeId=N>,
     {
         while let Some(&nx) = self.stack.last() {
+            if !graph.includes_node(nx) {
+                self.stack.pop();
+                continue;
+            }
             if self.discovered.visit(nx) {
                 // First time visiting `nx`: Push neighbors, don't pop `nx`
                 for succ in graph.neighbors(nx) {
@@ -220,11 +280,12 @@ impl<N, VM> DfsPostOrder<N, VM>
 ///
 /// **Note:** The algorithm may not behave correctly if nodes are removed
 /// during iteration. It may not necessarily visit added nodes or edges.
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct Bfs<N, VM> {
     /// The queue of nodes to visit
     pub stack: VecDeque<N>,
-    /// The map of discovered nodes
+    /// The map of discovered nodes; nodes marked as visited before the
+    /// traversal reaches them are skipped
     pub discovered: VM,
 }
 
@@ -247,11 +308,42 @@ impl<N, VM> Bfs<N, VM>
         }
     }
 
+    /// Create a new **Bfs** using the graph's visitor map, and no nodes to
+    /// visit.
+    pub fn empty<G>(graph: G) -> Self
+        where G: GraphRef + Visitable<NodeId=N, Map=VM>
+    {
+        Bfs {
+            stack: VecDeque::new(),
+            discovered: graph.visit_map(),
+        }
+    }
+
+    /// Clear the visit state, keeping the allocated queue and visit map.
+    pub fn reset<G>(&mut self, graph: G)
+        where G: GraphRef + Visitable<NodeId=N, Map=VM>
+    {
+        graph.reset_map(&mut self.discovered);
+        self.stack.clear();
+    }
+
+    /// Keep the discovered map, but clear the visit queue and restart
+    /// the bfs from a particular node.
+    pub fn move_to(&mut self, start: N)
+    {
+        self.discovered.visit(start);
+        self.stack.clear();
+        self.stack.push_back(start);
+    }
+
     /// Return the next node in the dfs, or **None** if the traversal is done.
     pub fn next<G>(&mut self, graph: G) -> Option<N>
         where G: IntoNeighbors<NodeId=N>
     {
         while let Some(node) = self.stack.pop_front() {
+            if !graph.includes_node(node) {
+                continue;
+            }
             for succ in graph.neighbors(node) {
                 if self.discovered.visit(succ) {
                     self.stack.push_back(succ);
@@ -265,15 +357,285 @@ impl<N, VM> Bfs<N, VM>
 
 }
 
+/// A breadth first search (BFS) of a graph that emits one layer of nodes
+/// at a time.
+///
+/// Each call to `.next()` returns all the nodes at the next distance (in
+/// number of edges) from the start node, so the `n`th layer, counting from
+/// zero, holds the nodes `n` hops away. Nodes not reachable from the start
+/// are never emitted.
+///
+/// Like `Bfs`, `BfsLayers` is not recursive and does not borrow the graph
+/// between calls to `.next()`.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::visit::BfsLayers;
+///
+/// let mut graph = Graph::<(), ()>::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// let d = graph.add_node(());
+/// graph.extend_with_edges(&[(a, b), (a, c), (b, d), (c, d)]);
+///
+/// let mut layers = BfsLayers::new(&graph, a);
+/// assert_eq!(layers.next(&graph), Some(vec![a]));
+/// let mut second = layers.next(&graph).unwrap();
+/// second.sort();
+/// assert_eq!(second, vec![b, c]);
+/// assert_eq!(layers.next(&graph), Some(vec![d]));
+/// assert_eq!(layers.next(&graph), None);
+/// ```
+///
+/// **Note:** The algorithm may not behave correctly if nodes are removed
+/// during iteration. It may not necessarily visit added nodes or edges.
+#[derive(Clone, Debug)]
+pub struct BfsLayers<N, VM> {
+    /// The layer to emit next
+    pub layer: Vec<N>,
+    /// The map of discovered nodes; nodes marked as visited before the
+    /// traversal reaches them are skipped
+    pub discovered: VM,
+}
+
+impl<N, VM> BfsLayers<N, VM>
+    where N: Copy + PartialEq,
+          VM: VisitMap<N>,
+{
+    /// Create a new **BfsLayers**, using the graph's visitor map, with
+    /// **start** as the first layer.
+    pub fn new<G>(graph: G, start: N) -> Self
+        where G: GraphRef + Visitable<NodeId=N, Map=VM>
+    {
+        let mut discovered = graph.visit_map();
+        discovered.visit(start);
+        BfsLayers {
+            layer: vec![start],
+            discovered: discovered,
+        }
+    }
+
+    /// Return the next layer of nodes, or **None** if the traversal is done.
+    ///
+    /// Layers are never empty.
+    pub fn next<G>(&mut self, graph: G) -> Option<Vec<N>>
+        where G: IntoNeighbors<NodeId=N>
+    {
+        self.layer.retain(|&node| graph.includes_node(node));
+        if self.layer.is_empty() {
+            return None;
+        }
+        let mut next_layer = Vec::new();
+        for &node in &self.layer {
+            for succ in graph.neighbors(node) {
+                if self.discovered.visit(succ) {
+                    next_layer.push(succ);
+                }
+            }
+        }
+        Some(replace(&mut self.layer, next_layer))
+    }
+}
+
+/// A depth first search that only explores nodes up to a given depth.
+///
+/// The depth of a node is the number of edges on the path the search took
+/// to it, so the traversal emits exactly the nodes within `max_depth` edges
+/// of the start node, each of them once. A node that was first reached on
+/// a long path is expanded again if the search later reaches it on a
+/// shorter one.
+///
+/// `.is_cut_off()` tells if the limit kept the search from reaching more
+/// nodes.
+///
+/// `DepthLimitedDfs` is not recursive and does not borrow the graph between
+/// calls to `.next()`. It keeps the depths of the nodes it has reached in a
+/// `HashMap`, so its memory use grows with the part of the graph within
+/// `max_depth` edges of the start, not with the size of the graph.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::visit::DepthLimitedDfs;
+///
+/// let mut graph = Graph::<(), ()>::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// let d = graph.add_node(());
+/// graph.extend_with_edges(&[(a, b), (b, c), (c, d), (a, c)]);
+///
+/// let mut dfs = DepthLimitedDfs::new(&graph, a, 1);
+/// let mut within_one = Vec::new();
+/// while let Some(nx) = dfs.next(&graph) {
+///     within_one.push(nx);
+/// }
+/// within_one.sort();
+/// assert_eq!(within_one, vec![a, b, c]);
+/// assert!(dfs.is_cut_off());
+/// ```
+///
+/// **Note:** The algorithm may not behave correctly if nodes are added or
+/// removed during iteration.
+#[derive(Clone, Debug)]
+pub struct DepthLimitedDfs<N> {
+    /// The stack of nodes to visit, with their depth
+    pub stack: Vec<(N, usize)>,
+    /// The smallest depth each node was expanded at
+    depths: HashMap<N, usize>,
+    max_depth: usize,
+    cut_off: bool,
+}
+
+impl<N> DepthLimitedDfs<N>
+    where N: Copy + Eq + Hash,
+{
+    /// Create a new **DepthLimitedDfs** that explores at most **max_depth**
+    /// edges away from **start**.
+    pub fn new<G>(_graph: G, start: N, max_depth: usize) -> Self
+        where G: GraphRef<NodeId=N>
+    {
+        DepthLimitedDfs {
+            stack: vec![(start, 0)],
+            depths: HashMap::new(),
+            max_depth: max_depth,
+            cut_off: false,
+        }
+    }
+
+    /// Return `true` if the depth limit kept the search from following some
+    /// edge, so a larger limit may reach more nodes.
+    pub fn is_cut_off(&self) -> bool {
+        self.cut_off
+    }
+
+    /// Return the next node in the search, or **None** if the traversal is
+    /// done.
+    pub fn next<G>(&mut self, graph: G) -> Option<N>
+        where G: IntoNeighbors<NodeId=N>
+    {
+        while let Some((node, depth)) = self.stack.pop() {
+            if !graph.includes_node(node) {
+                continue;
+            }
+            let first_visit = match self.depths.get(&node) {
+                Some(&d) if depth >= d => continue,
+                Some(_) => false,
+                None => true,
+            };
+            self.depths.insert(node, depth);
+            for succ in graph.neighbors(node) {
+                if self.depths.get(&succ).map_or(true, |&d| depth + 1 < d) {
+                    if depth < self.max_depth {
+                        self.stack.push((succ, depth + 1));
+                    } else {
+                        self.cut_off = true;
+                    }
+                }
+            }
+            if first_visit {
+                return Some(node);
+            }
+        }
+        None
+    }
+}
+
+/// Iterative deepening depth first search for a node that satisfies
+/// `is_goal`.
+///
+/// Runs a `DepthLimitedDfs` from `start` with the depth limits `0`, `1`,
+/// `2`, … until it finds a goal, and returns the goal together with its
+/// distance from `start` in number of edges. The first goal found is one of
+/// the goals closest to `start`. Returns `None` if no goal is reachable.
+///
+/// Each round only keeps the nodes within its current limit of `start`, so
+/// the memory use grows with the part of the graph the search explores,
+/// which suits graphs where the goal is close to the start but the
+/// reachable part of the graph is large, or that are only given by their
+/// `neighbors`.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::visit::iddfs;
+///
+/// let mut graph = Graph::<u32, ()>::new();
+/// let a = graph.add_node(0);
+/// let b = graph.add_node(1);
+/// let c = graph.add_node(2);
+/// graph.extend_with_edges(&[(a, b), (b, c)]);
+///
+/// assert_eq!(iddfs(&graph, a, |n| graph[n] == 2), Some((c, 2)));
+/// assert_eq!(iddfs(&graph, b, |n| graph[n] == 0), None);
+/// ```
+pub fn iddfs<G, F>(graph: G, start: G::NodeId, mut is_goal: F) -> Option<(G::NodeId, usize)>
+    where G: IntoNeighbors,
+          G::NodeId: Eq + Hash,
+          F: FnMut(G::NodeId) -> bool,
+{
+    let mut max_depth = 0;
+    loop {
+        let mut dfs = DepthLimitedDfs::new(graph, start, max_depth);
+        while let Some(node) = dfs.next(graph) {
+            if is_goal(node) {
+                return Some((node, max_depth));
+            }
+        }
+        if !dfs.is_cut_off() {
+            return None;
+        }
+        max_depth += 1;
+    }
+}
+
 /// A topological order traversal for a graph.
 ///
+/// Each node is emitted after all of its predecessors. Like `Dfs`, `Topo`
+/// does not borrow the graph between calls to `.next()`, so the graph's
+/// weights can be modified during the traversal.
+///
 /// **Note** that `Topo` only visits nodes that are not part of cycles,
-/// i.e. nodes in a true DAG. Use other visitors like `DfsPostOrder` or
+/// i.e. nodes in a true DAG. The traversal ends early when the remaining
+/// nodes all have a predecessor in a cycle; `.is_complete()` tells if the
+/// traversal emitted every node. Use other visitors like `DfsPostOrder` or
 /// algorithms like kosaraju_scc to handle graphs with possible cycles.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::visit::Topo;
+///
+/// let mut graph = Graph::<u32, u32>::new();
+/// let a = graph.add_node(1);
+/// let b = graph.add_node(2);
+/// let c = graph.add_node(0);
+/// let ab = graph.add_edge(a, b, 0);
+/// let bc = graph.add_edge(b, c, 0);
+///
+/// // propagate sums along the edges
+/// let mut topo = Topo::new(&graph);
+/// while let Some(nx) = topo.next(&graph) {
+///     if let Some(e) = graph.first_edge(nx, petgraph::Incoming) {
+///         let source = graph.edge_endpoints(e).unwrap().0;
+///         graph[e] = graph[source];
+///         graph[nx] += graph[e];
+///     }
+/// }
+/// assert_eq!((graph[ab], graph[bc], graph[c]), (1, 3, 3));
+/// assert!(topo.is_complete(&graph));
+///
+/// // with a cycle, the traversal ends before the cycle
+/// graph.add_edge(c, b, 0);
+/// let mut topo = Topo::new(&graph);
+/// assert_eq!(topo.next(&graph), Some(a));
+/// assert_eq!(topo.next(&graph), None);
+/// assert!(!topo.is_complete(&graph));
+/// ```
 #[derive(Clone)]
 pub struct Topo<N, VM> {
     tovisit: Vec<N>,
     ordered: VM,
+    /// The number of emitted nodes
+    count: usize,
 }
 
 impl<N, VM> Topo<N, VM>
@@ -307,6 +669,7 @@ impl<N, VM> Topo<N, VM>
         Topo {
             ordered: graph.visit_map(),
             tovisit: Vec::new(),
+            count: 0,
         }
     }
 
@@ -316,6 +679,7 @@ impl<N, VM> Topo<N, VM>
     {
         graph.reset_map(&mut self.ordered);
         self.tovisit.clear();
+        self.count = 0;
         self.extend_with_initials(graph);
     }
 
@@ -323,7 +687,8 @@ impl<N, VM> Topo<N, VM>
     /// `None` if the traversal is at the end.
     ///
     /// *Note:* The graph may not have a complete topological order, and the only
-    /// way to know is to run the whole traversal and make sure it visits every node.
+    /// way to know is to run the whole traversal and make sure it visits every
+    /// node, with `.is_complete()`.
     pub fn next<G>(&mut self, g: G) -> Option<N>
         where G: IntoNeighborsDirected + Visitable<NodeId=N, Map=VM>,
     {
@@ -333,6 +698,7 @@ impl<N, VM> Topo<N, VM>
                 continue;
             }
             self.ordered.visit(nix);
+            self.count += 1;
             for neigh in g.neighbors(nix) {
                 // Look at each neighbor, and those that only have incoming edges
                 // from the already ordered list, they are the next to visit.
@@ -344,14 +710,119 @@ impl<N, VM> Topo<N, VM>
         }
         None
     }
+
+    /// Return the number of nodes emitted so far.
+    pub fn visited_count(&self) -> usize {
+        self.count
+    }
+
+    /// Return `true` if every node of the graph has been emitted.
+    ///
+    /// When `.next()` has returned `None`, this is `false` if and only if
+    /// the graph has a cycle.
+    pub fn is_complete<G>(&self, graph: G) -> bool
+        where G: NodeCount,
+    {
+        self.count == graph.node_count()
+    }
+}
+
+
+/// Return an iterator that runs a depth first search from every node that
+/// is not yet visited, covering the whole graph.
+///
+/// The iterator yields `(component, node)` pairs where `component` counts
+/// the searches started so far, beginning at `0`. Every node is yielded
+/// exactly once. In an undirected graph each search covers exactly one
+/// connected component; in a directed graph a search covers the nodes
+/// reachable from its start that earlier searches didn't reach.
+///
+/// Computes in **O(|V| + |E|)** time for the whole iteration.
+///
+/// ```
+/// use petgraph::{Graph, Undirected};
+/// use petgraph::visit::dfs_visit_all;
+///
+/// let mut graph = Graph::<(), (), Undirected>::new_undirected();
+/// graph.extend_with_edges(&[(0, 1), (2, 3)]);
+///
+/// let components = dfs_visit_all(&graph).map(|(c, _)| c).collect::<Vec<_>>();
+/// assert_eq!(components, vec![0, 0, 1, 1]);
+/// ```
+pub fn dfs_visit_all<G>(graph: G) -> DfsVisitAll<G>
+    where G: IntoNeighbors + IntoNodeIdentifiers + Visitable,
+{
+    DfsVisitAll {
+        graph: graph,
+        dfs: Dfs::empty(graph),
+        roots: graph.node_identifiers(),
+        components: 0,
+    }
 }
 
+/// An iterator over all nodes of a graph, one depth first search at a time.
+///
+/// Created with [`dfs_visit_all`](fn.dfs_visit_all.html).
+pub struct DfsVisitAll<G>
+    where G: IntoNodeIdentifiers + Visitable,
+{
+    graph: G,
+    dfs: Dfs<G::NodeId, G::Map>,
+    roots: G::NodeIdentifiers,
+    components: usize,
+}
+
+impl<G> Iterator for DfsVisitAll<G>
+    where G: IntoNeighbors + IntoNodeIdentifiers + Visitable,
+{
+    type Item = (usize, G::NodeId);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(node) = self.dfs.next(self.graph) {
+                return Some((self.components - 1, node));
+            }
+            let dfs = &mut self.dfs;
+            match self.roots.find(|&n| !dfs.discovered.is_visited(&n)) {
+                Some(root) => {
+                    dfs.move_to(root);
+                    self.components += 1;
+                }
+                None => return None,
+            }
+        }
+    }
+}
 
 /// A walker is a traversal state, but where part of the traversal
 /// information is supplied manually to each next call.
 ///
 /// This for example allows graph traversals that don't hold a borrow of the
 /// graph they are traversing.
+///
+/// `Dfs`, `DfsPostOrder`, `Bfs`, `BfsLayers` and `Topo` are walkers with the
+/// graph as their context. Use `.iter()` to get a regular iterator that
+/// borrows the graph, or walk a `&mut` reference to keep the traversal state.
+///
+/// ```
+/// use petgraph::Graph;
+/// use petgraph::visit::{Dfs, Walker};
+///
+/// let mut graph = Graph::<u32, ()>::new();
+/// let a = graph.add_node(1);
+/// let b = graph.add_node(2);
+/// let c = graph.add_node(3);
+/// graph.extend_with_edges(&[(a, b), (b, c)]);
+///
+/// let odd = Dfs::new(&graph, a).iter(&graph)
+///                              .filter(|&nx| graph[nx] % 2 == 1)
+///                              .collect::<Vec<_>>();
+/// assert_eq!(odd, vec![a, c]);
+///
+/// // Walk part of the way, then continue with the same state
+/// let mut dfs = Dfs::new(&graph, a);
+/// assert_eq!((&mut dfs).iter(&graph).take(2).count(), 2);
+/// assert_eq!(dfs.next(&graph), Some(c));
+/// ```
 pub trait Walker<Context> {
     type Item;
     /// Advance to the next item
@@ -380,17 +851,25 @@ impl<W, C> WalkerIter<W, C>
     where W: Walker<C>,
           C: Clone,
 {
+    /// Return the context.
     pub fn context(&self) -> C {
         self.context.clone()
     }
 
+    /// Return a reference to the walker.
     pub fn inner_ref(&self) -> &W {
         &self.walker
     }
 
+    /// Return a mutable reference to the walker.
     pub fn inner_mut(&mut self) -> &mut W {
         &mut self.walker
     }
+
+    /// Unwrap the walker, dropping the context.
+    pub fn into_inner(self) -> W {
+        self.walker
+    }
 }
 
 impl<W, C> Iterator for WalkerIter<W, C>
@@ -403,6 +882,15 @@ impl<W, C> Iterator for WalkerIter<W, C>
     }
 }
 
+impl<'a, C, W: ?Sized> Walker<C> for &'a mut W
+    where W: Walker<C>,
+{
+    type Item = W::Item;
+    fn walk_next(&mut self, context: C) -> Option<Self::Item> {
+        (**self).walk_next(context)
+    }
+}
+
 impl<G> Walker<G> for Dfs<G::NodeId, G::Map>
     where G: IntoNeighbors + Visitable
 {
@@ -430,6 +918,25 @@ impl<G> Walker<G> for Bfs<G::NodeId, G::Map>
     }
 }
 
+impl<G> Walker<G> for BfsLayers<G::NodeId, G::Map>
+    where G: IntoNeighbors + Visitable
+{
+    type Item = Vec<G::NodeId>;
+    fn walk_next(&mut self, context: G) -> Option<Self::Item> {
+        self.next(context)
+    }
+}
+
+impl<G> Walker<G> for DepthLimitedDfs<G::NodeId>
+    where G: IntoNeighbors,
+          G::NodeId: Eq + Hash,
+{
+    type Item = G::NodeId;
+    fn walk_next(&mut self, context: G) -> Option<Self::Item> {
+        self.next(context)
+    }
+}
+
 impl<G> Walker<G> for Topo<G::NodeId, G::Map>
     where G: IntoNeighborsDirected + Visitable,
 {
@@ -0,0 +1,71 @@
+extern crate petgraph;
+
+use std::sync::Arc;
+use std::thread;
+
+use petgraph::prelude::*;
+use petgraph::algo::dijkstra;
+use petgraph::csr::Csr;
+use petgraph::frozen_graph::{FrozenGraph, Searcher};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn send_sync() {
+    assert_send_sync::<Graph<String, Vec<u8>>>();
+    assert_send_sync::<UnGraph<String, Vec<u8>>>();
+    #[cfg(feature = "stable_graph")]
+    assert_send_sync::<StableGraph<String, Vec<u8>>>();
+    #[cfg(feature = "graphmap")]
+    assert_send_sync::<GraphMap<u32, Vec<u8>, Directed>>();
+    assert_send_sync::<Csr<String, Vec<u8>>>();
+    assert_send_sync::<FrozenGraph<String, Vec<u8>>>();
+    assert_send_sync::<Arc<FrozenGraph<String, Vec<u8>>>>();
+    assert_send_sync::<Searcher<String, Vec<u8>, u32>>();
+}
+
+fn grid(n: u32) -> Graph<(), u32> {
+    let mut gr = Graph::new();
+    for i in 0..n * n {
+        gr.add_node(());
+        let (x, y) = (i % n, i / n);
+        if x > 0 {
+            gr.add_edge(NodeIndex::new(i as usize - 1), NodeIndex::new(i as usize), 1 + i % 3);
+        }
+        if y > 0 {
+            gr.add_edge(NodeIndex::new((i - n) as usize), NodeIndex::new(i as usize), 2 + i % 5);
+        }
+    }
+    gr
+}
+
+#[test]
+fn threads_share_frozen_graph() {
+    let gr = Arc::new(FrozenGraph::new(grid(10)));
+    let threads = (0..4).map(|t| {
+        let mut searcher = Searcher::new(gr.clone());
+        thread::spawn(move || {
+            let mut results = Vec::new();
+            for start in (t..searcher.graph().node_count()).filter(|i| (i - t) % 7 == 0) {
+                let start = NodeIndex::new(start);
+                let dist = searcher.dijkstra(start, |e| *e.weight()).to_vec();
+                let reached = searcher.bfs(start).len();
+                results.push((start, dist, reached));
+            }
+            results
+        })
+    }).collect::<Vec<_>>();
+
+    for handle in threads {
+        for (start, dist, reached) in handle.join().unwrap() {
+            let expected = dijkstra(&**gr, start, None, |e| *e.weight());
+            assert_eq!(reached, expected.len());
+            for node in gr.node_indices() {
+                assert_eq!(dist[node.index()], expected.get(&node).cloned());
+            }
+        }
+    }
+
+    let gr = Arc::try_unwrap(gr).unwrap().into_inner();
+    assert_eq!(gr.node_count(), 100);
+}
@@ -1,10 +1,19 @@
 extern crate petgraph;
+#[cfg(feature = "rand")]
+extern crate rand;
+
+// shared with tests/quickcheck.rs, without the quickcheck utilities
+#[path = "utils/matching.rs"]
+mod matching;
 
 use std::collections::HashSet;
 use std::hash::Hash;
 
+use matching::brute_force_max_matching;
+
 use petgraph::prelude::*;
 use petgraph::{
+    Direction,
     EdgeType,
 };
 
@@ -38,7 +47,10 @@ use petgraph::algo::{
 };
 
 use petgraph::dot::{
+    Config,
     Dot,
+    DotEdge,
+    DotNode,
 };
 
 fn set<I>(iter: I) -> HashSet<I::Item>
@@ -155,6 +167,194 @@ fn bfs() {
     assert_eq!(bfs.next(&gr), None);
 }
 
+#[test]
+fn bfs_layers() {
+    use petgraph::visit::BfsLayers;
+
+    fn sorted(mut layer: Vec<NodeIndex>) -> Vec<NodeIndex> {
+        layer.sort();
+        layer
+    }
+
+    // 0 -> 1 -> 3 -> 5
+    // 0 -> 2 -> 3, 2 -> 4 -> 5 and a shortcut 0 -> 4
+    // 6 is not reachable from 0, but reaches 0
+    let mut gr = Graph::<(), ()>::new();
+    gr.extend_with_edges(&[(0, 1), (0, 2), (1, 3), (2, 3), (2, 4), (0, 4),
+                           (3, 5), (4, 5), (5, 0), (6, 0)]);
+    let mut layers = BfsLayers::new(&gr, n(0));
+    assert_eq!(layers.next(&gr), Some(vec![n(0)]));
+    assert_eq!(layers.next(&gr).map(sorted), Some(vec![n(1), n(2), n(4)]));
+    assert_eq!(layers.next(&gr).map(sorted), Some(vec![n(3), n(5)]));
+    assert_eq!(layers.next(&gr), None);
+    assert_eq!(layers.next(&gr), None);
+
+    // every node is in the layer of its hop distance
+    let hops = dijkstra(&gr, n(0), None, |_| 1);
+    for (depth, layer) in BfsLayers::new(&gr, n(0)).iter(&gr).enumerate() {
+        for node in layer {
+            assert_eq!(hops[&node], depth);
+        }
+    }
+    let total: usize = BfsLayers::new(&gr, n(0)).iter(&gr).map(|l| l.len()).sum();
+    assert_eq!(total, hops.len());
+
+    let rev = Reversed(&gr);
+    let layers = BfsLayers::new(rev, n(0)).iter(rev).map(sorted).collect::<Vec<_>>();
+    assert_eq!(layers, vec![vec![n(0)], vec![n(5), n(6)], vec![n(3), n(4)],
+                            vec![n(1), n(2)]]);
+}
+
+#[test]
+fn walker() {
+    use petgraph::visit::{BfsLayers, DfsPostOrder};
+
+    let mut gr = Graph::<u32, ()>::new();
+    gr.extend_with_edges(&[(0, 1), (1, 2), (0, 3), (3, 4)]);
+    for (i, w) in gr.node_weights_mut().enumerate() {
+        *w = i as u32;
+    }
+
+    let even = Dfs::new(&gr, n(0)).iter(&gr)
+                                  .filter(|&nx| gr[nx] % 2 == 0)
+                                  .collect::<Vec<_>>();
+    assert_eq!(even, vec![n(0), n(2), n(4)]);
+    assert_eq!(DfsPostOrder::new(&gr, n(0)).iter(&gr).map(|nx| gr[nx]).collect::<Vec<_>>(),
+               vec![2, 1, 4, 3, 0]);
+    assert_eq!(Bfs::new(&gr, n(0)).iter(&gr).last(), Some(n(2)));
+    assert_eq!(BfsLayers::new(&gr, n(0)).iter(&gr).map(|l| l.len()).collect::<Vec<_>>(),
+               vec![1, 2, 2]);
+    assert_eq!(Topo::new(&gr).iter(&gr).count(), 5);
+
+    // walking through &mut keeps the state in the original walker
+    let mut bfs = Bfs::new(&gr, n(0));
+    assert_eq!((&mut bfs).iter(&gr).take(3).count(), 3);
+    let rest = bfs.iter(&gr);
+    assert_eq!(rest.inner_ref().stack.len(), 2);
+    let bfs = rest.into_inner();
+    assert_eq!(bfs.iter(&gr).count(), 2);
+}
+
+#[test]
+fn premarked_visit_map() {
+    use petgraph::visit::NodeFiltered;
+
+    // two routes from 0 to 5: 0 - 1 - 2 - 5 and 0 - 3 - 4 - 5
+    let gr = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 5), (0, 3), (3, 4), (4, 5),
+                                           (5, 6)]);
+    let region: [NodeIndex; 2] = [n(1), n(3)];
+
+    let mut dfs = Dfs::new(&gr, n(0));
+    for &nx in &region {
+        dfs.discovered.visit(nx);
+    }
+    assert_eq!(dfs.iter(&gr).collect::<Vec<_>>(), vec![n(0)]);
+
+    let mut bfs = Bfs::new(&gr, n(0));
+    bfs.discovered.visit(region[0]);
+    let reached = set(bfs.iter(&gr));
+    assert_eq!(reached, set(vec![n(0), n(3), n(4), n(5), n(6)]));
+
+    // the same as searching the graph without the region
+    let without = NodeFiltered::from_fn(&gr, |nx| nx != n(1));
+    assert_eq!(reached, set(Bfs::new(&without, n(0)).iter(&without)));
+    assert!(!reached.contains(&n(2)));
+}
+
+#[test]
+fn dfs_visit_all() {
+    use petgraph::algo::connected_components;
+    use petgraph::visit::dfs_visit_all;
+
+    // three components, one of them a single node
+    let mut gr = Graph::<(), (), Undirected>::new_undirected();
+    gr.extend_with_edges(&[(0, 4), (4, 2), (1, 3), (5, 5)]);
+    let visits = dfs_visit_all(&gr).collect::<Vec<_>>();
+    assert_eq!(visits.len(), gr.node_count());
+    assert_eq!(set(visits.iter().map(|&(_, nx)| nx)), set(gr.node_indices()));
+    assert_eq!(set(visits.iter().map(|&(c, _)| c)).len(), connected_components(&gr));
+    let component = |nx: NodeIndex| visits.iter().find(|v| v.1 == nx).unwrap().0;
+    assert_eq!((component(n(0)), component(n(2)), component(n(4))), (0, 0, 0));
+    assert_eq!((component(n(1)), component(n(3))), (1, 1));
+    assert_eq!(component(n(5)), 2);
+
+    // directed: a search doesn't revisit what earlier searches reached
+    let dir = Graph::<(), ()>::from_edges(&[(1, 0), (0, 2), (3, 2)]);
+    let visits = dfs_visit_all(&dir).collect::<Vec<_>>();
+    assert_eq!(visits, vec![(0, n(0)), (0, n(2)), (1, n(1)), (2, n(3))]);
+
+    assert_eq!(dfs_visit_all(&Graph::<(), ()>::new()).count(), 0);
+}
+
+#[test]
+fn depth_limited_dfs() {
+    use petgraph::visit::{DepthLimitedDfs, iddfs};
+
+    // 3 is reached on the long path 0 -> 1 -> 2 -> 3 and the short path
+    // 0 -> 3; 4 is only within the limit through the short path
+    for &reverse in &[false, true] {
+        let mut edges = vec![(0, 1), (1, 2), (2, 3), (0, 3), (3, 4), (4, 5)];
+        if reverse {
+            edges.reverse();
+        }
+        let gr = Graph::<(), ()>::from_edges(&edges);
+        let mut dfs = DepthLimitedDfs::new(&gr, n(0), 2);
+        let reached = set(dfs.iter(&gr));
+        assert_eq!(reached, set(vec![n(0), n(1), n(2), n(3), n(4)]));
+
+        let mut dfs = DepthLimitedDfs::new(&gr, n(0), 4);
+        assert_eq!(dfs.iter(&gr).count(), 6);
+
+        assert_eq!(iddfs(&gr, n(0), |nx| nx == n(4)), Some((n(4), 2)));
+        assert_eq!(iddfs(&gr, n(0), |nx| nx == n(0)), Some((n(0), 0)));
+        assert_eq!(iddfs(&gr, n(3), |nx| nx == n(1)), None);
+    }
+
+    let gr = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    let mut dfs = DepthLimitedDfs::new(&gr, n(0), 1);
+    assert_eq!((&mut dfs).iter(&gr).collect::<Vec<_>>(), vec![n(0), n(1)]);
+    assert!(dfs.is_cut_off());
+    let mut dfs = DepthLimitedDfs::new(&gr, n(0), 2);
+    assert_eq!((&mut dfs).iter(&gr).count(), 3);
+    assert!(!dfs.is_cut_off());
+    let mut dfs = DepthLimitedDfs::new(&gr, n(0), 0);
+    assert_eq!((&mut dfs).iter(&gr).count(), 1);
+    assert!(dfs.is_cut_off());
+}
+
+#[test]
+fn reversed_edges() {
+    use petgraph::visit::{IntoEdges, IntoEdgesDirected, EdgeRef};
+
+    let mut gr = Graph::<(), u32>::new();
+    gr.extend_with_edges(&[(0, 1, 1), (2, 1, 2), (1, 3, 3), (1, 1, 4)]);
+    let rev = Reversed(&gr);
+    let mut edges = rev.edges(n(1)).map(|e| (e.source(), e.target(), *e.weight()))
+                                   .collect::<Vec<_>>();
+    edges.sort();
+    assert_eq!(edges, vec![(n(1), n(0), 1), (n(1), n(1), 4), (n(1), n(2), 2)]);
+    let mut edges = rev.edges_directed(n(1), Incoming).map(|e| (e.source(), e.target()))
+                                                      .collect::<Vec<_>>();
+    edges.sort();
+    assert_eq!(edges, vec![(n(1), n(1)), (n(3), n(1))]);
+
+    // reversing twice is the original graph
+    let twice = Reversed(rev);
+    assert_eq!(set(twice.edges(n(1)).map(|e| e.id())), set(gr.edges(n(1)).map(|e| e.id())));
+
+    let scores = dijkstra(rev, n(3), None, |e| *e.weight());
+    assert_eq!(scores[&n(0)], 4);
+    assert_eq!(scores[&n(2)], 5);
+
+    #[cfg(feature = "stable_graph")]
+    {
+        let mut st = StableGraph::<(), u32>::new();
+        st.extend_with_edges(&[(0, 1, 1), (2, 1, 2), (1, 3, 3), (1, 1, 4)]);
+        let scores = dijkstra(Reversed(&st), n(3), None, |e| *e.weight());
+        assert_eq!(scores[&n(0)], 4);
+    }
+}
+
 
 
 #[test]
@@ -216,6 +416,42 @@ fn mst() {
 
 }
 
+#[test]
+fn forest_connectivity() {
+    use petgraph::algo::{connected_components, ForestConnectivity};
+    use petgraph::graph::node_index;
+
+    let mut forest = ForestConnectivity::<u8>::default();
+    let n = node_index::<u8>;
+    assert_eq!(forest.node_bound(), 0);
+    assert!(forest.connected(n(3), n(3)));
+    assert!(!forest.connected(n(3), n(4)));
+    assert!(!forest.try_add_edge(n(2), n(2)));
+    assert_eq!(forest.node_bound(), 3);
+    assert_eq!(forest.tree_count(), 3);
+
+    assert!(forest.try_add_edge(n(0), n(1)));
+    assert!(forest.try_add_edge(n(254), n(1)));
+    assert_eq!(forest.node_bound(), 255);
+    assert!(forest.connected(n(0), n(254)));
+    assert!(!forest.try_add_edge(n(0), n(254)));
+    assert!(!forest.connected(n(0), n(2)));
+    assert_eq!(forest.tree_count(), 253);
+
+    forest.reset();
+    assert_eq!(forest.node_bound(), 255);
+    assert!(!forest.connected(n(0), n(1)));
+    assert!(forest.try_add_edge(n(0), n(254)));
+
+    // the edges a forest accepts are a spanning forest of the graph
+    let gr = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (3, 4), (4, 3), (5, 5)]);
+    let mut forest = ForestConnectivity::new(gr.node_count());
+    let accepted = gr.raw_edges().iter().filter(|e| forest.try_add_edge(e.source(), e.target()))
+                                        .count();
+    assert_eq!(accepted, gr.node_count() - connected_components(&gr));
+    assert_eq!(forest.tree_count(), connected_components(&gr));
+}
+
 #[test]
 fn selfloop() {
     let mut gr = Graph::new();
@@ -309,6 +545,138 @@ fn update_edge()
     }
 }
 
+#[test]
+fn remove_edge_between()
+{
+    {
+        let mut gr = Graph::new();
+        let a = gr.add_node("a");
+        let b = gr.add_node("b");
+        let c = gr.add_node("c");
+        gr.add_edge(a, b, 1);
+        gr.add_edge(a, b, 2);
+        gr.add_edge(b, c, 3);
+        // directed: no edge from b to a
+        assert_eq!(gr.remove_edge_between(b, a), None);
+        assert_eq!(gr.remove_edge_between(c, a), None);
+        assert_eq!(gr.edge_count(), 3);
+
+        // parallel edges are removed one at a time, lowest edge index first
+        assert_eq!(gr.remove_edge_between(a, b), Some(1));
+        assert_graph_consistent(&gr);
+        assert_eq!(gr.remove_edge_between(a, b), Some(2));
+        assert_graph_consistent(&gr);
+        assert_eq!(gr.remove_edge_between(a, b), None);
+        assert_eq!(gr.edge_count(), 1);
+        assert!(gr.contains_edge(b, c));
+    }
+
+    {
+        let mut gr = Graph::new_undirected();
+        let a = gr.add_node("a");
+        let b = gr.add_node("b");
+        gr.add_edge(a, b, 1);
+        gr.add_edge(b, a, 2);
+        gr.add_edge(a, a, 3);
+        // either orientation, lowest edge index first
+        assert_eq!(gr.remove_edge_between(b, a), Some(1));
+        assert_eq!(gr.remove_edge_between(a, b), Some(2));
+        assert_eq!(gr.remove_edge_between(a, b), None);
+        assert_eq!(gr.remove_edge_between(a, a), Some(3));
+        assert_eq!(gr.edge_count(), 0);
+        assert_graph_consistent(&gr);
+    }
+}
+
+#[test]
+fn remove_edges_between()
+{
+    let mut gr = Graph::new();
+    let a = gr.add_node("a");
+    let b = gr.add_node("b");
+    let c = gr.add_node("c");
+    gr.add_edge(a, b, 1);
+    gr.add_edge(b, a, 2);
+    gr.add_edge(a, c, 3);
+    gr.add_edge(a, b, 4);
+    gr.add_edge(c, b, 5);
+    gr.add_edge(a, b, 6);
+    // the last edge is one of those removed, and moves while removing them
+    assert_eq!(gr.remove_edges_between(a, b), vec![1, 6, 4]);
+    assert_graph_consistent(&gr);
+    assert_eq!(gr.remove_edges_between(a, b), vec![]);
+    let mut left = gr.raw_edges().iter().map(|e| e.weight).collect::<Vec<_>>();
+    left.sort();
+    assert_eq!(left, vec![2, 3, 5]);
+    assert!(gr.contains_edge(b, a));
+
+    let mut gr = gr.into_edge_type::<Undirected>();
+    gr.add_edge(a, b, 7);
+    let mut removed = gr.remove_edges_between(a, b);
+    removed.sort();
+    assert_eq!(removed, vec![2, 7]);
+    assert_eq!(gr.edge_count(), 2);
+    assert_graph_consistent(&gr);
+}
+
+#[test]
+fn merge_parallel_edges()
+{
+    let mut gr = Graph::new();
+    let a = gr.add_node("a");
+    let b = gr.add_node("b");
+    let c = gr.add_node("c");
+    gr.add_edge(a, b, 1);
+    gr.add_edge(b, a, 2);
+    gr.add_edge(a, a, 3);
+    gr.add_edge(a, b, 4);
+    gr.add_edge(b, c, 5);
+    gr.add_edge(a, a, 6);
+    gr.add_edge(a, b, 7);
+    let mut un = gr.clone().into_edge_type::<Undirected>();
+
+    assert_eq!(gr.merge_parallel_edges(|x, y| x + y), 3);
+    assert_graph_consistent(&gr);
+    let edges = gr.raw_edges().iter()
+                  .map(|e| (e.source(), e.target(), e.weight))
+                  .collect::<Vec<_>>();
+    assert_eq!(edges, vec![(a, b, 12), (b, a, 2), (a, a, 9), (b, c, 5)]);
+    assert_eq!(gr.merge_parallel_edges(|x, y| x + y), 0);
+
+    // the weights are merged in the order of the edge indices
+    assert_eq!(un.merge_parallel_edges(|x, y| 10 * x + y), 4);
+    assert_graph_consistent(&un);
+    let edges = un.raw_edges().iter()
+                  .map(|e| (e.source(), e.target(), e.weight))
+                  .collect::<Vec<_>>();
+    assert_eq!(edges, vec![(a, b, 1247), (a, a, 36), (b, c, 5)]);
+}
+
+#[test]
+fn merge_parallel_edges_panic()
+{
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut gr = Graph::<_, _>::from_edges(&[(0, 1, 1), (1, 2, 2), (0, 1, 3), (1, 2, 4),
+                                             (2, 3, 5), (2, 3, 6)]);
+    gr.add_node(());
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        gr.merge_parallel_edges(|x, y| if x == 1 { panic!("merge") } else { x + y })
+    }));
+    assert!(result.is_err());
+    // only the edges given to the panicking merge are gone
+    assert_eq!(gr.node_count(), 5);
+    assert_graph_consistent(&gr);
+    let mut edges = gr.raw_edges().iter().map(|e| (e.source().index(), e.target().index(),
+                                                   e.weight))
+                                  .collect::<Vec<_>>();
+    edges.sort();
+    assert_eq!(edges, vec![(1, 2, 2), (1, 2, 4), (2, 3, 5), (2, 3, 6)]);
+    assert_eq!(gr.merge_parallel_edges(|x, y| x + y), 2);
+    assert_graph_consistent(&gr);
+    assert_eq!(gr.edge_weight(gr.find_edge(n(2), n(3)).unwrap()), Some(&11));
+}
+
 #[test]
 fn dijk() {
     let mut g = Graph::new_undirected();
@@ -342,6 +710,39 @@ fn dijk() {
     assert_eq!(scores[&c], 9);
 }
 
+#[test]
+fn astar_path() {
+    use petgraph::algo::astar;
+    let mut g = Graph::<(i32, i32), i32, Undirected>::new_undirected();
+    let a = g.add_node((0, 0));
+    let b = g.add_node((2, 0));
+    let c = g.add_node((1, 1));
+    let d = g.add_node((2, 1));
+    let e = g.add_node((3, 2));
+    let f = g.add_node((4, 2));
+    let lone = g.add_node((5, 5));
+    g.add_edge(a, b, 2);
+    g.add_edge(a, d, 4);
+    g.add_edge(b, c, 1);
+    g.add_edge(b, f, 7);
+    g.add_edge(c, e, 5);
+    g.add_edge(e, f, 2);
+    g.add_edge(d, e, 2);
+
+    // the manhattan distance never overestimates the cost of a path
+    let distance = |x: NodeIndex| {
+        let ((x1, y1), (x2, y2)) = (g[x], g[f]);
+        (x1 - x2).abs() + (y1 - y2).abs()
+    };
+    let (cost, path) = astar(&g, a, |x| x == f, |e| *e.weight(), &distance).unwrap();
+    assert_eq!(cost, 8);
+    assert_eq!(path.nodes(), &[a, d, e, f]);
+    assert_eq!(astar(&g, a, |x| x == f, |e| *e.weight(), |_| 0).unwrap(), (cost, path));
+
+    assert_eq!(astar(&g, a, |x| x == a, |e| *e.weight(), |_| 0).unwrap().1.nodes(), &[a]);
+    assert_eq!(astar(&g, a, |x| x == lone, |e| *e.weight(), |_| 0), None);
+}
+
 #[cfg(feature = "generate")]
 #[test]
 fn test_generate_undirected() {
@@ -761,134 +1162,1076 @@ fn connected_comp()
     assert_eq!(petgraph::algo::connected_components(&gr), 2);
 }
 
-#[should_panic]
 #[test]
-fn oob_index()
+fn vertex_cover_independent_set()
 {
-    let mut gr = Graph::<_, ()>::new();
-    let a = gr.add_node(0);
-    let b = gr.add_node(1);
-    gr.remove_node(a);
-    gr[b];
+    use petgraph::algo::{min_vertex_cover_approx, max_independent_set_approx};
+
+    // a star with a tail and a self loop
+    let gr: UnGraph<(), ()> = Graph::from_edges(&[
+        (0, 1), (0, 2), (0, 3), (0, 4),
+        (4, 5), (6, 6),
+    ]);
+    let cover = min_vertex_cover_approx(&gr);
+    assert_eq!(cover, vec![n(0), n(1), n(4), n(5), n(6)]);
+    for edge in gr.edge_references() {
+        assert!(cover.contains(&edge.source()) || cover.contains(&edge.target()));
+    }
+    assert_eq!(min_vertex_cover_approx(&gr), cover);
+
+    let set = max_independent_set_approx(&gr);
+    assert_eq!(set, vec![n(1), n(2), n(3), n(5)]);
+    for edge in gr.edge_references() {
+        assert!(!(set.contains(&edge.source()) && set.contains(&edge.target())));
+    }
+    assert_eq!(max_independent_set_approx(&gr), set);
+
+    let empty = Graph::<(), ()>::new();
+    assert!(min_vertex_cover_approx(&empty).is_empty());
+    assert!(max_independent_set_approx(&empty).is_empty());
 }
 
 #[test]
-fn usize_index()
+fn betweenness()
 {
-    let mut gr = Graph::<_, _, Directed, usize>::with_capacity(0, 0);
-    let a = gr.add_node(0);
-    let b = gr.add_node(1);
-    let e = gr.add_edge(a, b, 1.2);
-    let mut dfs = Dfs::new(&gr, a);
-    while let Some(nx) = dfs.next(&gr) {
-        gr[nx] += 1;
-    }
-    assert_eq!(gr[a], 1);
-    assert_eq!(gr[b], 2);
-    assert_eq!(gr[e], 1.2);
+    use petgraph::algo::betweenness_centrality;
+
+    let path: UnGraph<(), ()> = Graph::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+    assert_eq!(betweenness_centrality(&path, |_| 1), vec![0., 2., 2., 0.]);
+    let dpath = path.clone().into_edge_type::<Directed>();
+    assert_eq!(betweenness_centrality(&dpath, |_| 1), vec![0., 2., 2., 0.]);
+
+    // a cycle of four nodes; the pair 0, 2 has two shortest paths
+    let mut gr: UnGraph<(), u32> = Graph::from_edges(&[
+        (0, 1, 1), (0, 2, 1), (1, 3, 1), (2, 3, 1),
+    ]);
+    assert_eq!(betweenness_centrality(&gr, |e| *e.weight()),
+               vec![0.5, 0.5, 0.5, 0.5]);
+    // the direct edge 0 - 2 is now as long as the way around
+    gr[EdgeIndex::new(1)] = 3;
+    assert_eq!(betweenness_centrality(&gr, |e| *e.weight()),
+               vec![0., 1.5, 0., 1.5]);
 }
 
+#[cfg(feature = "rand")]
 #[test]
-fn u8_index()
+fn betweenness_sampled()
 {
-    let mut gr = Graph::<_, (), Undirected, u8>::with_capacity(0, 0);
-    for _ in 0..255 {
-        gr.add_node(());
-    }
+    use petgraph::algo::{betweenness_centrality, betweenness_centrality_sampled};
+    use rand::{SeedableRng, XorShiftRng};
+
+    let mut gr: Graph<(), f64> = Graph::from_edges(&[
+        (0, 1, 1.), (1, 2, 2.), (2, 0, 0.5), (2, 3, 1.),
+        (3, 4, 1.), (4, 5, 1.5), (5, 3, 1.), (1, 4, 3.),
+        (5, 6, 1.), (6, 7, 1.), (7, 1, 2.),
+    ]);
+    let exact = betweenness_centrality(&gr, |e| *e.weight());
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    assert_eq!(betweenness_centrality_sampled(&gr, &mut rng, 8, |e| *e.weight()),
+               exact);
+    assert_eq!(betweenness_centrality_sampled(&gr, &mut rng, 100, |e| *e.weight()),
+               exact);
+    assert_eq!(betweenness_centrality_sampled(&gr, &mut rng, 0, |e| *e.weight()),
+               vec![0.; 8]);
+
+    let sample = |seed| {
+        let mut rng = XorShiftRng::from_seed([seed, 2, 3, 4]);
+        betweenness_centrality_sampled(&gr, &mut rng, 3, |e| *e.weight())
+    };
+    assert_eq!(sample(1), sample(1));
+    assert!(sample(1) != exact);
+
+    gr.remove_node(n(7));
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    assert_eq!(betweenness_centrality_sampled(&gr, &mut rng, 7, |e| *e.weight()),
+               betweenness_centrality(&gr, |e| *e.weight()));
 }
 
-#[should_panic]
 #[test]
-fn u8_index_overflow()
+fn transitive_closure()
 {
-    let mut gr = Graph::<_, (), Undirected, u8>::with_capacity(0, 0);
-    for _ in 0..256 {
-        gr.add_node(());
-    }
+    use petgraph::algo::transitive_closure;
+
+    // a cycle 1 -> 2 -> 3 -> 1 between 0 and 4, and an isolated node 5
+    let mut gr: Graph<(), ()> = Graph::from_edges(&[
+        (0, 1), (1, 2), (2, 3), (3, 1), (3, 4), (4, 4),
+    ]);
+    gr.add_node(());
+    let closure = transitive_closure(&gr);
+    assert!(closure.reachable(n(0), n(4)));
+    assert!(!closure.reachable(n(4), n(0)));
+    assert!(!closure.reachable(n(0), n(0)));
+    assert!(closure.reachable(n(2), n(2)));
+    assert!(closure.reachable(n(4), n(4)));
+    assert!(!closure.reachable(n(5), n(5)));
+    assert_eq!(closure.descendants(n(0)).collect::<Vec<_>>(),
+               vec![n(1), n(2), n(3), n(4)]);
+    assert_eq!(closure.descendants(n(3)).collect::<Vec<_>>(),
+               vec![n(1), n(2), n(3), n(4)]);
+    assert_eq!(closure.descendants(n(4)).collect::<Vec<_>>(), vec![n(4)]);
+    assert_eq!(closure.descendants(n(5)).count(), 0);
+
+    let ungr = gr.into_edge_type::<Undirected>();
+    let closure = transitive_closure(&ungr);
+    assert!(closure.reachable(n(4), n(0)));
+    assert!(closure.reachable(n(0), n(0)));
+    assert!(!closure.reachable(n(0), n(5)));
 }
 
-#[should_panic]
 #[test]
-fn u8_index_overflow_edges()
+fn contract_degree_two_chains()
 {
-    let mut gr = Graph::<_, (), Undirected, u8>::with_capacity(0, 0);
-    let a = gr.add_node('a');
-    let b = gr.add_node('b');
-    for _ in 0..256 {
-        gr.add_edge(a, b, ());
+    use petgraph::algo::contract_degree_two_chains;
+
+    // a chain 0 - 1 - 2 - 3, where 1 is protected, and a triangle 3, 4, 5
+    // that would need a self loop to be contracted completely
+    let mut gr: UnGraph<&str, u32> = UnGraph::default();
+    let names = ["a", "b", "c", "d", "e", "f", "g", "h"];
+    for name in &names {
+        gr.add_node(*name);
     }
+    gr.extend_with_edges(&[
+        (0, 1, 1), (1, 2, 2), (2, 3, 4),
+        (3, 4, 8), (4, 5, 16), (5, 3, 32),
+        (3, 6, 64), (6, 6, 128), (3, 7, 256), (7, 3, 512),
+    ]);
+    let count = contract_degree_two_chains(&mut gr, |a, b| a + b, |_, &w| w == "b");
+    // "c" and one of the triangle nodes are contracted; "g" has a self loop
+    // and "h" has both edges to "d"
+    assert_eq!(count, 2);
+    let mut remaining = gr.raw_nodes().iter().map(|n| n.weight).collect::<Vec<_>>();
+    remaining.sort();
+    assert_eq!(remaining, vec!["a", "b", "d", "e", "g", "h"]);
+    let find = |name| gr.node_indices().find(|&i| gr[i] == name).unwrap();
+    let weights = |a, b| {
+        let mut ws = gr.edges(find(a)).filter(|e| e.target() == find(b))
+                       .map(|e| *e.weight()).collect::<Vec<_>>();
+        ws.sort();
+        ws
+    };
+    assert_eq!(weights("b", "d"), vec![6]);
+    // the triangle became two parallel edges
+    assert_eq!(weights("d", "e"), vec![8, 48]);
+    assert_eq!(gr.edge_count(), 8);
+
+    // directed: only through paths, in one or both directions
+    let mut gr: Graph<(), u32> = Graph::from_edges(&[
+        (0, 1, 1), (1, 2, 2),
+        (2, 3, 4), (3, 2, 8), (3, 4, 16), (4, 3, 32),
+        (5, 4, 64), (6, 4, 128),
+    ]);
+    assert_eq!(contract_degree_two_chains(&mut gr, |a, b| a + b, |_, _| false), 2);
+    assert_eq!(gr.node_count(), 5);
+    let mut edges = gr.edge_references().map(|e| *e.weight()).collect::<Vec<_>>();
+    edges.sort();
+    assert_eq!(edges, vec![3, 20, 40, 64, 128]);
 }
 
 #[test]
-fn test_weight_iterators() {
-    let mut gr = Graph::<_,_>::new();
-    let a = gr.add_node("A");
-    let b = gr.add_node("B");
-    let c = gr.add_node("C");
-    let d = gr.add_node("D");
-    let e = gr.add_node("E");
-    let f = gr.add_node("F");
-    let g = gr.add_node("G");
-    gr.add_edge(a, b, 7.0);
-    gr.add_edge(a, d, 5.);
-    gr.add_edge(d, b, 9.);
-    gr.add_edge(b, c, 8.);
-    gr.add_edge(b, e, 7.);
-    gr.add_edge(c, e, 5.);
-    gr.add_edge(d, e, 15.);
-    gr.add_edge(d, f, 6.);
-    gr.add_edge(f, e, 8.);
-    gr.add_edge(f, g, 11.);
-    gr.add_edge(e, g, 9.);
+fn tree_forest_dag()
+{
+    use petgraph::algo::{is_tree, is_forest, is_dag};
 
-    assert_eq!(gr.node_weights_mut().count(), gr.node_count());
-    assert_eq!(gr.edge_weights_mut().count(), gr.edge_count());
+    let empty = Graph::<(), ()>::new();
+    assert!(!is_tree(&empty));
+    assert!(is_forest(&empty));
+    assert!(is_dag(&empty));
 
-    // add a disjoint part
-    let h = gr.add_node("H");
-    let i = gr.add_node("I");
-    let j = gr.add_node("J");
-    gr.add_edge(h, i, 1.);
-    gr.add_edge(h, j, 3.);
-    gr.add_edge(i, j, 1.);
+    let mut gr = Graph::<(), ()>::new();
+    gr.add_node(());
+    assert!(is_tree(&gr));
+    assert!(is_forest(&gr));
+    gr.add_node(());
+    assert!(!is_tree(&gr));
+    assert!(is_forest(&gr));
+
+    let mut gr: Graph<(), ()> = Graph::from_edges(&[(0, 1), (0, 2), (2, 3)]);
+    assert!(is_tree(&gr));
+    assert!(is_forest(&gr));
+    assert!(is_dag(&gr));
+    let ungr = gr.clone().into_edge_type::<Undirected>();
+    assert!(is_tree(&ungr));
+    assert!(!is_dag(&ungr));
+
+    // a second edge between 3 and 2, in the other direction
+    gr.add_edge(n(3), n(2), ());
+    assert!(!is_tree(&gr));
+    assert!(!is_forest(&gr));
+    assert!(!is_dag(&gr));
+
+    // a diamond is acyclic, but not a forest
+    let gr: Graph<(), ()> = Graph::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3)]);
+    assert!(!is_forest(&gr));
+    assert!(is_dag(&gr));
+
+    let mut gr: Graph<(), ()> = Graph::from_edges(&[(0, 1), (2, 3)]);
+    assert!(!is_tree(&gr));
+    assert!(is_forest(&gr));
+    gr.add_edge(n(3), n(3), ());
+    assert!(!is_forest(&gr));
+    assert!(!is_dag(&gr));
+}
 
-    assert_eq!(gr.node_weights_mut().count(), gr.node_count());
-    assert_eq!(gr.edge_weights_mut().count(), gr.edge_count());
+#[test]
+fn topological_layers()
+{
+    use petgraph::algo::{topological_layers, topological_layer_numbers};
 
-    for nw in gr.node_weights_mut() {
-        *nw = "x";
+    let mut gr: Graph<(), ()> = Graph::from_edges(&[
+        (0, 1), (1, 2), (0, 2), (3, 2),
+    ]);
+    gr.add_node(());
+    assert_eq!(topological_layers(&gr).unwrap(),
+               vec![vec![n(4), n(3), n(0)], vec![n(1)], vec![n(2)]]);
+    assert_eq!(topological_layer_numbers(&gr).unwrap(), vec![0, 1, 2, 0, 0]);
+
+    gr.add_edge(n(2), n(0), ());
+    assert!(topological_layers(&gr).is_err());
+    assert!(topological_layer_numbers(&gr).is_err());
+    assert!(topological_layers(&Graph::<(), ()>::new()).unwrap().is_empty());
+}
+
+#[test]
+fn graph_summary()
+{
+    use petgraph::algo::{summary, GraphSummary};
+
+    let empty = summary(&Graph::<(), ()>::new());
+    assert_eq!(empty, GraphSummary {
+        node_count: 0,
+        edge_count: 0,
+        self_loops: 0,
+        parallel_edges: 0,
+        density: 0.,
+        min_degree: 0,
+        max_degree: 0,
+        mean_degree: 0.,
+        connected_components: 0,
+        largest_component_size: 0,
+        is_dag: Some(true),
+    });
+
+    // a multigraph with a self loop, parallel edges and an isolated node
+    let mut gr: Graph<(), ()> = Graph::from_edges(&[
+        (0, 1), (1, 0), (1, 2), (1, 2), (2, 2), (3, 4),
+    ]);
+    gr.add_node(());
+    let s = summary(&gr);
+    assert_eq!(s, GraphSummary {
+        node_count: 6,
+        edge_count: 6,
+        self_loops: 1,
+        parallel_edges: 1,
+        density: 0.2,
+        min_degree: 0,
+        max_degree: 4,
+        mean_degree: 2.,
+        connected_components: 3,
+        largest_component_size: 3,
+        is_dag: Some(false),
+    });
+    assert_eq!(s.to_string(), "\
+nodes:                6
+edges:                6
+self loops:           1
+parallel edges:       1
+density:              0.2000
+degree:               min 0, max 4, mean 2.00
+connected components: 3 (largest: 3 nodes)
+acyclic:              no
+");
+
+    let ungr = gr.into_edge_type::<Undirected>();
+    let s = summary(&ungr);
+    assert_eq!(s.parallel_edges, 2);
+    assert_eq!(s.density, 0.4);
+    assert_eq!(s.is_dag, None);
+    assert!(!s.to_string().contains("acyclic"));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn random_walk()
+{
+    use petgraph::algo::RandomWalk;
+    use rand::{SeedableRng, XorShiftRng};
+
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    // a path ends the walk
+    let path: Graph<(), ()> = Graph::from_edges(&[(0, 1), (1, 2)]);
+    assert_eq!(RandomWalk::new(&path, n(0), &mut rng).collect::<Vec<_>>(),
+               vec![n(0), n(1), n(2)]);
+    assert_eq!(RandomWalk::new(&path, n(2), &mut rng).collect::<Vec<_>>(),
+               vec![n(2)]);
+
+    // a cycle with a chord: every step follows an edge
+    let gr: Graph<(), u32> = Graph::from_edges(&[
+        (0, 1, 1), (1, 2, 1), (2, 0, 1), (0, 2, 0),
+    ]);
+    let walk = RandomWalk::new(&gr, n(0), &mut rng).take(100).collect::<Vec<_>>();
+    assert_eq!(walk.len(), 100);
+    for pair in walk.windows(2) {
+        assert!(gr.contains_edge(pair[0], pair[1]));
     }
-    for node in gr.raw_nodes() {
-        assert_eq!(node.weight, "x");
+    assert!(walk.windows(2).any(|w| w == [n(0), n(2)]));
+    // the chord has weight zero, so it is never taken
+    let walk = RandomWalk::with_weights(&gr, n(0), &mut rng, |e| *e.weight() as f64)
+        .take(100).collect::<Vec<_>>();
+    assert!(!walk.windows(2).any(|w| w == [n(0), n(2)]));
+
+    // undirected graphs walk both ways
+    let ungr = path.into_edge_type::<Undirected>();
+    let walk = RandomWalk::new(&ungr, n(2), &mut rng).take(50).collect::<Vec<_>>();
+    assert_eq!(walk.len(), 50);
+    assert!(walk.contains(&n(0)));
+
+    // seeded walks are reproducible
+    let seeded = |seed| {
+        let rng = XorShiftRng::from_seed([seed, 2, 3, 4]);
+        RandomWalk::new(&gr, n(0), rng).take(20).collect::<Vec<_>>()
+    };
+    assert_eq!(seeded(7), seeded(7));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn biased_random_walks()
+{
+    use petgraph::algo::{biased_random_walks, RandomWalk};
+    use rand::{SeedableRng, XorShiftRng};
+
+    let mut rng = XorShiftRng::from_seed([1, 2, 3, 4]);
+    // walks end at dead ends, and edges of weight zero are not followed
+    let path: Graph<(), u32> = Graph::from_edges(&[(0, 1, 1), (1, 2, 1), (2, 3, 0)]);
+    let walks = biased_random_walks(&path, &mut rng, 2, 5, 1., 1., |e| *e.weight() as f64);
+    assert_eq!(walks, vec![
+        vec![n(0), n(1), n(2)], vec![n(1), n(2)], vec![n(2)], vec![n(3)],
+        vec![n(0), n(1), n(2)], vec![n(1), n(2)], vec![n(2)], vec![n(3)],
+    ]);
+    let walks = biased_random_walks(&path, &mut rng, 1, 2, 1., 1., |_| 1.);
+    assert_eq!(walks[0], vec![n(0), n(1)]);
+    assert_eq!(biased_random_walks(&path, &mut rng, 1, 0, 1., 1., |_| 1.)[0], vec![]);
+
+    // a triangle with a tail
+    let gr = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4)]);
+    let walks = biased_random_walks(&gr, &mut rng, 10, 20, 0.5, 2., |_| 1.);
+    assert_eq!(walks.len(), 50);
+    for walk in &walks {
+        assert_eq!(walk.len(), 20);
+        for pair in walk.windows(2) {
+            assert!(gr.contains_edge(pair[0], pair[1]));
+        }
     }
 
-    let old = gr.clone();
-    for (index, ew) in gr.edge_weights_mut().enumerate() {
-        assert_eq!(old[EdgeIndex::new(index)], *ew);
-        *ew = - *ew;
+    // seeded walks are reproducible
+    let seeded = |seed| {
+        let mut rng = XorShiftRng::from_seed([seed, 2, 3, 4]);
+        biased_random_walks(&gr, &mut rng, 3, 10, 2., 0.5, |_| 1.)
+    };
+    assert_eq!(seeded(7), seeded(7));
+
+    // a small p makes the walk return: each node of a cycle has one other
+    // neighbor, 100 times less likely
+    let cycle = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+    let walks = biased_random_walks(&cycle, &mut rng, 200, 3, 0.01, 1., |_| 1.);
+    let returns = walks.iter().filter(|w| w[2] == w[0]).count();
+    assert!(returns > walks.len() * 95 / 100);
+    // a small q makes the walk move away
+    let walks = biased_random_walks(&cycle, &mut rng, 200, 3, 1., 0.01, |_| 1.);
+    let returns = walks.iter().filter(|w| w[2] == w[0]).count();
+    assert!(returns < walks.len() * 5 / 100);
+
+    // with p = q = 1, the distribution of the fourth node matches RandomWalk
+    let samples = 20000;
+    let mut biased = vec![0; 5];
+    for walk in biased_random_walks(&gr, &mut rng, samples, 4, 1., 1., |_| 1.) {
+        if walk[0] == n(0) {
+            biased[walk[3].index()] += 1;
+        }
     }
-    for (index, edge) in gr.raw_edges().iter().enumerate() {
-        assert_eq!(edge.weight, -1. * old[EdgeIndex::new(index)]);
+    let mut plain = vec![0; 5];
+    for _ in 0..samples {
+        let last = RandomWalk::new(&gr, n(0), &mut rng).nth(3).unwrap();
+        plain[last.index()] += 1;
+    }
+    for i in 0..5 {
+        let diff = (biased[i] as f64 - plain[i] as f64) / samples as f64;
+        assert!(diff.abs() < 0.02, "node {}: {:?} vs {:?}", i, biased, plain);
     }
 }
 
 #[test]
-fn walk_edges() {
-    let mut gr = Graph::<_,_>::new();
-    let a = gr.add_node(0.);
-    let b = gr.add_node(1.);
-    let c = gr.add_node(2.);
-    let d = gr.add_node(3.);
-    let e0 = gr.add_edge(a, b, 0.);
-    let e1 = gr.add_edge(a, d, 0.);
-    let e2 = gr.add_edge(b, c, 0.);
-    let e3 = gr.add_edge(c, a, 0.);
+fn landmarks()
+{
+    use petgraph::algo::Landmarks;
+
+    //   1   2   3
+    // 0 → 1 → 2 → 3
+    //  ↘    9    ↗
+    //    → 4 →
+    let gr: Graph<(), u32> = Graph::from_edges(&[
+        (0, 1, 1), (1, 2, 2), (2, 3, 3), (0, 4, 9), (4, 3, 9),
+    ]);
+    let lm = Landmarks::from_nodes(&gr, &[n(0), n(3)], |e| *e.weight());
+    assert_eq!(lm.landmarks(), &[n(0), n(3)]);
+    // via landmark 0, from 0 to 3: exact
+    assert_eq!(lm.estimate(n(0), n(3)), (6, Some(6)));
+    // 1 → 2: at least d(0, 2) - d(0, 1) = 2, at most d(1, 3) + d(3, 2) = ∞
+    assert_eq!(lm.estimate(n(1), n(2)), (2, None));
+    assert_eq!(lm.estimate(n(2), n(2)), (0, Some(0)));
+    // 3 can't go back to 0
+    assert_eq!(lm.estimate(n(3), n(0)).1, None);
+
+    let found = lm.alt_astar(n(0), n(3), |e| *e.weight()).unwrap();
+    assert_eq!(found.cost, 6);
+    assert_eq!(found.path.nodes(), &[n(0), n(1), n(2), n(3)]);
+    assert!(found.settled <= 4);
+    assert_eq!(lm.alt_astar(n(3), n(0), |e| *e.weight()), None);
+    assert_eq!(lm.alt_astar(n(4), n(1), |e| *e.weight()), None);
+    assert_eq!(lm.alt_astar(n(4), n(4), |e| *e.weight()).unwrap().cost, 0);
+
+    // undirected graphs go both ways
+    let ungr = gr.into_edge_type::<Undirected>();
+    let lm = Landmarks::from_nodes(&ungr, &[n(0)], |e| *e.weight());
+    assert_eq!(lm.estimate(n(3), n(0)), (6, Some(6)));
+    let found = lm.alt_astar(n(3), n(0), |e| *e.weight()).unwrap();
+    assert_eq!(found.path.nodes(), &[n(3), n(2), n(1), n(0)]);
+}
 
-    // Set edge weights to difference: target - source.
-    let mut dfs = Dfs::new(&gr, a);
-    while let Some(source) = dfs.next(&gr) {
-        let mut edges = gr.neighbors_directed(source, Outgoing).detach();
-        while let Some((edge, target)) = edges.next(&gr) {
+#[cfg(feature = "rand")]
+#[test]
+fn landmarks_settle_fewer_nodes()
+{
+    use petgraph::algo::{dijkstra, Landmarks};
+    use rand::{Rng, SeedableRng, XorShiftRng};
+
+    // a grid with random costs
+    let mut rng = XorShiftRng::from_seed([5, 6, 7, 8]);
+    let size = 20;
+    let mut gr = UnGraph::<(), u32>::default();
+    for _ in 0..size * size {
+        gr.add_node(());
+    }
+    for i in 0..size * size {
+        if i % size > 0 {
+            gr.add_edge(n(i - 1), n(i), rng.gen_range(1, 10));
+        }
+        if i >= size {
+            gr.add_edge(n(i - size), n(i), rng.gen_range(1, 10));
+        }
+    }
+    let alt = Landmarks::new(&gr, &mut rng, 4, |e| *e.weight());
+    assert_eq!(alt.landmarks().len(), 4);
+    let plain = Landmarks::from_nodes(&gr, &[], |e| *e.weight());
+    let (mut alt_settled, mut plain_settled) = (0, 0);
+    for _ in 0..20 {
+        let a = n(rng.gen_range(0, size * size));
+        let b = n(rng.gen_range(0, size * size));
+        let cost = dijkstra(&gr, a, Some(b), |e| *e.weight())[&b];
+        let (lower, upper) = alt.estimate(a, b);
+        assert!(lower <= cost && cost <= upper.unwrap());
+        let found = alt.alt_astar(a, b, |e| *e.weight()).unwrap();
+        assert_eq!(found.cost, cost);
+        assert_eq!(found.path.cost(&gr, |e| *e.weight()), Some(cost));
+        alt_settled += found.settled;
+        plain_settled += plain.alt_astar(a, b, |e| *e.weight()).unwrap().settled;
+    }
+    assert!(alt_settled * 2 < plain_settled, "{} vs {}", alt_settled, plain_settled);
+
+    // more landmarks than nodes
+    let small = Graph::<(), u32>::from_edges(&[(0, 1, 1)]);
+    let lm = Landmarks::new(&small, &mut rng, 5, |e| *e.weight());
+    assert_eq!(lm.landmarks().len(), 2);
+}
+
+#[test]
+fn min_cost_max_flow()
+{
+    use petgraph::algo::min_cost_max_flow;
+
+    // A transportation problem: two suppliers with 20 and 30 units, three
+    // consumers that need 10, 25 and 15 units. Edge weights are
+    // (capacity, cost per unit).
+    let (source, s1, s2, c1, c2, c3, sink) = (0u32, 1, 2, 3, 4, 5, 6);
+    let gr: Graph<(), (i32, i32)> = Graph::from_edges(&[
+        (source, s1, (20, 0)), (source, s2, (30, 0)),
+        (s1, c1, (50, 8)), (s1, c2, (50, 6)), (s1, c3, (50, 10)),
+        (s2, c1, (50, 9)), (s2, c2, (50, 12)), (s2, c3, (50, 13)),
+        (c1, sink, (10, 0)), (c2, sink, (25, 0)), (c3, sink, (15, 0)),
+    ]);
+    let (flow, cost, edge_flow) = min_cost_max_flow(&gr, source.into(), sink.into(),
+                                                    |e| e.weight().0,
+                                                    |e| e.weight().1).unwrap();
+    assert_eq!(flow, 50);
+    assert_eq!(cost, 465);
+    assert_eq!(edge_flow, vec![20, 30, 0, 20, 0, 10, 5, 15, 10, 25, 15]);
+
+    // negative costs
+    let gr: Graph<(), (i32, i32)> = Graph::from_edges(&[
+        (0, 1, (1, 5)), (0, 2, (1, 1)), (1, 3, (1, -10)), (2, 3, (2, 0)),
+    ]);
+    let (flow, cost, _) = min_cost_max_flow(&gr, n(0), n(3),
+                                            |e| e.weight().0,
+                                            |e| e.weight().1).unwrap();
+    assert_eq!((flow, cost), (2, -4));
+    assert_eq!(min_cost_max_flow(&gr, n(3), n(0), |e| e.weight().0, |e| e.weight().1)
+                   .unwrap().0, 0);
+
+    // a cycle of negative cost
+    let gr: Graph<(), (f64, f64)> = Graph::from_edges(&[
+        (0, 1, (1., 1.)), (1, 2, (1., -3.)), (2, 1, (1., 1.)),
+    ]);
+    assert!(min_cost_max_flow(&gr, n(0), n(2), |e| e.weight().0, |e| e.weight().1)
+                .is_err());
+}
+
+#[test]
+fn max_weight_bipartite_matching()
+{
+    use petgraph::algo::max_weight_bipartite_matching;
+
+    // three workers (0, 1, 2) and two jobs (3, 4)
+    let gr: UnGraph<(), i32> = Graph::from_edges(&[
+        (0, 3, 4), (0, 4, 7), (1, 3, 5), (2, 4, 6), (2, 3, 1),
+    ]);
+    let left = [n(0), n(1), n(2)];
+    let (pairs, total) = max_weight_bipartite_matching(&gr, &left, |e| *e.weight());
+    assert_eq!(total, 12);
+    assert_eq!(pairs, vec![(n(0), n(4)), (n(1), n(3))]);
+
+    // the other side on the left; edges are used in either direction
+    let gr: Graph<(), i32> = Graph::from_edges(&[
+        (3, 0, 4), (4, 0, 7), (3, 1, 5), (4, 2, 6), (2, 3, 1),
+    ]);
+    let (pairs, total) = max_weight_bipartite_matching(&gr, &[n(3), n(4)], |e| *e.weight());
+    assert_eq!(total, 12);
+    assert_eq!(pairs, vec![(n(3), n(1)), (n(4), n(0))]);
+
+    // missing edges can't be matched, and negative edges are left out
+    let gr: UnGraph<(), i32> = Graph::from_edges(&[
+        (0, 2, 10), (1, 2, 9), (1, 3, -1),
+    ]);
+    let (pairs, total) = max_weight_bipartite_matching(&gr, &[n(0), n(1)], |e| *e.weight());
+    assert_eq!(total, 10);
+    assert_eq!(pairs, vec![(n(0), n(2))]);
+
+    let gr: UnGraph<(), i32> = Graph::new_undirected();
+    let (pairs, total) = max_weight_bipartite_matching(&gr, &[], |e| *e.weight());
+    assert_eq!((pairs, total), (vec![], 0));
+}
+
+#[test]
+fn maximum_matching()
+{
+    use petgraph::algo::maximum_matching;
+
+    // a blossom: a triangle with a stem on two corners
+    let gr = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (0, 3), (1, 4)]);
+    let m = maximum_matching(&gr);
+    assert_eq!(m.len(), 2);
+    for v in gr.node_indices() {
+        if let Some(w) = m.mate(v) {
+            assert_eq!(m.mate(w), Some(v));
+        }
+    }
+    assert_eq!(gr.node_indices().filter(|&v| !m.contains_node(v)).count(), 1);
+
+    // the Petersen graph has a perfect matching, which a greedy matching
+    // may miss
+    let gr = UnGraph::<(), ()>::from_edges(&[
+        (0, 1), (1, 2), (2, 3), (3, 4), (4, 0),
+        (0, 5), (1, 6), (2, 7), (3, 8), (4, 9),
+        (5, 7), (7, 9), (9, 6), (6, 8), (8, 5),
+    ]);
+    assert_eq!(maximum_matching(&gr).len(), 5);
+
+    // directions, self loops and parallel edges don't matter
+    let mut gr = Graph::<(), ()>::from_edges(&[(0, 0), (1, 0), (1, 0), (1, 1)]);
+    let m = maximum_matching(&gr);
+    assert_eq!(m.edges(), &[EdgeIndex::new(1)]);
+    assert_eq!(m.mate(n(0)), Some(n(1)));
+    gr.add_node(());
+    assert_eq!(maximum_matching(&gr).len(), 1);
+    assert!(maximum_matching(&Graph::<(), ()>::new()).is_empty());
+
+    // all graphs of up to 6 nodes
+    for size in 0..7 {
+        let pairs = (0..size).flat_map(|a| (a + 1..size).map(move |b| (a, b)))
+                             .collect::<Vec<_>>();
+        for mask in 0..1u32 << pairs.len() {
+            let mut gr = UnGraph::<(), ()>::with_capacity(size, pairs.len());
+            let mut adj = vec![vec![false; size]; size];
+            for _ in 0..size {
+                gr.add_node(());
+            }
+            for (i, &(a, b)) in pairs.iter().enumerate() {
+                if mask & (1 << i) != 0 {
+                    gr.add_edge(n(a), n(b), ());
+                    adj[a][b] = true;
+                }
+            }
+            let m = maximum_matching(&gr);
+            assert_eq!(m.len(), brute_force_max_matching(&adj, 0, &mut vec![false; size]));
+            let mut matched = vec![false; size];
+            for &e in m.edges() {
+                let (a, b) = gr.edge_endpoints(e).unwrap();
+                assert!(!matched[a.index()] && !matched[b.index()]);
+                matched[a.index()] = true;
+                matched[b.index()] = true;
+                assert_eq!(m.mate(a), Some(b));
+                assert_eq!(m.mate(b), Some(a));
+            }
+            for v in gr.node_indices() {
+                assert_eq!(m.contains_node(v), matched[v.index()]);
+            }
+        }
+    }
+}
+
+#[test]
+fn path()
+{
+    use petgraph::path::Path;
+
+    let mut gr = Graph::<(), u32>::new();
+    let a = gr.add_node(());
+    let b = gr.add_node(());
+    let c = gr.add_node(());
+    gr.add_edge(a, b, 3);
+    let bc = gr.add_edge(b, c, 5);
+    let bc_cheap = gr.add_edge(b, c, 2);
+    gr.add_edge(b, c, 2);
+
+    // invalid walks
+    assert!(!Path::<NodeIndex>::from(vec![]).is_valid(&gr));
+    assert!(!Path::from(vec![a, c]).is_valid(&gr));
+    assert!(!Path::from(vec![b, a]).is_valid(&gr));
+    assert!(!Path::new(n(3)).is_valid(&gr));
+    assert_eq!(Path::from(vec![a, b, a]).cost(&gr, |e| *e.weight()), None);
+    assert_eq!(Path::from(vec![c, b]).edges(&gr, |e| *e.weight()), None);
+    assert!(Path::new(c).is_valid(&gr));
+    assert_eq!(Path::new(c).cost(&gr, |e| *e.weight()), Some(0));
+
+    // the cheapest of parallel edges, the first one of a tie
+    let path = Path::from(vec![a, b, c]);
+    assert!(path.is_valid(&gr));
+    assert_eq!(path.cost(&gr, |e| *e.weight()), Some(5));
+    assert_eq!(path.edges(&gr, |e| *e.weight()), Some(vec![EdgeIndex::new(0), bc_cheap]));
+    assert_eq!(path.cost(&gr, |e| 10 - *e.weight()), Some(12));
+    assert_eq!(path.edges(&gr, |e| 10 - *e.weight()).unwrap()[1], bc);
+    assert_eq!(path.len(), 2);
+
+    // reversed, the path is a walk in the undirected graph only
+    let mut rev = path.clone();
+    rev.reverse();
+    assert_eq!(rev.nodes(), &[c, b, a]);
+    assert!(!rev.is_valid(&gr));
+    let ungr = gr.clone().into_edge_type::<Undirected>();
+    assert!(rev.is_valid(&ungr));
+    assert_eq!(rev.cost(&ungr, |e| *e.weight()), Some(5));
+
+    // concatenation needs matching endpoints
+    assert_eq!(path.concat(&rev), Some(Path::from(vec![a, b, c, b, a])));
+    assert_eq!(path.concat(&path), None);
+    assert_eq!(path.concat(&Path::from(vec![])), None);
+    assert_eq!(path.concat(&Path::new(c)), Some(path.clone()));
+    assert_eq!(Vec::from(path), vec![a, b, c]);
+}
+
+#[test]
+fn transaction()
+{
+    let mut gr = Graph::<&str, u32>::new();
+    let a = gr.add_node("a");
+    let b = gr.add_node("b");
+    let c = gr.add_node("c");
+    gr.add_edge(a, b, 1);
+    gr.add_edge(b, c, 2);
+
+    // reads see the staged changes, and nothing is applied on error
+    let result: Result<(), _> = gr.transaction(|tx| {
+        let d = tx.add_node("d");
+        assert_eq!(d, n(3));
+        assert_eq!(tx.node_weight(d), Some(&"d"));
+        let cd = tx.add_edge(c, d, 3).unwrap();
+        assert_eq!(tx.edge_endpoints(cd), Some((c, d)));
+        assert_eq!(tx.neighbors(c), vec![d]);
+        assert_eq!(tx.neighbors_directed(d, Incoming), vec![c]);
+        assert!(tx.has_path(a, d));
+
+        assert!(tx.remove_node(b));
+        assert!(!tx.remove_node(b));
+        assert!(!tx.contains_node(b));
+        assert_eq!(tx.node_weight(b), None);
+        assert_eq!(tx.edge_weight(EdgeIndex::new(0)), None);
+        assert_eq!(tx.neighbors(a), vec![]);
+        assert!(!tx.has_path(a, d));
+        assert_eq!((tx.node_count(), tx.edge_count()), (3, 1));
+        // edges to removed nodes can't be added
+        assert_eq!(tx.add_edge(a, b, 4), None);
+        Err("rollback")
+    });
+    assert_eq!(result, Err("rollback"));
+    assert_eq!((gr.node_count(), gr.edge_count()), (3, 2));
+    assert_eq!(gr.neighbors(b).collect::<Vec<_>>(), vec![c]);
+
+    // replace each a → b → c with a → c, applied in one go, in DAG mode
+    fn bypass(gr: &mut Graph<&'static str, u32>) -> Result<usize, &'static str> {
+        gr.transaction(|tx| {
+            let mut bypassed = 0;
+            for i in 0..tx.node_count() {
+                let b = n(i);
+                let preds = tx.neighbors_directed(b, Incoming);
+                let succs = tx.neighbors(b);
+                if preds.len() != 1 || succs.len() != 1 {
+                    continue;
+                }
+                let (a, c) = (preds[0], succs[0]);
+                let w = tx.edge_weight(tx.find_edge(a, b).unwrap()).unwrap()
+                        + tx.edge_weight(tx.find_edge(b, c).unwrap()).unwrap();
+                tx.remove_node(b);
+                if tx.has_path(c, a) {
+                    return Err("cycle");
+                }
+                try!(tx.add_edge(a, c, w).ok_or("missing node"));
+                bypassed += 1;
+            }
+            Ok(bypassed)
+        })
+    }
+    let mut cyclic = gr.clone();
+    cyclic.add_edge(c, a, 5);
+    assert_eq!(bypass(&mut cyclic), Err("cycle"));
+    assert_eq!((cyclic.node_count(), cyclic.edge_count()), (3, 3));
+
+    assert_eq!(bypass(&mut gr), Ok(1));
+    assert_eq!(gr.node_count(), 2);
+    assert_eq!(gr.edge_count(), 1);
+    let (x, y) = gr.edge_endpoints(EdgeIndex::new(0)).unwrap();
+    assert_eq!((gr[x], gr[y], gr[EdgeIndex::new(0)]), ("a", "c", 3));
+
+    // the graph after a commit is the same as after the same changes made
+    // directly, with the additions first
+    let mut direct = gr.clone();
+    let result: Result<_, ()> = gr.transaction(|tx| {
+        let d = tx.add_node("d");
+        let f = tx.add_node("f");
+        tx.add_edge(n(0), d, 7);
+        tx.add_edge(d, f, 8);
+        tx.add_edge(f, f, 9);
+        tx.remove_edge(EdgeIndex::new(0));
+        tx.remove_node(n(1));
+        assert_eq!(tx.neighbors_directed(f, Incoming).len(), 2);
+        Ok(())
+    });
+    assert!(result.is_ok());
+    let d = direct.add_node("d");
+    let f = direct.add_node("f");
+    direct.add_edge(n(0), d, 7);
+    direct.add_edge(d, f, 8);
+    direct.add_edge(f, f, 9);
+    direct.remove_edge(EdgeIndex::new(0));
+    direct.remove_node(n(1));
+    assert_eq!(format!("{:?}", gr), format!("{:?}", direct));
+
+    // undirected self loops are listed once
+    let mut ungr = UnGraph::<(), ()>::from_edges(&[(0, 1)]);
+    let result: Result<_, ()> = ungr.transaction(|tx| {
+        tx.add_edge(n(1), n(1), ());
+        assert_eq!(tx.neighbors(n(1)).len(), 2);
+        assert!(tx.contains_edge(n(1), n(0)));
+        Ok(tx.edges_directed(n(1), Incoming).len())
+    });
+    assert_eq!(result, Ok(2));
+    assert_eq!(ungr.edge_count(), 2);
+}
+
+#[should_panic]
+#[test]
+fn oob_index()
+{
+    let mut gr = Graph::<_, ()>::new();
+    let a = gr.add_node(0);
+    let b = gr.add_node(1);
+    gr.remove_node(a);
+    gr[b];
+}
+
+#[test]
+fn usize_index()
+{
+    let mut gr = Graph::<_, _, Directed, usize>::with_capacity(0, 0);
+    let a = gr.add_node(0);
+    let b = gr.add_node(1);
+    let e = gr.add_edge(a, b, 1.2);
+    let mut dfs = Dfs::new(&gr, a);
+    while let Some(nx) = dfs.next(&gr) {
+        gr[nx] += 1;
+    }
+    assert_eq!(gr[a], 1);
+    assert_eq!(gr[b], 2);
+    assert_eq!(gr[e], 1.2);
+}
+
+#[test]
+fn u8_index()
+{
+    let mut gr = Graph::<_, (), Undirected, u8>::with_capacity(0, 0);
+    for _ in 0..255 {
+        gr.add_node(());
+    }
+}
+
+#[should_panic]
+#[test]
+fn u8_index_overflow()
+{
+    let mut gr = Graph::<_, (), Undirected, u8>::with_capacity(0, 0);
+    for _ in 0..256 {
+        gr.add_node(());
+    }
+}
+
+#[should_panic]
+#[test]
+fn u8_index_overflow_edges()
+{
+    let mut gr = Graph::<_, (), Undirected, u8>::with_capacity(0, 0);
+    let a = gr.add_node('a');
+    let b = gr.add_node('b');
+    for _ in 0..256 {
+        gr.add_edge(a, b, ());
+    }
+}
+
+#[test]
+fn u16_index_algorithms()
+{
+    use std::mem::size_of;
+    assert_eq!(size_of::<NodeIndex<u16>>(), 2);
+    assert_eq!(size_of::<EdgeIndex<u16>>(), 2);
+
+    let mut gr = Graph::<_, _, Directed, u16>::with_capacity(0, 0);
+    let a = gr.add_node("a");
+    let b = gr.add_node("b");
+    let c = gr.add_node("c");
+    gr.add_edge(a, b, 2);
+    gr.add_edge(b, c, 3);
+    gr.add_edge(a, c, 7);
+    let dist = dijkstra(&gr, a, None, |e| *e.weight());
+    assert_eq!(dist[&c], 5);
+    assert_eq!(pg::algo::toposort(&gr, None).ok(), Some(vec![a, b, c]));
+    assert_eq!(pg::algo::kosaraju_scc(&gr).len(), 3);
+    assert!(has_path_connecting(&gr, a, c, None));
+}
+
+#[test]
+fn clear_edges_keeps_capacity()
+{
+    let mut gr = Graph::<_, _>::with_capacity(4, 8);
+    let nodes = (0..4).map(|i| gr.add_node(i)).collect::<Vec<_>>();
+    assert!(gr.capacity().0 >= 4 && gr.capacity().1 >= 8);
+    for round in 0..3 {
+        for i in 0..4 {
+            gr.add_edge(nodes[i], nodes[(i + round + 1) % 4], round);
+        }
+        assert_eq!(gr.edge_count(), 4);
+        assert_graph_consistent(&gr);
+        let capacity = gr.capacity();
+        gr.clear_edges();
+        assert_eq!(gr.capacity(), capacity);
+        assert_eq!(gr.node_count(), 4);
+        assert_eq!(gr.edge_count(), 0);
+        for &a in &nodes {
+            assert_eq!(gr.neighbors_undirected(a).count(), 0);
+        }
+        assert_graph_consistent(&gr);
+    }
+    gr.reserve_edges(20);
+    assert!(gr.capacity().1 >= 20);
+    gr.shrink_to_fit();
+    // shrinking is only a request, but keeps room for what is there
+    assert!(gr.capacity().0 >= gr.node_count());
+    assert!(gr.capacity().1 >= gr.edge_count());
+    gr.clear();
+    assert_eq!(gr.node_count(), 0);
+    assert!(gr.capacity().0 >= 4);
+}
+
+#[test]
+fn from_raw_parts()
+{
+    let mut gr = Graph::<_, _>::new();
+    let a = gr.add_node("a");
+    let b = gr.add_node("b");
+    let c = gr.add_node("c");
+    gr.add_edge(a, b, 1);
+    gr.add_edge(b, c, 2);
+    gr.add_edge(c, a, 3);
+    gr.add_edge(a, b, 4);
+    gr.add_edge(b, b, 5);
+    assert_eq!(gr.raw_nodes()[1].weight(), &"b");
+    assert_eq!(gr.raw_edges()[2].weight(), &3);
+
+    let endpoints = gr.raw_edges().iter()
+                      .map(|e| (e.source(), e.target()))
+                      .collect::<Vec<_>>();
+    let (nodes, edges) = gr.clone().into_nodes_edges();
+    let node_weights = nodes.into_iter().map(|n| n.weight).collect::<Vec<_>>();
+    let edge_weights = edges.into_iter().map(|e| e.weight).collect::<Vec<_>>();
+    let copy = Graph::<_, _>::from_raw_parts(node_weights, edge_weights, &endpoints).unwrap();
+    assert_graph_consistent(&copy);
+    assert_eq!(copy.node_count(), 3);
+    assert_eq!(copy.edge_count(), 5);
+    for x in gr.node_indices() {
+        assert_eq!(copy[x], gr[x]);
+        for &dir in &[Outgoing, Incoming] {
+            assert_eq!(copy.edges_directed(x, dir).map(|e| (e.id(), *e.weight())).collect::<Vec<_>>(),
+                       gr.edges_directed(x, dir).map(|e| (e.id(), *e.weight())).collect::<Vec<_>>());
+        }
+    }
+
+    let err = Graph::<_, _>::from_raw_parts(vec![0, 1], vec![(), ()], &[(a, b), (b, c)]);
+    assert_eq!(err.unwrap_err().edge(), 1);
+}
+
+#[test]
+fn try_add_edge()
+{
+    use petgraph::graph::EdgeAddError;
+    let mut gr = Graph::<_, _, Directed, u8>::with_capacity(0, 0);
+    let a = gr.add_node('a');
+    let b = gr.add_node('b');
+    let c = NodeIndex::new(2);
+    assert_eq!(gr.try_add_edge(c, a, ()), Err(EdgeAddError::SourceMissing));
+    assert_eq!(gr.try_add_edge(a, c, ()), Err(EdgeAddError::TargetMissing));
+    assert_eq!(gr.try_add_edge(c, c, ()), Err(EdgeAddError::SourceMissing));
+    assert_eq!(gr.try_update_edge(a, c, ()), Err(EdgeAddError::TargetMissing));
+    assert_eq!(gr.edge_count(), 0);
+    assert_graph_consistent(&gr);
+
+    let e = gr.try_add_edge(a, b, ()).unwrap();
+    assert_eq!(gr.try_update_edge(a, b, ()), Ok(e));
+    assert_eq!(gr.try_update_edge(b, a, ()), Ok(EdgeIndex::new(1)));
+    for _ in 2..255 {
+        assert!(gr.try_add_edge(a, a, ()).is_ok());
+    }
+    assert_eq!(gr.try_add_edge(a, b, ()), Err(EdgeAddError::IndexExhausted));
+    assert_eq!(gr.try_update_edge(b, b, ()), Err(EdgeAddError::IndexExhausted));
+    assert_eq!(gr.try_update_edge(a, b, ()), Ok(e));
+    assert_eq!(gr.edge_count(), 255);
+    assert_graph_consistent(&gr);
+}
+
+#[test]
+fn update_and_replace_weights()
+{
+    let mut gr = Graph::<_, _>::new();
+    let a = gr.add_node("a");
+    let b = gr.add_node("b");
+    let c = gr.add_node("c");
+    let e = gr.update_edge_with(a, b, 1, |w| *w += 1);
+    assert_eq!(gr[e], 1);
+    assert_eq!(gr.update_edge_with(a, b, 1, |w| *w += 1), e);
+    assert_eq!(gr[e], 2);
+    let f = gr.update_edge_with(b, a, 1, |w| *w += 1);
+    assert!(e != f);
+    assert_eq!(gr.edge_count(), 2);
+
+    assert_eq!(gr.update_edge_replacing(a, b, 10), (e, Some(2)));
+    assert_eq!(gr[e], 10);
+    let (g, old) = gr.update_edge_replacing(b, c, 5);
+    assert_eq!(old, None);
+    assert_eq!(gr[g], 5);
+    assert_eq!(gr.edge_count(), 3);
+
+    assert_eq!(gr.replace_node_weight(a, "x"), Some("a"));
+    assert_eq!(gr.replace_node_weight(n(3), "y"), None);
+    gr.swap_node_weights(a, c);
+    gr.swap_node_weights(b, b);
+    assert_eq!((gr[a], gr[b], gr[c]), ("c", "b", "x"));
+    assert_eq!(gr.find_edge(a, b), Some(e));
+    assert_graph_consistent(&gr);
+
+    let mut un = Graph::<(), i32, Undirected>::new_undirected();
+    let a = un.add_node(());
+    let b = un.add_node(());
+    un.update_edge_with(a, b, 1, |w| *w += 1);
+    let e = un.update_edge_with(b, a, 1, |w| *w += 1);
+    assert_eq!(un.edge_count(), 1);
+    assert_eq!(un[e], 2);
+}
+
+#[test]
+fn test_weight_iterators() {
+    let mut gr = Graph::<_,_>::new();
+    let a = gr.add_node("A");
+    let b = gr.add_node("B");
+    let c = gr.add_node("C");
+    let d = gr.add_node("D");
+    let e = gr.add_node("E");
+    let f = gr.add_node("F");
+    let g = gr.add_node("G");
+    gr.add_edge(a, b, 7.0);
+    gr.add_edge(a, d, 5.);
+    gr.add_edge(d, b, 9.);
+    gr.add_edge(b, c, 8.);
+    gr.add_edge(b, e, 7.);
+    gr.add_edge(c, e, 5.);
+    gr.add_edge(d, e, 15.);
+    gr.add_edge(d, f, 6.);
+    gr.add_edge(f, e, 8.);
+    gr.add_edge(f, g, 11.);
+    gr.add_edge(e, g, 9.);
+
+    assert_eq!(gr.node_weights_mut().count(), gr.node_count());
+    assert_eq!(gr.edge_weights_mut().count(), gr.edge_count());
+
+    // add a disjoint part
+    let h = gr.add_node("H");
+    let i = gr.add_node("I");
+    let j = gr.add_node("J");
+    gr.add_edge(h, i, 1.);
+    gr.add_edge(h, j, 3.);
+    gr.add_edge(i, j, 1.);
+
+    assert_eq!(gr.node_weights_mut().count(), gr.node_count());
+    assert_eq!(gr.edge_weights_mut().count(), gr.edge_count());
+
+    for nw in gr.node_weights_mut() {
+        *nw = "x";
+    }
+    for node in gr.raw_nodes() {
+        assert_eq!(node.weight, "x");
+    }
+
+    let old = gr.clone();
+    for (index, ew) in gr.edge_weights_mut().enumerate() {
+        assert_eq!(old[EdgeIndex::new(index)], *ew);
+        *ew = - *ew;
+    }
+    for (index, edge) in gr.raw_edges().iter().enumerate() {
+        assert_eq!(edge.weight, -1. * old[EdgeIndex::new(index)]);
+    }
+}
+
+#[test]
+fn walk_edges() {
+    let mut gr = Graph::<_,_>::new();
+    let a = gr.add_node(0.);
+    let b = gr.add_node(1.);
+    let c = gr.add_node(2.);
+    let d = gr.add_node(3.);
+    let e0 = gr.add_edge(a, b, 0.);
+    let e1 = gr.add_edge(a, d, 0.);
+    let e2 = gr.add_edge(b, c, 0.);
+    let e3 = gr.add_edge(c, a, 0.);
+
+    // Set edge weights to difference: target - source.
+    let mut dfs = Dfs::new(&gr, a);
+    while let Some(source) = dfs.next(&gr) {
+        let mut edges = gr.neighbors_directed(source, Outgoing).detach();
+        while let Some((edge, target)) = edges.next(&gr) {
             gr[edge] = gr[target] - gr[source];
         }
     }
@@ -960,6 +2303,46 @@ fn index_twice_mut() {
     }
 }
 
+#[test]
+fn index_twice_mut_nodes() {
+    let mut gr = Graph::<_, _>::new();
+    let a = gr.add_node(vec![1, 2]);
+    let b = gr.add_node(vec![3]);
+    let e = gr.add_edge(a, b, 0);
+    {
+        // move the value of one node to another
+        let (wa, wb) = gr.index_twice_mut(a, b);
+        wb.extend(wa.drain(..));
+    }
+    assert_eq!(gr[a], vec![]);
+    assert_eq!(gr[b], vec![3, 1, 2]);
+    {
+        let (wb, we) = gr.index_twice_mut(b, e);
+        *we = wb.len();
+    }
+    assert_eq!(gr[e], 3);
+    gr[e] += 1;
+    assert_eq!(gr[e], 4);
+}
+
+#[should_panic]
+#[test]
+fn index_twice_mut_same_node() {
+    let mut gr = Graph::<_, ()>::new();
+    let a = gr.add_node(0);
+    gr.index_twice_mut(a, a);
+}
+
+#[should_panic]
+#[test]
+fn oob_edge_index() {
+    let mut gr = Graph::<_, _>::new();
+    let a = gr.add_node(0);
+    let e = gr.add_edge(a, a, 1);
+    gr.remove_edge(e);
+    gr[e];
+}
+
 #[test]
 fn toposort_generic() {
     // This is a DAG, visit it in order
@@ -1020,6 +2403,56 @@ fn toposort_generic() {
     assert!(pg::algo::toposort(&gr2, None).is_err());
 }
 
+#[test]
+fn topo_incomplete() {
+    let mut gr = Graph::<_,_>::new();
+    let b = gr.add_node(("B", 0.));
+    let a = gr.add_node(("A", 0.));
+    let c = gr.add_node(("C", 0.));
+    let d = gr.add_node(("D", 0.));
+    let e = gr.add_node(("E", 0.));
+    let f = gr.add_node(("F", 0.));
+    let g = gr.add_node(("G", 0.));
+    gr.add_edge(a, b, 7.0);
+    gr.add_edge(a, d, 5.);
+    gr.add_edge(d, b, 9.);
+    gr.add_edge(b, c, 8.);
+    gr.add_edge(b, e, 7.);
+    gr.add_edge(c, e, 5.);
+    gr.add_edge(d, e, 15.);
+    gr.add_edge(d, f, 6.);
+    gr.add_edge(f, e, 8.);
+    gr.add_edge(f, g, 11.);
+    gr.add_edge(e, g, 9.);
+    let mut gr2 = gr.clone();
+    gr.add_edge(e, d, -1.);
+    gr2.add_edge(d, d, 0.);
+
+    // the traversal stops early at the cycles, and knows it
+    for graph in &[&gr, &gr2] {
+        let mut topo = Topo::new(*graph);
+        let mut count = 0;
+        while let Some(nx) = topo.next(*graph) {
+            assert!(nx != d);
+            count += 1;
+        }
+        assert_eq!(topo.visited_count(), count);
+        assert!(count < graph.node_count());
+        assert!(!topo.is_complete(*graph));
+    }
+    let mut topo = Topo::new(&gr);
+    while let Some(nx) = topo.next(&gr) {
+        // weights can change during the traversal
+        gr[nx].1 += 1.;
+    }
+    gr.remove_edge(gr.find_edge(e, d).unwrap());
+    topo.reset(&gr);
+    assert_eq!(topo.visited_count(), 0);
+    while let Some(_) = topo.next(&gr) { }
+    assert_eq!(topo.visited_count(), gr.node_count());
+    assert!(topo.is_complete(&gr));
+}
+
 #[test]
 fn test_has_path() {
     // This is a DAG, visit it in order
@@ -1065,6 +2498,36 @@ fn test_has_path() {
     assert!(!has_path_connecting(&gr, h, a, Some(&mut state)));
 }
 
+#[test]
+fn map_into_map() {
+    let mut g = Graph::<String, Vec<u8>>::new();
+    for i in 0..5 {
+        g.add_node(i.to_string());
+    }
+    for &(a, b) in &[(0, 1), (1, 2), (2, 0), (0, 1), (3, 3), (4, 2), (2, 4)] {
+        g.add_edge(n(a), n(b), vec![a as u8, b as u8]);
+    }
+    g.remove_node(n(1));
+
+    let mapped = g.map(|_, w| w.len(), |i, w| (i.index(), w.len()));
+    let moved = g.clone().into_map(|_, w| w + "!", |_, w| w);
+    for a in g.node_indices() {
+        assert_eq!(moved[a], g[a].clone() + "!");
+        for b in g.node_indices() {
+            assert_eq!(mapped.find_edge(a, b), g.find_edge(a, b));
+            assert_eq!(moved.find_edge(a, b), g.find_edge(a, b));
+        }
+        assert_eq!(mapped.edges(a).map(|e| e.id()).collect::<Vec<_>>(),
+                   g.edges(a).map(|e| e.id()).collect::<Vec<_>>());
+        assert_eq!(moved.neighbors(a).collect::<Vec<_>>(), g.neighbors(a).collect::<Vec<_>>());
+    }
+    for e in g.edge_indices() {
+        assert_eq!(mapped[e], (e.index(), 2));
+        assert_eq!(moved[e], g[e]);
+    }
+    assert_graph_consistent(&moved);
+}
+
 #[test]
 fn map_filter_map() {
     let mut g = Graph::new_undirected();
@@ -1116,6 +2579,43 @@ fn map_filter_map() {
     assert_graph_consistent(&g4);
 }
 
+#[test]
+fn filter_map_with_mapping() {
+    let mut g = Graph::new_undirected();
+    let a = g.add_node("A");
+    let b = g.add_node("B");
+    let c = g.add_node("C");
+    let d = g.add_node("D");
+    let e = g.add_node("E");
+    let f = g.add_node("F");
+    g.add_edge(a, b, 7);
+    g.add_edge(c, a, 9);
+    g.add_edge(a, d, 14);
+    g.add_edge(b, c, 10);
+    g.add_edge(d, c, 2);
+    g.add_edge(d, e, 9);
+    g.add_edge(b, f, 15);
+    g.add_edge(c, f, 11);
+    g.add_edge(e, f, 6);
+
+    // with the mapping to the new indices
+    let (g5, map) = g.filter_map_with_mapping(
+        |i, &name| if i == a || i == e { None } else { Some(name) },
+        |_, &weight| Some(weight));
+    assert_eq!(map.len(), g.node_count());
+    assert_eq!(map[a.index()], None);
+    assert_eq!(map[e.index()], None);
+    for i in g.node_indices() {
+        if let Some(j) = map[i.index()] {
+            assert_eq!(g5[j], g[i]);
+        }
+    }
+    let (b5, c5) = (map[b.index()].unwrap(), map[c.index()].unwrap());
+    assert_eq!(g5.find_edge(b5, c5).map(|e| g5[e]), Some(10));
+    assert_eq!(g5.edge_count(), g.edge_count() - 5);
+    assert_graph_consistent(&g5);
+}
+
 #[test]
 fn from_edges() {
     let n = NodeIndex::new;
@@ -1133,6 +2633,54 @@ fn from_edges() {
     assert_graph_consistent(&gr);
 }
 
+#[test]
+fn from_edges_weighted() {
+    let mut gr = Graph::<(), i32>::from_edges(&[(0, 1, 4), (0, 2, 7)]);
+    assert_eq!(gr.node_count(), 3);
+    assert_eq!(gr.edge_count(), 2);
+    assert_eq!(gr.find_edge(n(0), n(2)).map(|e| gr[e]), Some(7));
+
+    // nodes are added up to the largest endpoint
+    gr.extend_with_edges(&[(2, 5, 1)]);
+    assert_eq!(gr.node_count(), 6);
+    assert_eq!(gr.edge_count(), 3);
+    assert_eq!(gr.neighbors_undirected(n(4)).count(), 0);
+    assert_graph_consistent(&gr);
+
+    #[cfg(feature = "graphmap")]
+    {
+        let gm = pg::graphmap::DiGraphMap::<u32, i32>::from_edges(&[(0, 1, 4), (0, 2, 7)]);
+        assert_eq!(gm.node_count(), 3);
+        assert_eq!(gm.edge_weight(0, 2), Some(&7));
+    }
+}
+
+#[test]
+fn retain_nodes_complete() {
+    // K8, with even and odd node weights
+    let mut gr = Graph::<usize, (), Undirected>::new_undirected();
+    for i in 0..8 {
+        gr.add_node(i);
+    }
+    for i in 0..8 {
+        for j in i + 1..8 {
+            gr.add_edge(n(i), n(j), ());
+        }
+    }
+    assert_eq!(gr.edge_count(), 28);
+    gr.retain_nodes(|gr, i| gr[i] % 2 == 0);
+    assert_eq!(gr.node_count(), 4);
+    // the edges of K4 are left
+    assert_eq!(gr.edge_count(), 6);
+    let mut weights = gr.node_indices().map(|i| gr[i]).collect::<Vec<_>>();
+    weights.sort();
+    assert_eq!(weights, vec![0, 2, 4, 6]);
+    for i in gr.node_indices() {
+        assert_eq!(gr.neighbors(i).count(), 3);
+    }
+    assert_graph_consistent(&gr);
+}
+
 #[test]
 fn retain() {
     let mut gr = Graph::<i32, i32, Undirected>::from_edges(&[
@@ -1328,6 +2876,70 @@ fn neighbor_order() {
                vec![b, c, c, a]);
 }
 
+#[test]
+fn neighbors_into() {
+    let mut gr = Graph::new();
+    let a = gr.add_node("a");
+    let b = gr.add_node("b");
+    let c = gr.add_node("c");
+    gr.add_edge(a, b, 0);
+    gr.add_edge(a, b, 1);
+    gr.add_edge(a, a, 2);
+    gr.add_edge(c, a, 3);
+    gr.add_edge(b, c, 4);
+
+    fn check<Ty: EdgeType>(gr: &Graph<&str, i32, Ty>) {
+        // start from a non-empty buffer, it must be cleared
+        let mut nbuf = vec![n(7)];
+        let mut ebuf = Vec::new();
+        for node in gr.node_indices() {
+            gr.neighbors_into(node, &mut nbuf);
+            assert_eq!(nbuf, gr.neighbors(node).collect::<Vec<_>>());
+            gr.edges_into(node, &mut ebuf);
+            assert_eq!(ebuf.iter().map(|e| e.id()).collect::<Vec<_>>(),
+                       gr.edges(node).map(|e| e.id()).collect::<Vec<_>>());
+        }
+    }
+    check(&gr);
+    check(&gr.into_edge_type::<Undirected>());
+}
+
+#[test]
+fn directions() {
+    assert_eq!(Direction::all(), [Outgoing, Incoming]);
+    for (i, &d) in Direction::all().iter().enumerate() {
+        assert_eq!(d.index(), i);
+        assert!(d.opposite() != d);
+        assert_eq!(d.opposite().opposite(), d);
+    }
+    assert!(Outgoing < Incoming);
+    assert_eq!(set(Direction::all().iter().cloned()).len(), 2);
+
+    let mut gr = Graph::new();
+    let a = gr.add_node("a");
+    let b = gr.add_node("b");
+    let c = gr.add_node("c");
+    gr.add_edge(a, b, 0);
+    gr.add_edge(c, a, 1);
+    gr.add_edge(a, c, 2);
+    for &d in &Direction::all() {
+        // walking the edge lists by hand matches edges_directed
+        let mut walked = Vec::new();
+        let mut next = gr.first_edge(a, d);
+        while let Some(e) = next {
+            walked.push(e);
+            next = gr.next_edge(e, d);
+        }
+        assert_eq!(walked, gr.edges_directed(a, d).map(|e| e.id()).collect::<Vec<_>>());
+        // an edge leaving `a` in direction `d` arrives in the opposite one
+        for e in gr.edges_directed(a, d) {
+            let other = if d == Outgoing { e.target() } else { e.source() };
+            assert!(gr.edges_directed(other, d.opposite()).any(|f| f.id() == e.id()));
+            assert!(gr.neighbors_directed(other, d.opposite()).any(|n| n == a));
+        }
+    }
+}
+
 #[test]
 fn dot() {
     // test alternate formatting
@@ -1458,6 +3070,187 @@ fn dfs_visit() {
     assert_eq!(&path, &[n(0), n(2), n(4)]);
 }
 
+#[test]
+fn dfs_visit_prune() {
+    use petgraph::visit::{Control, DfsEvent, depth_first_search};
+
+    let gr: Graph<(), ()> = Graph::from_edges(&[
+        (0, 1), (1, 2), (0, 3), (3, 4), (4, 1), (3, 5),
+    ]);
+    // prune at discovering 1, and at the tree edge to 5
+    let mut events = Vec::new();
+    depth_first_search(&gr, Some(n(0)), |event| {
+        events.push(event);
+        match event {
+            DfsEvent::Discover(u, _) if u == n(1) => Control::Prune,
+            DfsEvent::TreeEdge(_, v) if v == n(5) => Control::<()>::Prune,
+            _ => Control::Continue,
+        }
+    });
+    let discovered = events.iter().filter_map(|e| match *e {
+        DfsEvent::Discover(u, _) => Some(u.index()),
+        _ => None,
+    }).collect::<Vec<_>>();
+    let finished = events.iter().filter_map(|e| match *e {
+        DfsEvent::Finish(u, _) => Some(u.index()),
+        _ => None,
+    }).collect::<Vec<_>>();
+    // 1's edge to 2 is skipped, and 5 isn't visited
+    assert_eq!(set(discovered.iter().cloned()), set(vec![0, 1, 3, 4]));
+    assert_eq!(set(finished.iter().cloned()), set(vec![0, 1, 3, 4]));
+    // the pruned node finishes right after it is discovered
+    let pos = events.iter().position(|e| match *e {
+        DfsEvent::Discover(u, _) => u == n(1),
+        _ => false,
+    }).unwrap();
+    match events[pos + 1] {
+        DfsEvent::Finish(u, _) => assert_eq!(u, n(1)),
+        e => panic!("{:?}", e),
+    }
+
+    // a pruned tree edge target can still be reached by another edge
+    // (the edge to 2 is visited first: neighbors are in reverse edge order)
+    let gr: Graph<(), ()> = Graph::from_edges(&[(0, 1), (0, 2), (1, 2)]);
+    let mut tree_edges = Vec::new();
+    depth_first_search(&gr, Some(n(0)), |event| {
+        if let DfsEvent::TreeEdge(u, v) = event {
+            tree_edges.push((u.index(), v.index()));
+            if u == n(0) && v == n(2) {
+                return Control::<()>::Prune;
+            }
+        }
+        Control::Continue
+    });
+    assert_eq!(tree_edges, vec![(0, 2), (0, 1), (1, 2)]);
+}
+
+#[test]
+fn dfs_visit_deep() {
+    use petgraph::visit::{DfsEvent, depth_first_search};
+
+    // a path this long would overflow the stack in a recursive search
+    let len = 1_000_000;
+    let mut gr = Graph::<(), ()>::with_capacity(len, len);
+    gr.add_node(());
+    for i in 1..len {
+        gr.add_node(());
+        gr.add_edge(n(i - 1), n(i), ());
+    }
+    gr.add_edge(n(len - 1), n(0), ());
+    let mut back_edges = 0;
+    let mut finished = 0;
+    depth_first_search(&gr, Some(n(0)), |event| {
+        match event {
+            DfsEvent::BackEdge(u, v) => {
+                assert_eq!((u, v), (n(len - 1), n(0)));
+                back_edges += 1;
+            }
+            DfsEvent::Finish(..) => finished += 1,
+            _ => {}
+        }
+    });
+    assert_eq!(back_edges, 1);
+    assert_eq!(finished, len);
+}
+
+#[test]
+fn bfs_visit() {
+    use petgraph::visit::{BfsEvent, Control, Time, breadth_first_search};
+    use petgraph::visit::BfsEvent::*;
+
+    //   0 -> 1 -> 3
+    //   0 -> 2 -> 3 -> 4, and 5 -> 4
+    let gr = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3), (3, 4), (5, 4)]);
+    let mut events = Vec::new();
+    breadth_first_search(&gr, Some(n(0)), |e| events.push(e));
+    let rendered = events.iter().map(|e| match *e {
+        Discover(u, Time(t)) => format!("d{}@{}", u.index(), t),
+        TreeEdge(u, v) => format!("t{}{}", u.index(), v.index()),
+        NonTreeEdge(u, v) => format!("n{}{}", u.index(), v.index()),
+        Finish(u, Time(t)) => format!("f{}@{}", u.index(), t),
+    }).collect::<Vec<_>>();
+    assert_eq!(rendered, vec!["d0@0", "t02", "d2@1", "t01", "d1@2", "f0@3",
+                              "t23", "d3@4", "f2@5", "n13", "f1@6",
+                              "t34", "d4@7", "f3@8", "f4@9"]);
+
+    // several starts are searched together
+    let mut depth = vec![None; gr.node_count()];
+    breadth_first_search(&gr, vec![n(5), n(0)], |e| match e {
+        Discover(u, _) if depth[u.index()].is_none() => {
+            depth[u.index()] = Some(0)
+        }
+        TreeEdge(u, v) => depth[v.index()] = depth[u.index()].map(|d| d + 1),
+        _ => {}
+    });
+    assert_eq!(depth, vec![Some(0), Some(1), Some(1), Some(2), Some(1), Some(0)]);
+
+    // pruning a tree edge lets the node be found through another edge
+    let mut tree = Vec::new();
+    breadth_first_search(&gr, Some(n(0)), |e| {
+        if let TreeEdge(u, v) = e {
+            if (u, v) == (n(2), n(3)) {
+                return Control::<()>::Prune;
+            }
+            tree.push((u.index(), v.index()));
+        }
+        Control::Continue
+    });
+    assert_eq!(tree, vec![(0, 2), (0, 1), (1, 3), (3, 4)]);
+
+    // break with the first node at distance two
+    let mut parent = vec![None; gr.node_count()];
+    let found = breadth_first_search(&gr, Some(n(0)), |e| {
+        if let TreeEdge(u, v) = e {
+            parent[v.index()] = Some(u);
+            if parent[u.index()].is_some() {
+                return Control::Break(v);
+            }
+        }
+        Control::Continue
+    });
+    assert_eq!(found.break_value(), Some(n(3)));
+
+    // pruning at discovery finishes the node right away
+    let events = {
+        let mut events = Vec::new();
+        breadth_first_search(&gr, Some(n(0)), |e| {
+            events.push(e);
+            match e {
+                BfsEvent::Discover(..) => Control::<()>::Prune,
+                _ => Control::Continue,
+            }
+        });
+        events
+    };
+    assert_eq!(events.len(), 2);
+    if let BfsEvent::Finish(u, Time(1)) = events[1] {
+        assert_eq!(u, n(0));
+    } else {
+        panic!("expected finish, got {:?}", events[1]);
+    }
+}
+
+#[test]
+fn multi_source_bfs() {
+    use petgraph::visit::multi_source_bfs;
+
+    // 2 is as close to 0 as to 4
+    let gr = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4), (5, 6)]);
+    let labels = multi_source_bfs(&gr, vec![n(0), n(4)]);
+    assert_eq!(labels, vec![Some((0, 0)), Some((0, 1)), Some((0, 2)), Some((1, 1)),
+                            Some((1, 0)), None, None]);
+    let labels = multi_source_bfs(&gr, vec![n(4), n(0)]);
+    assert_eq!(labels[2], Some((0, 2)));
+
+    // a repeated source keeps its first position
+    let labels = multi_source_bfs(&gr, vec![n(5), n(0), n(5)]);
+    assert_eq!(labels[5], Some((0, 0)));
+    assert_eq!(labels[6], Some((0, 1)));
+    assert_eq!(labels[4], Some((1, 4)));
+
+    assert!(multi_source_bfs(&gr, None).iter().all(|l| l.is_none()));
+}
+
 
 #[test]
 fn filtered_post_order() {
@@ -1702,3 +3495,1242 @@ fn test_dominators_simple_fast() {
     assert_eq!(doms.immediate_dominator(z), None,
                "nodes that aren't reachable from the root do not have an idom");
 }
+
+#[test]
+fn weisfeiler_lehman_hash() {
+    use petgraph::algo::weisfeiler_lehman_hash;
+    use petgraph::visit::NodeRef;
+    fn hash<Ty: EdgeType>(g: &Graph<&str, u8, Ty>) -> u64 {
+        weisfeiler_lehman_hash(g, |n| *n.weight(), |e| *e.weight(), 3)
+    }
+
+    // ethanol, without hydrogens: C - C - O
+    let mut ethanol = Graph::new_undirected();
+    let c1 = ethanol.add_node("C");
+    let c2 = ethanol.add_node("C");
+    let o = ethanol.add_node("O");
+    ethanol.add_edge(c1, c2, 1);
+    ethanol.add_edge(c2, o, 1);
+
+    // the same, with the nodes and edges in another order
+    let mut permuted = Graph::new_undirected();
+    let o = permuted.add_node("O");
+    let c2 = permuted.add_node("C");
+    let c1 = permuted.add_node("C");
+    permuted.add_edge(o, c2, 1);
+    permuted.add_edge(c1, c2, 1);
+    assert_eq!(hash(&ethanol), hash(&permuted));
+
+    // the hash is the same on every run and platform
+    assert_eq!(hash(&ethanol), 100403617772480967);
+
+    // near misses: dimethyl ether, C - O - C
+    let mut ether = Graph::new_undirected();
+    let c1 = ether.add_node("C");
+    let o = ether.add_node("O");
+    let c2 = ether.add_node("C");
+    ether.add_edge(c1, o, 1);
+    ether.add_edge(o, c2, 1);
+    assert!(hash(&ethanol) != hash(&ether));
+
+    // acetaldehyde: C - C = O
+    let mut acetaldehyde = ethanol.clone();
+    acetaldehyde[EdgeIndex::new(1)] = 2;
+    assert!(hash(&ethanol) != hash(&acetaldehyde));
+
+    // labels can be left out
+    assert_eq!(weisfeiler_lehman_hash(&ethanol, |_| (), |_| (), 3),
+               weisfeiler_lehman_hash(&ether, |_| (), |_| (), 3));
+
+    // a path and a star of four nodes
+    let path = Graph::<&str, u8, Undirected>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+    let star = Graph::<&str, u8, Undirected>::from_edges(&[(0, 1), (0, 2), (0, 3)]);
+    assert!(hash(&path) != hash(&star));
+
+    // the direction of an edge counts
+    let forward = Graph::<&str, u8>::from_edges(&[(0, 1), (1, 2)]);
+    let inward = Graph::<&str, u8>::from_edges(&[(0, 1), (2, 1)]);
+    let reversed = Graph::<&str, u8>::from_edges(&[(1, 0), (2, 1)]);
+    assert!(hash(&forward) != hash(&inward));
+    assert_eq!(hash(&forward), hash(&reversed));
+}
+
+#[test]
+fn tsp() {
+    use petgraph::algo::{tsp_christofides_like, tsp_nearest_neighbor};
+
+    // the corners of a square, with the sides costing 1 and the diagonals 2
+    let square = Graph::<(), u32, Undirected>::from_edges(&[
+        (0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 1),
+        (0, 2, 2), (1, 3, 2),
+    ]);
+    let tour = vec![n(0), n(1), n(2), n(3)];
+    assert_eq!(tsp_nearest_neighbor(&square, n(0), |e| *e.weight(), false),
+               Some((tour.clone(), 4)));
+    assert_eq!(tsp_christofides_like(&square, |e| *e.weight(), false),
+               Some((tour, 4)));
+    assert_eq!(tsp_nearest_neighbor(&square, n(2), |e| *e.weight(), false),
+               Some((vec![n(2), n(1), n(0), n(3)], 4)));
+
+    // a path has no tour, unless the way back may take shortest paths
+    let path = Graph::<(), u32, Undirected>::from_edges(&[(0, 1, 1), (1, 2, 1), (2, 3, 1)]);
+    assert_eq!(tsp_nearest_neighbor(&path, n(0), |e| *e.weight(), false), None);
+    assert_eq!(tsp_christofides_like(&path, |e| *e.weight(), false), None);
+    let tour = vec![n(0), n(1), n(2), n(3)];
+    assert_eq!(tsp_nearest_neighbor(&path, n(0), |e| *e.weight(), true),
+               Some((tour.clone(), 6)));
+    assert_eq!(tsp_christofides_like(&path, |e| *e.weight(), true),
+               Some((tour, 6)));
+
+    // a directed cycle, in its own direction
+    let cycle = Graph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 2), (2, 0, 3)]);
+    assert_eq!(tsp_nearest_neighbor(&cycle, n(1), |e| *e.weight(), false),
+               Some((vec![n(1), n(2), n(0)], 6)));
+    assert_eq!(tsp_christofides_like(&cycle, |e| *e.weight(), false),
+               Some((vec![n(0), n(1), n(2)], 6)));
+
+    // unreachable nodes
+    let mut disconnected = path.clone();
+    disconnected.add_node(());
+    assert_eq!(tsp_nearest_neighbor(&disconnected, n(0), |e| *e.weight(), true), None);
+    assert_eq!(tsp_christofides_like(&disconnected, |e| *e.weight(), true), None);
+
+    // a single node
+    let mut single = Graph::<(), u32>::new();
+    single.add_node(());
+    assert_eq!(tsp_nearest_neighbor(&single, n(0), |e| *e.weight(), false),
+               Some((vec![n(0)], 0)));
+    assert_eq!(tsp_christofides_like(&single, |e| *e.weight(), false),
+               Some((vec![n(0)], 0)));
+    // a start node that is not in the graph
+    assert_eq!(tsp_nearest_neighbor(&single, n(1), |e| *e.weight(), false), None);
+}
+
+#[test]
+fn complement() {
+    let g = Graph::<&str, (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 2)]);
+    let c = g.complement(false);
+    assert_eq!(c.node_count(), 3);
+    assert_eq!(c.edge_count(), 1);
+    assert!(c.contains_edge(n(0), n(2)));
+    // n (n - 1) / 2 edges between them
+    assert_eq!(g.edge_count() - 1 + c.edge_count(), 3);
+
+    let c = g.complement(true);
+    assert_eq!(c.edge_count(), 3);
+    assert!(c.contains_edge(n(0), n(0)));
+    assert!(c.contains_edge(n(1), n(1)));
+    assert!(!c.contains_edge(n(2), n(2)));
+
+    // node weights are kept
+    let mut g = Graph::<_, ()>::new();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    g.add_edge(a, b, ());
+    let c = g.complement(false);
+    assert_eq!(c[a], "a");
+    assert_eq!(c[b], "b");
+    assert_eq!(c.edge_count(), 1);
+    assert!(c.contains_edge(b, a));
+    assert_eq!(c.complement(false).edge_count(), 1);
+    assert!(c.complement(false).contains_edge(a, b));
+}
+
+#[test]
+fn extend_from_graph() {
+    let mut g = Graph::<&str, i32>::new();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    g.add_edge(a, b, 1);
+
+    let mut h = Graph::<&str, i32>::new();
+    let x = h.add_node("x");
+    let y = h.add_node("y");
+    let z = h.add_node("z");
+    h.add_edge(x, y, 2);
+    h.add_edge(y, z, 3);
+    h.add_edge(y, z, 4);
+
+    let map = g.extend_from_graph(&h);
+    assert_eq!(map, vec![n(2), n(3), n(4)]);
+    assert_eq!(g.node_count(), 5);
+    assert_eq!(g.edge_count(), 4);
+    assert_eq!(g[map[z.index()]], "z");
+    assert_eq!(g.edge_endpoints(EdgeIndex::new(1)), Some((map[x.index()], map[y.index()])));
+    // parallel edges are kept
+    assert_eq!(g.edges(map[y.index()]).filter(|e| e.target() == map[z.index()]).count(), 2);
+    assert!(!g.contains_edge(a, map[x.index()]));
+
+    // a graph with itself
+    let copy = g.clone();
+    g.extend_from_graph(&copy);
+    assert_eq!(g.node_count(), 10);
+    assert_eq!(g.edge_count(), 8);
+}
+
+#[test]
+fn union() {
+    let mut g = Graph::<&str, i32, Undirected>::new_undirected();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    g.add_edge(a, b, 1);
+
+    let mut h = Graph::<&str, i32, Undirected>::new_undirected();
+    let b2 = h.add_node("b");
+    let c = h.add_node("c");
+    let a2 = h.add_node("a");
+    h.add_edge(b2, c, 2);
+    h.add_edge(a2, b2, 3);
+
+    let map = g.union(&h, |w| *w);
+    assert_eq!(map, vec![b, n(2), a]);
+    assert_eq!(g.node_count(), 3);
+    assert_eq!(g[n(2)], "c");
+    assert_eq!(g.edge_count(), 3);
+    assert!(g.contains_edge(b, n(2)));
+    // the shared edge is now there twice
+    assert_eq!(g.edges(a).filter(|e| e.target() == b).count(), 2);
+
+    // by a key of the weight
+    let mut g = Graph::<(u32, char), ()>::new();
+    g.add_node((1, 'a'));
+    let mut h = Graph::<(u32, char), ()>::new();
+    let x = h.add_node((1, 'b'));
+    let y = h.add_node((2, 'c'));
+    h.add_edge(x, y, ());
+    let map = g.union(&h, |w| w.0);
+    assert_eq!(map, vec![n(0), n(1)]);
+    assert_eq!(g[n(0)], (1, 'a'));
+    assert!(g.contains_edge(n(0), n(1)));
+}
+
+#[test]
+fn graph_products() {
+    use petgraph::algo::{cartesian_product, tensor_product, CartesianEdge};
+
+    // the product of paths of 3 and 4 nodes is a 3 × 4 grid
+    let p3 = Graph::<u8, &str, Undirected>::from_edges(&[(0, 1, "a"), (1, 2, "b")]);
+    let mut p4 = Graph::<char, u32, Undirected>::from_edges(&[(0, 1, 1), (1, 2, 2), (2, 3, 3)]);
+    for (i, w) in p4.node_weights_mut().enumerate() {
+        *w = (b'w' + i as u8) as char;
+    }
+    let (grid, map) = cartesian_product(&p3, &p4);
+    assert_eq!(grid.node_count(), 12);
+    // 3 rows of 3 edges and 4 columns of 2 edges
+    assert_eq!(grid.edge_count(), 3 * 3 + 4 * 2);
+    let corner = map.index(n(0), n(0));
+    let middle = map.index(n(1), n(1));
+    assert_eq!(grid.neighbors(corner).count(), 2);
+    assert_eq!(grid.neighbors(middle).count(), 4);
+    assert_eq!(map.factors(middle), (n(1), n(1)));
+    assert_eq!(grid[map.index(n(2), n(3))], (0, 'z'));
+    let e = grid.find_edge(map.index(n(1), n(2)), map.index(n(2), n(2))).unwrap();
+    assert_eq!(grid[e], CartesianEdge::First("b"));
+    let e = grid.find_edge(map.index(n(1), n(2)), map.index(n(1), n(3))).unwrap();
+    assert_eq!(grid[e], CartesianEdge::Second(3));
+    for p in grid.node_indices() {
+        let (a, b) = map.factors(p);
+        assert_eq!(map.index(a, b), p);
+    }
+
+    // two directed cycles give a directed torus
+    let c3 = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    let (torus, map) = cartesian_product(&c3, &c3);
+    assert_eq!(torus.edge_count(), 18);
+    for p in torus.node_indices() {
+        assert_eq!(torus.neighbors_directed(p, Outgoing).count(), 2);
+        assert_eq!(torus.neighbors_directed(p, Incoming).count(), 2);
+    }
+    assert!(torus.contains_edge(map.index(n(2), n(1)), map.index(n(0), n(1))));
+
+    // the tensor product of an edge with a triangle is a hexagon
+    let k2 = Graph::<(), i32, Undirected>::from_edges(&[(0, 1, 1)]);
+    let k3 = Graph::<(), i32, Undirected>::from_edges(&[(0, 1, 2), (1, 2, 3), (2, 0, 4)]);
+    let (hexagon, map) = tensor_product(&k2, &k3);
+    assert_eq!(hexagon.node_count(), 6);
+    assert_eq!(hexagon.edge_count(), 6);
+    for p in hexagon.node_indices() {
+        assert_eq!(hexagon.neighbors(p).count(), 2);
+    }
+    let e = hexagon.find_edge(map.index(n(0), n(1)), map.index(n(1), n(2))).unwrap();
+    assert_eq!(hexagon[e], (1, 3));
+    assert!(!hexagon.contains_edge(map.index(n(0), n(0)), map.index(n(1), n(0))));
+
+    // directed tensor products follow both edges
+    let (product, map) = tensor_product(&c3, &c3);
+    assert_eq!(product.edge_count(), 9);
+    assert!(product.contains_edge(map.index(n(0), n(1)), map.index(n(1), n(2))));
+    assert!(!product.contains_edge(map.index(n(0), n(1)), map.index(n(1), n(0))));
+}
+
+#[test]
+fn contract_edge() {
+    // a - b - c, a - c, with a self loop at b
+    let mut g = Graph::<String, i32, Undirected>::new_undirected();
+    let a = g.add_node("a".to_string());
+    let b = g.add_node("b".to_string());
+    let c = g.add_node("c".to_string());
+    let ab = g.add_edge(a, b, 1);
+    g.add_edge(b, c, 2);
+    g.add_edge(a, c, 3);
+    g.add_edge(b, b, 4);
+    g.add_edge(b, a, 5);
+
+    let merged = g.contract_edge(ab, |x, y| x + &y, false).unwrap();
+    assert_eq!(merged, a);
+    assert_eq!(g.node_count(), 2);
+    assert_eq!(g[a], "ab");
+    // c took the place of b
+    let c = n(1);
+    assert_eq!(g[c], "c");
+    // the other edge between a and b is dropped; the self loop of b and
+    // the parallel edges to c are kept
+    let mut weights = g.raw_edges().iter().map(|e| e.weight).collect::<Vec<_>>();
+    weights.sort();
+    assert_eq!(weights, vec![2, 3, 4]);
+    assert_eq!(g.edges(c).filter(|e| e.target() == a).count(), 2);
+    assert!(g.contains_edge(a, a));
+
+    // keeping the self loops
+    let mut g = Graph::<i32, (), Directed>::new();
+    let a = g.add_node(1);
+    let b = g.add_node(2);
+    let c = g.add_node(4);
+    let ab = g.add_edge(a, b, ());
+    g.add_edge(b, a, ());
+    g.add_edge(c, b, ());
+    g.add_edge(b, c, ());
+    let merged = g.contract_edge(ab, |x, y| x + y, true).unwrap();
+    assert_eq!(merged, a);
+    assert_eq!(g[a], 3);
+    assert_eq!(g.edge_count(), 3);
+    assert!(g.contains_edge(a, a));
+    let c = n(1);
+    assert!(g.contains_edge(c, a));
+    assert!(g.contains_edge(a, c));
+
+    // a self loop is only removed
+    let l = g.find_edge(a, a).unwrap();
+    assert_eq!(g.contract_edge(l, |x, _| x, true), Some(a));
+    assert_eq!(g.node_count(), 2);
+    assert_eq!(g.edge_count(), 2);
+    assert_eq!(g.contract_edge(EdgeIndex::new(5), |x, _| x, true), None);
+}
+
+#[test]
+fn merge_nodes() {
+    // the merged node is the last one, and takes the place of the removed
+    let mut g = Graph::<Vec<u8>, u8>::new();
+    let a = g.add_node(vec![0]);
+    let b = g.add_node(vec![1]);
+    let c = g.add_node(vec![2]);
+    g.add_edge(a, c, 0);
+    g.add_edge(c, b, 1);
+    g.add_edge(b, a, 2);
+    let merged = g.merge_nodes(c, a, |mut x, y| { x.extend(y); x }, false);
+    assert_eq!(merged, a);
+    assert_eq!(g.node_count(), 2);
+    assert_eq!(g[merged], vec![2, 0]);
+    assert_eq!(g[b], vec![1]);
+    assert_eq!(g.edge_count(), 2);
+    assert!(g.contains_edge(merged, b));
+    assert!(g.contains_edge(b, merged));
+
+    // the same node
+    assert_eq!(g.merge_nodes(b, b, |x, _| x, false), b);
+    assert_eq!(g.node_count(), 2);
+}
+
+#[test]
+fn descendants_ancestors() {
+    use petgraph::algo::{ancestors, ancestors_into, descendants, descendants_into};
+    fn sorted(mut v: Vec<NodeIndex>) -> Vec<NodeIndex> {
+        v.sort();
+        v
+    }
+
+    // 0 → 1 → 2 → 3, 1 → 4, and a cycle 5 → 6 → 5 reachable from 3
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (1, 4), (3, 5), (5, 6), (6, 5)]);
+    assert_eq!(sorted(descendants(&g, n(1), None)), vec![n(2), n(3), n(4), n(5), n(6)]);
+    assert_eq!(sorted(ancestors(&g, n(3), None)), vec![n(0), n(1), n(2)]);
+    assert_eq!(descendants(&g, n(4), None), vec![]);
+    assert_eq!(ancestors(&g, n(0), None), vec![]);
+    // the node itself is left out, even on a cycle
+    assert_eq!(descendants(&g, n(5), None), vec![n(6)]);
+    assert_eq!(sorted(ancestors(&g, n(5), None)), vec![n(0), n(1), n(2), n(3), n(6)]);
+
+    // a workspace and output buffer for many calls
+    let mut space = DfsSpace::new(&g);
+    let mut out = Vec::new();
+    for a in g.node_indices() {
+        descendants_into(&g, a, &mut out, &mut space);
+        assert_eq!(out, descendants(&g, a, None));
+        for &b in &out {
+            assert!(has_path_connecting(&g, a, b, Some(&mut space)));
+        }
+        ancestors_into(&g, a, &mut out, &mut space);
+        assert_eq!(out, ancestors(&g, a, Some(&mut space)));
+        for &b in &out {
+            assert!(has_path_connecting(&g, b, a, None));
+        }
+    }
+}
+
+#[test]
+fn minimum_path_cover() {
+    use petgraph::algo::minimum_path_cover;
+    use petgraph::path::Path;
+
+    // two chains 0 → 1 → 2 and 3 → 4, with 3 → 1 crossing over
+    let g = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (3, 4), (3, 1)]);
+    let paths = minimum_path_cover(&g).unwrap();
+    assert_eq!(paths.len(), 2);
+    let mut nodes = paths.iter().flat_map(|p| p).cloned().collect::<Vec<_>>();
+    nodes.sort();
+    assert_eq!(nodes, g.node_indices().collect::<Vec<_>>());
+    for path in &paths {
+        assert!(path.is_valid(&g));
+    }
+
+    // a star needs a path for all but one leaf
+    let star = Graph::<(), ()>::from_edges(&[(0, 1), (0, 2), (0, 3)]);
+    let paths = minimum_path_cover(&star).unwrap();
+    assert_eq!(paths.len(), 3);
+    assert!(paths.iter().any(|p| p.len() == 1 && p.start() == Some(n(0))));
+
+    // nodes without edges are paths of their own
+    let mut g = Graph::<(), ()>::new();
+    g.add_node(());
+    g.add_node(());
+    assert_eq!(minimum_path_cover(&g).unwrap(), vec![Path::new(n(0)), Path::new(n(1))]);
+
+    // a cycle is an error
+    let cyclic = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    assert!(minimum_path_cover(&cyclic).is_err());
+}
+
+#[test]
+fn bfs_spanning_forest() {
+    use petgraph::algo::bfs_spanning_forest;
+
+    // 0 - 1 - 2 - 3 - 4, 0 - 5 - 4, and 6 - 7 apart
+    let mut g = Graph::<(), (), Undirected>::new_undirected();
+    for _ in 0..9 {
+        g.add_node(());
+    }
+    let e01 = g.add_edge(n(0), n(1), ());
+    let e12 = g.add_edge(n(1), n(2), ());
+    let e23 = g.add_edge(n(2), n(3), ());
+    let e34 = g.add_edge(n(3), n(4), ());
+    let e05 = g.add_edge(n(0), n(5), ());
+    let e54 = g.add_edge(n(5), n(4), ());
+    let e67 = g.add_edge(n(6), n(7), ());
+
+    let forest = bfs_spanning_forest(&g, &[n(0), n(7)]);
+    assert_eq!(forest, vec![
+        None,
+        Some((n(0), e01)),
+        Some((n(1), e12)),
+        Some((n(4), e34)),
+        Some((n(5), e54)),
+        Some((n(0), e05)),
+        Some((n(7), e67)),
+        None,
+        // not reached
+        None,
+    ]);
+
+    // a node goes to the nearest root
+    let forest = bfs_spanning_forest(&g, &[n(0), n(3)]);
+    assert_eq!(forest[2], Some((n(3), e23)));
+    assert_eq!(forest[4], Some((n(3), e34)));
+    assert_eq!(forest[5], Some((n(0), e05)));
+    assert_eq!(forest[6], None);
+
+    // directed edges are followed forwards only
+    let d = Graph::<(), ()>::from_edges(&[(0, 1), (2, 1), (1, 3)]);
+    let forest = bfs_spanning_forest(&d, &[n(0)]);
+    assert_eq!(forest.iter().map(|p| p.map(|(a, _)| a)).collect::<Vec<_>>(),
+               vec![None, Some(n(0)), None, Some(n(1))]);
+}
+
+#[test]
+fn tree_diameter() {
+    use petgraph::algo::tree_diameter;
+    use petgraph::path::Path;
+
+    //        0
+    //      /   \
+    //     1     2
+    //    / \     \
+    //   3   4     5
+    //            /
+    //           6
+    let g = Graph::<(), u32, Undirected>::from_edges(&[
+        (0, 1, 1), (0, 2, 1), (1, 3, 1), (1, 4, 1), (2, 5, 1), (5, 6, 1),
+    ]);
+    let (cost, path) = tree_diameter(&g, |_| 1).unwrap();
+    assert_eq!(cost, 5);
+    assert_eq!(path.len(), 5);
+    // from 3 or 4 to 6
+    let mut ends = vec![path.start().unwrap(), path.end().unwrap()];
+    ends.sort();
+    assert!(ends == vec![n(3), n(6)] || ends == vec![n(4), n(6)]);
+    assert!(path.is_valid(&g));
+
+    // with costs, the long leaf edge wins
+    let (cost, path) = tree_diameter(&g, |e| if e.target() == n(4) { 10 } else { 1 }).unwrap();
+    assert_eq!(cost, 14);
+    assert_eq!(path.len(), 5);
+    assert!(path.start() == Some(n(4)) || path.end() == Some(n(4)));
+
+    // a single node
+    let mut single = Graph::<(), u32>::new();
+    single.add_node(());
+    assert_eq!(tree_diameter(&single, |e| *e.weight()).unwrap(), (0, Path::new(n(0))));
+
+    // a directed tree is treated as undirected
+    let d = Graph::<(), u32>::from_edges(&[(1, 0, 2), (2, 0, 3)]);
+    let (cost, path) = tree_diameter(&d, |e| *e.weight()).unwrap();
+    assert_eq!(cost, 5);
+    assert!(path.nodes() == &[n(1), n(0), n(2)] || path.nodes() == &[n(2), n(0), n(1)]);
+
+    // not trees
+    let cycle = Graph::<(), u32, Undirected>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    assert!(tree_diameter(&cycle, |_| 1).is_err());
+    let mut forest = g.clone();
+    forest.add_node(());
+    assert!(tree_diameter(&forest, |_| 1).is_err());
+    assert!(tree_diameter(&Graph::<(), u32>::new(), |_| 1).is_err());
+}
+
+#[test]
+fn to_undirected() {
+    // 0 → 1 → 2, with 1 → 0 back
+    let mut g = Graph::<&str, u32>::new();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    let c = g.add_node("c");
+    g.add_edge(a, b, 4);
+    g.add_edge(b, c, 1);
+    g.add_edge(b, a, 3);
+    g.add_edge(c, c, 7);
+
+    // dijkstra over the undirected view can use edges in both directions
+    let dist = dijkstra(&g, c, None, |e| *e.weight());
+    assert_eq!(dist.get(&a), None);
+    let view = g.clone().into_edge_type::<Undirected>();
+    assert_eq!(view.edge_count(), 4);
+    assert_eq!(view.edges(a).filter(|e| e.target() == b).count(), 2);
+    let dist = dijkstra(&view, c, None, |e| *e.weight());
+    assert_eq!(dist[&a], 4);
+    assert_eq!(dist[&b], 1);
+
+    // merging the antiparallel pair into one edge
+    let u = g.to_undirected(|x, y| Some(if x < y { *x } else { *y }));
+    assert_eq!(u.node_count(), 3);
+    assert_eq!(u[a], "a");
+    assert_eq!(u.edge_count(), 3);
+    assert_eq!(u.edges(a).filter(|e| e.target() == b).map(|e| *e.weight())
+                .collect::<Vec<_>>(), vec![3]);
+    assert!(u.contains_edge(c, c));
+    assert_eq!(u[EdgeIndex::new(0)], 3);
+
+    // keeping both
+    let u = g.to_undirected(|_, _| None);
+    assert_eq!(u.edge_count(), 4);
+    assert_eq!(u.raw_edges().iter().map(|e| e.weight).collect::<Vec<_>>(), vec![4, 1, 3, 7]);
+
+    // parallel edges pair up one to one
+    let g = Graph::<(), u32>::from_edges(&[(0, 1, 1), (0, 1, 2), (1, 0, 10), (1, 0, 20), (1, 0, 30)]);
+    let u = g.to_undirected(|x, y| Some(x + y));
+    assert_eq!(u.raw_edges().iter().map(|e| e.weight).collect::<Vec<_>>(), vec![11, 22, 30]);
+}
+
+#[test]
+fn edge_endpoints() {
+    let mut g = Graph::<(), (), Undirected>::new_undirected();
+    let a = g.add_node(());
+    let b = g.add_node(());
+    let e = g.add_edge(b, a, ());
+    // in the order they were added, even if undirected
+    assert_eq!(g.edge_endpoints(e), Some((b, a)));
+    let found = g.find_edge(a, b).unwrap();
+    assert_eq!(found, e);
+    assert_eq!(g.edge_endpoints(found), Some((b, a)));
+    assert_eq!(g.edge_endpoints(EdgeIndex::new(1)), None);
+    assert_eq!(g.edge_endpoints(EdgeIndex::end()), None);
+}
+
+#[test]
+fn edge_ids_for_mutation() {
+    let mut g = Graph::<(), u32>::new();
+    let a = g.add_node(());
+    let b = g.add_node(());
+    let c = g.add_node(());
+    g.add_edge(a, b, 1);
+    g.add_edge(a, b, 2);
+    g.add_edge(c, a, 3);
+    g.add_edge(a, c, 4);
+
+    // the parallel edges have their own ids
+    let parallel = g.edges_directed(a, Outgoing).filter(|e| e.target() == b)
+                    .map(|e| e.id()).collect::<Vec<_>>();
+    assert_eq!(parallel.len(), 2);
+    for e in g.edges_directed(a, Incoming) {
+        assert_eq!(e.source(), c);
+        assert_eq!(e.target(), a);
+        assert_eq!(*e.weight(), 3);
+        assert_eq!(g.edge_endpoints(e.id()), Some((c, a)));
+    }
+
+    // walk the neighbors with edge ids while changing the graph
+    let mut walker = g.neighbors(a).detach();
+    while let Some((e, n)) = walker.next(&g) {
+        if n == b {
+            g[e] += 10;
+        }
+    }
+    let mut weights = g.edges(a).map(|e| *e.weight()).collect::<Vec<_>>();
+    weights.sort();
+    assert_eq!(weights, vec![4, 11, 12]);
+
+    // and remove the edges found with them, highest index first
+    let mut to_remove = parallel;
+    to_remove.sort();
+    while let Some(e) = to_remove.pop() {
+        g.remove_edge(e);
+    }
+    assert!(!g.contains_edge(a, b));
+    assert_eq!(g.edge_count(), 2);
+}
+
+#[test]
+fn neighbors_undirected_order() {
+    let mut g = Graph::<(), ()>::new();
+    let a = g.add_node(());
+    let b = g.add_node(());
+    let c = g.add_node(());
+    let d = g.add_node(());
+    g.add_edge(a, b, ());
+    g.add_edge(c, a, ());
+    g.add_edge(a, a, ());
+    g.add_edge(a, b, ());
+    g.add_edge(d, a, ());
+    g.add_edge(a, c, ());
+    // outgoing newest first, then incoming newest first, the self loop once
+    assert_eq!(g.neighbors_undirected(a).collect::<Vec<_>>(),
+               vec![c, b, a, b, d, c]);
+    assert_eq!(g.neighbors_undirected(b).collect::<Vec<_>>(), vec![a, a]);
+    let mut walker = g.neighbors_undirected(a).detach();
+    let mut count = 0;
+    while let Some(_) = walker.next_node(&g) {
+        count += 1;
+    }
+    assert_eq!(count, 6);
+}
+
+#[test]
+fn edges_connecting() {
+    let mut g = Graph::<_, _>::new();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    let c = g.add_node("c");
+    let e1 = g.add_edge(a, b, 1);
+    g.add_edge(b, a, 2);
+    let e3 = g.add_edge(a, b, 3);
+    g.add_edge(a, c, 4);
+    let e5 = g.add_edge(a, b, 5);
+    assert!(g.contains_edge(a, b));
+    assert!(!g.contains_edge(c, a));
+    let mut found = g.edges_connecting(a, b)
+                     .map(|e| (e.id(), *e.weight()))
+                     .collect::<Vec<_>>();
+    found.sort();
+    assert_eq!(found, vec![(e1, 1), (e3, 3), (e5, 5)]);
+    assert_eq!(g.edges_connecting(b, a).map(|e| *e.weight()).collect::<Vec<_>>(), vec![2]);
+    assert_eq!(g.edges_connecting(c, a).count(), 0);
+    assert_eq!(g.edges_connecting(n(7), a).count(), 0);
+
+    let mut u = g.into_edge_type::<Undirected>();
+    let d = u.add_node("d");
+    u.add_edge(d, d, 6);
+    assert_eq!(u.edges_connecting(a, b).count(), 4);
+    assert_eq!(u.edges_connecting(b, a).count(), 4);
+    assert_eq!(u.edges_connecting(c, a).map(|e| *e.weight()).collect::<Vec<_>>(), vec![4]);
+    assert_eq!(u.edges_connecting(d, d).map(|e| *e.weight()).collect::<Vec<_>>(), vec![6]);
+}
+
+#[test]
+fn degrees() {
+    let mut g = Graph::<(), ()>::new();
+    let a = g.add_node(());
+    let b = g.add_node(());
+    let c = g.add_node(());
+    g.add_edge(a, b, ());
+    g.add_edge(a, b, ());
+    g.add_edge(c, a, ());
+    g.add_edge(a, a, ());
+    // parallel edges count each, a self loop once in each direction
+    assert_eq!(g.out_degree(a), 3);
+    assert_eq!(g.in_degree(a), 2);
+    assert_eq!(g.degree(a), 5);
+    assert_eq!((g.out_degree(b), g.in_degree(b), g.degree(b)), (0, 2, 2));
+    assert_eq!((g.out_degree(c), g.in_degree(c), g.degree(c)), (1, 0, 1));
+    assert_eq!(g.degree(n(5)), 0);
+
+    let u = g.into_edge_type::<Undirected>();
+    // a self loop counts twice
+    assert_eq!(u.degree(a), 5);
+    assert_eq!(u.out_degree(a), 5);
+    assert_eq!(u.in_degree(a), 5);
+    assert_eq!(u.degree(b), 2);
+    assert_eq!(u.node_indices().map(|x| u.degree(x)).sum::<usize>(), 2 * u.edge_count());
+}
+
+#[test]
+fn walk_neighbors_remove_edges() {
+    let mut g = Graph::<_, _, Undirected>::new_undirected();
+    let nodes = (0..5).map(|i| g.add_node(i)).collect::<Vec<_>>();
+    let mut w = 0;
+    for &a in &nodes {
+        for &b in &nodes {
+            g.add_edge(a, b, w);
+            w += 1;
+        }
+    }
+    let a = nodes[2];
+    let mut expected = g.clone();
+    expected.retain_edges(|g, e| {
+        let (x, y) = g.edge_endpoints(e).unwrap();
+        (x != a && y != a) || g[e] % 3 != 0
+    });
+
+    // remove some edges of `a`, and walk its edges anew after each removal
+    let mut walker = g.neighbors(a).detach();
+    let mut added = false;
+    while let Some((e, n)) = walker.next(&g) {
+        if g[e] % 3 == 0 {
+            g.remove_edge(e);
+            walker = g.neighbors(a).detach();
+        } else if !added && n == nodes[0] {
+            // an edge added during the walk is not visited
+            g.add_edge(a, nodes[4], 1000);
+            added = true;
+        }
+    }
+    assert_graph_consistent(&g);
+    assert_eq!(g.edges(a).filter(|e| *e.weight() % 3 == 0).count(), 0);
+    assert_eq!(g.edge_count(), expected.edge_count() + 1);
+    let weights = |g: &Graph<_, i32, _>| {
+        let mut w = g.raw_edges().iter().map(|e| e.weight).collect::<Vec<_>>();
+        w.sort();
+        w
+    };
+    let mut with_added = weights(&expected);
+    with_added.push(1000);
+    with_added.sort();
+    assert_eq!(weights(&g), with_added);
+}
+
+#[test]
+fn subgraph() {
+    let mut g = Graph::<_, _>::new();
+    let nodes = (0..6).map(|i| g.add_node(i)).collect::<Vec<_>>();
+    // a cycle 0 -> 1 -> 2 -> 0 with an edge 1 -> 0, and a tail 2 -> 3 -> 4 -> 5
+    for &(a, b) in &[(0, 1), (1, 2), (2, 0), (1, 0), (2, 3), (3, 4), (4, 5)] {
+        g.add_edge(nodes[a], nodes[b], (a, b));
+    }
+    let sccs = pg::algo::kosaraju_scc(&g);
+    assert_eq!(sccs.len(), 4);
+    let cycle = sccs.into_iter().find(|scc| scc.len() == 3).unwrap();
+
+    let (sub, original) = g.subgraph(cycle.iter().cloned()).unwrap();
+    assert_graph_consistent(&sub);
+    assert_eq!(sub.node_count(), 3);
+    assert_eq!(sub.edge_count(), 4);
+    assert_eq!(original, cycle);
+    for e in sub.edge_references() {
+        let (a, b) = *e.weight();
+        assert_eq!(original[e.source().index()], nodes[a]);
+        assert_eq!(original[e.target().index()], nodes[b]);
+        assert_eq!(sub[e.source()], a);
+    }
+
+    // repeated nodes are added once, in order of first appearance
+    let (sub, original) = g.subgraph(vec![nodes[3], nodes[2], nodes[3]]).unwrap();
+    assert_eq!(original, vec![nodes[3], nodes[2]]);
+    assert_eq!(sub.edge_count(), 1);
+    assert_eq!(sub.edge_endpoints(EdgeIndex::new(0)), Some((n(1), n(0))));
+
+    let err = g.subgraph(vec![nodes[0], n(6)]).unwrap_err();
+    assert_eq!(err.node_id(), n(6));
+}
+
+#[test]
+fn adjacency_matrix() {
+    use petgraph::visit::GetAdjacencyMatrix;
+
+    let mut g = Graph::<(), i32>::from_edges(&[(0, 1, 5), (1, 2, 7), (2, 2, 9)]);
+    g.add_edge(n(0), n(1), 3);
+    let m = g.adjacency_matrix();
+    let set = (0..9).filter(|&i| m.contains(i)).collect::<Vec<_>>();
+    assert_eq!(set, vec![1, 5, 8]);
+    let w = g.adjacency_matrix_weighted(|_, &w| w);
+    assert_eq!(w, vec![None, Some(5), None,
+                       None, None, Some(7),
+                       None, None, Some(9)]);
+
+    let h = Graph::<(), i32>::from_adjacency_matrix(3, |i, j| w[i * 3 + j]);
+    assert_eq!(h.edge_count(), 3);
+    assert_eq!(h.adjacency_matrix_weighted(|_, &w| w), w);
+    assert_eq!(h.adjacency_matrix(), m);
+
+    // undirected: symmetric, self loops on the diagonal once
+    let u = g.into_edge_type::<Undirected>();
+    let m = u.adjacency_matrix();
+    let set = (0..9).filter(|&i| m.contains(i)).collect::<Vec<_>>();
+    assert_eq!(set, vec![1, 3, 5, 7, 8]);
+    let w = u.adjacency_matrix_weighted(|e, _| e.index());
+    assert_eq!(w, vec![None, Some(0), None,
+                       Some(0), None, Some(1),
+                       None, Some(1), Some(2)]);
+
+    let mut calls = 0;
+    let v = Graph::<(), usize, Undirected>::from_adjacency_matrix(3, |i, j| {
+        calls += 1;
+        assert!(i <= j);
+        w[i * 3 + j]
+    });
+    assert_eq!(calls, 6);
+    assert_eq!(v.edge_count(), 3);
+    assert_eq!(v.adjacency_matrix_weighted(|_, &w| w), w);
+    assert!(v.find_edge(n(2), n(1)).is_some());
+
+    let empty = Graph::<(), ()>::from_adjacency_matrix(0, |_, _| Some(()));
+    assert_eq!(empty.node_count(), 0);
+    assert!(empty.adjacency_matrix_weighted(|_, _| ()).is_empty());
+}
+
+#[test]
+fn node_and_edge_references() {
+    use petgraph::visit::EdgeRef;
+
+    let mut g = Graph::new();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    let c = g.add_node("c");
+    g.add_edge(a, b, 1);
+    g.add_edge(c, a, 2);
+
+    let mut nodes = g.node_references();
+    assert_eq!(nodes.len(), 3);
+    assert_eq!(nodes.next(), Some((a, &"a")));
+    assert_eq!(nodes.len(), 2);
+    assert_eq!(nodes.next_back(), Some((c, &"c")));
+    assert_eq!(nodes.collect::<Vec<_>>(), vec![(b, &"b")]);
+
+    let mut edges = g.edge_references();
+    assert_eq!(edges.len(), 2);
+    let e = edges.next().unwrap();
+    assert_eq!((e.id(), e.source(), e.target(), *e.weight()), (pg::graph::edge_index(0), a, b, 1));
+    let e = edges.next_back().unwrap();
+    assert_eq!((e.id(), e.source(), e.target(), *e.weight()), (pg::graph::edge_index(1), c, a, 2));
+    assert_eq!(edges.len(), 0);
+    assert!(edges.next().is_none());
+}
+
+#[test]
+fn node_index_by_weight() {
+    let mut g = Graph::<_, ()>::new();
+    let a = g.add_node(3);
+    let b = g.add_node(4);
+    let c = g.add_node(3);
+    assert_eq!(g.node_index_by_weight(|&w| w == 3), Some(a));
+    assert_eq!(g.node_index_by_weight(|&w| w == 4), Some(b));
+    assert_eq!(g.node_index_by_weight(|&w| w == 5), None);
+    assert_eq!(g.node_indices_by_weight(|&w| w == 3).collect::<Vec<_>>(), vec![a, c]);
+    assert_eq!(g.node_indices_by_weight(|&w| w > 5).count(), 0);
+}
+
+#[test]
+fn node_index_map() {
+    use petgraph::graph::NodeIndexMap;
+
+    let mut map = NodeIndexMap::<String, u32, Undirected>::new();
+    let a = map.get_or_insert("a".to_string());
+    let b = map.get_or_insert("b".to_string());
+    assert_eq!(map.get_or_insert("a".to_string()), a);
+    assert_eq!(map.get("b"), Some(b));
+    assert_eq!(map.get("c"), None);
+    let e = map.add_edge(a, b, 1);
+    assert_eq!(map.update_edge(b, a, 2), e);
+    *map.edge_weight_mut(e).unwrap() += 1;
+    assert_eq!(map[e], 3);
+    assert_eq!(map[b], "b");
+    assert_eq!(map.node_count(), 2);
+    let g = map.clone().into_graph();
+    assert_eq!(g.node_count(), 2);
+    assert_eq!(g.edge_weight(e), Some(&3));
+
+    let mut g = Graph::<_, ()>::new();
+    g.add_node('x');
+    let y = g.add_node('y');
+    let mut map = NodeIndexMap::from_graph(g.clone()).unwrap();
+    assert_eq!(map.get(&'y'), Some(y));
+    let z = map.get_or_insert('z');
+    assert_eq!(z, n(2));
+    g.add_node('x');
+    assert_eq!(NodeIndexMap::from_graph(g).unwrap_err().node_id(), n(2));
+}
+
+#[test]
+fn frozen() {
+    use petgraph::visit::Dfs;
+
+    let mut g = Graph::<_, _>::from_edges(&[(0, 1, 1.), (1, 2, 2.), (0, 2, 4.)]);
+    let e = g.find_edge(n(1), n(2)).unwrap();
+    {
+        let mut f = g.frozen();
+        // read-only methods through Deref
+        assert_eq!(f.node_count(), 3);
+        assert_eq!(f.edge_count(), 3);
+        assert_eq!(f.find_edge(n(1), n(2)), Some(e));
+        assert_eq!(f.neighbors(n(0)).count(), 2);
+        assert_eq!(f.edge_endpoints(e), Some((n(1), n(2))));
+        assert!(f.contains_edge(n(0), n(2)));
+
+        // graph traits through &Frozen
+        let dist = dijkstra(&f, n(0), None, |e| *e.weight());
+        assert_eq!(dist[&n(2)], 3.);
+        let mut dfs = Dfs::new(&f, n(0));
+        let mut count = 0;
+        while let Some(_) = dfs.next(&f) {
+            count += 1;
+        }
+        assert_eq!(count, 3);
+
+        // weight mutation
+        *f.edge_weight_mut(e).unwrap() = 10.;
+        f[n(1)] = 5;
+        *f.node_weight_mut(n(2)).unwrap() += 1;
+        for w in f.edge_weights_mut() {
+            *w *= 2.;
+        }
+        for w in f.node_weights_mut() {
+            *w += 1;
+        }
+        let (x, y) = f.index_twice_mut(n(0), e);
+        *x += 1;
+        *y += 1.;
+        assert_eq!(f[e], 21.);
+    }
+    assert_eq!(g.node_weights_mut().map(|w| *w).collect::<Vec<_>>(), vec![2, 6, 2]);
+    assert_eq!(g.raw_edges().iter().map(|e| e.weight).collect::<Vec<_>>(), vec![2., 21., 8.]);
+}
+
+#[test]
+fn dot_config_and_attrs() {
+    let mut gr = Graph::new();
+    let a = gr.add_node("a");
+    let b = gr.add_node("b");
+    gr.add_edge(a, b, 7);
+    gr.add_edge(b, b, 8);
+
+    let dot = Dot::with_config(&gr, &[Config::NodeNoLabel, Config::EdgeIndexLabel]);
+    assert_eq!(format!("{}", dot), "\
+digraph {
+    0
+    1
+    0 -> 1 [label=\"0\"]
+    1 -> 1 [label=\"1\"]
+}
+");
+
+    let edge_attrs = |_, e: pg::graph::EdgeReference<i32>| {
+        if e.source() == e.target() { "style=dashed".to_string() } else { String::new() }
+    };
+    let node_attrs = |_, (n, _): (NodeIndex, &&str)| format!("shape=box, id={}", n.index());
+    let dot = Dot::with_attr_getters(&gr, &[Config::EdgeNoLabel], &edge_attrs, &node_attrs);
+    assert_eq!(format!("{}", dot), "\
+digraph {
+    0 [label=\"a\" shape=box, id=0]
+    1 [label=\"b\" shape=box, id=1]
+    0 -> 1
+    1 -> 1 [style=dashed]
+}
+");
+}
+
+/// Check that `dot` is a well formed sequence of DOT statements: strings are
+/// terminated and contain no raw line breaks, and the edge operator
+/// matches the graph type.
+fn assert_dot_syntax(dot: &str) {
+    let mut lines = dot.lines();
+    let header = lines.next().unwrap();
+    let directed = match header {
+        "digraph {" => true,
+        "graph {" => false,
+        _ => panic!("bad header {:?}", header),
+    };
+    assert!(dot.ends_with("}\n"));
+    for line in lines.filter(|&l| l != "}") {
+        let mut in_string = false;
+        let mut outside = String::new();
+        let mut chars = line.chars();
+        while let Some(c) = chars.next() {
+            match (in_string, c) {
+                (true, '\\') => { assert!(chars.next().is_some(), "{:?}", line); }
+                (_, '"') => in_string = !in_string,
+                (true, _) => {}
+                (false, c) => outside.push(c),
+            }
+        }
+        assert!(!in_string, "unterminated string in {:?}", line);
+        let (wrong, right) = if directed { ("--", "->") } else { ("->", "--") };
+        assert!(!outside.contains(wrong), "{:?}", line);
+        let words = outside.split_whitespace().collect::<Vec<_>>();
+        assert!(words.len() == 1 || words[1] == right || words[1].starts_with('['),
+                "{:?}", line);
+    }
+}
+
+#[test]
+fn dot_escaping() {
+    let weights = ["he said \"hi\"\n", "back\\slash", "ünïcødé ⇒", "\\\"", "a\nb\n"];
+    let mut gr = Graph::new();
+    for &w in &weights {
+        gr.add_node(w);
+    }
+    gr.add_edge(n(0), n(1), "\"quoted\" edge\\");
+    gr.add_edge(n(1), n(2), "line\nbreak");
+
+    let display = format!("{}", Dot::new(&gr));
+    assert_dot_syntax(&display);
+    assert!(display.contains(r#"0 [label="he said \"hi\"\l"]"#), "{}", display);
+    assert!(display.contains(r#"1 [label="back\\slash"]"#), "{}", display);
+    assert!(display.contains("2 [label=\"ünïcødé ⇒\"]"), "{}", display);
+    assert!(display.contains(r#"3 [label="\\\""]"#), "{}", display);
+    assert!(display.contains(r#"0 -> 1 [label="\"quoted\" edge\\"]"#), "{}", display);
+
+    // Debug labels are quoted and escaped by Debug first
+    let debug = format!("{:?}", Dot::new(&gr));
+    assert_dot_syntax(&debug);
+    assert!(debug.contains(r#"0 [label="\"he said \\\"hi\\\"\\n\""]"#), "{}", debug);
+    let alternate = format!("{:#?}", Dot::new(&gr));
+    assert_dot_syntax(&alternate);
+    assert!(alternate.contains(r#"0 [label="\"he said \\\"hi\\\"\\n\"\l"]"#), "{}", alternate);
+    assert!(alternate.contains(r#"1 [label="\"back\\\\slash\"\l"]"#), "{}", alternate);
+
+    let un = gr.into_edge_type::<Undirected>();
+    let display = format!("{}", Dot::new(&un));
+    assert_dot_syntax(&display);
+    assert!(display.starts_with("graph {\n"));
+    assert!(display.contains("1 -- 2 [label=\"line\\lbreak\"]"), "{}", display);
+}
+
+#[test]
+fn dot_graph_attributes() {
+    let mut gr = Graph::new_undirected();
+    let a = gr.add_node("a");
+    let b = gr.add_node("b");
+    let c = gr.add_node("c");
+    gr.add_edge(a, b, 1);
+    gr.add_edge(b, c, 2);
+    gr.add_edge(a, c, 4);
+
+    // color the minimum spanning tree, box the leaves
+    let mst = set(min_spanning_tree(&gr).filter_map(|elt| match elt {
+        pg::data::Element::Edge { source, target, .. } => Some((source, target)),
+        _ => None,
+    }));
+    let edge_attrs = |_, e: pg::graph::EdgeReference<i32>| {
+        let (s, t) = (e.source().index(), e.target().index());
+        if mst.contains(&(s, t)) { "color=red".to_string() } else { String::new() }
+    };
+    let node_attrs = |_, (n, _): (NodeIndex, &&str)| {
+        let degree = mst.iter().filter(|&&(s, t)| s == n.index() || t == n.index()).count();
+        if degree == 1 { "shape=box".to_string() } else { String::new() }
+    };
+    let dot = Dot::with_attr_getters(&gr, &[], &edge_attrs, &node_attrs)
+        .graph_attributes("rankdir=LR, splines=ortho")
+        .node_defaults("shape=circle, style=filled")
+        .edge_defaults("color=gray");
+    let output = format!("{}", dot);
+    assert_dot_syntax(&output);
+    assert_eq!(output, include_str!("golden/dot_attributes.dot"));
+
+    // empty extras write nothing
+    let plain = Dot::new(&gr).graph_attributes("").node_defaults("").edge_defaults("");
+    assert_eq!(format!("{}", plain), format!("{}", Dot::new(&gr)));
+}
+
+#[test]
+fn dot_parse_round_trip() {
+    let mut gr = Graph::new();
+    let a = gr.add_node("a");
+    let b = gr.add_node("b c");
+    let c = gr.add_node("\"quoted\"");
+    let d = gr.add_node("ünïcødé");
+    gr.add_edge(a, b, 1);
+    gr.add_edge(b, c, 2);
+    gr.add_edge(c, a, 3);
+    gr.add_edge(a, a, 4);
+    gr.add_edge(c, d, 5);
+    gr.add_edge(c, d, 6);
+
+    let edge_attrs = |_, e: petgraph::graph::EdgeReference<_>| {
+        if *e.weight() % 2 == 0 { "color=red".to_string() } else { String::new() }
+    };
+    let node_attrs = |_, _| "shape=box".to_string();
+    let output = format!("{}", Dot::with_attr_getters(&gr, &[], &edge_attrs, &node_attrs));
+    let parsed: Graph<DotNode, DotEdge> = pg::dot::parse(&output).unwrap();
+    assert_eq!(parsed.node_count(), gr.node_count());
+    assert_eq!(parsed.edge_count(), gr.edge_count());
+    for (i, node) in parsed.node_references() {
+        assert_eq!(node.id, i.index().to_string());
+        assert_eq!(node.attributes["label"], gr[i]);
+        assert_eq!(node.attributes["shape"], "box");
+    }
+    for (parsed_edge, edge) in parsed.raw_edges().iter().zip(gr.raw_edges()) {
+        assert_eq!(parsed_edge.source(), edge.source());
+        assert_eq!(parsed_edge.target(), edge.target());
+        assert_eq!(parsed_edge.weight.attributes["label"], edge.weight.to_string());
+        assert_eq!(parsed_edge.weight.attributes.get("color").is_some(),
+                   edge.weight % 2 == 0);
+    }
+
+    let un = gr.into_edge_type::<Undirected>();
+    let output = format!("{:?}", Dot::with_config(&un, &[Config::EdgeNoLabel]));
+    let parsed: Graph<DotNode, DotEdge, Undirected> = pg::dot::parse(&output).unwrap();
+    assert_eq!(parsed.node_count(), un.node_count());
+    assert_eq!(parsed.edge_count(), un.edge_count());
+    // only `\"` is unescaped, so the backslashes Debug and `Dot` add remain
+    assert_eq!(parsed[n(2)].attributes["label"], r#""\\"quoted\\"""#);
+    for (parsed_edge, edge) in parsed.raw_edges().iter().zip(un.raw_edges()) {
+        assert_eq!((parsed_edge.source(), parsed_edge.target()),
+                   (edge.source(), edge.target()));
+        assert!(parsed_edge.weight.attributes.is_empty());
+    }
+}
+
+#[test]
+fn dot_parse() {
+    let input = r#"
+        /* a comment */
+        strict digraph "G" {
+            rankdir = LR; // graph attribute
+            node [shape=box]
+            a -> b -> c [color=blue, weight=2];
+            node [shape=circle]
+            d [label="multi" + "part"]
+            c -> { d e } -> f
+            subgraph cluster_0 {
+                edge [style=dotted]
+                g -> h:port:n
+            }
+            i
+        }
+    "#;
+    let g: Graph<DotNode, DotEdge> = pg::dot::parse(input).unwrap();
+    let ids = g.raw_nodes().iter().map(|x| &x.weight.id[..]).collect::<Vec<_>>();
+    assert_eq!(ids, vec!["a", "b", "c", "d", "e", "f", "g", "h", "i"]);
+    assert_eq!(g[n(0)].attributes["shape"], "box");
+    assert_eq!(g[n(2)].attributes["shape"], "box");
+    assert_eq!(g[n(3)].attributes["shape"], "circle");
+    assert_eq!(g[n(3)].attributes["label"], "multipart");
+    assert_eq!(g.edge_count(), 2 + 2 + 2 + 1);
+    assert!(g.raw_edges()[..2].iter().all(|e| e.weight.attributes["color"] == "blue"));
+    // c -> {d e} -> f
+    for &(a, b) in &[(2, 3), (2, 4), (3, 5), (4, 5)] {
+        assert!(g.contains_edge(n(a), n(b)), "{} -> {}", a, b);
+    }
+    let gh = g.find_edge(n(6), n(7)).unwrap();
+    assert_eq!(g[gh].attributes["style"], "dotted");
+    // edge defaults are local to the subgraph
+    assert!(g.raw_edges().iter().filter(|e| e.weight.attributes.contains_key("style"))
+             .count() == 1);
+
+    let un: Graph<DotNode, DotEdge, Undirected> =
+        pg::dot::parse("graph { a -- b; b -- a }").unwrap();
+    assert_eq!(un.edge_count(), 2);
+}
+
+#[test]
+fn dot_parse_errors() {
+    fn error<Ty: pg::EdgeType>(input: &str) -> (usize, usize) {
+        let err = pg::dot::parse::<Ty>(input).unwrap_err();
+        assert!(!err.message().is_empty());
+        (err.line(), err.column())
+    }
+    assert_eq!(error::<Directed>("graph { a }"), (1, 1));
+    assert_eq!(error::<Undirected>("digraph { a }"), (1, 1));
+    assert_eq!(error::<Directed>("digraph {\n  a -- b\n}"), (2, 5));
+    assert_eq!(error::<Directed>("digraph {\n  a -> b [color]\n}"), (2, 16));
+    assert_eq!(error::<Directed>("digraph { a -> }"), (1, 16));
+    assert_eq!(error::<Directed>("digraph {\n  a [label=\"oops]\n}"), (2, 12));
+    assert_eq!(error::<Directed>("digraph { a }  b"), (1, 16));
+    assert_eq!(error::<Directed>("digraph { a /* b"), (1, 13));
+    assert_eq!(error::<Directed>("digraph { a ! }"), (1, 13));
+    let err = pg::dot::parse::<Directed>("digraph {\n  a -> }").unwrap_err();
+    assert_eq!(err.to_string(), format!("line 2, column 8: {}", err.message()));
+}
+
+#[test]
+fn write_dot() {
+    use std::cmp;
+    use std::io::{self, Write};
+
+    /// Record the size of the largest write and the bytes written at each
+    /// flush, and fail after `limit` bytes.
+    struct Recorder {
+        written: usize,
+        largest: usize,
+        limit: usize,
+        flushes: Vec<usize>,
+    }
+    impl Write for Recorder {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.written + buf.len() > self.limit {
+                return Err(io::Error::new(io::ErrorKind::Other, "full"));
+            }
+            self.written += buf.len();
+            self.largest = cmp::max(self.largest, buf.len());
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            self.flushes.push(self.written);
+            Ok(())
+        }
+    }
+
+    let mut gr = Graph::<u32, u32>::new();
+    for i in 0..1000 {
+        gr.add_node(i);
+    }
+    for i in 0..10_000 {
+        gr.add_edge(n(i % 1000), n(i * 7 % 1000), i as u32);
+    }
+    let edge_attrs = |_, e: pg::graph::EdgeReference<u32>| {
+        if *e.weight() % 2 == 0 { "color=red".to_string() } else { String::new() }
+    };
+    let node_attrs = |_, _| String::new();
+
+    let mut output = Vec::new();
+    pg::dot::write_dot(&mut output, &gr, &[], &edge_attrs, &node_attrs).unwrap();
+    let expected = format!("{}", Dot::with_attr_getters(&gr, &[], &edge_attrs, &node_attrs));
+    assert_eq!(String::from_utf8(output).unwrap(), expected);
+
+    let mut recorder = Recorder { written: 0, largest: 0, limit: !0, flushes: Vec::new() };
+    pg::dot::write_dot(&mut recorder, &gr, &[], &edge_attrs, &node_attrs).unwrap();
+    assert_eq!(recorder.written, expected.len());
+    assert!(recorder.largest < 64, "largest write: {}", recorder.largest);
+    // flushed after the node statements and at the end
+    let nodes_len = expected.find("    0 -> ").unwrap();
+    assert_eq!(recorder.flushes, vec![nodes_len, expected.len()]);
+
+    let mut full = Recorder { written: 0, largest: 0, limit: 1000, flushes: Vec::new() };
+    let err = pg::dot::write_dot(&mut full, &gr, &[], &edge_attrs, &node_attrs).unwrap_err();
+    assert_eq!(err.to_string(), "full");
+    assert!(full.flushes.is_empty());
+}
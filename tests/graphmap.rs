@@ -51,6 +51,38 @@ fn simple() {
        vec![("A", 0), ("B", 7), ("C", 9), ("D", 11), ("E", 20), ("F", 20)]);
 }
 
+#[test]
+fn dijkstra_by_key() {
+    let edges = [
+        ("A", "B", 7),
+        ("C", "A", 9),
+        ("A", "D", 14),
+        ("B", "C", 10),
+        ("D", "C", 2),
+        ("D", "E", 9),
+        ("B", "F", 15),
+        ("C", "F", 11),
+        ("E", "F", 6),
+    ];
+    let gr = UnGraphMap::<_, u32>::from_edges(&edges);
+    let mut scores: Vec<_> = gr.dijkstra("A", None).into_iter().collect();
+    scores.sort();
+    assert_eq!(scores,
+       vec![("A", 0), ("B", 7), ("C", 9), ("D", 11), ("E", 20), ("F", 20)]);
+    assert_eq!(gr.dijkstra("A", Some("C"))["C"], 9);
+
+    assert_eq!(gr.shortest_path("A", "E"), Some((20, vec!["A", "C", "D", "E"].into())));
+    assert_eq!(gr.shortest_path("E", "A"), Some((20, vec!["E", "D", "C", "A"].into())));
+    assert_eq!(gr.shortest_path("B", "B"), Some((0, vec!["B"].into())));
+    assert_eq!(gr.shortest_path("A", "X"), None);
+
+    let mut gr = DiGraphMap::<_, u32>::from_edges(&edges);
+    gr.add_node("G");
+    assert_eq!(gr.shortest_path("A", "F"), Some((22, vec!["A", "B", "F"].into())));
+    assert_eq!(gr.shortest_path("F", "A"), None);
+    assert_eq!(gr.shortest_path("A", "G"), None);
+}
+
 #[test]
 fn remov()
 {
@@ -133,6 +165,24 @@ fn dfs() {
     assert_eq!(Dfs::new(&gr, z).iter(&gr).count(), 1);
 }
 
+#[test]
+fn dfs_discovered() {
+    let mut gr = UnGraphMap::default();
+    let h = gr.add_node("H");
+    let i = gr.add_node("I");
+    let j = gr.add_node("J");
+    let k = gr.add_node("K");
+    gr.add_edge(h, i, 1.);
+    gr.add_edge(h, j, 3.);
+    gr.add_edge(i, j, 1.);
+    gr.add_edge(i, k, 2.);
+
+    // nodes marked as visited beforehand are avoided
+    let mut dfs = Dfs::new(&gr, h);
+    dfs.discovered.insert(i);
+    assert_eq!(dfs.iter(&gr).count(), 2);
+}
+
 #[test]
 fn edge_iterator() {
     let mut gr = UnGraphMap::new();
@@ -156,6 +206,24 @@ fn edge_iterator() {
     assert_eq!(real_edges, expected_edges);
 }
 
+#[test]
+fn neighbors_into() {
+    let gr = UnGraphMap::<_, _>::from_edges(&[
+        (0, 1, 1),
+        (0, 2, 2),
+        (1, 2, 3),
+        (2, 2, 4),
+    ]);
+    let mut nbuf = Vec::new();
+    let mut ebuf = Vec::new();
+    for node in gr.nodes() {
+        gr.neighbors_into(node, &mut nbuf);
+        assert_eq!(nbuf, gr.neighbors(node).collect::<Vec<_>>());
+        gr.edges_into(node, &mut ebuf);
+        assert_eq!(ebuf, gr.edges(node).collect::<Vec<_>>());
+    }
+}
+
 #[test]
 fn from_edges() {
     let gr = GraphMap::<_, _, Undirected>::from_edges(&[
@@ -279,3 +347,283 @@ fn test_into_graph() {
         assert_eq!(&gr[(aw, bw)], edge.weight());
     }
 }
+
+#[test]
+fn degree() {
+    let mut g = DiGraphMap::new();
+    g.add_edge(1, 2, ());
+    g.add_edge(3, 1, ());
+    g.add_edge(1, 1, ());
+    assert_eq!(g.degree(1), 4);
+    assert_eq!(g.degree(2), 1);
+    assert_eq!(g.degree(4), 0);
+
+    let mut g = UnGraphMap::new();
+    g.add_edge(1, 2, ());
+    g.add_edge(2, 1, ());
+    g.add_edge(1, 1, ());
+    g.add_node(3);
+    // a self loop counts twice
+    assert_eq!(g.degree(1), 3);
+    assert_eq!(g.degree(2), 1);
+    assert_eq!(g.degree(3), 0);
+    assert_eq!(g.nodes().map(|x| g.degree(x)).sum::<usize>(), 2 * g.edge_count());
+}
+
+#[test]
+fn directed_neighbors_and_removal() {
+    let mut g = DiGraphMap::new();
+    assert_eq!(g.add_edge(1, 2, "a"), None);
+    assert_eq!(g.add_edge(2, 1, "b"), None);
+    assert_eq!(g.add_edge(3, 1, "c"), None);
+    assert_eq!(g.add_edge(1, 1, "d"), None);
+    assert_eq!(g.add_edge(1, 2, "e"), Some("a"));
+    assert_eq!(g.edge_count(), 4);
+    assert_eq!(g.edge_weight(1, 2), Some(&"e"));
+    assert_eq!(g.edge_weight(2, 1), Some(&"b"));
+    assert_eq!(g.edge_weight(1, 3), None);
+
+    fn sorted<I: Iterator<Item=i32>>(it: I) -> Vec<i32> {
+        let mut v = it.collect::<Vec<_>>();
+        v.sort();
+        v
+    }
+    assert_eq!(sorted(g.neighbors_directed(1, Outgoing)), vec![1, 2]);
+    assert_eq!(sorted(g.neighbors_directed(1, Incoming)), vec![1, 2, 3]);
+    assert_eq!(sorted(g.neighbors_directed(3, Incoming)), vec![]);
+
+    assert_eq!(g.remove_edge(1, 2), Some("e"));
+    assert_eq!(g.remove_edge(1, 2), None);
+    assert_eq!(g.edge_weight(2, 1), Some(&"b"));
+    assert_eq!(sorted(g.neighbors_directed(2, Incoming)), vec![]);
+    assert_eq!(sorted(g.neighbors_directed(2, Outgoing)), vec![1]);
+
+    assert!(g.remove_node(1));
+    assert_eq!(g.edge_count(), 0);
+    assert_eq!(sorted(g.neighbors_directed(2, Outgoing)), vec![]);
+    assert_eq!(sorted(g.neighbors_directed(3, Outgoing)), vec![]);
+}
+
+#[test]
+fn all_edges_mut() {
+    let mut g = UnGraphMap::new();
+    g.add_edge(3, 1, 1);
+    g.add_edge(1, 2, 2);
+    g.add_edge(2, 2, 3);
+    assert_eq!(g.add_edge(1, 3, 4), Some(1));
+    // each edge once, with the lesser endpoint first
+    let mut edges = g.all_edges().map(|(a, b, &w)| (a, b, w)).collect::<Vec<_>>();
+    edges.sort();
+    assert_eq!(edges, vec![(1, 2, 2), (1, 3, 4), (2, 2, 3)]);
+
+    for (a, b, w) in g.all_edges_mut() {
+        assert!(a <= b);
+        *w *= 10;
+    }
+    assert_eq!(g.edge_weight(3, 1), Some(&40));
+    *g.edge_weight_mut(2, 1).unwrap() += 1;
+    assert_eq!(g[(1, 2)], 21);
+    assert!(g.contains_edge(2, 2));
+
+    let mut d = DiGraphMap::new();
+    d.add_edge(3, 1, 1);
+    d.add_edge(1, 3, 2);
+    for (a, b, w) in d.all_edges_mut() {
+        *w += a * 10 + b;
+    }
+    let mut edges = d.all_edges().map(|(a, b, &w)| (a, b, w)).collect::<Vec<_>>();
+    edges.sort();
+    assert_eq!(edges, vec![(1, 3, 15), (3, 1, 32)]);
+}
+
+#[test]
+fn remove_node_cleans_neighbors() {
+    let mut g = UnGraphMap::<_, ()>::new();
+    g.extend(&[(1, 2), (1, 3), (3, 1), (1, 1), (2, 3), (4, 1)]);
+    assert!(g.remove_node(1));
+    assert!(!g.remove_node(1));
+    assert_eq!(g.edge_count(), 1);
+    for n in g.nodes() {
+        assert!(g.neighbors(n).all(|m| m != 1));
+        assert_eq!(g.edge_weight(n, 1), None);
+        assert_eq!(g.edge_weight(1, n), None);
+    }
+    assert_eq!(g.neighbors(4).count(), 0);
+
+    let mut d = DiGraphMap::<_, ()>::new();
+    d.extend(&[(1, 2), (3, 1), (1, 1), (2, 3), (2, 1)]);
+    assert!(d.remove_node(1));
+    assert_eq!(d.edge_count(), 1);
+    for n in d.nodes() {
+        for &dir in &[Outgoing, Incoming] {
+            assert!(d.neighbors_directed(n, dir).all(|m| m != 1));
+        }
+        assert_eq!(d.edge_weight(n, 1), None);
+        assert_eq!(d.edge_weight(1, n), None);
+    }
+    assert_eq!(d.all_edges().map(|(a, b, _)| (a, b)).collect::<Vec<_>>(), vec![(2, 3)]);
+}
+
+#[test]
+fn retain_edges() {
+    let mut g = DiGraphMap::new();
+    g.add_edge(1, 2, 1);
+    g.add_edge(2, 1, 2);
+    g.add_edge(2, 3, 3);
+    g.add_edge(3, 3, 4);
+    g.retain_edges(|a, b, w| {
+        *w += 10;
+        a < b
+    });
+    assert_eq!(g.edge_count(), 2);
+    assert_eq!(g.node_count(), 3);
+    assert_eq!(g.edge_weight(1, 2), Some(&11));
+    assert_eq!(g.edge_weight(2, 3), Some(&13));
+    assert_eq!(g.neighbors_directed(1, Incoming).count(), 0);
+    assert_eq!(g.neighbors_directed(3, Outgoing).count(), 0);
+    assert_eq!(g.neighbors_directed(3, Incoming).collect::<Vec<_>>(), vec![2]);
+}
+
+#[test]
+fn from_graph() {
+    let mut g = Graph::<_, _>::new();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    let c = g.add_node("c");
+    let d = g.add_node("A");
+    g.add_edge(a, b, 1);
+    g.add_edge(b, c, 2);
+    g.add_edge(a, b, 3);
+    g.add_edge(c, d, 4);
+    g.add_edge(b, a, 5);
+
+    let map = DiGraphMap::from_graph(&g, |w| w.to_lowercase().chars().next().unwrap());
+    assert_eq!(map.node_count(), 3);
+    assert_eq!(map.nodes().collect::<Vec<_>>(), vec!['a', 'b', 'c']);
+    assert_eq!(map.edge_count(), 4);
+    // the last parallel edge wins
+    assert_eq!(map[('a', 'b')], 3);
+    assert_eq!(map[('b', 'a')], 5);
+    assert_eq!(map[('c', 'a')], 4);
+
+    // and back, with the indices in the order of the nodes
+    let back: Graph<_, _> = map.into_graph();
+    assert_eq!(back.node_count(), 3);
+    assert_eq!(back[a], 'a');
+    assert_eq!(back[c], 'c');
+    assert_eq!(back.edge_weight(back.find_edge(c, a).unwrap()), Some(&4));
+
+    let un = UnGraphMap::from_graph(&g.into_edge_type::<Undirected>(), |w| *w);
+    assert_eq!(un.edge_count(), 3);
+    assert_eq!(un[("b", "a")], 5);
+}
+
+#[test]
+fn custom_hasher() {
+    use std::hash::{BuildHasherDefault, Hasher};
+
+    /// FNV-1a
+    #[derive(Default)]
+    struct Fnv(u64);
+    impl Hasher for Fnv {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+        fn write(&mut self, bytes: &[u8]) {
+            for &byte in bytes {
+                self.0 = (self.0 ^ byte as u64).wrapping_mul(0x100000001b3);
+            }
+        }
+    }
+    type Fnv1a = BuildHasherDefault<Fnv>;
+
+    let mut g = GraphMap::<u32, u32, Directed, Fnv1a>::with_capacity(4, 8);
+    assert!(g.capacity().0 >= 4 && g.capacity().1 >= 8);
+    g.extend(&[(0, 1, 1), (1, 2, 1), (0, 2, 5)]);
+    assert_eq!(g.edge_count(), 3);
+    let scores = dijkstra(&g, 0, None, |e| *e.2);
+    assert_eq!(scores[&2], 2);
+
+    let h: GraphMap<u32, (), Undirected, Fnv1a> = vec![(0, 1), (1, 2), (2, 0)].into_iter().collect();
+    assert_eq!(h.edge_count(), 3);
+    assert_eq!(h.neighbors(0).count(), 2);
+
+    let mut k = GraphMap::<_, _, Undirected, _>::with_hasher(Fnv1a::default());
+    k.add_edge("a", "b", 1);
+    assert_eq!(k[("b", "a")], 1);
+    let gr: Graph<_, _, _> = k.into_graph();
+    assert_eq!(gr.edge_count(), 1);
+}
+
+#[test]
+fn deterministic_order() {
+    fn build() -> DiGraphMap<u32, u32> {
+        let mut g = DiGraphMap::new();
+        for i in (0..40).rev() {
+            g.add_node(i * 7 % 40);
+        }
+        for i in 0..200 {
+            g.add_edge(i * 13 % 40, i * 31 % 40, i);
+        }
+        g
+    }
+    fn traversal(g: &DiGraphMap<u32, u32>) -> String {
+        let mut s = format!("{:?}\n", g.nodes().collect::<Vec<_>>());
+        for n in g.nodes() {
+            s += &format!("{:?} {:?}\n", g.neighbors(n).collect::<Vec<_>>(),
+                          g.neighbors_directed(n, Incoming).collect::<Vec<_>>());
+        }
+        s += &format!("{:?}\n", g.all_edges().collect::<Vec<_>>());
+        s += &format!("{:?}\n", Dfs::new(g, 0).iter(g).collect::<Vec<_>>());
+        s += &format!("{}", Dot::new(g));
+        s
+    }
+    let g = build();
+    // the nodes and edges are in the order they were added in
+    assert_eq!(g.nodes().take(3).collect::<Vec<_>>(), vec![33, 26, 19]);
+    assert_eq!(g.all_edges().map(|e| (e.0, e.1)).take(3).collect::<Vec<_>>(),
+               vec![(0, 0), (13, 31), (26, 22)]);
+    assert_eq!(traversal(&g), traversal(&build()));
+
+    let mut h = build();
+    let mut k = build();
+    h.remove_node(5);
+    k.remove_node(5);
+    h.remove_edge(13, 31);
+    k.remove_edge(13, 31);
+    assert_eq!(traversal(&h), traversal(&k));
+}
+
+#[test]
+fn dot() {
+    let mut gr = UnGraphMap::new();
+    gr.add_edge("a", "b", 1);
+    gr.add_edge("b", "say \"c\"", 2);
+    gr.add_edge("a", "a", 3);
+    gr.add_node("d");
+    assert_eq!(format!("{}", Dot::new(&gr)), "\
+graph {
+    0 [label=\"a\"]
+    1 [label=\"b\"]
+    2 [label=\"say \\\"c\\\"\"]
+    3 [label=\"d\"]
+    0 -- 1 [label=\"1\"]
+    1 -- 2 [label=\"2\"]
+    0 -- 0 [label=\"3\"]
+}
+");
+
+    // each edge once, also when stored in both directions
+    let mut di = DiGraphMap::new();
+    di.add_edge(10, 20, ());
+    di.add_edge(20, 10, ());
+    let output = format!("{:?}", Dot::with_config(&di, &[Config::EdgeNoLabel]));
+    assert_eq!(output, "\
+digraph {
+    0 [label=\"10\"]
+    1 [label=\"20\"]
+    0 -> 1
+    1 -> 0
+}
+");
+}
@@ -0,0 +1,142 @@
+extern crate petgraph;
+
+use std::collections::HashMap;
+
+use petgraph::prelude::*;
+use petgraph::graph::node_index as n;
+use petgraph::graphml::{self, Attributes};
+
+fn to_string<Ty: petgraph::EdgeType>(g: &Graph<&str, f64, Ty>) -> String {
+    let mut output = Vec::new();
+    graphml::to_graphml(&mut output, g,
+                        |(i, w)| {
+                            let mut attrs = vec![("name".to_string(), w.to_string())];
+                            if i.index() % 2 == 0 {
+                                attrs.push(("even".to_string(), "yes".to_string()));
+                            }
+                            attrs
+                        },
+                        |e| vec![("weight".to_string(), e.weight().to_string())]).unwrap();
+    String::from_utf8(output).unwrap()
+}
+
+#[test]
+fn round_trip() {
+    let mut g = Graph::new();
+    let a = g.add_node("a");
+    let b = g.add_node("<b & \"c\">");
+    let c = g.add_node("ünïcødé 'd'");
+    g.add_edge(a, b, 1.5);
+    g.add_edge(b, c, -2.);
+    g.add_edge(c, a, 1e10);
+    g.add_edge(a, a, 0.);
+    g.add_edge(a, b, 7.);
+
+    let output = to_string(&g);
+    assert!(output.contains(r#"<key id="n_d0" for="node" attr.name="even" attr.type="string"/>"#));
+    assert!(output.contains(r#"<key id="e_d0" for="edge" attr.name="weight" attr.type="string"/>"#));
+    assert!(output.contains(r#"<graph id="G" edgedefault="directed">"#));
+    assert!(output.contains("&lt;b &amp; &quot;c&quot;&gt;"), "{}", output);
+
+    let parsed: Graph<Attributes, Attributes> = graphml::parse(&output).unwrap();
+    assert_eq!(parsed.node_count(), g.node_count());
+    assert_eq!(parsed.edge_count(), g.edge_count());
+    for (i, attrs) in parsed.node_references() {
+        assert_eq!(attrs["name"], g[i]);
+        assert_eq!(attrs.get("even").is_some(), i.index() % 2 == 0);
+    }
+    for (parsed_edge, edge) in parsed.raw_edges().iter().zip(g.raw_edges()) {
+        assert_eq!((parsed_edge.source(), parsed_edge.target()),
+                   (edge.source(), edge.target()));
+        assert_eq!(parsed_edge.weight["weight"].parse::<f64>().unwrap(), edge.weight);
+    }
+
+    let un = g.into_edge_type::<Undirected>();
+    let output = to_string(&un);
+    assert!(output.contains(r#"<graph id="G" edgedefault="undirected">"#));
+    let parsed: Graph<Attributes, Attributes, Undirected> = graphml::parse(&output).unwrap();
+    assert_eq!(parsed.edge_count(), un.edge_count());
+    assert!(graphml::parse::<Directed>(&output).is_err());
+
+    // no attributes at all
+    let empty = Graph::<(), ()>::from_edges(&[(0, 1)]);
+    let mut output = Vec::new();
+    graphml::to_graphml(&mut output, &empty, |_| vec![], |_| vec![]).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(!output.contains("<key"));
+    let parsed: Graph<Attributes, Attributes> = graphml::parse(&output).unwrap();
+    assert_eq!(parsed.node_count(), 2);
+    assert!(parsed[n(0)].is_empty());
+}
+
+#[test]
+fn parse_foreign() {
+    // A document like yEd writes it, with extra keys, namespaces and markup.
+    let input = r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<!DOCTYPE graphml [ <!ENTITY unused "x"> ]>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns" xmlns:y="http://www.yworks.com/xml/graphml">
+  <!-- keys -->
+  <key for="node" id="d0" yfiles.type="nodegraphics"/>
+  <key attr.name="color" attr.type="string" for="node" id="d1">
+    <default>yellow</default>
+  </key>
+  <key attr.name="weight" attr.type="double" for="edge" id="d2"/>
+  <key attr.name="note" attr.type="string" for="all" id="d3">
+    <default><![CDATA[<none>]]></default>
+  </key>
+  <graph id="G" edgedefault="undirected" parse.order="free">
+    <data key="d3">graph note</data>
+    <edge id="e0" source="n1" target="n0" directed="false">
+      <data key="d2">2.5</data>
+    </edge>
+    <node id="n0">
+      <data key="d0">
+        <y:ShapeNode><y:NodeLabel>ignored</y:NodeLabel></y:ShapeNode>
+      </data>
+      <data key="d1">red</data>
+      <port name="north"/>
+    </node>
+    <node id="n1"><data key="d3">a &amp; b &#x263A;</data><data key="undeclared">x</data></node>
+    <hyperedge><endpoint node="n0"/><endpoint node="n1"/></hyperedge>
+  </graph>
+</graphml>
+"#;
+    let g: Graph<Attributes, Attributes, Undirected> = graphml::parse(input).unwrap();
+    assert_eq!(g.node_count(), 2);
+    assert_eq!(g.edge_count(), 1);
+    let mut n0 = HashMap::new();
+    n0.insert("color".to_string(), "red".to_string());
+    n0.insert("note".to_string(), "<none>".to_string());
+    assert_eq!(g[n(0)], n0);
+    assert_eq!(g[n(1)]["color"], "yellow");
+    assert_eq!(g[n(1)]["note"], "a & b \u{263A}");
+    assert_eq!(g[n(1)]["undeclared"], "x");
+    let e = g.find_edge(n(1), n(0)).unwrap();
+    assert_eq!(g[e]["weight"], "2.5");
+    assert_eq!(g[e]["note"], "<none>");
+}
+
+#[test]
+fn parse_errors() {
+    fn error(input: &str) -> (usize, usize) {
+        let err = graphml::parse::<Directed>(input).unwrap_err();
+        assert_eq!(err.to_string(),
+                   format!("line {}, column {}: {}", err.line(), err.column(), err.message()));
+        (err.line(), err.column())
+    }
+    assert_eq!(error(""), (1, 1));
+    assert_eq!(error("<graph/>"), (1, 1));
+    assert_eq!(error("<graphml>\n  <graph>\n</graphml>"), (2, 3));
+    assert_eq!(error("<graphml>\n  <graph edgedefault=\"undirected\"/>\n</graphml>"), (2, 3));
+    assert_eq!(error("<graphml><graph edgedefault=\"directed\">\n  <node/>\n</graph></graphml>"),
+               (2, 3));
+    assert_eq!(error("<graphml><graph edgedefault=\"directed\">\n\
+                      <node id=\"a\"/><node id=\"a\"/></graph></graphml>"), (2, 15));
+    assert_eq!(error("<graphml><graph edgedefault=\"directed\">\n\
+                      <edge source=\"a\" target=\"b\"/></graph></graphml>"), (2, 1));
+    assert_eq!(error("<graphml><graph edgedefault=\"directed\">\n</node></graphml>"), (2, 1));
+    assert_eq!(error("<graphml>\n<graph edgedefault=\"directed\">"), (2, 31));
+    assert_eq!(error("<graphml>\n<key id=\"k\" x=y/>"), (2, 15));
+    assert_eq!(error("<graphml>&bogus;</graphml>"), (1, 10));
+    assert_eq!(error("<graphml><!-- </graphml>"), (1, 10));
+}
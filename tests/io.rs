@@ -0,0 +1,224 @@
+extern crate petgraph;
+
+use petgraph::prelude::*;
+use petgraph::graph::node_index as n;
+use petgraph::io::{
+    read_edge_list,
+    write_edge_list,
+    write_gexf,
+    write_pajek,
+    Duplicates,
+    EdgeListOptions,
+    ReadError,
+};
+#[cfg(feature = "graphmap")]
+use petgraph::io::{read_edge_list_graphmap, write_edge_list_graphmap};
+
+fn parse_error<T>(result: Result<T, ReadError>) -> (usize, String) {
+    match result {
+        Err(ReadError::Parse { line, message }) => (line, message),
+        Err(e) => panic!("unexpected error {}", e),
+        Ok(_) => panic!("expected an error"),
+    }
+}
+
+#[test]
+fn read_whitespace() {
+    let input = "\
+# a comment
+  # an indented comment
+0 1 1.5
+
+1\t2   -2
+3 3
+";
+    let g: Graph<(), f32> = read_edge_list(input.as_bytes(), &EdgeListOptions::new()).unwrap();
+    assert_eq!(g.node_count(), 4);
+    assert_eq!(g.edge_count(), 3);
+    assert_eq!(g[g.find_edge(n(0), n(1)).unwrap()], 1.5);
+    assert_eq!(g[g.find_edge(n(1), n(2)).unwrap()], -2.);
+    assert_eq!(g[g.find_edge(n(3), n(3)).unwrap()], 0.);
+    assert!(g.find_edge(n(1), n(0)).is_none());
+
+    let un: Graph<(), u8, Undirected> =
+        read_edge_list("0 1\n".as_bytes(), &EdgeListOptions::new()).unwrap();
+    assert!(un.find_edge(n(1), n(0)).is_some());
+}
+
+#[test]
+fn read_csv() {
+    let input = "% source, target, weight\r\n1, 2, 10\r\n2,4,20\r\n";
+    let options = EdgeListOptions::new()
+        .delimiter(',')
+        .one_based(true)
+        .comment_prefix("%");
+    let g: Graph<(), u32> = read_edge_list(input.as_bytes(), &options).unwrap();
+    assert_eq!(g.node_count(), 4);
+    assert_eq!(g.edge_count(), 2);
+    assert_eq!(g[g.find_edge(n(1), n(3)).unwrap()], 20);
+
+    // `#` is not a comment anymore
+    let input = "# 1,2\n";
+    let (line, _) = parse_error(read_edge_list::<_, (), u32, Directed, u32>(input.as_bytes(),
+                                                                             &options));
+    assert_eq!(line, 1);
+}
+
+#[test]
+fn duplicates() {
+    let input = "0 1 1\n1 0 2\n0 1 3\n";
+    let read = |duplicates, directed| {
+        let options = EdgeListOptions::new().duplicates(duplicates);
+        if directed {
+            read_edge_list::<_, (), i32, Directed, u32>(input.as_bytes(), &options)
+                .map(|g| g.raw_edges().iter().map(|e| e.weight).collect::<Vec<_>>())
+        } else {
+            read_edge_list::<_, (), i32, Undirected, u32>(input.as_bytes(), &options)
+                .map(|g| g.raw_edges().iter().map(|e| e.weight).collect::<Vec<_>>())
+        }
+    };
+    assert_eq!(read(Duplicates::Keep, true).unwrap(), vec![1, 2, 3]);
+    assert_eq!(read(Duplicates::Keep, false).unwrap(), vec![1, 2, 3]);
+    assert_eq!(read(Duplicates::Merge, true).unwrap(), vec![3, 2]);
+    assert_eq!(read(Duplicates::Merge, false).unwrap(), vec![3]);
+    assert_eq!(parse_error(read(Duplicates::Error, true)).0, 3);
+    assert_eq!(parse_error(read(Duplicates::Error, false)).0, 2);
+}
+
+#[cfg(feature = "graphmap")]
+#[test]
+fn read_graphmap() {
+    let input = "10 20 1\n20 10 2\n30 10\n";
+    let options = EdgeListOptions::new();
+    let g: DiGraphMap<usize, i32> = read_edge_list_graphmap(input.as_bytes(), &options).unwrap();
+    assert_eq!(g.node_count(), 3);
+    assert_eq!(g.edge_count(), 3);
+    assert_eq!(g.edge_weight(30, 10), Some(&0));
+
+    let g: UnGraphMap<usize, i32> = read_edge_list_graphmap(input.as_bytes(), &options).unwrap();
+    assert_eq!(g.edge_count(), 2);
+    assert_eq!(g.edge_weight(10, 20), Some(&2));
+
+    let options = options.duplicates(Duplicates::Error).one_based(true);
+    let (line, message) = parse_error(
+        read_edge_list_graphmap::<_, i32, Undirected>(input.as_bytes(), &options));
+    assert_eq!((line, &message[..]), (2, "duplicate edge"));
+}
+
+#[test]
+fn malformed() {
+    fn error(input: &str, options: &EdgeListOptions) -> (usize, String) {
+        let result = read_edge_list::<_, (), f64, Directed, u8>(input.as_bytes(), options);
+        let (line, message) = parse_error(result);
+        let err = read_edge_list::<_, (), f64, Directed, u8>(input.as_bytes(), options)
+            .unwrap_err();
+        assert_eq!(err.to_string(), format!("line {}: {}", line, message));
+        (line, message)
+    }
+    let options = EdgeListOptions::new();
+    assert_eq!(error("0 1\n2\n", &options).0, 2);
+    assert_eq!(error("0 1 2 3\n", &options).0, 1);
+    assert_eq!(error("\n\n0 x\n", &options).0, 3);
+    assert_eq!(error("-1 0\n", &options).0, 1);
+    assert_eq!(error("0 1\n0 1 heavy\n", &options).0, 2);
+    assert_eq!(error("0 1,5\n", &options).0, 1);
+    // 255 is the invalid index `end` for u8
+    assert_eq!(error("0 254\n0 255\n", &options).0, 2);
+    assert_eq!(error("1 0\n", &options.clone().one_based(true)).0, 1);
+    assert_eq!(error("1;2\n", &options.clone().delimiter(',')).0, 1);
+}
+
+#[test]
+fn write() {
+    let mut g = Graph::<(), f64>::new();
+    g.extend_with_edges(&[(0, 1, 0.5), (2, 0, 1.), (1, 1, -3.)]);
+    let mut output = Vec::new();
+    write_edge_list(&mut output, &g, &EdgeListOptions::new()).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), "0 1 0.5\n2 0 1\n1 1 -3\n");
+
+    for options in &[EdgeListOptions::new(),
+                     EdgeListOptions::new().delimiter(',').one_based(true),
+                     EdgeListOptions::new().delimiter('\t')] {
+        let mut output = Vec::new();
+        write_edge_list(&mut output, &g, options).unwrap();
+        let h: Graph<(), f64> = read_edge_list(&output[..], options).unwrap();
+        assert_eq!(h.node_count(), g.node_count());
+        assert_eq!(h.raw_edges().iter().map(|e| (e.source(), e.target(), e.weight))
+                                .collect::<Vec<_>>(),
+                   g.raw_edges().iter().map(|e| (e.source(), e.target(), e.weight))
+                                .collect::<Vec<_>>());
+    }
+}
+
+#[cfg(feature = "graphmap")]
+#[test]
+fn write_graphmap() {
+    let map = DiGraphMap::<u32, u8>::from_edges(&[(7, 3, 1)]);
+    let mut output = Vec::new();
+    write_edge_list(&mut output, &map, &EdgeListOptions::new()).unwrap();
+    // GraphMap node ids are their indices
+    assert_eq!(String::from_utf8(output).unwrap(), "0 1 1\n");
+    let mut output = Vec::new();
+    write_edge_list_graphmap(&mut output, &map, &EdgeListOptions::new()).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), "7 3 1\n");
+
+    let map = DiGraphMap::<usize, f64>::from_edges(&[(7, 3, 0.5), (3, 10, 1.), (10, 10, -3.),
+                                                     (0, 7, 2.)]);
+    for options in &[EdgeListOptions::new(),
+                     EdgeListOptions::new().delimiter(',')] {
+        let mut output = Vec::new();
+        write_edge_list_graphmap(&mut output, &map, options).unwrap();
+        let read: DiGraphMap<usize, f64> = read_edge_list_graphmap(&output[..], options).unwrap();
+        assert_eq!(read.nodes().collect::<Vec<_>>(), map.nodes().collect::<Vec<_>>());
+        assert_eq!(read.all_edges().collect::<Vec<_>>(), map.all_edges().collect::<Vec<_>>());
+    }
+
+    let un = UnGraphMap::<usize, u32>::from_edges(&[(5, 1, 2), (1, 2, 3)]);
+    let mut output = Vec::new();
+    write_edge_list_graphmap(&mut output, &un, &EdgeListOptions::new()).unwrap();
+    let read: UnGraphMap<usize, u32> = read_edge_list_graphmap(&output[..],
+                                                              &EdgeListOptions::new()).unwrap();
+    assert_eq!(read.all_edges().collect::<Vec<_>>(), un.all_edges().collect::<Vec<_>>());
+}
+
+fn labeled_graph() -> Graph<&'static str, f64> {
+    let mut g = Graph::new();
+    let a = g.add_node("Alice");
+    let b = g.add_node("Bob \"the builder\"");
+    let c = g.add_node("Carol & <Dan>");
+    g.add_edge(a, b, 1.5);
+    g.add_edge(b, c, 2.);
+    g.add_edge(c, a, 0.25);
+    g.add_edge(a, a, 1.);
+    g
+}
+
+#[test]
+fn pajek() {
+    let g = labeled_graph();
+    let mut output = Vec::new();
+    write_pajek(&mut output, &g, |(_, w)| w.to_string()).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), include_str!("golden/pajek.net"));
+
+    let un = g.into_edge_type::<Undirected>();
+    let mut output = Vec::new();
+    write_pajek(&mut output, &un, |(_, w)| w.to_string()).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains("*Edges\n1 2 1.5\n"), "{}", output);
+    assert!(!output.contains("*Arcs"));
+}
+
+#[test]
+fn gexf() {
+    let g = labeled_graph();
+    let mut output = Vec::new();
+    write_gexf(&mut output, &g, |(_, w)| w.to_string()).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), include_str!("golden/gexf.gexf"));
+
+    let un = g.into_edge_type::<Undirected>();
+    let mut output = Vec::new();
+    write_gexf(&mut output, &un, |(i, _)| format!("node {}", i.index())).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    assert!(output.contains(r#"<graph mode="static" defaultedgetype="undirected">"#));
+    assert!(output.contains(r#"<node id="2" label="node 2"/>"#));
+}
@@ -12,6 +12,9 @@ use petgraph::graph::{
 use petgraph::algo::{
     is_isomorphic,
     is_isomorphic_matching,
+    find_isomorphism,
+    find_isomorphism_matching,
+    subgraph_isomorphisms_iter,
 };
 
 /// Petersen A and B are isomorphic
@@ -470,6 +473,84 @@ fn iso_matching() {
     assert!(!is_isomorphic_matching(&g0, &g2, |x, y| x == y, |x, y| x == y));
 }
 
+#[test]
+fn iso_matching_prunes() {
+    // With distinct node weights, the node matcher prunes the search while
+    // it runs, so it is called no more than once per pair of nodes.
+    let a = str_to_graph(COXETER_A).map(|i, _| i.index(), |_, _| ());
+    let n = a.node_count();
+    let mut b = Graph::new_undirected();
+    for i in 0..n {
+        b.add_node(n - 1 - i);
+    }
+    for e in a.raw_edges() {
+        b.add_edge(node_index(n - 1 - e.source().index()),
+                   node_index(n - 1 - e.target().index()), ());
+    }
+    let mut calls = 0;
+    assert!(is_isomorphic_matching(&a, &b, |x, y| { calls += 1; x == y }, |_, _| true));
+    assert!(calls <= n * n);
+
+    // a single mismatched weight
+    b[node_index(0)] = n;
+    let mut calls = 0;
+    assert!(!is_isomorphic_matching(&a, &b, |x, y| { calls += 1; x == y }, |_, _| true));
+    assert!(calls <= n * n);
+}
+
+/// Assert that `mapping` is an isomorphism from `g0` to `g1`: a bijection
+/// of the nodes that maps every edge to an edge.
+fn assert_isomorphism<N, E, Ty: EdgeType>(g0: &Graph<N, E, Ty>, g1: &Graph<N, E, Ty>,
+                                          mapping: &[NodeIndex]) {
+    assert_eq!(mapping.len(), g0.node_count());
+    assert_eq!(g0.node_count(), g1.node_count());
+    let mut seen = vec![false; g1.node_count()];
+    for &m in mapping {
+        assert!(!seen[m.index()], "not a bijection: {:?}", mapping);
+        seen[m.index()] = true;
+    }
+    assert_eq!(g0.edge_count(), g1.edge_count());
+    for e in g0.raw_edges() {
+        let (a, b) = (mapping[e.source().index()], mapping[e.target().index()]);
+        assert!(g1.contains_edge(a, b), "edge {:?} is not mapped to an edge", (e.source(), e.target()));
+    }
+}
+
+#[test]
+fn iso_mapping() {
+    let pairs = [(PETERSEN_A, PETERSEN_B), (COXETER_A, COXETER_B),
+                 (FULL_A, FULL_B), (G1U, G2U)];
+    for &(a, b) in &pairs {
+        let (g0, g1) = (str_to_graph(a), str_to_graph(b));
+        let mapping = find_isomorphism(&g0, &g1).unwrap();
+        assert_isomorphism(&g0, &g1, &mapping);
+        let (g0, g1) = (str_to_digraph(a), str_to_digraph(b));
+        let mapping = find_isomorphism(&g0, &g1).unwrap();
+        assert_isomorphism(&g0, &g1, &mapping);
+    }
+
+    // a directed path maps onto itself with the nodes shuffled
+    let g0 = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+    let g1 = Graph::<(), ()>::from_edges(&[(2, 0), (3, 2), (1, 3)]);
+    assert_eq!(find_isomorphism(&g0, &g1),
+               Some(vec![node_index(1), node_index(3), node_index(2), node_index(0)]));
+
+    assert_eq!(find_isomorphism(&str_to_graph(PRAUST_A), &str_to_graph(PRAUST_B)), None);
+
+    // with weights, the mapping must also match the weights
+    let g0 = Graph::<u32, ()>::from_edges(&[(0, 1), (1, 2), (2, 0)])
+        .map(|i, _| i.index() as u32, |_, _| ());
+    let mut g1 = g0.clone();
+    g1[node_index(0)] = 2;
+    g1[node_index(2)] = 0;
+    assert_eq!(find_isomorphism_matching(&g0, &g1, |x, y| x == y, |_, _| true), None);
+    g1.clear_edges();
+    g1.extend_with_edges(&[(2, 1), (1, 0), (0, 2)]);
+    let mapping = find_isomorphism_matching(&g0, &g1, |x, y| x == y, |_, _| true).unwrap();
+    assert_isomorphism(&g0, &g1, &mapping);
+    assert_eq!(mapping, vec![node_index(2), node_index(1), node_index(0)]);
+}
+
 // isomorphism isn't correct for multigraphs.
 // Keep this testcase to document how
 #[should_panic]
@@ -563,3 +644,112 @@ const COXETER_B: &'static str = "
  0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 0 1 0 1 0 0 0 0 0 0 0 0 1 0
 ";
 
+
+/// Check that `mapping` embeds `pattern` in `target`
+fn assert_embedding<N, E, Ty: EdgeType>(pattern: &Graph<N, E, Ty>, target: &Graph<N, E, Ty>,
+                                        mapping: &[NodeIndex])
+{
+    assert_eq!(mapping.len(), pattern.node_count());
+    let mut images = mapping.to_vec();
+    images.sort();
+    images.dedup();
+    assert_eq!(images.len(), mapping.len());
+    for edge in pattern.edge_references() {
+        let (a, b) = (mapping[edge.source().index()], mapping[edge.target().index()]);
+        assert!(target.find_edge(a, b).is_some());
+    }
+}
+
+#[test]
+fn subgraph_triangles() {
+    let triangle = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    // K4 on 0..4, with a fifth triangle 0, 4, 5 hanging off it
+    let target = Graph::<(), (), Undirected>::from_edges(&[
+        (0, 1), (0, 2), (0, 3), (1, 2), (1, 3), (2, 3),
+        (0, 4), (4, 5), (5, 0), (5, 6),
+    ]);
+    for &induced in &[false, true] {
+        let mappings = subgraph_isomorphisms_iter(&triangle, &target, |_, _| true,
+                                                  |_, _| true, induced)
+            .collect::<Vec<_>>();
+        // each triangle six times, once for each of its symmetries
+        assert_eq!(mappings.len(), 5 * 6);
+        for mapping in &mappings {
+            assert_embedding(&triangle, &target, mapping);
+        }
+        let mut triangles = mappings.iter().map(|m| {
+            let mut t = m.clone();
+            t.sort();
+            t
+        }).collect::<Vec<_>>();
+        triangles.sort();
+        triangles.dedup();
+        assert_eq!(triangles.len(), 5);
+        assert!(triangles.contains(&vec![node_index(0), node_index(4), node_index(5)]));
+    }
+
+    // found lazily
+    let mut iter = subgraph_isomorphisms_iter(&triangle, &target, |_, _| true,
+                                              |_, _| true, false);
+    let first = iter.next().unwrap();
+    assert_embedding(&triangle, &target, &first);
+
+    // a triangle in a directed graph is a cycle
+    let cycle = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    let target = Graph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3), (3, 0), (0, 2)]);
+    let mappings = subgraph_isomorphisms_iter(&cycle, &target, |_, _| true,
+                                              |_, _| true, false)
+        .collect::<Vec<_>>();
+    // the cycles 0 → 1 → 2 and 0 → 2 → 3, each three times
+    assert_eq!(mappings.len(), 6);
+    for mapping in &mappings {
+        assert_embedding(&cycle, &target, mapping);
+    }
+}
+
+#[test]
+fn subgraph_induced() {
+    // a path of three nodes is in a triangle, but not as an induced subgraph
+    let path = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2)]);
+    let triangle = Graph::<(), (), Undirected>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    assert_eq!(subgraph_isomorphisms_iter(&path, &triangle, |_, _| true, |_, _| true, false)
+                   .count(), 6);
+    assert_eq!(subgraph_isomorphisms_iter(&path, &triangle, |_, _| true, |_, _| true, true)
+                   .count(), 0);
+
+    // the pattern is larger than the target
+    assert_eq!(subgraph_isomorphisms_iter(&triangle, &path, |_, _| true, |_, _| true, false)
+                   .count(), 0);
+
+    // an empty pattern matches once
+    let empty = Graph::<(), (), Undirected>::with_capacity(0, 0);
+    assert_eq!(subgraph_isomorphisms_iter(&empty, &triangle, |_, _| true, |_, _| true, true)
+                   .collect::<Vec<_>>(), vec![vec![]]);
+}
+
+#[test]
+fn subgraph_matching() {
+    // a carbon bonded to an oxygen with a double bond
+    let mut pattern = Graph::new_undirected();
+    let c = pattern.add_node("C");
+    let o = pattern.add_node("O");
+    pattern.add_edge(c, o, 2);
+
+    // acetic acid, without hydrogens
+    let mut target = Graph::new_undirected();
+    let c1 = target.add_node("C");
+    let c2 = target.add_node("C");
+    let o1 = target.add_node("O");
+    let o2 = target.add_node("O");
+    target.add_edge(c1, c2, 1);
+    target.add_edge(c2, o1, 2);
+    target.add_edge(c2, o2, 1);
+
+    let mappings = subgraph_isomorphisms_iter(&pattern, &target, |a, b| a == b,
+                                              |a, b| a == b, false)
+        .collect::<Vec<_>>();
+    assert_eq!(mappings, vec![vec![c2, o1]]);
+    let bonds = subgraph_isomorphisms_iter(&pattern, &target, |a, b| a == b,
+                                           |_, _| true, false);
+    assert_eq!(bonds.count(), 2);
+}
@@ -0,0 +1,151 @@
+extern crate petgraph;
+
+use petgraph::prelude::*;
+use petgraph::matrix_graph::{MatrixGraph, UnMatrix, node_index};
+use petgraph::algo::{dijkstra, is_cyclic_directed};
+use petgraph::visit::{EdgeRef, IntoEdgeReferences};
+
+#[test]
+fn add_remove_edges() {
+    let mut g = MatrixGraph::new();
+    let a = g.add_node('a');
+    let b = g.add_node('b');
+    let c = g.add_node('c');
+    g.add_edge(a, b, 1);
+    g.add_edge(b, c, 2);
+    g.add_edge(c, c, 3);
+    assert_eq!(g.node_count(), 3);
+    assert_eq!(g.edge_count(), 3);
+    assert!(g.has_edge(a, b));
+    assert!(!g.has_edge(b, a));
+    assert_eq!(g[(b, c)], 2);
+    assert_eq!(g.edge_weight(c, b), None);
+
+    assert_eq!(g.update_edge(a, b, 10), Some(1));
+    assert_eq!(g.update_edge(b, a, 11), None);
+    assert_eq!(g.edge_count(), 4);
+    g[(b, a)] += 1;
+    assert_eq!(g.edge_weight(b, a), Some(&12));
+
+    assert_eq!(g.remove_edge(a, b), Some(10));
+    assert_eq!(g.remove_edge(a, b), None);
+    assert_eq!(g.edge_count(), 3);
+    assert_eq!(g.neighbors(b).collect::<Vec<_>>(), vec![a, c]);
+    assert_eq!(g.neighbors_directed(c, Incoming).collect::<Vec<_>>(), vec![b, c]);
+}
+
+#[should_panic]
+#[test]
+fn add_existing_edge() {
+    let mut g = MatrixGraph::<(), ()>::new();
+    let a = g.add_node(());
+    g.add_edge(a, a, ());
+    g.add_edge(a, a, ());
+}
+
+#[test]
+fn undirected() {
+    let mut g = UnMatrix::<_, _>::new_undirected();
+    let a = g.add_node(0);
+    let b = g.add_node(1);
+    let c = g.add_node(2);
+    g.add_edge(b, a, 'x');
+    g.add_edge(c, c, 'y');
+    assert!(g.has_edge(a, b));
+    assert!(g.has_edge(b, a));
+    assert_eq!(g.update_edge(a, b, 'z'), Some('x'));
+    assert_eq!(g.edge_count(), 2);
+    assert_eq!(g.neighbors(a).collect::<Vec<_>>(), vec![b]);
+    assert_eq!(g.neighbors(b).collect::<Vec<_>>(), vec![a]);
+    assert_eq!(g.neighbors(c).collect::<Vec<_>>(), vec![c]);
+    assert_eq!(g.neighbors_directed(b, Incoming).collect::<Vec<_>>(), vec![a]);
+
+    let edges = g.edge_references()
+                 .map(|e| (e.source(), e.target(), *e.weight()))
+                 .collect::<Vec<_>>();
+    assert_eq!(edges, vec![(a, b, 'z'), (c, c, 'y')]);
+}
+
+#[test]
+fn remove_node() {
+    let mut g = MatrixGraph::new();
+    let nodes = (0..6).map(|i| g.add_node(i)).collect::<Vec<_>>();
+    for &a in &nodes {
+        for &b in &nodes {
+            if a != b {
+                g.add_edge(a, b, ());
+            }
+        }
+    }
+    assert_eq!(g.edge_count(), 30);
+    assert_eq!(g.remove_node(nodes[2]), 2);
+    assert_eq!(g.node_count(), 5);
+    assert_eq!(g.edge_count(), 20);
+    assert!(!g.contains_node(nodes[2]));
+    assert_eq!(g.node_weight(nodes[2]), None);
+    assert!(g.neighbors(nodes[0]).all(|n| n != nodes[2]));
+    assert_eq!(g.node_indices().count(), 5);
+
+    // the vacant index is reused, without edges
+    let x = g.add_node(10);
+    assert_eq!(x, nodes[2]);
+    assert_eq!(g[x], 10);
+    assert_eq!(g.neighbors(x).count(), 0);
+    assert_eq!(g.neighbors_directed(x, Incoming).count(), 0);
+}
+
+#[test]
+fn grow() {
+    let mut g: MatrixGraph<(), usize> = MatrixGraph::with_capacity(1);
+    let a = g.add_node(());
+    g.add_edge(a, a, 0);
+    let mut prev = a;
+    for i in 1..100 {
+        let n = g.add_node(());
+        g.add_edge(prev, n, i);
+        prev = n;
+    }
+    assert_eq!(g.edge_count(), 100);
+    assert_eq!(g.edge_weight(a, a), Some(&0));
+    for i in 1..100 {
+        assert_eq!(g[(node_index(i - 1), node_index(i))], i);
+    }
+}
+
+#[test]
+fn traversal() {
+    let mut g = MatrixGraph::new();
+    let a = g.add_node("a");
+    let b = g.add_node("b");
+    let c = g.add_node("c");
+    let d = g.add_node("d");
+    let e = g.add_node("e");
+    g.add_edge(a, b, 7);
+    g.add_edge(a, c, 2);
+    g.add_edge(c, b, 3);
+    g.add_edge(b, d, 1);
+
+    let mut dfs = Dfs::new(&g, a);
+    let mut order = Vec::new();
+    while let Some(n) = dfs.next(&g) {
+        order.push(n);
+    }
+    order.sort();
+    assert_eq!(order, vec![a, b, c, d]);
+
+    let mut bfs = Bfs::new(&g, a);
+    let mut order = Vec::new();
+    while let Some(n) = bfs.next(&g) {
+        order.push(n);
+    }
+    assert_eq!(order, vec![a, b, c, d]);
+
+    let dist = dijkstra(&g, a, None, |e| *e.weight());
+    assert_eq!(dist[&b], 5);
+    assert_eq!(dist[&d], 6);
+    assert!(!dist.contains_key(&e));
+
+    assert!(!is_cyclic_directed(&g));
+    g.add_edge(d, a, 1);
+    assert!(is_cyclic_directed(&g));
+}
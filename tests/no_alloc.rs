@@ -0,0 +1,203 @@
+//! Check that the `_into` algorithm variants don't allocate once their
+//! buffers have grown to fit.
+//!
+//! The counting allocator counts per thread, so the other tests in this file
+//! don't disturb the counts.
+//!
+//! A global allocator needs Rust 1.28, so these tests only run with the
+//! `alloc_tests` feature.
+#![cfg(feature = "alloc_tests")]
+extern crate petgraph;
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use petgraph::prelude::*;
+use petgraph::algo::{
+    ancestors_into,
+    connected_components_into,
+    descendants_into,
+    dijkstra_into,
+    DijkstraSpace,
+    has_path_connecting,
+    toposort,
+    toposort_into,
+    DfsSpace,
+};
+use petgraph::unionfind::UnionFind;
+use petgraph::visit::{Bfs, Walker};
+
+struct Counting;
+
+thread_local!(static ALLOCATIONS: Cell<usize> = Cell::new(0));
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Return the result of `f` and the number of allocations it made.
+fn count_allocations<F, R>(f: F) -> (R, usize)
+    where F: FnOnce() -> R
+{
+    let before = ALLOCATIONS.with(|n| n.get());
+    let result = f();
+    let after = ALLOCATIONS.with(|n| n.get());
+    (result, after - before)
+}
+
+fn dag() -> Graph<(), u32> {
+    Graph::from_edges(&[
+        (0, 1, 7), (0, 2, 9), (0, 5, 14),
+        (1, 2, 10), (1, 3, 15), (2, 3, 11),
+        (2, 5, 2), (3, 4, 6), (5, 4, 9),
+        (6, 7, 1),
+    ])
+}
+
+#[test]
+fn dijkstra_into_no_alloc() {
+    let gr = dag();
+    let mut dist = vec![None; gr.node_count()];
+    let mut space = DijkstraSpace::new();
+    dijkstra_into(&gr, n(0), |e| *e.weight(), &mut dist, &mut space);
+    let first = dist.clone();
+    assert_eq!(first, vec![Some(0), Some(7), Some(9), Some(20), Some(20), Some(11),
+                           None, None]);
+
+    let ((), allocs) = count_allocations(|| {
+        dijkstra_into(&gr, n(0), |e| *e.weight(), &mut dist, &mut space)
+    });
+    assert_eq!(allocs, 0);
+    assert_eq!(dist, first);
+}
+
+#[test]
+fn toposort_into_no_alloc() {
+    let gr = dag();
+    let mut order = Vec::new();
+    let mut space = DfsSpace::new(&gr);
+    toposort_into(&gr, &mut order, &mut space).unwrap();
+    let first = order.clone();
+    assert_eq!(first.len(), gr.node_count());
+
+    let (result, allocs) = count_allocations(|| {
+        toposort_into(&gr, &mut order, &mut space)
+    });
+    assert_eq!(allocs, 0);
+    assert!(result.is_ok());
+    assert_eq!(order, first);
+}
+
+#[test]
+fn connected_components_into_no_alloc() {
+    let gr = dag();
+    let mut labels = vec![0; gr.node_count()];
+    let mut sets = UnionFind::new_empty();
+    assert_eq!(connected_components_into(&gr, &mut labels, &mut sets), 2);
+    let first = labels.clone();
+    assert_eq!(first, vec![0, 0, 0, 0, 0, 0, 1, 1]);
+
+    let (count, allocs) = count_allocations(|| {
+        connected_components_into(&gr, &mut labels, &mut sets)
+    });
+    assert_eq!(allocs, 0);
+    assert_eq!(count, 2);
+    assert_eq!(labels, first);
+}
+
+#[test]
+fn descendants_ancestors_into_no_alloc() {
+    let gr = dag();
+    let mut nodes = Vec::with_capacity(gr.node_count());
+    let mut space = DfsSpace::new(&gr);
+    descendants_into(&gr, n(0), &mut nodes, &mut space);
+    assert_eq!(nodes.len(), 5);
+
+    let ((), allocs) = count_allocations(|| {
+        for a in gr.node_indices() {
+            descendants_into(&gr, a, &mut nodes, &mut space);
+            ancestors_into(&gr, a, &mut nodes, &mut space);
+        }
+    });
+    assert_eq!(allocs, 0);
+    ancestors_into(&gr, n(4), &mut nodes, &mut space);
+    nodes.sort();
+    assert_eq!(nodes, vec![n(0), n(1), n(2), n(3), n(5)]);
+}
+
+#[test]
+fn dfs_space_no_alloc() {
+    let gr = dag();
+    let mut space = DfsSpace::new(&gr);
+    assert!(toposort(&gr, Some(&mut space)).is_ok());
+
+    let (paths, allocs) = count_allocations(|| {
+        let mut paths = 0;
+        for a in gr.node_indices() {
+            for b in gr.node_indices() {
+                if has_path_connecting(&gr, a, b, Some(&mut space)) {
+                    paths += 1;
+                }
+            }
+        }
+        paths
+    });
+    assert_eq!(allocs, 0);
+    // 8 trivial paths, 14 in the first component and 1 in the second
+    assert_eq!(paths, 8 + 14 + 1);
+
+    // without a workspace each call allocates
+    let (_, allocs) = count_allocations(|| has_path_connecting(&gr, n(0), n(4), None));
+    assert!(allocs > 0);
+}
+
+#[test]
+fn bfs_reset_no_alloc() {
+    let gr = dag();
+    let mut bfs = Bfs::new(&gr, n(0));
+    let mut first = 0;
+    while let Some(_) = bfs.next(&gr) {
+        first += 1;
+    }
+    assert_eq!(first, 6);
+
+    let (counts, allocs) = count_allocations(|| {
+        let mut counts = [0; 8];
+        for (a, count) in gr.node_indices().zip(&mut counts) {
+            bfs.reset(&gr);
+            bfs.move_to(a);
+            while let Some(_) = bfs.next(&gr) {
+                *count += 1;
+            }
+        }
+        counts
+    });
+    assert_eq!(allocs, 0);
+    assert_eq!(counts, [6, 5, 4, 2, 1, 2, 2, 1]);
+
+    // move_to without reset keeps the visited nodes
+    let mut bfs = Bfs::empty(&gr);
+    bfs.move_to(n(3));
+    assert_eq!((&mut bfs).iter(&gr).collect::<Vec<_>>(), vec![n(3), n(4)]);
+    bfs.move_to(n(2));
+    assert_eq!((&mut bfs).iter(&gr).collect::<Vec<_>>(), vec![n(2), n(5)]);
+}
+
+fn n(i: usize) -> NodeIndex {
+    NodeIndex::new(i)
+}
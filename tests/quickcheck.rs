@@ -4,10 +4,11 @@ extern crate rand;
 extern crate petgraph;
 
 extern crate odds;
+extern crate fixedbitset;
 
 mod utils;
 
-use utils::Small;
+use utils::{Small, brute_force_max_matching};
 
 use odds::prelude::*;
 use std::collections::HashSet;
@@ -31,8 +32,11 @@ use petgraph::algo::{
     kosaraju_scc,
     tarjan_scc,
     dijkstra,
+    dijkstra_restricted,
+    bfs_restricted,
+    has_path_connecting,
 };
-use petgraph::visit::{Topo, Reversed};
+use petgraph::visit::{DfsPostOrder, Topo, Reversed, VisitMap, Walker};
 use petgraph::data::FromElements;
 use petgraph::graph::{IndexType, node_index, edge_index};
 use petgraph::graphmap::{
@@ -81,6 +85,42 @@ quickcheck! {
     }
 }
 
+quickcheck! {
+    fn mst_is_forest(g: Graph<(), u32>) -> bool {
+        use petgraph::algo::{connected_components, is_forest, is_tree};
+        let mst = mst_graph(&g);
+        assert!(is_forest(&mst));
+        // spanning trees of connected graphs are trees
+        assert_eq!(is_tree(&mst), connected_components(&g) == 1);
+        assert_eq!(is_tree(&g), is_forest(&g) && connected_components(&g) == 1);
+        true
+    }
+}
+
+quickcheck! {
+    fn is_dag_toposort(g: Graph<(), ()>) -> bool {
+        use petgraph::algo::is_dag;
+        is_dag(&g) == toposort(&g, None).is_ok()
+    }
+
+    fn dag_is_dag(dag: DAG<()>) -> bool {
+        use petgraph::algo::is_dag;
+        is_dag(&dag.0)
+    }
+}
+
+quickcheck! {
+    fn reversed_neighbors(g: Graph<(), u8>) -> bool {
+        let h = g.reversed();
+        g.node_indices().all(|a| {
+            g.neighbors_directed(a, Incoming).collect::<Vec<_>>() ==
+                h.neighbors_directed(a, Outgoing).collect::<Vec<_>>() &&
+            g.neighbors_directed(a, Outgoing).collect::<Vec<_>>() ==
+                h.neighbors_directed(a, Incoming).collect::<Vec<_>>()
+        }) && g.edge_indices().all(|e| g[e] == h[e])
+    }
+}
+
 quickcheck! {
     fn reverse_undirected(g: Small<UnGraph<(), ()>>) -> bool {
         let mut h = (*g).clone();
@@ -600,6 +640,31 @@ fn full_topo() {
     quickcheck::quickcheck(prop as fn(_) -> bool);
 }
 
+quickcheck! {
+    fn topological_layers_order_edges(dag: DAG<()>) -> bool {
+        use petgraph::algo::{topological_layers, topological_layer_numbers};
+        let gr = dag.0;
+        let layers = topological_layers(&gr).unwrap();
+        let layer_of = topological_layer_numbers(&gr).unwrap();
+        assert_eq!(layers.iter().map(|l| l.len()).sum::<usize>(), gr.node_count());
+        for (i, layer) in layers.iter().enumerate() {
+            assert!(!layer.is_empty());
+            for &node in layer {
+                assert_eq!(layer_of[node.index()], i);
+                // longest path layering: a predecessor is in the layer right below
+                if i > 0 {
+                    assert!(gr.neighbors_directed(node, Incoming)
+                              .any(|p| layer_of[p.index()] == i - 1));
+                }
+            }
+        }
+        for edge in gr.edge_references() {
+            assert!(layer_of[edge.source().index()] < layer_of[edge.target().index()]);
+        }
+        true
+    }
+}
+
 #[test]
 fn full_topo_generic() {
     fn prop_generic(DAG(mut gr): DAG<usize>) -> bool {
@@ -665,6 +730,245 @@ quickcheck! {
     }
 }
 
+quickcheck! {
+    // checks that the restricted searches agree with searching the
+    // induced subgraph
+    fn restricted_search_induced_subgraph(g: Graph<u32, u32>, node: usize,
+                                          subset: Vec<bool>) -> bool {
+        use fixedbitset::FixedBitSet;
+        if g.node_count() == 0 {
+            return true;
+        }
+        let mut allowed = FixedBitSet::with_capacity(g.node_count());
+        for i in 0..g.node_count() {
+            allowed.set(i, subset.get(i).cloned().unwrap_or(true));
+        }
+        // build the induced subgraph, remembering the original indices
+        let sub = g.filter_map(|i, _| if allowed[i.index()] { Some(i) } else { None },
+                               |_, &w| Some(w));
+        let new_index = |i: NodeIndex| sub.node_indices().find(|&j| sub[j] == i);
+        let start = node_index(node % g.node_count());
+
+        let restricted = dijkstra_restricted(&g, start, None, |e| *e.weight(), &allowed);
+        let bfs_order = bfs_restricted(&g, start, &allowed);
+        match new_index(start) {
+            None => {
+                assert!(restricted.is_empty());
+                assert!(bfs_order.is_empty());
+            }
+            Some(sub_start) => {
+                let expected = dijkstra(&sub, sub_start, None, |e| *e.weight());
+                assert_eq!(restricted.len(), expected.len());
+                for (n, cost) in expected {
+                    assert_eq!(restricted[&sub[n]], cost);
+                }
+                let expected_order = Bfs::new(&sub, sub_start).iter(&sub)
+                    .map(|n| sub[n]).collect::<Vec<_>>();
+                assert_eq!(bfs_order, expected_order);
+            }
+        }
+        true
+    }
+}
+
+quickcheck! {
+    fn vertex_cover_covers_edges(g: Graph<(), ()>) -> bool {
+        use petgraph::algo::min_vertex_cover_approx;
+        let cover = set(min_vertex_cover_approx(&g));
+        assert_eq!(min_vertex_cover_approx(&g), min_vertex_cover_approx(&g));
+        g.edge_references().all(|e| cover.contains(&e.source()) || cover.contains(&e.target()))
+    }
+}
+
+quickcheck! {
+    fn independent_set_is_maximal(g: Graph<(), ()>) -> bool {
+        use petgraph::algo::max_independent_set_approx;
+        let indep = set(max_independent_set_approx(&g));
+        assert_eq!(max_independent_set_approx(&g), max_independent_set_approx(&g));
+        // no two members are adjacent
+        for e in g.edge_references() {
+            assert!(!(indep.contains(&e.source()) && indep.contains(&e.target())));
+        }
+        // every other node without a self loop has a neighbor in the set
+        for node in g.node_indices() {
+            if !indep.contains(&node) && !g.contains_edge(node, node) {
+                assert!(g.neighbors_undirected(node).any(|n| indep.contains(&n)));
+            }
+        }
+        true
+    }
+}
+
+quickcheck! {
+    fn transitive_closure_reachability(g: Small<Graph<(), ()>>) -> bool {
+        use petgraph::algo::{has_path_connecting, transitive_closure};
+        let closure = transitive_closure(&*g);
+        for a in g.node_indices() {
+            let mut reach = Vec::new();
+            for b in g.node_indices() {
+                // a path of at least one edge
+                let expected = g.neighbors(a).any(|n| has_path_connecting(&*g, n, b, None));
+                assert_eq!(closure.reachable(a, b), expected);
+                if expected {
+                    reach.push(b);
+                }
+            }
+            assert_eq!(closure.descendants(a).collect::<Vec<_>>(), reach);
+        }
+        true
+    }
+}
+
+#[test]
+fn contract_degree_two_chains_distances() {
+    fn prop<Ty: EdgeType>(g: Small<Graph<(), u16, Ty>>) -> bool {
+        use petgraph::algo::contract_degree_two_chains;
+        // node weights remember the original index
+        let g = g.map(|i, _| i.index(), |_, &w| w as u32);
+        let mut contracted = g.clone();
+        let count = contract_degree_two_chains(&mut contracted, |a, b| a + b, |_, _| false);
+        assert_eq!(contracted.node_count() + count, g.node_count());
+        for a in contracted.node_indices() {
+            let before = dijkstra(&g, node_index(contracted[a]), None, |e| *e.weight());
+            let after = dijkstra(&contracted, a, None, |e| *e.weight());
+            for b in contracted.node_indices() {
+                assert_eq!(before.get(&node_index(contracted[b])), after.get(&b));
+            }
+        }
+        true
+    }
+    quickcheck::quickcheck(prop as fn(Small<Graph<_, _, Directed>>) -> bool);
+    quickcheck::quickcheck(prop as fn(Small<Graph<_, _, Undirected>>) -> bool);
+}
+
+quickcheck! {
+    fn astar_matches_dijkstra(g: Graph<(), u32>, a: usize, b: usize) -> bool {
+        use petgraph::algo::astar;
+        if g.node_count() == 0 {
+            return true;
+        }
+        let a = node_index(a % g.node_count());
+        let b = node_index(b % g.node_count());
+        let distances = dijkstra(&g, a, None, |e| *e.weight());
+        match astar(&g, a, |x| x == b, |e| *e.weight(), |_| 0) {
+            None => !distances.contains_key(&b),
+            Some((cost, path)) => {
+                cost == distances[&b] &&
+                    path.start() == Some(a) && path.end() == Some(b) &&
+                    path.cost(&g, |e| *e.weight()) == Some(cost)
+            }
+        }
+    }
+}
+
+quickcheck! {
+    fn dijkstra_into_matches_dijkstra(g: Graph<u32, u32>, node: usize) -> bool {
+        use petgraph::algo::{dijkstra_into, DijkstraSpace};
+        if g.node_count() == 0 {
+            return true;
+        }
+        let start = node_index(node % g.node_count());
+        let expected = dijkstra(&g, start, None, |e| *e.weight());
+        let mut dist = vec![Some(!0); g.node_count()];
+        let mut space = DijkstraSpace::new();
+        dijkstra_into(&g, start, |e| *e.weight(), &mut dist, &mut space);
+        for v in g.node_indices() {
+            assert_eq!(dist[v.index()], expected.get(&v).cloned());
+        }
+        true
+    }
+}
+
+quickcheck! {
+    fn connected_components_labels(g: Small<Graph<(), ()>>) -> bool {
+        use petgraph::algo::{connected_components, connected_components_into};
+        use petgraph::unionfind::UnionFind;
+        let mut labels = vec![!0; g.node_count()];
+        let count = connected_components_into(&*g, &mut labels, &mut UnionFind::new_empty());
+        assert_eq!(count, connected_components(&*g));
+        for e in g.edge_references() {
+            assert_eq!(labels[e.source().index()], labels[e.target().index()]);
+        }
+        // the labels are numbered in order of first appearance
+        let mut next = 0;
+        for &label in &labels {
+            assert!(label <= next);
+            if label == next {
+                next += 1;
+            }
+        }
+        assert_eq!(next, count);
+        // and nodes with different labels are not connected
+        let ungraph = (*g).clone().into_edge_type::<Undirected>();
+        for a in g.node_indices() {
+            for b in g.node_indices() {
+                assert_eq!(labels[a.index()] == labels[b.index()],
+                           has_path_connecting(&ungraph, a, b, None));
+            }
+        }
+        true
+    }
+}
+
+quickcheck! {
+    fn min_cost_max_flow_is_optimal(g: Small<Graph<(), (u8, u8)>>, s: usize, t: usize) -> bool {
+        use petgraph::algo::{bellman_ford, min_cost_max_flow};
+        if g.node_count() == 0 {
+            return true;
+        }
+        let s = node_index(s % g.node_count());
+        let t = node_index(t % g.node_count());
+        let (flow, cost, edge_flow) = min_cost_max_flow(&*g, s, t,
+                                                        |e| e.weight().0 as i32,
+                                                        |e| e.weight().1 as i32).unwrap();
+        // a feasible flow of the given value and cost
+        let mut balance = vec![0; g.node_count()];
+        let mut total_cost = 0;
+        for e in g.edge_references() {
+            let f = edge_flow[e.id().index()];
+            assert!(0 <= f && f <= e.weight().0 as i32);
+            balance[e.source().index()] -= f;
+            balance[e.target().index()] += f;
+            total_cost += f * e.weight().1 as i32;
+        }
+        assert_eq!(total_cost, cost);
+        for v in g.node_indices() {
+            if s == t || (v != s && v != t) {
+                assert_eq!(balance[v.index()], 0);
+            }
+        }
+        if s != t {
+            assert_eq!(balance[t.index()], flow);
+        }
+
+        // the residual graph, with an extra node connected to all others
+        let mut residual = Graph::<(), f64>::new();
+        for _ in 0..g.node_count() + 1 {
+            residual.add_node(());
+        }
+        for e in g.edge_references() {
+            let f = edge_flow[e.id().index()];
+            let (cap, c) = (e.weight().0 as i32, e.weight().1 as f64);
+            if f < cap {
+                residual.add_edge(e.source(), e.target(), c);
+            }
+            if f > 0 {
+                residual.add_edge(e.target(), e.source(), -c);
+            }
+        }
+        // maximal: the sink can't be reached in the residual graph
+        if s != t {
+            assert!(!has_path_connecting(&residual, s, t, None));
+        }
+        // minimal cost: there is no cycle of negative cost
+        let extra = node_index(g.node_count());
+        for v in g.node_indices() {
+            residual.add_edge(extra, v, 0.);
+        }
+        bellman_ford(&residual, extra).is_ok()
+    }
+}
+
 fn set<I>(iter: I) -> HashSet<I::Item>
     where I: IntoIterator,
           I::Item: Hash + Eq,
@@ -719,3 +1023,706 @@ quickcheck! {
         true
     }
 }
+
+/// The best total weight of a matching of `weights`, a `rows` by `cols`
+/// matrix, over all the ways to match rows `row..`, with `used` columns.
+fn brute_force_matching(weights: &[Option<i32>], cols: usize, row: usize,
+                        used: &mut [bool]) -> i32 {
+    if row * cols == weights.len() {
+        return 0;
+    }
+    let mut best = brute_force_matching(weights, cols, row + 1, used);
+    for j in 0..cols {
+        if let (false, Some(w)) = (used[j], weights[row * cols + j]) {
+            used[j] = true;
+            let total = w + brute_force_matching(weights, cols, row + 1, used);
+            used[j] = false;
+            if total > best {
+                best = total;
+            }
+        }
+    }
+    best
+}
+
+quickcheck! {
+    fn max_weight_bipartite_matching_brute_force(weights: Vec<Option<i8>>, rows: u8, cols: u8)
+        -> bool
+    {
+        use petgraph::algo::max_weight_bipartite_matching;
+        // mostly 6 by 6, sometimes rectangular
+        let rows = if rows % 4 == 0 { rows as usize % 7 } else { 6 };
+        let cols = if cols % 4 == 0 { cols as usize % 7 } else { 6 };
+        let weights = (0..rows * cols).map(|i| {
+            weights.get(i).cloned().unwrap_or(None).map(|w| w as i32)
+        }).collect::<Vec<_>>();
+
+        let mut gr = UnGraph::<(), i32>::new_undirected();
+        let left = (0..rows).map(|_| gr.add_node(())).collect::<Vec<_>>();
+        let right = (0..cols).map(|_| gr.add_node(())).collect::<Vec<_>>();
+        for i in 0..rows {
+            for j in 0..cols {
+                if let Some(w) = weights[i * cols + j] {
+                    gr.add_edge(left[i], right[j], w);
+                }
+            }
+        }
+        let (pairs, total) = max_weight_bipartite_matching(&gr, &left, |e| *e.weight());
+
+        // a valid matching of the reported weight
+        let mut matched = HashSet::new();
+        let mut sum = 0;
+        for &(a, b) in &pairs {
+            assert!(matched.insert(a) && matched.insert(b));
+            let (i, j) = (a.index(), b.index() - rows);
+            sum += weights[i * cols + j].unwrap();
+        }
+        assert_eq!(sum, total);
+        assert_eq!(total, brute_force_matching(&weights, cols, 0, &mut vec![false; cols]));
+        true
+    }
+}
+
+quickcheck! {
+    fn maximum_matching_brute_force(edges: Vec<(u8, u8)>) -> bool {
+        use petgraph::algo::maximum_matching;
+        // at most 8 nodes, with self loops and parallel edges
+        let mut gr = UnGraph::<(), ()>::new_undirected();
+        let size = edges.iter().map(|&(a, b)| if a % 8 > b % 8 { a % 8 } else { b % 8 })
+                        .max().map_or(0, |x| x as usize + 1);
+        for _ in 0..size {
+            gr.add_node(());
+        }
+        let mut adj = vec![vec![false; size]; size];
+        for &(a, b) in &edges {
+            let (a, b) = ((a % 8) as usize, (b % 8) as usize);
+            gr.add_edge(node_index(a), node_index(b), ());
+            adj[a][b] = a != b;
+            adj[b][a] = a != b;
+        }
+        let m = maximum_matching(&gr);
+        let mut matched = vec![false; size];
+        for &e in m.edges() {
+            let (a, b) = gr.edge_endpoints(e).unwrap();
+            assert!(a != b && !matched[a.index()] && !matched[b.index()]);
+            matched[a.index()] = true;
+            matched[b.index()] = true;
+        }
+        m.len() == brute_force_max_matching(&adj, 0, &mut vec![false; size])
+    }
+}
+
+fn landmarks_bounds<Ty: EdgeType>(g: &Graph<(), u32, Ty>, picks: &[usize]) -> bool {
+    use petgraph::algo::Landmarks;
+    if g.node_count() == 0 {
+        return true;
+    }
+    let landmarks = picks.iter().take(3).map(|&i| node_index(i % g.node_count()))
+                         .collect::<Vec<_>>();
+    let lm = Landmarks::from_nodes(g, &landmarks, |e| *e.weight());
+    for a in g.node_indices() {
+        let dist = dijkstra(g, a, None, |e| *e.weight());
+        for b in g.node_indices() {
+            let (lower, upper) = lm.estimate(a, b);
+            let found = lm.alt_astar(a, b, |e| *e.weight());
+            match dist.get(&b) {
+                Some(&d) => {
+                    assert!(lower <= d);
+                    assert!(upper.map_or(true, |u| d <= u));
+                    let found = found.unwrap();
+                    assert_eq!(found.cost, d);
+                    assert_eq!(found.path.cost(g, |e| *e.weight()), Some(d));
+                }
+                None => {
+                    assert_eq!(upper, None);
+                    assert_eq!(found, None);
+                }
+            }
+        }
+    }
+    true
+}
+
+quickcheck! {
+    fn landmarks_bounds_directed(g: Small<Graph<(), u32>>, picks: Vec<usize>) -> bool {
+        landmarks_bounds(&g, &picks)
+    }
+
+    fn landmarks_bounds_undirected(g: Small<Graph<(), u32, Undirected>>, picks: Vec<usize>)
+        -> bool
+    {
+        landmarks_bounds(&g, &picks)
+    }
+}
+
+#[test]
+fn wl_hash_permuted() {
+    use petgraph::algo::weisfeiler_lehman_hash;
+    use petgraph::visit::NodeRef;
+    fn prop<Ty: EdgeType>(g: Small<Graph<i8, i8, Ty>>) -> bool {
+        let mut rng = rand::thread_rng();
+        // a copy with the nodes and the edges in a random order
+        let mut map = g.node_indices().collect::<Vec<_>>();
+        rng.shuffle(&mut map);
+        let mut edges = g.edge_indices().collect::<Vec<_>>();
+        rng.shuffle(&mut edges);
+        let mut ng = Graph::<_, _, Ty>::with_capacity(g.node_count(), g.edge_count());
+        for _ in g.node_indices() {
+            ng.add_node(0);
+        }
+        for i in g.node_indices() {
+            ng[map[i.index()]] = g[i];
+        }
+        for &i in &edges {
+            let (s, t) = g.edge_endpoints(i).unwrap();
+            ng.add_edge(map[s.index()], map[t.index()], g[i]);
+        }
+        let hash = |g: &Graph<i8, i8, Ty>| {
+            weisfeiler_lehman_hash(g, |n| *n.weight(), |e| *e.weight(), 3)
+        };
+        hash(&g) == hash(&ng)
+    }
+    quickcheck::quickcheck(prop::<Undirected> as fn(_) -> bool);
+    quickcheck::quickcheck(prop::<Directed> as fn(_) -> bool);
+}
+
+/// The cost of the cheapest tour that starts with `tour` and goes on
+/// through the unvisited nodes, by the distances `dist`.
+fn brute_force_tour(dist: &[Vec<u32>], tour: &mut Vec<usize>, visited: &mut [bool]) -> u32 {
+    let n = dist.len();
+    let last = *tour.last().unwrap();
+    if tour.len() == n {
+        return dist[last][tour[0]];
+    }
+    let mut best = !0;
+    for v in 0..n {
+        if !visited[v] {
+            visited[v] = true;
+            tour.push(v);
+            let cost = dist[last][v] + brute_force_tour(dist, tour, visited);
+            tour.pop();
+            visited[v] = false;
+            if cost < best {
+                best = cost;
+            }
+        }
+    }
+    best
+}
+
+quickcheck! {
+    fn tsp_tours(g: Small<Graph<(), u32, Undirected>>) -> bool {
+        use petgraph::algo::{connected_components, tsp_christofides_like,
+                             tsp_nearest_neighbor};
+        let n = g.node_count();
+        if n == 0 || n > 7 || connected_components(&*g) != 1 {
+            return true;
+        }
+        let g = g.map(|_, _| (), |_, &w| w % 100);
+        let dist = g.node_indices().map(|a| {
+            let d = dijkstra(&g, a, None, |e| *e.weight());
+            g.node_indices().map(|b| d[&b]).collect::<Vec<_>>()
+        }).collect::<Vec<_>>();
+        let mut visited = vec![false; n];
+        visited[0] = true;
+        let optimal = if n == 1 { 0 } else { brute_force_tour(&dist, &mut vec![0], &mut visited) };
+        let tours = [
+            tsp_nearest_neighbor(&g, node_index(n - 1), |e| *e.weight(), true).unwrap(),
+            tsp_christofides_like(&g, |e| *e.weight(), true).unwrap(),
+        ];
+        for &(ref tour, cost) in &tours {
+            let mut sorted = tour.iter().map(|a| a.index()).collect::<Vec<_>>();
+            sorted.sort();
+            assert_eq!(sorted, (0..n).collect::<Vec<_>>());
+            let mut total = 0;
+            if n > 1 {
+                for (k, a) in tour.iter().enumerate() {
+                    total += dist[a.index()][tour[(k + 1) % n].index()];
+                }
+            }
+            assert_eq!(cost, total);
+            assert!(cost >= optimal);
+        }
+        assert!(tours[1].1 <= 2 * optimal);
+        true
+    }
+}
+
+quickcheck! {
+    fn complement_edge_count(g: Small<Graph<(), (), Undirected>>) -> bool {
+        // the same graph without self loops and parallel edges
+        let mut simple = Graph::<(), (), Undirected>::with_capacity(g.node_count(), 0);
+        for _ in g.node_indices() {
+            simple.add_node(());
+        }
+        for edge in g.edge_references() {
+            let (a, b) = (edge.source(), edge.target());
+            if a != b && !simple.contains_edge(a, b) {
+                simple.add_edge(a, b, ());
+            }
+        }
+        let n = g.node_count();
+        let c = simple.complement(false);
+        assert_eq!(simple.edge_count() + c.edge_count(), n * n.saturating_sub(1) / 2);
+        for edge in c.edge_references() {
+            assert!(!simple.contains_edge(edge.source(), edge.target()));
+        }
+        c.complement(false).edge_count() == simple.edge_count()
+    }
+}
+
+quickcheck! {
+    fn merge_nodes_degrees(g: Small<Graph<u32, (), Undirected>>, a: usize, b: usize,
+                           self_loops: bool) -> bool
+    {
+        let n = g.node_count();
+        if n == 0 {
+            return true;
+        }
+        let (a, b) = (node_index(a % n), node_index(b % n));
+        if a == b {
+            return true;
+        }
+        let joining = g.edges(a).filter(|e| e.target() == b).count();
+        // a self loop counts twice in the degree
+        let degree = |g: &Graph<u32, (), Undirected>, x| {
+            g.edges(x).map(|e| if e.target() == x { 2 } else { 1 }).sum::<usize>()
+        };
+        let (wa, wb) = (g[a], g[b]);
+        let expected = degree(&g, a) + degree(&g, b) - if self_loops { 0 } else { 2 * joining };
+        let mut g = (*g).clone();
+        let edges = g.edge_count();
+        let merged = g.merge_nodes(a, b, |x, y| x ^ y.rotate_left(1), self_loops);
+        assert_eq!(g.node_count(), n - 1);
+        assert_eq!(g[merged], wa ^ wb.rotate_left(1));
+        assert_eq!(degree(&g, merged), expected);
+        g.edge_count() == edges - if self_loops { 0 } else { joining }
+    }
+}
+
+/// Find an augmenting path from `a` for Kuhn's bipartite matching algorithm.
+fn kuhn_augment(adj: &[Vec<usize>], a: usize, mate: &mut [Option<usize>],
+                seen: &mut [bool]) -> bool
+{
+    for &b in &adj[a] {
+        if !seen[b] {
+            seen[b] = true;
+            if mate[b].map_or(true, |c| kuhn_augment(adj, c, mate, seen)) {
+                mate[b] = Some(a);
+                return true;
+            }
+        }
+    }
+    false
+}
+
+quickcheck! {
+    fn minimum_path_cover_dag(g: Small<Graph<(), ()>>) -> bool {
+        use petgraph::algo::minimum_path_cover;
+        assert!(minimum_path_cover(&*g).is_err() == is_cyclic_directed(&*g));
+        // a DAG of the edges that go to a higher index
+        let mut dag = g.map(|_, _| (), |_, _| ());
+        dag.retain_edges(|g, e| {
+            let (a, b) = g.edge_endpoints(e).unwrap();
+            a < b
+        });
+        let paths = minimum_path_cover(&dag).unwrap();
+        let mut nodes = paths.iter().flat_map(|p| p).cloned().collect::<Vec<_>>();
+        nodes.sort();
+        assert_eq!(nodes, dag.node_indices().collect::<Vec<_>>());
+        for path in &paths {
+            assert!(path.is_valid(&dag));
+        }
+        // the fewest paths leave out as many links as a maximum matching of
+        // the outgoing to the incoming ends of the edges has
+        let n = dag.node_count();
+        let adj = dag.node_indices().map(|a| {
+            dag.neighbors(a).map(|b| b.index()).collect::<Vec<_>>()
+        }).collect::<Vec<_>>();
+        let mut mate = vec![None; n];
+        let mut matched = 0;
+        for a in 0..n {
+            if kuhn_augment(&adj, a, &mut mate, &mut vec![false; n]) {
+                matched += 1;
+            }
+        }
+        paths.len() == n - matched
+    }
+}
+
+quickcheck! {
+    fn tree_diameter_brute_force(parents: Vec<(usize, u8)>) -> bool {
+        use petgraph::algo::tree_diameter;
+        // a tree where node i + 1 hangs off a node before it
+        let mut g = Graph::<(), u32, Undirected>::new_undirected();
+        g.add_node(());
+        for (i, &(p, w)) in parents.iter().take(30).enumerate() {
+            let a = g.add_node(());
+            g.add_edge(node_index(p % (i + 1)), a, w as u32);
+        }
+        let (cost, path) = tree_diameter(&g, |e| *e.weight()).unwrap();
+        let longest = g.node_indices().flat_map(|a| {
+            dijkstra(&g, a, None, |e| *e.weight()).into_iter().map(|(_, d)| d)
+        }).max().unwrap();
+        assert_eq!(cost, longest);
+        path.cost(&g, |e| *e.weight()) == Some(cost)
+    }
+}
+
+quickcheck! {
+    fn degree_sum(g: Graph<(), ()>) -> bool {
+        let mut out = 0;
+        let mut inc = 0;
+        for a in g.node_indices() {
+            assert_eq!(g.out_degree(a), g.neighbors(a).count());
+            assert_eq!(g.in_degree(a), g.neighbors_directed(a, Incoming).count());
+            assert_eq!(g.degree(a), g.out_degree(a) + g.in_degree(a));
+            out += g.out_degree(a);
+            inc += g.in_degree(a);
+        }
+        let u = g.into_edge_type::<Undirected>();
+        let sum = u.node_indices().map(|a| u.degree(a)).sum::<usize>();
+        out == u.edge_count() && inc == u.edge_count() && sum == 2 * u.edge_count()
+    }
+}
+
+quickcheck! {
+    fn merge_parallel_edges_sums(g: Graph<(), u8, Undirected>) -> bool {
+        use std::cmp::{max, min};
+        use std::collections::HashMap;
+        let mut sums = HashMap::new();
+        for e in g.raw_edges() {
+            let key = (min(e.source(), e.target()), max(e.source(), e.target()));
+            *sums.entry(key).or_insert(0) += e.weight as u32;
+        }
+        let mut merged = g.map(|_, _| (), |_, &w| w as u32);
+        let removed = merged.merge_parallel_edges(|x, y| x + y);
+        assert_graph_consistent(&merged);
+        assert_eq!(removed + sums.len(), g.edge_count());
+        for e in merged.raw_edges() {
+            let key = (min(e.source(), e.target()), max(e.source(), e.target()));
+            assert_eq!(sums[&key], e.weight);
+        }
+        merged.edge_count() == sums.len()
+    }
+}
+
+/// All nodes of `gr` in DFS post-order, restarting from each node that
+/// isn't visited yet.
+fn full_dfs_post_order<N, Ty: EdgeType>(gr: &Graph<N, (), Ty>) -> Vec<NodeIndex> {
+    let mut order = Vec::new();
+    let mut dfs = DfsPostOrder::empty(gr);
+    for i in gr.node_indices() {
+        if dfs.discovered.is_visited(&i) {
+            continue;
+        }
+        dfs.move_to(i);
+        while let Some(nx) = dfs.next(gr) {
+            order.push(nx);
+        }
+    }
+    order
+}
+
+#[test]
+fn dfs_post_order_reversed_is_topo_order() {
+    fn prop(DAG(gr): DAG<()>) -> bool {
+        let mut order = full_dfs_post_order(&gr);
+        order.reverse();
+        is_topo_order(&gr, &order)
+    }
+    quickcheck::quickcheck(prop as fn(_) -> bool);
+}
+
+#[test]
+fn dfs_post_order_visits_once() {
+    fn prop<Ty: EdgeType>(gr: Graph<(), (), Ty>) -> bool {
+        let order = full_dfs_post_order(&gr);
+        let unique = order.iter().cloned().collect::<HashSet<_>>();
+        order.len() == gr.node_count() && unique.len() == order.len()
+    }
+    quickcheck::quickcheck(prop as fn(Graph<_, _, Directed>) -> bool);
+    quickcheck::quickcheck(prop as fn(Graph<_, _, Undirected>) -> bool);
+}
+
+#[test]
+fn dfs_visit_back_edges() {
+    use petgraph::visit::{DfsEvent, depth_first_search};
+
+    fn has_back_edge(gr: &Graph<(), ()>) -> bool {
+        let mut found = false;
+        depth_first_search(gr, gr.node_indices(), |event| {
+            if let DfsEvent::BackEdge(..) = event {
+                found = true;
+            }
+        });
+        found
+    }
+    // no back edges in a DAG
+    fn prop_dag(DAG(gr): DAG<()>) -> bool {
+        !has_back_edge(&gr)
+    }
+    quickcheck::quickcheck(prop_dag as fn(_) -> bool);
+    // a back edge exactly when there is a cycle
+    fn prop(gr: Graph<(), ()>) -> bool {
+        has_back_edge(&gr) == is_cyclic_directed(&gr)
+    }
+    quickcheck::quickcheck(prop as fn(_) -> bool);
+}
+
+#[test]
+fn node_filtered_matches_copy() {
+    use petgraph::visit::{Dfs, DfsPostOrder, NodeFiltered, IntoNeighbors};
+
+    fn prop<Ty: EdgeType>(g: Small<Graph<(), u16, Ty>>, mask: u64) -> bool {
+        let g = g.map(|_, _| (), |_, &w| w as u32);
+        let include = |i: NodeIndex| mask & (1 << (i.index() % 64)) != 0;
+        let view = NodeFiltered::from_fn(&g, include);
+        // node weights of the copy remember the original index
+        let copy = g.filter_map(|i, _| if include(i) { Some(i) } else { None },
+                                |_, &w| Some(w));
+        for start in g.node_indices() {
+            if !include(start) {
+                // excluded nodes are invisible, also as start nodes
+                assert_eq!(view.neighbors(start).count(), 0);
+                assert_eq!(Dfs::new(&view, start).iter(&view).count(), 0);
+                assert_eq!(DfsPostOrder::new(&view, start).iter(&view).count(), 0);
+                assert_eq!(Bfs::new(&view, start).iter(&view).count(), 0);
+                assert!(dijkstra(&view, start, None, |e| *e.weight()).is_empty());
+                continue;
+            }
+            let copy_start = copy.node_indices().find(|&i| copy[i] == start).unwrap();
+            let reached = set(Dfs::new(&view, start).iter(&view));
+            let copy_reached = set(Dfs::new(&copy, copy_start).iter(&copy).map(|i| copy[i]));
+            assert_eq!(reached, copy_reached);
+            let reached = set(Bfs::new(&view, start).iter(&view));
+            assert_eq!(reached, copy_reached);
+
+            let scores = dijkstra(&view, start, None, |e| *e.weight());
+            let copy_scores = dijkstra(&copy, copy_start, None, |e| *e.weight());
+            assert_eq!(scores, copy_scores.into_iter().map(|(i, d)| (copy[i], d)).collect());
+        }
+        true
+    }
+    quickcheck::quickcheck(prop as fn(Small<Graph<_, _, Directed>>, _) -> bool);
+    quickcheck::quickcheck(prop as fn(Small<Graph<_, _, Undirected>>, _) -> bool);
+}
+
+#[test]
+fn edge_filtered_matches_copy() {
+    use petgraph::algo::connected_components;
+    use petgraph::visit::{Dfs, EdgeFiltered};
+
+    fn prop<Ty: EdgeType>(g: Small<Graph<(), u16, Ty>>) -> bool {
+        let g = g.map(|_, _| (), |_, &w| w as u32);
+        let view = EdgeFiltered::from_fn(&g, |e| e.weight() % 3 != 0);
+        let copy = g.filter_map(|_, _| Some(()),
+                                |_, &w| if w % 3 != 0 { Some(w) } else { None });
+        // node indices are the same in the copy
+        for start in g.node_indices() {
+            assert_eq!(set(Dfs::new(&view, start).iter(&view)),
+                       set(Dfs::new(&copy, start).iter(&copy)));
+            assert_eq!(set(Bfs::new(&view, start).iter(&view)),
+                       set(Bfs::new(&copy, start).iter(&copy)));
+            assert_eq!(dijkstra(&view, start, None, |e| *e.weight()),
+                       dijkstra(&copy, start, None, |e| *e.weight()));
+        }
+        g.is_directed() || connected_components(&view) == connected_components(&copy)
+    }
+    quickcheck::quickcheck(prop as fn(Small<Graph<_, _, Directed>>) -> bool);
+    quickcheck::quickcheck(prop as fn(Small<Graph<_, _, Undirected>>) -> bool);
+}
+
+quickcheck! {
+    fn reversed_dfs_visits_ancestors(g: Small<Graph<(), ()>>, node: usize) -> bool {
+        use petgraph::visit::Dfs;
+        if g.node_count() == 0 {
+            return true;
+        }
+        let target = node_index(node % g.node_count());
+        let rev = Reversed(&*g);
+        let ancestors = set(Dfs::new(rev, target).iter(rev));
+        g.node_indices().all(|a| {
+            ancestors.contains(&a) == has_path_connecting(&*g, a, target, None)
+        })
+    }
+
+    fn reversed_dijkstra_distances_to(g: Small<Graph<(), u16>>, node: usize) -> bool {
+        if g.node_count() == 0 {
+            return true;
+        }
+        let target = node_index(node % g.node_count());
+        let to_target = dijkstra(Reversed(&*g), target, None, |e| *e.weight() as u32);
+        g.node_indices().all(|a| {
+            let from_a = dijkstra(&*g, a, Some(target), |e| *e.weight() as u32);
+            to_target.get(&a) == from_a.get(&target)
+        })
+    }
+}
+
+#[test]
+fn graphmap_algorithms_match_graph() {
+    use petgraph::visit::Dfs;
+
+    fn prop<Ty: EdgeType + Clone>(gm: Small<GraphMap<i8, u16, Ty>>) -> bool {
+        let gm = gm.0;
+        let g: Graph<i8, u16, Ty> = gm.clone().into_graph();
+        // node weights of `g` are the graphmap node ids
+        for (ix, &id) in g.node_references() {
+            let scores = dijkstra(&g, ix, None, |e| *e.weight() as u32);
+            let map_scores = dijkstra(&gm, id, None, |e| *e.2 as u32);
+            assert_eq!(map_scores, scores.into_iter().map(|(i, d)| (g[i], d)).collect());
+
+            assert_eq!(set(Dfs::new(&gm, id).iter(&gm)),
+                       set(Dfs::new(&g, ix).iter(&g).map(|i| g[i])));
+            assert_eq!(set(Bfs::new(&gm, id).iter(&gm)),
+                       set(Bfs::new(&g, ix).iter(&g).map(|i| g[i])));
+            if gm.is_directed() {
+                let rev = Reversed(&gm);
+                assert_eq!(set(Bfs::new(rev, id).iter(rev)),
+                           set(Bfs::new(Reversed(&g), ix).iter(Reversed(&g)).map(|i| g[i])));
+            }
+        }
+        if gm.is_directed() {
+            match toposort(&gm, None) {
+                Ok(order) => {
+                    let position = |n| order.iter().position(|&x| x == n).unwrap();
+                    assert_eq!(order.len(), gm.node_count());
+                    assert!(gm.all_edges().all(|(a, b, _)| position(a) < position(b)));
+                    assert!(toposort(&g, None).is_ok());
+                }
+                Err(_) => assert!(toposort(&g, None).is_err()),
+            }
+        }
+        true
+    }
+    quickcheck::quickcheck(prop as fn(Small<DiGraphMap<_, _>>) -> bool);
+    quickcheck::quickcheck(prop as fn(Small<UnGraphMap<_, _>>) -> bool);
+}
+
+quickcheck! {
+    fn dfs_timestamps_nest(g: Graph<(), ()>, start: usize) -> bool {
+        use petgraph::visit::dfs_timestamps;
+        let starts = if g.node_count() == 0 {
+            None
+        } else {
+            Some(node_index(start % g.node_count()))
+        };
+        let (d, f) = dfs_timestamps(&g, starts);
+        let mut times = d.iter().chain(&f).cloned().collect::<Vec<_>>();
+        times.sort();
+        assert_eq!(times, (0..2 * g.node_count()).collect::<Vec<_>>());
+        if let Some(s) = starts {
+            assert_eq!(d[s.index()], 0);
+        }
+        for i in 0..g.node_count() {
+            assert!(d[i] < f[i]);
+            for j in 0..g.node_count() {
+                // intervals are disjoint or nested
+                assert!(f[i] < d[j] || f[j] < d[i] ||
+                        (d[i] < d[j]) == (f[j] < f[i]));
+            }
+        }
+        // an edge finishes at its target first, unless it is a back edge
+        g.raw_edges().iter().all(|e| {
+            let (u, v) = (e.source().index(), e.target().index());
+            f[v] < f[u] || (d[v] <= d[u] && f[u] <= f[v])
+        })
+    }
+}
+
+quickcheck! {
+    fn depth_limited_dfs_is_hop_ball(g: Small<Graph<(), ()>>, node: usize, depth: u8) -> bool {
+        use petgraph::visit::DepthLimitedDfs;
+        if g.node_count() == 0 {
+            return true;
+        }
+        let start = node_index(node % g.node_count());
+        let max_depth = depth as usize % 6;
+        let hops = dijkstra(&*g, start, None, |_| 1);
+        let mut dfs = DepthLimitedDfs::new(&*g, start, max_depth);
+        let reached = (&mut dfs).iter(&*g).collect::<Vec<_>>();
+        assert_eq!(reached.len(), set(reached.iter().cloned()).len());
+        assert_eq!(set(reached), set(hops.keys().cloned().filter(|n| hops[n] <= max_depth)));
+        assert!(dfs.is_cut_off() || hops.values().all(|&h| h <= max_depth));
+        true
+    }
+
+    fn iddfs_finds_closest_goal(g: Small<Graph<(), ()>>, node: usize, goals: u64) -> bool {
+        use petgraph::visit::iddfs;
+        if g.node_count() == 0 {
+            return true;
+        }
+        let start = node_index(node % g.node_count());
+        let is_goal = |n: NodeIndex| goals & (1 << (n.index() % 64)) != 0;
+        let hops = dijkstra(&*g, start, None, |_| 1);
+        let closest = hops.iter().filter(|&(&n, _)| is_goal(n)).map(|(_, &h)| h).min();
+        match iddfs(&*g, start, is_goal) {
+            Some((goal, depth)) => is_goal(goal) && Some(depth) == closest &&
+                                   hops[&goal] == depth,
+            None => closest.is_none(),
+        }
+    }
+}
+
+quickcheck! {
+    fn multi_source_bfs_nearest_source(g: Small<Graph<(), ()>>, sources: Vec<usize>) -> bool {
+        use petgraph::visit::multi_source_bfs;
+        if g.node_count() == 0 {
+            return true;
+        }
+        let sources = sources.iter().map(|&s| node_index(s % g.node_count()))
+                                    .collect::<Vec<_>>();
+        let labels = multi_source_bfs(&*g, sources.iter().cloned());
+        let hops = sources.iter().map(|&s| dijkstra(&*g, s, None, |_| 1u32))
+                                 .collect::<Vec<_>>();
+        g.node_indices().all(|v| {
+            // the first of the closest sources
+            let expected = hops.iter().enumerate()
+                .filter_map(|(i, h)| h.get(&v).map(|&d| (d, i)))
+                .min()
+                .map(|(d, i)| (i, d));
+            labels[v.index()] == expected
+        })
+    }
+}
+
+#[test]
+fn arbitrary_undirected_has_self_loops_and_isolated_nodes() {
+    use quickcheck::{Arbitrary, StdGen};
+
+    let mut gen = StdGen::new(rand::thread_rng(), 20);
+    // counts for Graph and UnGraphMap
+    let (mut loops, mut isolated) = ([0; 2], [0; 2]);
+    for _ in 0..200 {
+        let g = Graph::<(), (), Undirected>::arbitrary(&mut gen);
+        assert!(g.node_count() <= 20);
+        if g.edge_references().any(|e| e.source() == e.target()) {
+            loops[0] += 1;
+        }
+        if g.node_indices().any(|a| g.neighbors(a).next().is_none()) {
+            isolated[0] += 1;
+        }
+        let gm = UnGraphMap::<i8, ()>::arbitrary(&mut gen);
+        assert!(gm.node_count() <= 20);
+        if gm.all_edges().any(|(a, b, _)| a == b) {
+            loops[1] += 1;
+        }
+        if gm.nodes().any(|a| gm.neighbors(a).next().is_none()) {
+            isolated[1] += 1;
+        }
+    }
+    assert!(loops[0] > 0 && loops[1] > 0, "{:?}", loops);
+    assert!(isolated[0] > 0 && isolated[1] > 0, "{:?}", isolated);
+}
+
+quickcheck! {
+    fn graphmap_shrink_is_subgraph(g: DiGraphMap<i8, ()>) -> bool {
+        use quickcheck::Arbitrary;
+        g.shrink().all(|h| {
+            h.node_count() < g.node_count() &&
+                h.nodes().all(|n| g.contains_node(n)) &&
+                h.all_edges().all(|(a, b, _)| g.contains_edge(a, b))
+        })
+    }
+}
@@ -185,3 +185,57 @@ fn test_edge_iterators_undir() {
             gr.edges(i));
     }
 }
+
+#[test]
+fn traverse_after_removals() {
+    use petgraph::algo::dijkstra;
+    use petgraph::visit::{Bfs, Dfs};
+
+    // a path 0 - 1 - 2 - 3 - 4 with a shortcut 0 - 4
+    let mut g = StableGraph::<u32, u32, Undirected>::default();
+    let nodes = (0..5).map(|i| g.add_node(i)).collect::<Vec<_>>();
+    for i in 0..4 {
+        g.add_edge(nodes[i], nodes[i + 1], 1);
+    }
+    g.add_edge(nodes[0], nodes[4], 10);
+
+    // remove interior nodes; the other indices stay valid
+    g.remove_node(nodes[1]);
+    g.remove_node(nodes[2]);
+    assert_eq!(g.node_count(), 3);
+    assert_eq!(g.edge_count(), 2);
+    assert_equal(g.node_indices(), vec![nodes[0], nodes[3], nodes[4]]);
+    assert_eq!(g[nodes[3]], 3);
+    assert_eq!(g.find_edge(nodes[3], nodes[4]).map(|e| g[e]), Some(1));
+
+    let mut bfs = Bfs::new(&g, nodes[0]);
+    let mut order = Vec::new();
+    while let Some(x) = bfs.next(&g) {
+        order.push(x);
+    }
+    assert_eq!(order, vec![nodes[0], nodes[4], nodes[3]]);
+
+    let mut dfs = Dfs::new(&g, nodes[3]);
+    let mut count = 0;
+    while let Some(x) = dfs.next(&g) {
+        assert!(g.contains_node(x));
+        count += 1;
+    }
+    assert_eq!(count, 3);
+
+    let dist = dijkstra(&g, nodes[0], None, |e| *e.weight());
+    assert_eq!(dist.len(), 3);
+    assert_eq!(dist[&nodes[3]], 11);
+
+    // removed nodes have no times
+    let (discover, finish) = petgraph::visit::dfs_timestamps(&g, None);
+    assert_eq!(discover, vec![0, !0, !0, 2, 1]);
+    assert_eq!(finish, vec![5, !0, !0, 3, 4]);
+
+    // a new node takes a vacant slot
+    let x = g.add_node(7);
+    assert!(x == nodes[1] || x == nodes[2]);
+    assert_eq!(g[x], 7);
+    assert_eq!(g.neighbors(x).count(), 0);
+    assert_eq!(g[nodes[4]], 4);
+}
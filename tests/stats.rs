@@ -0,0 +1,90 @@
+extern crate petgraph;
+
+use std::f64::NAN;
+
+use petgraph::prelude::*;
+use petgraph::stats::{Histogram, WeightStats};
+
+#[test]
+fn weight_stats() {
+    let stats = WeightStats::from_weights((1..21).map(|x| x as f64)).unwrap();
+    assert_eq!(stats.count, 20);
+    assert_eq!(stats.nan_count, 0);
+    assert_eq!((stats.min, stats.max), (1., 20.));
+    assert_eq!(stats.mean, 10.5);
+    assert_eq!(stats.median, 10.5);
+    assert!((stats.p95 - 19.05).abs() < 1e-9);
+
+    // the order doesn't matter, and an odd count has a middle element
+    let stats = WeightStats::from_weights(vec![5., -1., 3.]).unwrap();
+    assert_eq!((stats.min, stats.max, stats.median), (-1., 5., 3.));
+    assert_eq!(stats.mean, 7. / 3.);
+
+    let stats = WeightStats::from_weights(Some(2.5)).unwrap();
+    assert_eq!((stats.min, stats.max, stats.mean, stats.median, stats.p95),
+               (2.5, 2.5, 2.5, 2.5, 2.5));
+
+    // NaN is counted and left out
+    let stats = WeightStats::from_weights(vec![NAN, 4., NAN, 2.]).unwrap();
+    assert_eq!((stats.count, stats.nan_count), (2, 2));
+    assert_eq!((stats.min, stats.max, stats.mean, stats.median), (2., 4., 3., 3.));
+
+    assert_eq!(WeightStats::from_weights(vec![]), None);
+    assert_eq!(WeightStats::from_weights(vec![NAN]), None);
+}
+
+#[test]
+fn histogram() {
+    let h = Histogram::from_weights(vec![0., 1., 2.5, 5., 7.5, 9.99, 10.], 4).unwrap();
+    assert_eq!((h.min, h.max), (0., 10.));
+    assert_eq!(h.bin_width(), 2.5);
+    assert_eq!(h.counts, vec![2, 1, 1, 3]);
+    assert_eq!(h.iter().collect::<Vec<_>>(),
+               vec![(0., 2), (2.5, 1), (5., 1), (7.5, 3)]);
+    assert_eq!(h.bin(-1.), None);
+    assert_eq!(h.bin(NAN), None);
+    assert_eq!(h.bin(10.), Some(3));
+
+    // equal weights all go in the first bin; NaN is left out
+    let h = Histogram::from_weights(vec![3., NAN, 3.], 3).unwrap();
+    assert_eq!(h.counts, vec![2, 0, 0]);
+
+    assert_eq!(Histogram::from_weights(vec![NAN], 3), None);
+}
+
+#[should_panic]
+#[test]
+fn histogram_no_bins() {
+    Histogram::from_weights(vec![1.], 0);
+}
+
+#[test]
+fn graph_weight_stats() {
+    let mut gr = Graph::<&str, u32>::new();
+    assert_eq!(gr.edge_weight_stats(|&w| w as f64), None);
+    assert_eq!(gr.node_weight_histogram(2, |n| n.len() as f64), None);
+
+    let a = gr.add_node("a");
+    let b = gr.add_node("bb");
+    let c = gr.add_node("cccc");
+    gr.add_edge(a, b, 1);
+    gr.add_edge(b, c, 3);
+    gr.add_edge(c, a, 8);
+    gr.add_edge(a, c, 4);
+
+    let stats = gr.edge_weight_stats(|&w| w as f64).unwrap();
+    assert_eq!((stats.count, stats.min, stats.max, stats.mean, stats.median),
+               (4, 1., 8., 4., 3.5));
+    let stats = gr.node_weight_stats(|n| n.len() as f64).unwrap();
+    assert_eq!((stats.count, stats.median), (3, 2.));
+
+    let h = gr.edge_weight_histogram(2, |&w| w as f64).unwrap();
+    assert_eq!(h.counts, vec![3, 1]);
+    let h = gr.node_weight_histogram(3, |n| n.len() as f64).unwrap();
+    assert_eq!(h.counts, vec![1, 1, 1]);
+
+    // e.g. to color the heaviest edges in a dot file
+    let threshold = gr.edge_weight_stats(|&w| w as f64).unwrap().median;
+    let heavy = gr.edge_references().filter(|e| *e.weight() as f64 > threshold).count();
+    assert_eq!(heavy, 2);
+}
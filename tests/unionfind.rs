@@ -2,6 +2,7 @@ extern crate rand;
 extern crate petgraph;
 
 use rand::{Rng, thread_rng, ChaChaRng};
+use std::cmp;
 use std::collections::HashSet;
 use petgraph::unionfind::UnionFind;
 
@@ -33,6 +34,27 @@ fn uf_test() {
     assert_eq!(set.len(), 3);
 }
 
+#[test]
+fn uf_labeling() {
+    let mut u = UnionFind::<usize>::new(8);
+    u.union(0, 1);
+    u.union(1, 3);
+    u.union(1, 4);
+    u.union(4, 7);
+    u.union(5, 6);
+    assert_eq!(u.len_sets(), 3);
+    assert_eq!((u.set_size(3), u.set_size(2), u.set_size(6)), (5, 1, 2));
+
+    let labels = u.into_labeling();
+    assert_eq!(labels.iter().collect::<HashSet<_>>().len(), 3);
+    for &(a, b) in &[(0, 1), (0, 3), (0, 4), (0, 7), (5, 6)] {
+        assert_eq!(labels[a], labels[b]);
+    }
+    for &(a, b) in &[(0, 2), (0, 5), (2, 5)] {
+        assert!(labels[a] != labels[b]);
+    }
+}
+
 #[test]
 fn uf_rand() {
     let n = 1 << 14;
@@ -76,3 +98,88 @@ fn labeling()
     let v = u.into_labeling();
     assert!(v.iter().all(|x| *x == v[0]));
 }
+
+#[test]
+fn union_rep() {
+    let mut rng: ChaChaRng = thread_rng().gen();
+    let n = 64;
+    let mut u = UnionFind::<u16>::new(n);
+    for _ in 0..n * 2 {
+        let a = rng.gen_range(0, n as u16);
+        let b = rng.gen_range(0, n as u16);
+        let (ar, br) = (u.find(a), u.find(b));
+        match u.union_rep(a, b) {
+            Some(rep) => {
+                assert!(ar != br);
+                assert!(rep == ar || rep == br);
+                assert_eq!(u.find(a), rep);
+                assert_eq!(u.find(b), rep);
+            }
+            None => assert_eq!(ar, br),
+        }
+    }
+    let reps = (0..n as u16).map(|x| u.find(x)).collect::<HashSet<_>>();
+    assert_eq!(u.len_sets(), reps.len());
+    assert_eq!(reps.iter().map(|&r| u.set_size(r)).sum::<usize>(), n);
+    for x in 0..n as u16 {
+        let size = (0..n as u16).filter(|&y| u.find(y) == u.find(x)).count();
+        assert_eq!(u.set_size(x), size);
+    }
+}
+
+#[test]
+fn set_size_full_u8() {
+    // the size of a set of all 256 elements doesn't fit in a u8
+    let mut u = UnionFind::<u8>::new(256);
+    for i in 0..255 {
+        assert!(u.union(i, i + 1));
+    }
+    assert_eq!(u.len_sets(), 1);
+    assert_eq!(u.set_size(0), 256);
+    assert_eq!(u.set_size(255), 256);
+}
+
+#[test]
+fn insert() {
+    let mut u = UnionFind::<u32>::default();
+    assert!(u.is_empty());
+    assert_eq!(u.len_sets(), 0);
+    u.reserve(10);
+    // stream edges, adding the elements as they show up
+    for &(a, b) in &[(0, 1), (2, 1), (4, 3), (5, 5)] {
+        while u.len() <= cmp::max(a, b) as usize {
+            let x = u.insert();
+            assert_eq!(x as usize + 1, u.len());
+            assert_eq!(u.find(x), x);
+        }
+        u.union(a, b);
+    }
+    assert_eq!(u.len(), 6);
+    assert_eq!(u.len_sets(), 3);
+    assert_eq!(u.set_size(2), 3);
+
+    // elements from `new` and `insert` work together
+    let mut u = UnionFind::<u8>::new(2);
+    let c = u.insert();
+    assert_eq!(c, 2);
+    assert!(u.union(0, c));
+    assert_eq!(u.find(2), u.find(0));
+    assert_eq!(u.into_labeling().len(), 3);
+}
+
+#[test]
+#[should_panic(expected = "element 3 is out of bounds for 3 elements")]
+fn find_out_of_bounds() {
+    let mut u = UnionFind::<u32>::new_empty();
+    for _ in 0..3 {
+        u.insert();
+    }
+    u.union(0, 3);
+}
+
+#[test]
+#[should_panic(expected = "out of range for the index type")]
+fn insert_overflow() {
+    let mut u = UnionFind::<u8>::new(256);
+    u.insert();
+}
@@ -0,0 +1,22 @@
+/// The size of a maximum matching of the nodes `v..` of the adjacency
+/// matrix `adj`, by brute force.
+pub fn brute_force_max_matching(adj: &[Vec<bool>], v: usize, matched: &mut [bool]) -> usize {
+    if v == adj.len() {
+        return 0;
+    }
+    if matched[v] {
+        return brute_force_max_matching(adj, v + 1, matched);
+    }
+    let mut best = brute_force_max_matching(adj, v + 1, matched);
+    for w in v + 1..adj.len() {
+        if adj[v][w] && !matched[w] {
+            matched[w] = true;
+            let size = 1 + brute_force_max_matching(adj, v + 1, matched);
+            matched[w] = false;
+            if size > best {
+                best = size;
+            }
+        }
+    }
+    best
+}
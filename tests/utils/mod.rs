@@ -1,5 +1,5 @@
-
-
 mod qc;
+mod matching;
 
 pub use self::qc::*;
+pub use self::matching::*;